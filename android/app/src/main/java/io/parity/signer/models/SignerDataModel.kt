@@ -226,6 +226,8 @@ class SignerDataModel : ViewModel() {
 		if (checkRefresh) _onBoardingDone.value =
 			OnBoardingState.Yes else _onBoardingDone.value = OnBoardingState.No
 		if (checkRefresh) {
+			substrateMigrateNetworkSpecs(dbName)
+			substrateMigrateAddressDetails(dbName)
 			refreshNetworks()
 			//TODO: support state with all networks deleted (low priority)
 			if (true) {
@@ -524,6 +526,52 @@ class SignerDataModel : ViewModel() {
 		return sharedPreferences.getString(selectedSeed.value, "") ?: ""
 	}
 
+	/**
+	 * Rename seed both in encrypted storage and in all identities bound to it
+	 */
+	fun renameSeed(seedName: String, newSeedName: String) {
+
+		//Check if seed name already exists; storage could have seeds without identities
+		if (seedNames.value?.contains(newSeedName) as Boolean) {
+			_lastError.value = "Seed with this name already exists!"
+			return
+		}
+
+		//Run standard login prompt!
+		authentication.authenticate(activity) {
+			try {
+				val seedPhrase = sharedPreferences.getString(seedName, "") ?: ""
+				if (seedPhrase == "") {
+					_lastError.value = "Seed not found in secure storage"
+					return@authenticate
+				}
+
+				//Rename seed in identities - should make sure this works before moving key
+				substrateRenameSeed(seedName, newSeedName, dbName)
+
+				//Move seed in encrypted storage
+				val storageUpdated = with(sharedPreferences.edit()) {
+					putString(newSeedName, seedPhrase)
+					remove(seedName)
+					commit()
+				}
+				if (!storageUpdated) {
+					//Revert identities so that they stay bound to stored seed
+					substrateRenameSeed(newSeedName, seedName, dbName)
+					_lastError.value = "Seed could not be renamed in secure storage"
+					return@authenticate
+				}
+
+				//Refresh model
+				refreshSeedNames()
+				selectSeed(newSeedName)
+			} catch (e: java.lang.Exception) {
+				_lastError.value = e.toString()
+				Log.e("Seed rename error", e.toString())
+			}
+		}
+	}
+
 	//MARK: Seed management end
 
 	//MARK: Network management begin
@@ -753,6 +801,18 @@ class SignerDataModel : ViewModel() {
 		dbname: String
 	): String
 
+	external fun substrateExportSeedWatchOnly(
+		seedName: String,
+		includePaths: Boolean,
+		dbname: String
+	): String
+
+	external fun substrateExportSelectedWatchOnly(
+		selection: String,
+		includePaths: Boolean,
+		dbname: String
+	): String
+
 	external fun qrparserGetPacketsTotal(data: String, cleaned: Boolean): Int
 	external fun qrparserTryDecodeQrSequence(
 		data: String,
@@ -783,6 +843,7 @@ class SignerDataModel : ViewModel() {
 	): String
 
 	external fun dbGetAllIdentities(dbname: String): String
+	external fun dbFindIdentitiesByAddress(address: String, dbname: String): String
 	external fun substrateTryCreateSeed(
 		seedName: String,
 		crypto: String,
@@ -810,6 +871,17 @@ class SignerDataModel : ViewModel() {
 		dbname: String
 	)
 
+	external fun substrateTryCreateIdentitiesBulk(
+		nameTemplate: String,
+		seedName: String,
+		seedPhrase: String,
+		crypto: String,
+		pathTemplate: String,
+		networks: String,
+		dryRun: Boolean,
+		dbname: String
+	): String
+
 	external fun substrateSuggestName(path: String): String
 	external fun substrateDeleteIdentity(
 		pubKey: String,
@@ -817,6 +889,46 @@ class SignerDataModel : ViewModel() {
 		dbname: String
 	)
 
+	external fun substrateRenameIdentity(
+		pubKey: String,
+		crypto: String,
+		newName: String,
+		dbname: String
+	)
+
+	external fun substrateAddIdentityTag(
+		pubKey: String,
+		crypto: String,
+		tag: String,
+		dbname: String
+	)
+
+	external fun substrateRemoveIdentityTag(
+		pubKey: String,
+		crypto: String,
+		tag: String,
+		dbname: String
+	)
+
+	external fun substrateSetIdentityNotes(
+		pubKey: String,
+		crypto: String,
+		notes: String,
+		dbname: String
+	)
+
+	external fun substrateEnableIdentityNetwork(
+		pubKey: String,
+		network: String,
+		dbname: String
+	)
+
+	external fun substrateDisableIdentityNetwork(
+		pubKey: String,
+		network: String,
+		dbname: String
+	)
+
 	external fun substrateGetNetworkSpecs(network: String, dbname: String): String
 	external fun substrateRemoveNetwork(network: String, dbname: String)
 	external fun substrateRemoveMetadata(
@@ -825,7 +937,18 @@ class SignerDataModel : ViewModel() {
 		dbname: String
 	)
 
+	external fun substrateSetRetentionPolicy(
+		policy: String,
+		dbname: String
+	)
+
+	external fun substrateGetRetentionPolicy(dbname: String): String
+	external fun substrateCompressStoredMetadata(dbname: String): Int
+	external fun substrateMigrateNetworkSpecs(dbname: String): Int
+	external fun substrateMigrateAddressDetails(dbname: String): Int
+
 	external fun substrateRemoveSeed(seedName: String, dbname: String)
+	external fun substrateRenameSeed(seedName: String, newSeedName: String, dbname: String)
 	external fun historyPrintHistory(dbname: String): String
 	external fun historyClearHistory(dbname: String)
 	external fun historyInitHistory(dbname: String)
@@ -36,6 +36,21 @@ extension Address {
  * Address-related operations in data model
  */
 extension SignerDataModel {
+    /**
+     * Re-encode addresses stored before identity tags and notes were supported
+     */
+    func migrateAddressDetails() {
+        var err = ExternError()
+        let err_ptr: UnsafeMutablePointer<ExternError> = UnsafeMutablePointer(&err)
+        let _ = migrate_address_details(err_ptr, self.dbName)
+        if err_ptr.pointee.code != 0 {
+            self.lastError = String(cString: err_ptr.pointee.message)
+            print("Address details migration failed")
+            print(self.lastError)
+            signer_destroy_string(err_ptr.pointee.message)
+        }
+    }
+    
     /**
      * Refresh list of known addresses
      */
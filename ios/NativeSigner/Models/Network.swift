@@ -30,6 +30,21 @@ extension Network {
 //MARK: network management
 
 extension SignerDataModel {
+    /**
+     * Re-encode network specs stored before multi-token networks were supported
+     */
+    func migrateNetworkSpecs() {
+        var err = ExternError()
+        let err_ptr: UnsafeMutablePointer<ExternError> = UnsafeMutablePointer(&err)
+        let _ = migrate_network_specs(err_ptr, self.dbName)
+        if err_ptr.pointee.code != 0 {
+            self.lastError = String(cString: err_ptr.pointee.message)
+            print("Network specs migration failed")
+            print(self.lastError)
+            signer_destroy_string(err_ptr.pointee.message)
+        }
+    }
+    
     func refreshNetworks() {
         var err = ExternError()
         let err_ptr: UnsafeMutablePointer<ExternError> = UnsafeMutablePointer(&err)
@@ -57,6 +57,8 @@ class SignerDataModel: ObservableObject {
         self.dbName = NSHomeDirectory() + "/Documents/Database"
         self.onboardingDone = FileManager.default.fileExists(atPath: NSHomeDirectory() + "/Documents/Database")
         if self.onboardingDone {
+            self.migrateNetworkSpecs()
+            self.migrateAddressDetails()
             self.refreshSeeds()
             self.totalRefresh()
         }
@@ -210,4 +210,48 @@ extension SignerDataModel {
             print("remove seed from secure storage error: " + self.lastError)
         }
     }
+    
+    /**
+     * Renames seed in keyring and in all derived keys
+     */
+    func renameSeed(seedName: String, newSeedName: String) {
+        var err = ExternError()
+        let err_ptr: UnsafeMutablePointer<ExternError> = UnsafeMutablePointer(&err)
+        if checkSeedCollision(seedName: newSeedName) {
+            print("Key collision")
+            self.lastError = "Seed with this name already exists"
+            return
+        }
+        rename_seed(err_ptr, seedName, newSeedName, dbName)
+        if err_ptr.pointee.code != 0 {
+            self.lastError = String(cString: err_ptr.pointee.message)
+            print("Rust returned error")
+            print(self.lastError)
+            signer_destroy_string(err_ptr.pointee.message)
+            return
+        }
+        let query = [
+            kSecClass as String: kSecClassGenericPassword,
+            kSecAttrAccount as String: seedName
+        ] as CFDictionary
+        let attributes = [
+            kSecAttrAccount as String: newSeedName
+        ] as CFDictionary
+        let status = SecItemUpdate(query, attributes)
+        guard status == errSecSuccess else {
+            print("key rename failure")
+            print(status)
+            self.lastError = SecCopyErrorMessageString(status, nil)! as String
+            //Revert derived keys so that they stay bound to the seed in keyring
+            rename_seed(err_ptr, newSeedName, seedName, dbName)
+            if err_ptr.pointee.code != 0 {
+                print("Seed rename revert failed")
+                print(String(cString: err_ptr.pointee.message))
+                signer_destroy_string(err_ptr.pointee.message)
+            }
+            return
+        }
+        self.refreshSeeds()
+        self.selectSeed(seedName: newSeedName)
+    }
 }
@@ -84,6 +84,9 @@ const char * get_relevant_identities(struct ExternError*, const char* seed_name,
 // Show all keys
 const char * get_all_identities(struct ExternError*, const char* dbname);
 
+// Find keys by ss58 address or hex public key
+const char * find_identities_by_address(struct ExternError*, const char* address, const char* dbname);
+
 // Function to create new seed
 const char * try_create_seed(struct ExternError*, const char* seed_name, const char* crypto, const char* seed_phrase, int seed_length, const char* dbname);
 
@@ -96,12 +99,39 @@ int8_t check_path(struct ExternError*, const char* path);
 // Function to create new address
 void try_create_identity(struct ExternError*, const char* id_name, const char* seed_name, const char* seed_phrase, const char* crypto, const char* path, const char* network, int8_t has_password, const char* dbname);
 
+// Show animated QR with all identities of the seed for watch-only import in hot wallet
+const char * export_seed_watch_only(struct ExternError*, const char* seed_name, int8_t include_paths, const char* dbname);
+
+// Show animated QR with selected identities (public key:network key pairs separated by commas) for watch-only import in hot wallet
+const char * export_selected_watch_only(struct ExternError*, const char* selection, int8_t include_paths, const char* dbname);
+
+// Create identities in bulk for derivation path template with range, e.g. //stash//{0..49}, networks separated by commas
+const char * try_create_identities_bulk(struct ExternError*, const char* name_template, const char* seed_name, const char* seed_phrase, const char* crypto, const char* path_template, const char* networks, int8_t dry_run, const char* dbname);
+
 // Suggest convenient name for new identity created
 const char * suggest_name(struct ExternError*, const char* path);
 
 // Delete identity (really removes network from allowed networks list and trims identities with no networks)
 void delete_identity(struct ExternError*, const char* pub_key, const char* network, const char* dbname);
 
+// Rename identity
+void rename_identity(struct ExternError*, const char* pub_key, const char* crypto, const char* new_name, const char* dbname);
+
+// Add tag to identity
+void add_identity_tag(struct ExternError*, const char* pub_key, const char* crypto, const char* tag, const char* dbname);
+
+// Remove tag from identity
+void remove_identity_tag(struct ExternError*, const char* pub_key, const char* crypto, const char* tag, const char* dbname);
+
+// Set notes for identity
+void set_identity_notes(struct ExternError*, const char* pub_key, const char* crypto, const char* notes, const char* dbname);
+
+// Enable network for existing identity (no seed needed)
+void enable_identity_network(struct ExternError*, const char* pub_key, const char* network, const char* dbname);
+
+// Disable network for identity (identity record is kept)
+void disable_identity_network(struct ExternError*, const char* pub_key, const char* network, const char* dbname);
+
 // Get network specs for settings screen
 const char * get_network_specs(struct ExternError*, const char* network_name, const char* dbname);
 
@@ -111,9 +141,27 @@ void remove_network(struct ExternError*, const char* network_name, const char* d
 // Removes metadata record from db
 void remove_metadata(struct ExternError*, const char* network_name, int network_version, const char* dbname);
 
+// Set metadata retention policy: keep_all, keep_latest:<n> or drop_older_than:<x>
+void set_retention_policy(struct ExternError*, const char* policy, const char* dbname);
+
+// Show current metadata retention policy
+const char * get_retention_policy(struct ExternError*, const char* dbname);
+
+// Compress metadata entries stored uncompressed by earlier versions, returns number of compressed entries
+int compress_stored_metadata(struct ExternError*, const char* dbname);
+
+// Re-encode network specs stored before multi-token networks were supported, returns number of migrated entries
+int migrate_network_specs(struct ExternError*, const char* dbname);
+
+// Re-encode identities stored before identity tags and notes were supported, returns number of migrated entries
+int migrate_address_details(struct ExternError*, const char* dbname);
+
 // Cleans identities after seed removal - deletes identities bound to given seed
 void remove_seed(struct ExternError*, const char* seed_name, const char* dbname);
 
+// Rename seed in all identities bound to it
+void rename_seed(struct ExternError*, const char* seed_name, const char* new_seed_name, const char* dbname);
+
 // History access operations
 // Fetch history for display
 const char * print_history(struct ExternError*, const char* dbname);
@@ -4,6 +4,7 @@
 /// network metadata (whatever user wants to keep),
 /// network specs as ChainSpecs (with order on device and with verifier for each network)
 /// settings with types information and general verifier information
+/// asset registries of networks
 /// TEMPORARILY user identities and addresses - TO BE REMOVED SOON
 /// transaction information
 
@@ -17,10 +18,12 @@ pub const ADDRTREE: &[u8] = b"addresses";
 pub const SETTREE: &[u8] = b"settings";
 pub const TRANSACTION: &[u8] = b"transaction";
 pub const HISTORY: &[u8] = b"history";
+pub const ASSETS: &[u8] = b"assets";
 
 /// Key names used for settings tree:  
 pub const TYPES: &[u8] = b"types";
 pub const GENERALVERIFIER: &[u8] = b"general_verifier";
+pub const RETENTIONPOLICY: &[u8] = b"retention_policy";
 
 /// Key names used for transaction tree:  
 pub const SIGNTRANS: &[u8] = b"sign_transaction";
@@ -29,6 +32,10 @@ pub const ADDMETAVERIFIER: &[u8] = b"add_metadata_verifier";
 pub const LOADTYPES: &[u8] = b"load_types";
 pub const ADDGENERALVERIFIER: &[u8] = b"add_general_verifier";
 pub const ADDNETWORK: &[u8] = b"add_network";
+pub const SETVERIFIERS: &[u8] = b"set_verifiers";
+pub const ROTATEVERIFIER: &[u8] = b"rotate_verifier";
+pub const IMPORTDERIVATIONS: &[u8] = b"import_derivations";
+pub const LOADASSETS: &[u8] = b"load_assets";
 
 
 /// HOT database on external device:  
@@ -56,19 +63,34 @@ pub const ADDRESS_BOOK: &[u8] = b"address_book";
 pub const COLOR: &str = "#660D35";
 pub const SECONDARY_COLOR: &str = "#262626";
 
-/// File name parts used for exports in generate_message crate
-pub const ADD: &str = "../files/for_signing/sign_me_add_network";
-pub const LOAD: &str = "../files/for_signing/sign_me_load_metadata";
-pub const TYLO: &str = "../files/for_signing/sign_me_load_types";
-pub const SPECS: &str = "../files/for_signing/sign_me_add_specs";
+/// File name prefixes for load_metadata and add_network exports in generate_message crate,
+/// files are placed into the folder set for the run
+pub const ADD: &str = "sign_me_add_network";
+pub const LOAD: &str = "sign_me_load_metadata";
+pub const TYLO: &str = "sign_me_load_types";
+pub const SPECS: &str = "sign_me_add_specs";
+pub const ASSETLOAD: &str = "sign_me_load_assets";
 
-/// Folder name used for imports in generate_message crate
+/// Default folder names used for imports and exports in generate_message crate,
+/// could be changed in generate_message workspace config
 pub const FOLDER: &str = "../files/for_signing";
 pub const EXPORT_FOLDER: &str = "../files/signed";
 
 /// QR making, raptorq:
 pub const CHUNK_SIZE: u16 = 1072;
 
+/// Metadata compression in the database:
+/// compressed entries start with the marker, raw entries start with `meta` prefix
+pub const COMPRESSED_META_MARKER: &[u8] = b"zstd";
+pub const META_COMPRESSION_LEVEL: i32 = 10;
+
+/// Compressed load_metadata and add_network qr payloads:
+/// maximum allowed size of decompressed content, to protect from compression bombs
+pub const MAX_DECOMPRESSED_CONTENT: usize = 8*1024*1024;
+
+/// Version of watch-only multi-account export format, first byte of the export content
+pub const WATCH_ONLY_VERSION: u8 = 1;
+
 /// QR making, both apng and png, grayscale:
 pub const MAIN_COLOR: u8 = 0x00;
 pub const BACK_COLOR: u8 = 0xFF;
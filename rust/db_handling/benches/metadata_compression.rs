@@ -0,0 +1,39 @@
+//! Benchmarks for compressed metadata storage: compares stored size
+//! and time needed to get metadata version from raw and compressed database entries.
+//! Run with `cargo bench` from db_handling directory.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use definitions::metadata::{compress_meta, decompress_meta};
+use meta_reading::decode_metadata::get_meta_const;
+use regex::Regex;
+use std::fs;
+
+const METADATA_FILE: &str = "metadata_database.ts";
+
+/// function to collect raw metadata entries from default metadata file
+fn default_metadata() -> Vec<(String, Vec<u8>)> {
+    let contents = fs::read_to_string(METADATA_FILE).unwrap();
+    let reg_meta = Regex::new(r#"(?i)\["signer_metadata_(?P<name>[^\]]+)_v(?P<version>[0-9]+)","(0x)?(?P<meta>6d657461([0-9a-z][0-9a-z])+)"\]"#).unwrap();
+    reg_meta.captures_iter(&contents)
+        .map(|caps| (format!("{}{}", &caps["name"], &caps["version"]), hex::decode(&caps["meta"]).unwrap()))
+        .collect()
+}
+
+fn metadata_compression(c: &mut Criterion) {
+    let entries = default_metadata();
+    let mut group = c.benchmark_group("metadata_decode");
+    for (name, raw) in entries.iter() {
+        let compressed = compress_meta(raw).unwrap();
+        println!("{}: raw {} bytes, compressed {} bytes ({:.1}%)", name, raw.len(), compressed.len(), 100.0*(compressed.len() as f64)/(raw.len() as f64));
+        group.bench_with_input(BenchmarkId::new("raw", name), raw, |b, stored| b.iter(|| {
+            get_meta_const(&decompress_meta(black_box(stored)).unwrap()).unwrap()
+        }));
+        group.bench_with_input(BenchmarkId::new("compressed", name), &compressed, |b, stored| b.iter(|| {
+            get_meta_const(&decompress_meta(black_box(stored)).unwrap()).unwrap()
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, metadata_compression);
+criterion_main!(benches);
@@ -1,8 +1,9 @@
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
 use constants::ADDRESS_BOOK;
-use definitions::defaults::get_default_address_book;
+use definitions::{defaults::get_default_address_book, metadata::{AddressBookEntry, AddressBookEntryNoFallback, AddressBookEntryNoParachain}};
 use anyhow;
 
+use crate::error::{Error, NotDecodeable};
 use crate::helpers::{open_db, open_tree, flush_db, clear_tree, insert_into_tree};
 
 pub fn load_address_book (database_name: &str) -> anyhow::Result<()> {
@@ -21,3 +22,102 @@ pub fn load_address_book (database_name: &str) -> anyhow::Result<()> {
     flush_db(&database)?;
     Ok(())
 }
+
+/// Function to re-encode address book entries stored in hot database before fallback rpc endpoints
+/// or parachain information were added, as entries with no fallback rpc endpoints,
+/// and, if stored without parachain information, as networks with no relay chain;
+/// formats are tried from the newest one, as earlier format could be decoded from the later one;
+/// entries already in current format are left untouched;
+/// outputs the number of migrated entries
+pub fn migrate_address_book (database_name: &str) -> anyhow::Result<u32> {
+    
+    let database = open_db(database_name)?;
+    let address_book = open_tree(&database, ADDRESS_BOOK)?;
+    
+    let mut count = 0;
+    for x in address_book.iter() {
+        let (title, address_book_entry_encoded) = match x {
+            Ok(a) => a,
+            Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+        };
+        if <AddressBookEntry>::decode(&mut &address_book_entry_encoded[..]).is_err() {
+            let migrated = match <AddressBookEntryNoFallback>::decode(&mut &address_book_entry_encoded[..]) {
+                Ok(a) => a.with_no_fallback(),
+                Err(_) => match <AddressBookEntryNoParachain>::decode(&mut &address_book_entry_encoded[..]) {
+                    Ok(a) => a.with_no_parachain(),
+                    Err(_) => return Err(Error::NotDecodeable(NotDecodeable::AddressBookEntry).show()),
+                },
+            };
+            insert_into_tree(title.to_vec(), migrated.encode(), &address_book)?;
+            count = count + 1;
+        }
+    }
+    
+    flush_db(&database)?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constants::SPECSTREEPREP;
+    use definitions::{defaults::get_default_chainspecs_to_send, network_specs::{ChainSpecsToSend, ChainSpecsToSendNoParachain, generate_network_key}};
+    use crate::{migrate_hot, populate_hot};
+
+    #[test]
+    fn hot_database_without_parachain_info_is_migrated() {
+        let dbname = "tests/hot_database_without_parachain_info_is_migrated";
+        populate_hot(dbname).unwrap();
+        let address_book_set = get_default_address_book();
+        let specs_set = get_default_chainspecs_to_send();
+        {
+            let database = open_db(dbname).unwrap();
+            let address_book = open_tree(&database, ADDRESS_BOOK).unwrap();
+            let chainspecs_prep = open_tree(&database, SPECSTREEPREP).unwrap();
+        // all entries are replaced by the entries in the form used before parachain information was added
+            for x in address_book_set.iter() {
+                let entry = AddressBookEntryNoParachain {
+                    name: x.name.to_string(),
+                    genesis_hash: x.genesis_hash,
+                    address: x.address.to_string(),
+                    encryption: x.encryption,
+                    def: x.def,
+                };
+                insert_into_tree(x.name.encode(), entry.encode(), &address_book).unwrap();
+            }
+            for x in specs_set.iter() {
+                let specs = ChainSpecsToSendNoParachain {
+                    base58prefix: x.base58prefix,
+                    color: x.color.to_string(),
+                    decimals: x.decimals,
+                    encryption: x.encryption,
+                    genesis_hash: x.genesis_hash,
+                    logo: x.logo.to_string(),
+                    name: x.name.to_string(),
+                    path_id: x.path_id.to_string(),
+                    secondary_color: x.secondary_color.to_string(),
+                    title: x.title.to_string(),
+                    unit: x.unit.to_string(),
+                };
+                insert_into_tree(generate_network_key(&x.genesis_hash.to_vec(), x.encryption), specs.encode(), &chainspecs_prep).unwrap();
+            }
+            flush_db(&database).unwrap();
+        }
+        assert!(migrate_hot(dbname).unwrap() == (address_book_set.len() + specs_set.len()) as u32, "Expected all entries to be migrated.");
+        assert!(migrate_hot(dbname).unwrap() == 0, "Migrated entries should not be processed again.");
+        {
+            let database = open_db(dbname).unwrap();
+            let address_book = open_tree(&database, ADDRESS_BOOK).unwrap();
+            let chainspecs_prep = open_tree(&database, SPECSTREEPREP).unwrap();
+            for x in address_book_set.iter() {
+                let migrated = address_book.get(x.name.encode()).unwrap().unwrap();
+                assert!(<AddressBookEntry>::decode(&mut &migrated[..]).unwrap().encode() == x.encode(), "Migrated address book entry for {} is different from the default.", x.name);
+            }
+            for x in specs_set.iter() {
+                let migrated = chainspecs_prep.get(generate_network_key(&x.genesis_hash.to_vec(), x.encryption)).unwrap().unwrap();
+                assert!(<ChainSpecsToSend>::decode(&mut &migrated[..]).unwrap() == *x, "Migrated network specs for {} are different from the defaults.", x.name);
+            }
+        }
+        std::fs::remove_dir_all(dbname).unwrap();
+    }
+}
@@ -1,10 +1,10 @@
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
 use constants::{SPECSTREE, SPECSTREEPREP, VERIFIERS};
-use definitions::{defaults::{get_default_chainspecs, get_default_chainspecs_to_send, get_default_verifiers}, network_specs::{ChainSpecs, generate_network_key}};
+use definitions::{defaults::{get_default_chainspecs, get_default_chainspecs_to_send, get_default_verifiers}, network_specs::{ChainSpecs, ChainSpecsNoParachain, ChainSpecsNoTokens, ChainSpecsToSend, ChainSpecsToSendNoParachain, ChainSpecsToSendNoTokens, generate_network_key, show_parachain_info}};
 use anyhow;
 use hex;
 
-use crate::error::NotHex;
+use crate::error::{Error, NotDecodeable, NotHex};
 use crate::helpers::{open_db, open_tree, flush_db, clear_tree, insert_into_tree, unhex, get_and_decode_chain_specs, decode_chain_specs};
 
 /// Fetch ChainSpecs for 1 network from cold database by network key (genesis hash and encryption)
@@ -35,13 +35,31 @@ pub fn get_all_networks (database_name: &str) -> anyhow::Result<Vec<ChainSpecs>>
     Ok(out)
 }
 
-/// Print details for all saved networks
+/// Function to group networks by relay chain:
+/// each relay chain (or standalone network) is followed by its parachains sorted by parachain id,
+/// parachains with relay chain not in the set go last
+pub fn group_by_relay (network_specs_vec: Vec<ChainSpecs>) -> Vec<ChainSpecs> {
+    let (relay_chains, mut parachains): (Vec<ChainSpecs>, Vec<ChainSpecs>) = network_specs_vec.into_iter().partition(|x| x.relay_genesis_hash.is_none());
+    parachains.sort_by_key(|x| x.parachain_id);
+    let mut out: Vec<ChainSpecs> = Vec::new();
+    for x in relay_chains.into_iter() {
+        let genesis_hash = x.genesis_hash;
+        out.push(x);
+        let (children, others): (Vec<ChainSpecs>, Vec<ChainSpecs>) = parachains.into_iter().partition(|y| y.relay_genesis_hash == Some(genesis_hash));
+        out.extend(children);
+        parachains = others;
+    }
+    out.extend(parachains);
+    out
+}
+
+/// Print details for all saved networks, grouped by relay chain
 pub fn print_all_networks (database_name: &str) -> anyhow::Result<String> {
-    let network_specs_vec = get_all_networks (database_name)?;
+    let network_specs_vec = group_by_relay(get_all_networks (database_name)?);
     let mut out = String::from("[");
     for (i, x) in network_specs_vec.iter().enumerate() {
         if i>0 {out.push_str(",");}
-        out.push_str(&format!("{{\"key\":\"{}\",\"color\":\"{}\",\"logo\":\"{}\",\"order\":\"{}\",\"secondaryColor\":\"{}\",\"title\":\"{}\"}}", hex::encode(generate_network_key(&x.genesis_hash.to_vec(), x.encryption)), x.color, x.logo, x.order, x.secondary_color, x.title));
+        out.push_str(&format!("{{\"key\":\"{}\",\"color\":\"{}\",\"logo\":\"{}\",\"order\":\"{}\",\"secondaryColor\":\"{}\",\"title\":\"{}\",{}}}", hex::encode(generate_network_key(&x.genesis_hash.to_vec(), x.encryption)), x.color, x.logo, x.order, x.secondary_color, x.title, show_parachain_info(&x.relay_genesis_hash, &x.parachain_id)));
     }
     out.push_str("]");
     Ok(out)
@@ -97,6 +115,59 @@ pub fn load_chainspecs_to_send (database_name: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Function to re-encode network specs entries stored before parachain information was added
+/// or before multi-token networks were supported, both ChainSpecs in cold database
+/// and ChainSpecsToSend in hot database, as single-token networks;
+/// entries stored without parachain information are re-encoded as networks with no relay chain;
+/// the formats are tried from the newest to the oldest, since each earlier format
+/// is the beginning of the next one and would be decoded from it leaving the remaining bytes unused;
+/// entries already in current format are left untouched;
+/// outputs the number of migrated entries
+pub fn migrate_network_specs (database_name: &str) -> anyhow::Result<u32> {
+    
+    let database = open_db(database_name)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+    let chainspecs_prep = open_tree(&database, SPECSTREEPREP)?;
+    
+    let mut count = 0;
+    for x in chainspecs.iter() {
+        let (network_key, network_specs_encoded) = match x {
+            Ok(a) => a,
+            Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+        };
+        if <ChainSpecs>::decode(&mut &network_specs_encoded[..]).is_err() {
+            let migrated = match <ChainSpecsNoTokens>::decode(&mut &network_specs_encoded[..]) {
+                Ok(a) => a.with_no_tokens(),
+                Err(_) => match <ChainSpecsNoParachain>::decode(&mut &network_specs_encoded[..]) {
+                    Ok(a) => a.with_no_parachain(),
+                    Err(_) => return Err(Error::NotDecodeable(NotDecodeable::ChainSpecs).show()),
+                },
+            };
+            insert_into_tree(network_key.to_vec(), migrated.encode(), &chainspecs)?;
+            count = count + 1;
+        }
+    }
+    for x in chainspecs_prep.iter() {
+        let (network_key, network_specs_encoded) = match x {
+            Ok(a) => a,
+            Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+        };
+        if <ChainSpecsToSend>::decode(&mut &network_specs_encoded[..]).is_err() {
+            let migrated = match <ChainSpecsToSendNoTokens>::decode(&mut &network_specs_encoded[..]) {
+                Ok(a) => a.with_no_tokens(),
+                Err(_) => match <ChainSpecsToSendNoParachain>::decode(&mut &network_specs_encoded[..]) {
+                    Ok(a) => a.with_no_parachain(),
+                    Err(_) => return Err(Error::NotDecodeable(NotDecodeable::ChainSpecs).show()),
+                },
+            };
+            insert_into_tree(network_key.to_vec(), migrated.encode(), &chainspecs_prep)?;
+            count = count + 1;
+        }
+    }
+    flush_db(&database)?;
+    Ok(count)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,4 +176,100 @@ mod tests {
     fn get_constants() {
         let _test = get_default_chainspecs();
     }
+    
+    #[test]
+    fn parachains_follow_relay_chain() {
+        let mut specs_set = get_default_chainspecs();
+        let polkadot_genesis_hash = specs_set[1].genesis_hash;
+        let kusama_genesis_hash = specs_set[0].genesis_hash;
+        let mut parachain_1 = get_default_chainspecs().remove(3);
+        parachain_1.genesis_hash = [1; 32];
+        parachain_1.title = String::from("Parachain 2000");
+        parachain_1.relay_genesis_hash = Some(polkadot_genesis_hash);
+        parachain_1.parachain_id = Some(2000);
+        let mut parachain_2 = get_default_chainspecs().remove(3);
+        parachain_2.genesis_hash = [2; 32];
+        parachain_2.title = String::from("Parachain 1000");
+        parachain_2.relay_genesis_hash = Some(polkadot_genesis_hash);
+        parachain_2.parachain_id = Some(1000);
+        let mut parachain_3 = get_default_chainspecs().remove(3);
+        parachain_3.genesis_hash = [3; 32];
+        parachain_3.title = String::from("Orphan");
+        parachain_3.relay_genesis_hash = Some([4; 32]);
+        parachain_3.parachain_id = Some(3000);
+        let mut parachain_4 = get_default_chainspecs().remove(3);
+        parachain_4.genesis_hash = [5; 32];
+        parachain_4.title = String::from("Kusama parachain");
+        parachain_4.relay_genesis_hash = Some(kusama_genesis_hash);
+        parachain_4.parachain_id = Some(2000);
+        specs_set.insert(0, parachain_3);
+        specs_set.insert(0, parachain_1);
+        specs_set.push(parachain_4);
+        specs_set.push(parachain_2);
+        let titles: Vec<String> = group_by_relay(specs_set).into_iter().map(|x| x.title).collect();
+        assert!(titles == vec!["Kusama", "Kusama parachain", "Polkadot", "Parachain 1000", "Parachain 2000", "Rococo", "Westend", "Orphan"], "Got: {:?}", titles);
+    }
+    
+    // westend network specs as stored by the versions before parachain information was added,
+    // ChainSpecs in cold database and ChainSpecsToSend in hot database
+    const WESTEND_SPECS_NO_PARACHAIN: &str = "2a001c233636304433350c01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e1c77657374656e641c77657374656e6402242f2f77657374656e641c233236323632361c57657374656e640c574e44";
+    const WESTEND_SPECS_TO_SEND_NO_PARACHAIN: &str = "2a001c233636304433350c01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e1c77657374656e641c77657374656e64242f2f77657374656e641c233236323632361c57657374656e640c574e44";
+    
+    #[test]
+    fn network_specs_in_earlier_formats_are_migrated() {
+        let dbname = "tests/network_specs_in_earlier_formats_are_migrated";
+        let specs_set = get_default_chainspecs();
+        let westend_specs_to_send = get_default_chainspecs_to_send().remove(3);
+        let westend_key = generate_network_key(&westend_specs_to_send.genesis_hash.to_vec(), westend_specs_to_send.encryption);
+        {
+            let database = open_db(dbname).unwrap();
+            let chainspecs = open_tree(&database, SPECSTREE).unwrap();
+            let chainspecs_prep = open_tree(&database, SPECSTREEPREP).unwrap();
+            clear_tree(&chainspecs).unwrap();
+            clear_tree(&chainspecs_prep).unwrap();
+        // westend entry in format without parachain information, rococo entry in current format,
+        // kusama and polkadot entries in format with parachain information, but without tokens
+            for x in specs_set.iter() {
+                let network_key = generate_network_key(&x.genesis_hash.to_vec(), x.encryption);
+                let encoded = match x.name.as_str() {
+                    "westend" => hex::decode(WESTEND_SPECS_NO_PARACHAIN).unwrap(),
+                    "rococo" => x.encode(),
+                    _ => ChainSpecsNoTokens {
+                        base58prefix: x.base58prefix,
+                        color: x.color.to_string(),
+                        decimals: x.decimals,
+                        encryption: x.encryption,
+                        genesis_hash: x.genesis_hash,
+                        logo: x.logo.to_string(),
+                        name: x.name.to_string(),
+                        order: x.order,
+                        path_id: x.path_id.to_string(),
+                        secondary_color: x.secondary_color.to_string(),
+                        title: x.title.to_string(),
+                        unit: x.unit.to_string(),
+                        relay_genesis_hash: x.relay_genesis_hash,
+                        parachain_id: x.parachain_id,
+                    }.encode(),
+                };
+                insert_into_tree(network_key, encoded, &chainspecs).unwrap();
+            }
+            insert_into_tree(westend_key.to_vec(), hex::decode(WESTEND_SPECS_TO_SEND_NO_PARACHAIN).unwrap(), &chainspecs_prep).unwrap();
+            flush_db(&database).unwrap();
+        }
+        assert!(get_all_networks(dbname).is_err(), "Entries in earlier formats should not be decodeable as ChainSpecs.");
+        assert!(migrate_network_specs(dbname).unwrap() == specs_set.len() as u32, "Expected all entries except rococo, and westend entry in hot database tree, to be migrated.");
+        assert!(migrate_network_specs(dbname).unwrap() == 0, "Migrated entries should not be processed again.");
+        let mut migrated = get_all_networks(dbname).unwrap();
+        migrated.sort_by_key(|x| x.title.to_string());
+        let mut expected = specs_set;
+        expected.sort_by_key(|x| x.title.to_string());
+        assert!(migrated == expected, "Migrated entries are different from the defaults.");
+        {
+            let database = open_db(dbname).unwrap();
+            let chainspecs_prep = open_tree(&database, SPECSTREEPREP).unwrap();
+            let migrated_encoded = chainspecs_prep.get(westend_key).unwrap().unwrap();
+            assert!(<ChainSpecsToSend>::decode(&mut &migrated_encoded[..]).unwrap() == westend_specs_to_send, "Migrated westend entry in hot database tree is different from the default.");
+        }
+        std::fs::remove_dir_all(dbname).unwrap();
+    }
 }
@@ -24,6 +24,17 @@ pub enum Error {
     AddressKey(String),
     EncryptionMismatchId,
     EncryptionMismatchNetwork,
+    AddressInput(String),
+    SeedNameExists(String),
+    TagExists(String),
+    NetworkAlreadyEnabled,
+    NetworkNotEnabled,
+    PathTemplate,
+    BulkRangeTooLarge(u32),
+    BulkCollisions(String),
+    WatchOnlyExport(String),
+    RetentionPolicy(String),
+    MetadataCompression(String),
 }
 
 #[derive(PartialEq)]
@@ -45,6 +56,9 @@ pub enum NotFound {
     MetaFromName(String),
     Address,
     Verifier,
+    SeedName(String),
+    Tag(String),
+    SeedPhrase,
 }
 
 #[derive(PartialEq)]
@@ -61,6 +75,9 @@ pub enum NotDecodeable {
     Entry,
     NetworkKey,
     Verifier,
+    RetentionPolicy,
+    Assets,
+    AddressBookEntry,
 }
 
 #[derive(PartialEq)]
@@ -97,6 +114,9 @@ impl Error {
                     NotFound::MetaFromName(name) => anyhow!("No metadata entries found in the database for {}", name),
                     NotFound::Address => anyhow!("This address does not exist in the database"),
                     NotFound::Verifier => anyhow!("Network verifier not found"),
+                    NotFound::SeedName(x) => anyhow!("No identities found in the database for seed name {}", x),
+                    NotFound::Tag(x) => anyhow!("Identity has no tag {}", x),
+                    NotFound::SeedPhrase => anyhow!("No seed in the database matches the entered seed phrase. Seed should be created first."),
                 }
             },
             Error::NotDecodeable(e) => {
@@ -113,6 +133,9 @@ impl Error {
                     NotDecodeable::Entry => anyhow!("History entry from the database could not be decoded."),
                     NotDecodeable::NetworkKey => anyhow!("Network key could not be decoded."),
                     NotDecodeable::Verifier => anyhow!("Network verifier could not be decoded."),
+                    NotDecodeable::RetentionPolicy => anyhow!("Metadata retention policy could not be decoded."),
+                    NotDecodeable::Assets => anyhow!("Network asset registry could not be decoded."),
+                    NotDecodeable::AddressBookEntry => anyhow!("Address book entry is damaged and could not be decoded."),
                 }
             },
             Error::GenesisHashMismatch => anyhow!("Genesis hash mismatch."),
@@ -139,6 +162,17 @@ impl Error {
             Error::AddressKey(x) => anyhow!("Error generating address key. {}", x),
             Error::EncryptionMismatchId => anyhow!("Identity encryption algorithm not matching network encryption algorithm"),
             Error::EncryptionMismatchNetwork => anyhow!("Encryption algorithm from network specs not matching the one from network key"),
+            Error::AddressInput(x) => anyhow!("Unable to interpret {} as ss58 address or hex public key", x),
+            Error::SeedNameExists(x) => anyhow!("Seed name {} is already in use", x),
+            Error::TagExists(x) => anyhow!("Identity already has tag {}", x),
+            Error::NetworkAlreadyEnabled => anyhow!("Network is already enabled for this identity"),
+            Error::NetworkNotEnabled => anyhow!("Network is not enabled for this identity"),
+            Error::PathTemplate => anyhow!("Derivation path template should contain exactly one range in form {{start..end}}, with start not exceeding end"),
+            Error::BulkRangeTooLarge(x) => anyhow!("Derivation path template range should contain at most {} indices", x),
+            Error::BulkCollisions(x) => anyhow!("Addresses were not created, collisions found: [{}]", x),
+            Error::WatchOnlyExport(x) => anyhow!("Unable to make watch-only export qr code. {}", x),
+            Error::RetentionPolicy(x) => anyhow!("Bad metadata retention policy. {}", x),
+            Error::MetadataCompression(x) => anyhow!("Error processing compressed metadata entry. {}", x),
         }
     }
 }
@@ -1,6 +1,7 @@
 use sled::{Db, Tree, open, IVec};
 use anyhow;
-use definitions::{crypto::Encryption, metadata::{NameVersioned, VersionDecoded}, network_specs::{ChainSpecs, NetworkKey, NetworkKeySource, generate_network_key, generate_verifier_key, Verifier}, users::{AddressKey, AddressDetails}};
+use constants::RETENTIONPOLICY;
+use definitions::{crypto::Encryption, metadata::{NameVersioned, RetentionPolicy, VersionDecoded, compress_meta, decompress_meta}, network_specs::{ChainSpecs, NetworkKey, NetworkKeySource, generate_network_key, generate_verifier_key, Verifier}, users::{AddressKey, AddressDetails}};
 use meta_reading::decode_metadata::get_meta_const;
 use parity_scale_codec::Decode;
 use sp_runtime::MultiSigner;
@@ -108,7 +109,8 @@ pub fn decode_address_details(address_details_encoded: IVec) -> anyhow::Result<A
 }
 
 /// Function to check metadata vector from the database, and output if it's ok
-pub fn check_metadata(meta: Vec<u8>, versioned_name: &NameVersioned) -> anyhow::Result<Vec<u8>> {
+pub fn check_metadata(stored: Vec<u8>, versioned_name: &NameVersioned) -> anyhow::Result<Vec<u8>> {
+    let meta = meta_from_storage(&stored)?;
     let version_vector = match get_meta_const(&meta.to_vec()) {
         Ok(a) => a,
         Err(_) => return Err(Error::NotDecodeable(NotDecodeable::Metadata).show()),
@@ -122,6 +124,22 @@ pub fn check_metadata(meta: Vec<u8>, versioned_name: &NameVersioned) -> anyhow::
     Ok(meta)
 }
 
+/// Function to compress raw metadata before putting it into metadata tree of the database
+pub fn meta_for_storage(meta: &[u8]) -> anyhow::Result<Vec<u8>> {
+    match compress_meta(meta) {
+        Ok(a) => Ok(a),
+        Err(e) => return Err(Error::MetadataCompression(e).show()),
+    }
+}
+
+/// Function to get raw metadata from metadata tree entry
+pub fn meta_from_storage(stored: &[u8]) -> anyhow::Result<Vec<u8>> {
+    match decompress_meta(stored) {
+        Ok(a) => Ok(a),
+        Err(e) => return Err(Error::MetadataCompression(e).show()),
+    }
+}
+
 /// Function to find encryption aldorithm corresponding to network with known network key
 pub fn get_network_encryption (chainspecs: &Tree, network_key: &NetworkKey) -> anyhow::Result<Encryption> {
     let from_specs = get_and_decode_chain_specs(chainspecs, network_key)?.encryption;
@@ -240,3 +258,38 @@ pub fn remove_verifier (genesis_hash: [u8; 32], verifiers: &Tree) -> anyhow::Res
         Err(e) => return Err(Error::InternalDatabaseError(e).show()),
     }
 }
+
+/// Get metadata retention policy from settings tree; if no policy is set, all metadata is kept
+pub fn get_retention_policy (settings: &Tree) -> anyhow::Result<RetentionPolicy> {
+    match settings.get(RETENTIONPOLICY) {
+        Ok(Some(policy_encoded)) => match <RetentionPolicy>::decode(&mut &policy_encoded[..]) {
+            Ok(a) => Ok(a),
+            Err(_) => return Err(Error::NotDecodeable(NotDecodeable::RetentionPolicy).show()),
+        },
+        Ok(None) => Ok(RetentionPolicy::KeepAll),
+        Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+    }
+}
+
+/// Select metadata entries of the network that are to be removed under retention policy,
+/// when metadata with new_version is accepted;
+/// outputs versioned names with the stored metadata, decompressed
+pub fn metadata_to_remove (policy: RetentionPolicy, network_name: &str, new_version: u32, metadata: &Tree) -> anyhow::Result<Vec<(NameVersioned, Vec<u8>)>> {
+    let mut stored: Vec<(NameVersioned, IVec)> = Vec::new();
+    for x in metadata.iter() {
+        if let Ok((versioned_name_encoded, meta)) = x {
+            let versioned_name = match <NameVersioned>::decode(&mut &versioned_name_encoded[..]) {
+                Ok(a) => a,
+                Err(_) => return Err(Error::NotDecodeable(NotDecodeable::NameVersioned).show()),
+            };
+            if versioned_name.name == network_name {stored.push((versioned_name, meta))}
+        }
+    }
+    let stored_versions: Vec<u32> = stored.iter().map(|(versioned_name, _)| versioned_name.version).collect();
+    let versions_to_remove = policy.versions_to_remove(&stored_versions, new_version);
+    let mut out: Vec<(NameVersioned, Vec<u8>)> = Vec::new();
+    for (versioned_name, meta) in stored.into_iter() {
+        if versions_to_remove.contains(&versioned_name.version) {out.push((versioned_name, meta_from_storage(&meta)?))}
+    }
+    Ok(out)
+}
@@ -3,22 +3,23 @@
 //! best available tool and here they are only processed in plaintext.
 //! Zeroization is mostly delegated to os
 
-use sled::{Db, Tree};
-use sp_core::{Pair, ed25519, sr25519, ecdsa};
-use parity_scale_codec::Encode;
+use sled::{Batch, Db, Tree};
+use sp_core::{Pair, ed25519, sr25519, ecdsa, crypto::Ss58Codec};
+use parity_scale_codec::{Decode, Encode};
 use regex::Regex;
 use constants::{ADDRTREE, HISTORY, SPECSTREE};
-use definitions::{crypto::Encryption, history::Event, network_specs::{NetworkKey, generate_network_key}, users::{AddressDetails, SeedObject, AddressKey, print_as_base58, IdentityHistory}};
+use definitions::{crypto::Encryption, history::Event, network_specs::{NetworkKey, generate_network_key}, qr_transfers::ContentWatchOnly, users::{AddressDetails, AddressDetailsNoTags, SeedObject, AddressKey, escape_json, print_as_base58, IdentityHistory, IdentityDetailsHistory, SeedRenameHistory, WatchOnlyEntry, WatchOnlyNetwork}};
 use bip39::{Language, Mnemonic, MnemonicType};
 use zeroize::Zeroize;
 use lazy_static::lazy_static;
 use anyhow;
 use qrcode_static::png_qr_from_string;
+use qrcode_rtx::transform_into_qr_apng_vec;
 
-use crate::error::{Error, NotFound, NotHex, CreateAddress};
+use crate::error::{Error, NotDecodeable, NotFound, NotHex, CreateAddress};
 use crate::chainspecs::get_network;
 use crate::helpers::{open_db, open_tree, drop_tree, flush_db, insert_into_tree, remove_from_tree, unhex, get_and_decode_chain_specs, decode_chain_specs, decode_address_details, get_network_encryption, generate_address_key, reverse_address_key, reverse_network_key};
-use crate::manage_history::{enter_events_into_tree};
+use crate::manage_history::{apply_batch_and_enter_events, enter_events_into_tree};
 
 
 lazy_static! {
@@ -26,8 +27,13 @@ lazy_static! {
 // removed seed phrase part
 // last '+' used to be '*', but empty password is an error
     static ref REG_PATH: Regex = Regex::new(r"^(?P<path>(//?[^/]+)*)(///(?P<password>.+))?$").expect("known value");
+// range placeholder in derivation path template for bulk derivation, bounds are inclusive
+    static ref REG_RANGE: Regex = Regex::new(r"\{(?P<start>[0-9]+)\.\.(?P<end>[0-9]+)\}").expect("known value");
 }
 
+/// Maximum number of addresses created from single derivation path template
+const MAX_BULK_DERIVATIONS: u32 = 1000;
+
 /// get all identities from database for given seed_name (internal use only!)
 fn get_seed_identities (database: &Db, seed_name: &str) -> anyhow::Result<Vec<(AddressKey, AddressDetails)>> {
     let identities = open_tree(&database, ADDRTREE)?;
@@ -78,10 +84,12 @@ fn filter_addresses_by_seed_name_and_name (identities: &Tree, seed_name: &str, n
     Ok(out)
 }
 
-/// get all identities for given seed_name and network_key as hex string
+/// get all identities for given seed_name and network_key as hex string;
+/// parachains have own genesis hash and thus own network key,
+/// relay chain information from network specs is not needed here
 pub fn get_relevant_identities (seed_name: &str, network_key_string: &str, database_name: &str) -> anyhow::Result<Vec<(AddressKey, AddressDetails)>> {
     
-    let network_key = unhex(network_key_string, NotHex::NetworkKey)?; //TODO: add whatever is needed for parachains?
+    let network_key = unhex(network_key_string, NotHex::NetworkKey)?;
     let database = open_db(database_name)?;
     let identities_out = {
         if seed_name == "" {get_all_identities(&database)?}
@@ -90,6 +98,17 @@ pub fn get_relevant_identities (seed_name: &str, network_key_string: &str, datab
     Ok(identities_out.into_iter().filter(|(_, details)| details.network_id.contains(&network_key)).collect())
 }
 
+/// print identity tags as json array
+fn print_tags (tags: &Vec<String>) -> String {
+    let mut out = String::from("[");
+    for (i, x) in tags.iter().enumerate() {
+        if i>0 {out.push_str(",")}
+        out.push_str(&format!("\"{}\"", escape_json(x)));
+    }
+    out.push_str("]");
+    out
+}
+
 /// Function to print all relevant identities for given seed_name and network_key as hex string
 pub fn print_relevant_identities (seed_name: &str, network_key_string: &str, database_name: &str) -> anyhow::Result<String> {
     let relevant_identities = get_relevant_identities (seed_name, network_key_string, database_name)?;
@@ -102,7 +121,7 @@ pub fn print_relevant_identities (seed_name: &str, network_key_string: &str, dat
             Err(e) => return Err(Error::Base58(e.to_string()).show()),
         };
         let public_key_helper = reverse_address_key(&address_key)?;
-        let new = format!("{{\"public_key\":\"{}\",\"encryption\":\"{}\",\"ss58\":\"{}\",\"path\":\"{}\",\"has_password\":\"{}\",\"name\":\"{}\",\"seed_name\":\"{}\"}}", hex::encode(public_key_helper.public_key), public_key_helper.encryption.show(), base58print, address_details.path, address_details.has_pwd, address_details.name, address_details.seed_name);
+        let new = format!("{{\"public_key\":\"{}\",\"encryption\":\"{}\",\"ss58\":\"{}\",\"path\":\"{}\",\"has_password\":\"{}\",\"name\":\"{}\",\"seed_name\":\"{}\",\"tags\":{},\"notes\":\"{}\"}}", hex::encode(public_key_helper.public_key), public_key_helper.encryption.show(), base58print, address_details.path, address_details.has_pwd, address_details.name, address_details.seed_name, print_tags(&address_details.tags), escape_json(&address_details.notes));
         out.push_str(&new);
     }
     out.push_str("]");
@@ -125,7 +144,7 @@ pub fn print_all_identities (database_name: &str) -> anyhow::Result<String> {
                 Err(e) => return Err(Error::Base58(e.to_string()).show()),
             };
             let public_key_helper = reverse_address_key(&address_key.to_vec())?;
-            let new = format!("{{\"public_key\":\"{}\",\"encryption\":\"{}\",\"ss58\":\"{}\",\"path\":\"{}\",\"has_password\":\"{}\",\"name\":\"{}\",\"seed_name\":\"{}\"}}", hex::encode(public_key_helper.public_key), public_key_helper.encryption.show(), base58print, address_details.path, address_details.has_pwd, address_details.name, address_details.seed_name);
+            let new = format!("{{\"public_key\":\"{}\",\"encryption\":\"{}\",\"ss58\":\"{}\",\"path\":\"{}\",\"has_password\":\"{}\",\"name\":\"{}\",\"seed_name\":\"{}\",\"tags\":{},\"notes\":\"{}\"}}", hex::encode(public_key_helper.public_key), public_key_helper.encryption.show(), base58print, address_details.path, address_details.has_pwd, address_details.name, address_details.seed_name, print_tags(&address_details.tags), escape_json(&address_details.notes));
             out.push_str(&new);
         }
     }
@@ -133,6 +152,96 @@ pub fn print_all_identities (database_name: &str) -> anyhow::Result<String> {
     Ok(out)
 }
 
+/// Function to get public key from user input,
+/// input could be ss58 address with any base58 prefix, or public key as hex string (with or without 0x)
+fn public_key_from_address (address: &str) -> anyhow::Result<Vec<u8>> {
+    if let Ok(public_key) = hex::decode(address.trim_start_matches("0x")) {
+        if (public_key.len() == 32)||(public_key.len() == 33) {return Ok(public_key)}
+    }
+    if let Ok((public, _)) = sr25519::Public::from_ss58check_with_version(address) {return Ok(public.0.to_vec())}
+    if let Ok((public, _)) = ecdsa::Public::from_ss58check_with_version(address) {return Ok(public.0.to_vec())}
+    return Err(Error::AddressInput(address.to_string()).show())
+}
+
+/// Function to find identities with given public key in address tree;
+/// all encryption algorithms are tried, since public key alone does not define encryption
+pub fn find_identities_by_public_key (public_key: &Vec<u8>, identities: &Tree) -> anyhow::Result<Vec<(AddressKey, AddressDetails)>> {
+    let mut out: Vec<(AddressKey, AddressDetails)> = Vec::new();
+    for encryption in vec![Encryption::Ed25519, Encryption::Sr25519, Encryption::Ecdsa] {
+        if let Ok(address_key) = generate_address_key(public_key, encryption) {
+            match identities.get(&address_key) {
+                Ok(Some(address_details_encoded)) => {
+                    let address_details = decode_address_details(address_details_encoded)?;
+                    out.push((address_key, address_details));
+                },
+                Ok(None) => (),
+                Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Function to find identities by ss58 address with any base58 prefix or by hex public key
+pub fn find_identities_by_address (address: &str, database_name: &str) -> anyhow::Result<Vec<(AddressKey, AddressDetails)>> {
+    let public_key = public_key_from_address(address)?;
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    find_identities_by_public_key(&public_key, &identities)
+}
+
+/// Function to print identities found by ss58 address or hex public key,
+/// together with the networks each identity is associated with;
+/// ss58 line for each network is printed with the network base58prefix
+pub fn print_identities_by_address (address: &str, database_name: &str) -> anyhow::Result<String> {
+    let found_identities = find_identities_by_address(address, database_name)?;
+    let database = open_db(database_name)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+    let mut out = String::from("[");
+    for (i, (address_key, address_details)) in found_identities.iter().enumerate() {
+        if i>0 {out.push_str(",")}
+        let public_key_helper = reverse_address_key(&address_key)?;
+        let mut networks_print = String::from("[");
+        for (j, network_key) in address_details.network_id.iter().enumerate() {
+            if j>0 {networks_print.push_str(",")}
+            let network_specs = get_and_decode_chain_specs(&chainspecs, network_key)?;
+            let base58print = match print_as_base58 (&address_key, address_details.encryption, Some(network_specs.base58prefix)) {
+                Ok(a) => a,
+                Err(e) => return Err(Error::Base58(e.to_string()).show()),
+            };
+            networks_print.push_str(&format!("{{\"genesis_hash\":\"{}\",\"title\":\"{}\",\"ss58\":\"{}\"}}", hex::encode(&network_specs.genesis_hash), network_specs.title, base58print));
+        }
+        networks_print.push_str("]");
+        let new = format!("{{\"public_key\":\"{}\",\"encryption\":\"{}\",\"path\":\"{}\",\"has_password\":\"{}\",\"name\":\"{}\",\"seed_name\":\"{}\",\"tags\":{},\"notes\":\"{}\",\"networks\":{}}}", hex::encode(public_key_helper.public_key), public_key_helper.encryption.show(), address_details.path, address_details.has_pwd, address_details.name, address_details.seed_name, print_tags(&address_details.tags), escape_json(&address_details.notes), networks_print);
+        out.push_str(&new);
+    }
+    out.push_str("]");
+    Ok(out)
+}
+
+/// get encryption from its name
+fn get_encryption (encryption_name: &str) -> anyhow::Result<Encryption> {
+    match encryption_name {
+        "ed25519" => Ok(Encryption::Ed25519),
+        "sr25519" => Ok(Encryption::Sr25519),
+        "ecdsa" => Ok(Encryption::Ecdsa),
+        _ => return Err(Error::UnknownEncryption.show()),
+    }
+}
+
+/// get address key and address details for public key as hex string and known encryption
+fn get_address_details (identities: &Tree, pub_key: &str, encryption: Encryption) -> anyhow::Result<(AddressKey, AddressDetails)> {
+    let address_key = generate_address_key(&unhex(pub_key, NotHex::PublicKey)?, encryption)?;
+    match identities.get(&address_key) {
+        Ok(Some(address_details_encoded)) => {
+            let address_details = decode_address_details(address_details_encoded)?;
+            Ok((address_key, address_details))
+        },
+        Ok(None) => return Err(Error::NotFound(NotFound::Address).show()),
+        Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+    }
+}
+
 /// generate random phrase with given number of words
 fn generate_random_phrase (words_number: u32) -> anyhow::Result<String> {
     let mnemonic_type = MnemonicType::for_word_count(words_number as usize)?;
@@ -140,18 +249,23 @@ fn generate_random_phrase (words_number: u32) -> anyhow::Result<String> {
     Ok(mnemonic.into_phrase())
 }
 
-/// Create address from seed and path and insert it into the database
-fn create_address (database: &Db, path: &str, network_key: NetworkKey, name: &str, seed_object: &SeedObject, has_pwd: bool) -> anyhow::Result<()> {
+/// Cut password part from derivation path
+fn crop_path (path: &str) -> String {
+    match REG_PATH.captures(path) {
+        Some(caps) => match caps.name("path") {
+            Some(a) => a.as_str().to_string(),
+            None => String::new(),
+        },
+        None => String::new(),
+    }
+}
+
+/// Derive public key from seed and path
+pub fn derive_public_key (seed_object: &SeedObject, path: &str) -> anyhow::Result<Vec<u8>> {
 
     // TODO: check zeroize
 
     let mut full_address = seed_object.seed_phrase.to_owned() + path;
-    let chainspecs = open_tree(&database, SPECSTREE)?;
-    let history = open_tree(&database, HISTORY)?;
-    
-    if !chainspecs.contains_key(&network_key)? {return Err(Error::CreateAddress(CreateAddress::NetworkNotFound).show())}
-    if get_network_encryption (&chainspecs, &network_key)? != seed_object.encryption {return Err(Error::CreateAddress(CreateAddress::EncryptionMismatch).show())}
-    
     let public_key = match seed_object.encryption {
         Encryption::Ed25519 => {
             match ed25519::Pair::from_string(&full_address, None) {
@@ -173,6 +287,19 @@ fn create_address (database: &Db, path: &str, network_key: NetworkKey, name: &st
         },
     };
     full_address.zeroize();
+    Ok(public_key)
+}
+
+/// Create address from seed and path and insert it into the database
+fn create_address (database: &Db, path: &str, network_key: NetworkKey, name: &str, seed_object: &SeedObject, has_pwd: bool) -> anyhow::Result<()> {
+
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+    
+    if !chainspecs.contains_key(&network_key)? {return Err(Error::CreateAddress(CreateAddress::NetworkNotFound).show())}
+    if get_network_encryption (&chainspecs, &network_key)? != seed_object.encryption {return Err(Error::CreateAddress(CreateAddress::EncryptionMismatch).show())}
+    
+    let public_key = derive_public_key(seed_object, path)?;
     
     let identity_history_print = IdentityHistory {
         seed_name: &seed_object.seed_name,
@@ -206,20 +333,15 @@ fn create_address (database: &Db, path: &str, network_key: NetworkKey, name: &st
             let collided = filter_addresses_by_seed_name_and_name(&identities, &seed_name, name)?;
             if collided.len() !=0 {return Err(Error::IdentityExists.show())}
             
-            let cropped_path = match REG_PATH.captures(path) {
-                Some(caps) => match caps.name("path") {
-                    Some(a) => a.as_str(),
-                    None => "",
-                },
-                None => "",
-            };
             let address_details = AddressDetails {
                 seed_name,
-                path: cropped_path.to_string(),
+                path: crop_path(path),
                 has_pwd,
                 name: name.to_string(),
                 network_id: vec![network_key],
                 encryption: seed_object.encryption,
+                tags: Vec::new(),
+                notes: String::new(),
             };
             insert_into_tree(address_key, address_details.encode(), &identities)?;
             enter_events_into_tree(&history, events)?;
@@ -265,12 +387,7 @@ pub fn try_create_seed (seed_name: &str, encryption_name: &str, seed_phrase_prop
 
 // TODO: zeroize seed
 
-    let encryption = match encryption_name {
-        "ed25519" => Encryption::Ed25519,
-        "sr25519" => Encryption::Sr25519,
-        "ecdsa" => Encryption::Ecdsa,
-        _ => return Err(Error::UnknownEncryption.show()),
-    };
+    let encryption = get_encryption(encryption_name)?;
     
     let seed_object = SeedObject {
         seed_name: seed_name.to_string(),
@@ -358,6 +475,221 @@ pub fn delete_address(pub_key: &str, network_key_string: &str, database_name: &s
     Ok(())
 }
 
+/// Function to rename identity;
+/// new name should not be used by other identities of the same seed
+pub fn rename_identity (pub_key: &str, encryption_name: &str, new_name: &str, database_name: &str) -> anyhow::Result<()> {
+    let encryption = get_encryption(encryption_name)?;
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let (address_key, mut address_details) = get_address_details(&identities, pub_key, encryption)?;
+    if address_details.name == new_name {return Ok(())}
+    let collided = filter_addresses_by_seed_name_and_name(&identities, &address_details.seed_name, new_name)?;
+    if collided.len() !=0 {return Err(Error::IdentityExists.show())}
+
+    let identity_details_history_print = IdentityDetailsHistory {
+        seed_name: &address_details.seed_name,
+        encryption,
+        public_key: &pub_key,
+        path: &address_details.path,
+        old_value: &address_details.name,
+        new_value: new_name,
+    }.show();
+    address_details.name = new_name.to_string();
+    insert_into_tree(address_key, address_details.encode(), &identities)?;
+    enter_events_into_tree(&history, vec![Event::IdentityRenamed(identity_details_history_print)])?;
+    flush_db(&database)?;
+    Ok(())
+}
+
+/// Function to add tag to identity
+pub fn add_identity_tag (pub_key: &str, encryption_name: &str, tag: &str, database_name: &str) -> anyhow::Result<()> {
+    let encryption = get_encryption(encryption_name)?;
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let (address_key, mut address_details) = get_address_details(&identities, pub_key, encryption)?;
+    if address_details.tags.iter().any(|x| x == tag) {return Err(Error::TagExists(tag.to_string()).show())}
+
+    let identity_details_history_print = IdentityDetailsHistory {
+        seed_name: &address_details.seed_name,
+        encryption,
+        public_key: &pub_key,
+        path: &address_details.path,
+        old_value: "",
+        new_value: tag,
+    }.show();
+    address_details.tags.push(tag.to_string());
+    insert_into_tree(address_key, address_details.encode(), &identities)?;
+    enter_events_into_tree(&history, vec![Event::IdentityTagAdded(identity_details_history_print)])?;
+    flush_db(&database)?;
+    Ok(())
+}
+
+/// Function to remove tag from identity
+pub fn remove_identity_tag (pub_key: &str, encryption_name: &str, tag: &str, database_name: &str) -> anyhow::Result<()> {
+    let encryption = get_encryption(encryption_name)?;
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let (address_key, mut address_details) = get_address_details(&identities, pub_key, encryption)?;
+    if !address_details.tags.iter().any(|x| x == tag) {return Err(Error::NotFound(NotFound::Tag(tag.to_string())).show())}
+
+    let identity_details_history_print = IdentityDetailsHistory {
+        seed_name: &address_details.seed_name,
+        encryption,
+        public_key: &pub_key,
+        path: &address_details.path,
+        old_value: tag,
+        new_value: "",
+    }.show();
+    address_details.tags = address_details.tags.into_iter().filter(|x| x != tag).collect();
+    insert_into_tree(address_key, address_details.encode(), &identities)?;
+    enter_events_into_tree(&history, vec![Event::IdentityTagRemoved(identity_details_history_print)])?;
+    flush_db(&database)?;
+    Ok(())
+}
+
+/// Function to set notes for identity; empty notes line clears the notes
+pub fn set_identity_notes (pub_key: &str, encryption_name: &str, notes: &str, database_name: &str) -> anyhow::Result<()> {
+    let encryption = get_encryption(encryption_name)?;
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let (address_key, mut address_details) = get_address_details(&identities, pub_key, encryption)?;
+    if address_details.notes == notes {return Ok(())}
+
+    let identity_details_history_print = IdentityDetailsHistory {
+        seed_name: &address_details.seed_name,
+        encryption,
+        public_key: &pub_key,
+        path: &address_details.path,
+        old_value: &address_details.notes,
+        new_value: notes,
+    }.show();
+    address_details.notes = notes.to_string();
+    insert_into_tree(address_key, address_details.encode(), &identities)?;
+    enter_events_into_tree(&history, vec![Event::IdentityNotesUpdated(identity_details_history_print)])?;
+    flush_db(&database)?;
+    Ok(())
+}
+
+/// Function to enable network for existing identity;
+/// seed is not needed, since the public key is already known;
+/// the network must have the same encryption as the identity
+pub fn enable_identity_network (pub_key: &str, network_key_string: &str, database_name: &str) -> anyhow::Result<()> {
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+
+    let network_key = unhex(network_key_string, NotHex::NetworkKey)?;
+    let encryption = get_network_encryption (&chainspecs, &network_key)?;
+    let (address_key, mut address_details) = get_address_details(&identities, pub_key, encryption)?;
+    if address_details.network_id.contains(&network_key) {return Err(Error::NetworkAlreadyEnabled.show())}
+
+    let identity_history_print = IdentityHistory {
+        seed_name: &address_details.seed_name,
+        encryption,
+        public_key: &pub_key,
+        path: &address_details.path,
+        network_genesis_hash: &hex::encode(&reverse_network_key(&network_key)?.genesis_hash),
+    }.show();
+    address_details.network_id.push(network_key);
+    insert_into_tree(address_key, address_details.encode(), &identities)?;
+    enter_events_into_tree(&history, vec![Event::IdentityNetworkEnabled(identity_history_print)])?;
+    flush_db(&database)?;
+    Ok(())
+}
+
+/// Function to disable network for identity;
+/// unlike delete_address, the identity record is kept even if no networks remain enabled,
+/// so that the network could be enabled again without the seed
+pub fn disable_identity_network (pub_key: &str, network_key_string: &str, database_name: &str) -> anyhow::Result<()> {
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+
+    let network_key = unhex(network_key_string, NotHex::NetworkKey)?;
+    let encryption = get_network_encryption (&chainspecs, &network_key)?;
+    let (address_key, mut address_details) = get_address_details(&identities, pub_key, encryption)?;
+    if !address_details.network_id.contains(&network_key) {return Err(Error::NetworkNotEnabled.show())}
+
+    let identity_history_print = IdentityHistory {
+        seed_name: &address_details.seed_name,
+        encryption,
+        public_key: &pub_key,
+        path: &address_details.path,
+        network_genesis_hash: &hex::encode(&reverse_network_key(&network_key)?.genesis_hash),
+    }.show();
+    address_details.network_id = address_details.network_id.into_iter().filter(|id| *id != network_key).collect();
+    insert_into_tree(address_key, address_details.encode(), &identities)?;
+    enter_events_into_tree(&history, vec![Event::IdentityNetworkDisabled(identity_history_print)])?;
+    flush_db(&database)?;
+    Ok(())
+}
+
+/// Function to rename seed_name in all identities associated with it;
+/// new seed_name should not be already in use;
+/// seed phrase in the native secure storage is keyed by seed name and is renamed by the caller
+pub fn rename_seed (seed_name: &str, new_seed_name: &str, database_name: &str) -> anyhow::Result<()> {
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let seed_identities = filter_addresses_by_seed_name(&identities, seed_name)?;
+    if seed_identities.len() == 0 {return Err(Error::NotFound(NotFound::SeedName(seed_name.to_string())).show())}
+    if filter_addresses_by_seed_name(&identities, new_seed_name)?.len() != 0 {return Err(Error::SeedNameExists(new_seed_name.to_string()).show())}
+
+    let mut batch = Batch::default();
+    for (address_key, mut address_details) in seed_identities.into_iter() {
+        address_details.seed_name = new_seed_name.to_string();
+        batch.insert(address_key, address_details.encode());
+    }
+    let seed_rename_history_print = SeedRenameHistory {
+        old_seed_name: seed_name,
+        new_seed_name,
+    }.show();
+    apply_batch_and_enter_events(&identities, batch, &history, vec![Event::SeedRenamed(seed_rename_history_print)])?;
+    flush_db(&database)?;
+    Ok(())
+}
+
+/// Function to re-encode identities stored before identity tags and notes were introduced,
+/// as identities with no tags and empty notes;
+/// entries already in current format are left untouched;
+/// outputs the number of migrated entries
+pub fn migrate_address_details (database_name: &str) -> anyhow::Result<u32> {
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    
+    let mut batch = Batch::default();
+    let mut count = 0;
+    for x in identities.iter() {
+        let (address_key, address_details_encoded) = match x {
+            Ok(a) => a,
+            Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+        };
+        if <AddressDetails>::decode(&mut &address_details_encoded[..]).is_err() {
+            match <AddressDetailsNoTags>::decode(&mut &address_details_encoded[..]) {
+                Ok(a) => {
+                    batch.insert(address_key, a.with_no_tags().encode());
+                    count = count + 1;
+                },
+                Err(_) => return Err(Error::NotDecodeable(NotDecodeable::AddressDetails).show()),
+            }
+        }
+    }
+    if let Err(e) = identities.apply_batch(batch) {return Err(Error::InternalDatabaseError(e).show())}
+    flush_db(&database)?;
+    Ok(count)
+}
+
 /// Suggest address and name for weird N+1 feature request
 pub fn suggest_n_plus_one(path: &str, seed_name: &str, network_key_string: &str, database_name: &str) -> anyhow::Result<String> {
     let identities = get_relevant_identities(seed_name, network_key_string, database_name)?;
@@ -386,12 +718,7 @@ pub fn check_derivation_format(path: &str) -> anyhow::Result<bool> {
 pub fn try_create_address (id_name: &str, seed_name: &str, seed_phrase: &str, encryption_name: &str, path: &str, network_key_string: &str, has_pwd: bool, database_name: &str) -> anyhow::Result<()> {
     let database = open_db(database_name)?;
 
-    let encryption = match encryption_name {
-        "ed25519" => Encryption::Ed25519,
-        "sr25519" => Encryption::Sr25519,
-        "ecdsa" => Encryption::Ecdsa,
-        _ => return Err(Error::UnknownEncryption.show()),
-    };
+    let encryption = get_encryption(encryption_name)?;
     
     let seed_object = SeedObject {
         seed_name: seed_name.to_string(),
@@ -404,6 +731,155 @@ pub fn try_create_address (id_name: &str, seed_name: &str, seed_phrase: &str, en
     create_address(&database, path, network_key, id_name, &seed_object, has_pwd)
 }
 
+/// Find name of the seed with given seed phrase and encryption;
+/// root address (empty path) is created for every seed and is used for search
+pub fn get_seed_name_by_phrase (seed_phrase: &str, encryption: Encryption, database_name: &str) -> anyhow::Result<String> {
+    let seed_object = SeedObject {
+        seed_name: String::new(),
+        seed_phrase: seed_phrase.to_string(),
+        encryption,
+    };
+    let public_key = derive_public_key(&seed_object, "")?;
+    let address_key = generate_address_key(&public_key, encryption)?;
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    match identities.get(&address_key) {
+        Ok(Some(address_details_encoded)) => Ok(decode_address_details(address_details_encoded)?.seed_name),
+        Ok(None) => return Err(Error::NotFound(NotFound::SeedPhrase).show()),
+        Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+    }
+}
+
+/// get range placeholder and range bounds from derivation path template
+fn parse_path_template (path_template: &str) -> anyhow::Result<(String, u32, u32)> {
+    let mut found = REG_RANGE.captures_iter(path_template);
+    let caps = match found.next() {
+        Some(a) => a,
+        None => return Err(Error::PathTemplate.show()),
+    };
+    if found.next().is_some() {return Err(Error::PathTemplate.show())}
+    let start = match caps["start"].parse::<u32>() {
+        Ok(a) => a,
+        Err(_) => return Err(Error::PathTemplate.show()),
+    };
+    let end = match caps["end"].parse::<u32>() {
+        Ok(a) => a,
+        Err(_) => return Err(Error::PathTemplate.show()),
+    };
+    if start > end {return Err(Error::PathTemplate.show())}
+    if end - start >= MAX_BULK_DERIVATIONS {return Err(Error::BulkRangeTooLarge(MAX_BULK_DERIVATIONS).show())}
+    Ok((caps[0].to_string(), start, end))
+}
+
+/// Function to create addresses in bulk, for derivation path template with range, e.g. `//stash//{0..49}`;
+/// range bounds are inclusive, `{n}` in name template is replaced by the index,
+/// network keys are hex strings separated by commas;
+/// at most MAX_BULK_DERIVATIONS addresses could be created at once;
+/// addresses are created together with history entry in single atomic operation and only if no collisions were found;
+/// in dry run mode nothing is written, and derived public keys and found collisions are returned
+pub fn try_create_addresses_bulk (name_template: &str, seed_name: &str, seed_phrase: &str, encryption_name: &str, path_template: &str, network_keys_string: &str, dry_run: bool, database_name: &str) -> anyhow::Result<String> {
+    let encryption = get_encryption(encryption_name)?;
+    let seed_object = SeedObject {
+        seed_name: seed_name.to_string(),
+        seed_phrase: seed_phrase.to_string(),
+        encryption,
+    };
+    let (range_line, start, end) = parse_path_template(path_template)?;
+
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let mut network_keys: Vec<NetworkKey> = Vec::new();
+    for x in network_keys_string.split(',') {
+        let network_key = unhex(x.trim(), NotHex::NetworkKey)?;
+        if !chainspecs.contains_key(&network_key)? {return Err(Error::CreateAddress(CreateAddress::NetworkNotFound).show())}
+        if get_network_encryption (&chainspecs, &network_key)? != encryption {return Err(Error::CreateAddress(CreateAddress::EncryptionMismatch).show())}
+        if !network_keys.contains(&network_key) {network_keys.push(network_key)}
+    }
+
+    let mut prepared: Vec<(AddressKey, AddressDetails)> = Vec::new();
+    let mut events: Vec<Event> = Vec::new();
+    let mut derivations_print = String::new();
+    let mut collisions_print = String::new();
+
+    for index in start..=end {
+        let path = path_template.replacen(&range_line, &index.to_string(), 1);
+        let name = name_template.replace("{n}", &index.to_string());
+        let has_pwd = check_derivation_format(&path)?;
+        let public_key = derive_public_key(&seed_object, &path)?;
+        let address_key = generate_address_key(&public_key, encryption)?;
+        let cropped_path = crop_path(&path);
+        
+        if derivations_print.len()>0 {derivations_print.push_str(",")}
+        derivations_print.push_str(&format!("{{\"path\":\"{}\",\"name\":\"{}\",\"public_key\":\"{}\",\"has_password\":\"{}\"}}", cropped_path, name, hex::encode(&public_key), has_pwd));
+        
+        let mut address_details = {
+            if prepared.iter().any(|(_, details)| details.name == name) {
+                if collisions_print.len()>0 {collisions_print.push_str(",")}
+                collisions_print.push_str(&format!("{{\"path\":\"{}\",\"name\":\"{}\",\"error\":\"{}\"}}", cropped_path, name, Error::IdentityExists.show()));
+                continue;
+            }
+            match identities.get(&address_key) {
+                Ok(Some(address_details_encoded)) => {
+                    let address_details = decode_address_details(address_details_encoded)?;
+                    if address_details.name != name || address_details.path != cropped_path {
+                        if collisions_print.len()>0 {collisions_print.push_str(",")}
+                        collisions_print.push_str(&format!("{{\"path\":\"{}\",\"name\":\"{}\",\"error\":\"{}\"}}", cropped_path, name, Error::AddressKeyCollision{name: address_details.name, seed_name: address_details.seed_name}.show()));
+                        continue;
+                    }
+                    address_details
+                },
+                Ok(None) => {
+                    if filter_addresses_by_seed_name_and_name(&identities, seed_name, &name)?.len() != 0 {
+                        if collisions_print.len()>0 {collisions_print.push_str(",")}
+                        collisions_print.push_str(&format!("{{\"path\":\"{}\",\"name\":\"{}\",\"error\":\"{}\"}}", cropped_path, name, Error::IdentityExists.show()));
+                        continue;
+                    }
+                    AddressDetails {
+                        seed_name: seed_name.to_string(),
+                        path: cropped_path.to_string(),
+                        has_pwd,
+                        name: name.to_string(),
+                        network_id: Vec::new(),
+                        encryption,
+                        tags: Vec::new(),
+                        notes: String::new(),
+                    }
+                },
+                Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+            }
+        };
+        for network_key in network_keys.iter() {
+            if !address_details.network_id.contains(network_key) {
+                let identity_history_print = IdentityHistory {
+                    seed_name,
+                    encryption,
+                    public_key: &hex::encode(&public_key),
+                    path: &cropped_path,
+                    network_genesis_hash: &hex::encode(&reverse_network_key(network_key)?.genesis_hash),
+                }.show();
+                events.push(Event::IdentityAdded(identity_history_print));
+                address_details.network_id.push(network_key.to_vec());
+            }
+        }
+        prepared.push((address_key, address_details));
+    }
+    
+    let out = format!("{{\"derivations\":[{}],\"collisions\":[{}]}}", derivations_print, collisions_print);
+    if dry_run {return Ok(out)}
+    if collisions_print.len() != 0 {return Err(Error::BulkCollisions(collisions_print).show())}
+    
+    let mut batch = Batch::default();
+    for (address_key, address_details) in prepared.into_iter() {
+        batch.insert(address_key, address_details.encode());
+    }
+    apply_batch_and_enter_events(&identities, batch, &history, events)?;
+    flush_db(&database)?;
+    Ok(out)
+}
+
 /// Function to populate test cold database with Alice information
 pub fn load_test_identities (database_name: &str) -> anyhow::Result<()> {
     let database = open_db(database_name)?;
@@ -492,6 +968,101 @@ pub fn export_identity (pub_key: &str, network_key_string: &str, database_name:
     else {return Err(Error::NotFound(NotFound::NetworkKey).show())}
 }
 
+/// Make watch-only entry for identity with networks from the given list
+fn make_watch_only_entry (chainspecs: &Tree, address_key: &AddressKey, address_details: &AddressDetails, network_keys: &Vec<NetworkKey>, include_paths: bool) -> anyhow::Result<WatchOnlyEntry> {
+    let mut networks: Vec<WatchOnlyNetwork> = Vec::new();
+    for network_key in network_keys.iter() {
+        let chain_specs = get_and_decode_chain_specs(chainspecs, network_key)?;
+        networks.push(WatchOnlyNetwork {
+            genesis_hash: chain_specs.genesis_hash,
+            base58prefix: chain_specs.base58prefix,
+        });
+    }
+    let path = {
+        if include_paths {Some(address_details.path.to_string())}
+        else {None}
+    };
+    Ok(WatchOnlyEntry {
+        seed_name: address_details.seed_name.to_string(),
+        name: address_details.name.to_string(),
+        public_key: reverse_address_key(address_key)?.public_key,
+        encryption: address_details.encryption,
+        path,
+        networks,
+    })
+}
+
+/// Function to collect all identities of the seed for watch-only export
+pub fn seed_watch_only_entries (seed_name: &str, include_paths: bool, database_name: &str) -> anyhow::Result<Vec<WatchOnlyEntry>> {
+    let database = open_db(database_name)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+    let seed_identities = get_seed_identities(&database, seed_name)?;
+    if seed_identities.len() == 0 {return Err(Error::NotFound(NotFound::SeedName(seed_name.to_string())).show())}
+    let mut out: Vec<WatchOnlyEntry> = Vec::new();
+    for (address_key, address_details) in seed_identities.iter() {
+        out.push(make_watch_only_entry(&chainspecs, address_key, address_details, &address_details.network_id, include_paths)?);
+    }
+    Ok(out)
+}
+
+/// Function to collect selected identities for watch-only export;
+/// selection is comma-separated list of `<public key hex>:<network key hex>` pairs,
+/// identities could be from different seeds, networks for same identity are grouped together
+pub fn selected_watch_only_entries (selection: &str, include_paths: bool, database_name: &str) -> anyhow::Result<Vec<WatchOnlyEntry>> {
+    let database = open_db(database_name)?;
+    let identities = open_tree(&database, ADDRTREE)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+    let mut selected: Vec<(AddressKey, AddressDetails, Vec<NetworkKey>)> = Vec::new();
+    for pair in selection.split(',') {
+        let mut pair_split = pair.trim().splitn(2, ':');
+        let (pub_key, network_key_string) = match (pair_split.next(), pair_split.next()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return Err(Error::AddressInput(pair.to_string()).show()),
+        };
+        let network_key = unhex(network_key_string, NotHex::NetworkKey)?;
+        let encryption = get_network_encryption(&chainspecs, &network_key)?;
+        let address_key = generate_address_key(&unhex(pub_key, NotHex::PublicKey)?, encryption)?;
+        match selected.iter_mut().find(|(key, _, _)| *key == address_key) {
+            Some((_, address_details, network_keys)) => {
+                if !address_details.network_id.contains(&network_key) {return Err(Error::NotFound(NotFound::NetworkKey).show())}
+                if !network_keys.contains(&network_key) {network_keys.push(network_key)}
+            },
+            None => {
+                let address_details = match identities.get(&address_key) {
+                    Ok(Some(address_details_encoded)) => decode_address_details(address_details_encoded)?,
+                    Ok(None) => return Err(Error::NotFound(NotFound::Address).show()),
+                    Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+                };
+                if !address_details.network_id.contains(&network_key) {return Err(Error::NotFound(NotFound::NetworkKey).show())}
+                selected.push((address_key, address_details, vec![network_key]));
+            },
+        }
+    }
+    let mut out: Vec<WatchOnlyEntry> = Vec::new();
+    for (address_key, address_details, network_keys) in selected.iter() {
+        out.push(make_watch_only_entry(&chainspecs, address_key, address_details, network_keys, include_paths)?);
+    }
+    Ok(out)
+}
+
+/// Make animated fountain qr code (apng, hex encoded) with watch-only export content
+fn watch_only_qr (entries: &Vec<WatchOnlyEntry>) -> anyhow::Result<String> {
+    let content = ContentWatchOnly::generate(entries);
+    match transform_into_qr_apng_vec(&content.to_vec()) {
+        Ok(a) => Ok(hex::encode(a)),
+        Err(e) => return Err(Error::WatchOnlyExport(e.to_string()).show()),
+    }
+}
+
+/// Function to export all identities of the seed as watch-only animated qr code
+pub fn export_seed_watch_only (seed_name: &str, include_paths: bool, database_name: &str) -> anyhow::Result<String> {
+    watch_only_qr(&seed_watch_only_entries(seed_name, include_paths, database_name)?)
+}
+
+/// Function to export selected identities as watch-only animated qr code
+pub fn export_selected_watch_only (selection: &str, include_paths: bool, database_name: &str) -> anyhow::Result<String> {
+    watch_only_qr(&selected_watch_only_entries(selection, include_paths, database_name)?)
+}
 
 #[cfg(test)]
 mod tests {
@@ -499,7 +1070,7 @@ mod tests {
     use definitions::{crypto::Encryption, defaults::get_default_chainspecs, network_specs::generate_network_key};
     use std::fs;
     use sled::{Db, Tree, open};
-    use crate::{chainspecs::load_chainspecs, helpers::reverse_address_key};
+    use crate::{chainspecs::load_chainspecs, helpers::reverse_address_key, manage_history::print_history};
 
     static SEED: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
     static ENCRYPTION_NAME: &str = "sr25519";
@@ -555,7 +1126,7 @@ mod tests {
         let chainspecs = get_default_chainspecs();
         let default_addresses = get_relevant_identities("Alice", &hex::encode(generate_network_key(&chainspecs[0].genesis_hash.to_vec(), Encryption::Sr25519)), dbname).unwrap();
         assert!(default_addresses.len()>0);
-        assert_eq!(r#"[([1, 70, 235, 221, 239, 140, 217, 187, 22, 125, 195, 8, 120, 215, 17, 59, 126, 22, 142, 111, 6, 70, 190, 255, 215, 125, 105, 211, 155, 173, 118, 180, 122], AddressDetails { seed_name: "Alice", path: "", has_pwd: false, name: "root address", network_id: [[1, 128, 133, 63, 175, 251, 252, 103, 19, 193, 248, 153, 191, 22, 84, 127, 207, 191, 115, 58, 232, 54, 27, 140, 160, 18, 150, 153, 208, 29, 79, 33, 129, 253], [1, 128, 145, 177, 113, 187, 21, 142, 45, 56, 72, 250, 35, 169, 241, 194, 81, 130, 251, 142, 32, 49, 59, 44, 30, 180, 146, 25, 218, 122, 112, 206, 144, 195], [1, 128, 176, 168, 212, 147, 40, 92, 45, 247, 50, 144, 223, 183, 230, 31, 135, 15, 23, 180, 24, 1, 25, 122, 20, 156, 169, 54, 84, 73, 158, 163, 218, 254], [1, 128, 225, 67, 242, 56, 3, 172, 80, 232, 246, 248, 230, 38, 149, 209, 206, 158, 78, 29, 104, 170, 54, 193, 205, 44, 253, 21, 52, 2, 19, 243, 66, 62]], encryption: Sr25519 }), ([1, 100, 163, 18, 53, 212, 191, 155, 55, 207, 237, 58, 250, 138, 166, 7, 84, 103, 95, 156, 73, 21, 67, 4, 84, 211, 101, 192, 81, 18, 120, 77, 5], AddressDetails { seed_name: "Alice", path: "//kusama", has_pwd: false, name: "kusama root address", network_id: [[1, 128, 176, 168, 212, 147, 40, 92, 45, 247, 50, 144, 223, 183, 230, 31, 135, 15, 23, 180, 24, 1, 25, 122, 20, 156, 169, 54, 84, 73, 158, 163, 218, 254]], encryption: Sr25519 })]"#, format!("{:?}", default_addresses)); //because JSON export is what we care about
+        assert_eq!(r#"[([1, 70, 235, 221, 239, 140, 217, 187, 22, 125, 195, 8, 120, 215, 17, 59, 126, 22, 142, 111, 6, 70, 190, 255, 215, 125, 105, 211, 155, 173, 118, 180, 122], AddressDetails { seed_name: "Alice", path: "", has_pwd: false, name: "root address", network_id: [[1, 128, 133, 63, 175, 251, 252, 103, 19, 193, 248, 153, 191, 22, 84, 127, 207, 191, 115, 58, 232, 54, 27, 140, 160, 18, 150, 153, 208, 29, 79, 33, 129, 253], [1, 128, 145, 177, 113, 187, 21, 142, 45, 56, 72, 250, 35, 169, 241, 194, 81, 130, 251, 142, 32, 49, 59, 44, 30, 180, 146, 25, 218, 122, 112, 206, 144, 195], [1, 128, 176, 168, 212, 147, 40, 92, 45, 247, 50, 144, 223, 183, 230, 31, 135, 15, 23, 180, 24, 1, 25, 122, 20, 156, 169, 54, 84, 73, 158, 163, 218, 254], [1, 128, 225, 67, 242, 56, 3, 172, 80, 232, 246, 248, 230, 38, 149, 209, 206, 158, 78, 29, 104, 170, 54, 193, 205, 44, 253, 21, 52, 2, 19, 243, 66, 62]], encryption: Sr25519, tags: [], notes: "" }), ([1, 100, 163, 18, 53, 212, 191, 155, 55, 207, 237, 58, 250, 138, 166, 7, 84, 103, 95, 156, 73, 21, 67, 4, 84, 211, 101, 192, 81, 18, 120, 77, 5], AddressDetails { seed_name: "Alice", path: "//kusama", has_pwd: false, name: "kusama root address", network_id: [[1, 128, 176, 168, 212, 147, 40, 92, 45, 247, 50, 144, 223, 183, 230, 31, 135, 15, 23, 180, 24, 1, 25, 122, 20, 156, 169, 54, 84, 73, 158, 163, 218, 254]], encryption: Sr25519, tags: [], notes: "" })]"#, format!("{:?}", default_addresses)); //because JSON export is what we care about
         let database: Db = open(dbname).unwrap();
         let identities: Tree = database.open_tree(ADDRTREE).unwrap();
         let test_key = generate_address_key(&hex::decode("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a").unwrap(), Encryption::Sr25519).unwrap();
@@ -602,6 +1173,28 @@ mod tests {
         fs::remove_dir_all(dbname).unwrap();
     }
 
+    #[test]
+    fn find_identities_by_ss58_and_hex() {
+        let dbname = "tests/find_identities_by_ss58_and_hex";
+        load_chainspecs(dbname).expect("create default database");
+        try_create_seed("Alice", ENCRYPTION_NAME, SEED, 0, dbname).unwrap();
+        let found_by_ss58 = find_identities_by_address("12bzRJfh7arnnfPPUZHeJUaE62QLEwhK48QnH9LXeK2m1iZU", dbname).unwrap();
+        let found_by_other_ss58 = find_identities_by_address("5DfhGyQdFobKM8NsWvEeAKk5EQQgYe9AydgJ7rMB6E1EqRzV", dbname).unwrap();
+        let found_by_hex = find_identities_by_address("0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a", dbname).unwrap();
+        assert!(found_by_ss58.len() == 1);
+        assert_eq!(format!("{:?}", found_by_ss58), format!("{:?}", found_by_other_ss58));
+        assert_eq!(format!("{:?}", found_by_ss58), format!("{:?}", found_by_hex));
+        assert!(found_by_ss58[0].1.seed_name == "Alice");
+        assert!(found_by_ss58[0].1.path == "");
+        let printed = print_identities_by_address("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a", dbname).unwrap();
+        assert!(printed.contains(r#""title":"Polkadot","ss58":"12bzRJfh7arnnfPPUZHeJUaE62QLEwhK48QnH9LXeK2m1iZU""#), "Found: {}", printed);
+        assert!(printed.contains(r#""title":"Westend","ss58":"5DfhGyQdFobKM8NsWvEeAKk5EQQgYe9AydgJ7rMB6E1EqRzV""#), "Found: {}", printed);
+        let not_found = find_identities_by_address("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48", dbname).unwrap();
+        assert!(not_found.len() == 0);
+        assert!(find_identities_by_address("not an address", dbname).is_err());
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
     #[test]
     fn test_derive() { 
         let dbname = "tests/test_derive";
@@ -709,5 +1302,173 @@ mod tests {
         assert!(flag_to_check_key0_remains, "An address that should have only lost network was removed entirely");
         fs::remove_dir_all(dbname).unwrap();
     }
-}
+    #[test]
+    fn rename_identity_and_manage_tags_and_notes() {
+        let dbname = "tests/rename_identity_and_manage_tags_and_notes";
+        load_chainspecs(dbname).expect("create default database");
+        try_create_seed("Alice", ENCRYPTION_NAME, SEED, 0, dbname).unwrap();
+        let root_public = "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a";
+        assert!(rename_identity(root_public, ENCRYPTION_NAME, "kusama root address", dbname).is_err(), "Name is already used by other identity of the same seed");
+        rename_identity(root_public, ENCRYPTION_NAME, "main", dbname).unwrap();
+        add_identity_tag(root_public, ENCRYPTION_NAME, "savings", dbname).unwrap();
+        add_identity_tag(root_public, ENCRYPTION_NAME, "cold", dbname).unwrap();
+        assert!(add_identity_tag(root_public, ENCRYPTION_NAME, "cold", dbname).is_err(), "Tag is already there");
+        remove_identity_tag(root_public, ENCRYPTION_NAME, "savings", dbname).unwrap();
+        assert!(remove_identity_tag(root_public, ENCRYPTION_NAME, "savings", dbname).is_err(), "Tag was already removed");
+        set_identity_notes(root_public, ENCRYPTION_NAME, "do not use for staking", dbname).unwrap();
+        assert!(rename_identity(root_public, FALSE_ENCRYPTION_NAME, "other", dbname).is_err(), "No such identity with ecdsa encryption");
+        let found = find_identities_by_address(root_public, dbname).unwrap();
+        assert!(found.len() == 1);
+        assert!(found[0].1.name == "main");
+        assert!(found[0].1.tags == vec![String::from("cold")]);
+        assert!(found[0].1.notes == "do not use for staking");
+        let history_printed = print_history(dbname).unwrap();
+        assert!(history_printed.contains(r#"{"event":"identity_renamed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a","path":"","old_value":"root address","new_value":"main"}}"#), "Found: {}", history_printed);
+        assert!(history_printed.contains(r#""event":"identity_tag_added""#));
+        assert!(history_printed.contains(r#""event":"identity_tag_removed""#));
+        assert!(history_printed.contains(r#""event":"identity_notes_updated""#));
+        add_identity_tag(root_public, ENCRYPTION_NAME, r#"say "hi""#, dbname).unwrap();
+        set_identity_notes(root_public, ENCRYPTION_NAME, "line with \\ and \"quotes\"\nnext line", dbname).unwrap();
+        let identities_printed = print_all_identities(dbname).unwrap();
+        assert!(identities_printed.contains(r#""tags":["cold","say \"hi\""],"notes":"line with \\ and \"quotes\"\nnext line""#), "Found: {}", identities_printed);
+        let history_printed = print_history(dbname).unwrap();
+        assert!(history_printed.contains(r#""old_value":"do not use for staking","new_value":"line with \\ and \"quotes\"\nnext line""#), "Found: {}", history_printed);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+    
+    #[test]
+    fn identities_without_tags_are_migrated() {
+        let dbname = "tests/identities_without_tags_are_migrated";
+        load_chainspecs(dbname).expect("create default database");
+        try_create_seed("Alice", ENCRYPTION_NAME, SEED, 0, dbname).unwrap();
+        let root_public = "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a";
+        let total = {
+            let database: Db = open(dbname).unwrap();
+            let identities: Tree = database.open_tree(ADDRTREE).unwrap();
+            let address_key = generate_address_key(&hex::decode(root_public).unwrap(), Encryption::Sr25519).unwrap();
+            let address_details = decode_address_details(identities.get(&address_key).unwrap().unwrap()).unwrap();
+            // entry in format without tags and notes
+            let address_details_no_tags = AddressDetailsNoTags {
+                seed_name: address_details.seed_name,
+                path: address_details.path,
+                has_pwd: address_details.has_pwd,
+                name: address_details.name,
+                network_id: address_details.network_id,
+                encryption: address_details.encryption,
+            };
+            identities.insert(address_key, address_details_no_tags.encode()).unwrap();
+            database.flush().unwrap();
+            identities.len()
+        };
+        assert!(print_all_identities(dbname).is_err(), "Entry without tags should not be decodeable as AddressDetails.");
+        assert!(migrate_address_details(dbname).unwrap() == 1, "Expected exactly one entry to be migrated.");
+        assert!(migrate_address_details(dbname).unwrap() == 0, "Migrated entries should not be processed again.");
+        let found = find_identities_by_address(root_public, dbname).unwrap();
+        assert!(found.len() == 1);
+        assert!(found[0].1.name == "root address");
+        assert!(found[0].1.tags.len() == 0);
+        assert!(found[0].1.notes == "");
+        assert!(get_all_identities(&open_db(dbname).unwrap()).unwrap().len() == total, "All identities should remain in the database.");
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn disable_and_enable_identity_network_and_rename_seed() {
+        let dbname = "tests/disable_and_enable_identity_network_and_rename_seed";
+        load_chainspecs(dbname).expect("create default database");
+        try_create_seed("Alice", ENCRYPTION_NAME, SEED, 0, dbname).unwrap();
+        let chainspecs = get_default_chainspecs();
+        let network_id_string_0 = hex::encode(generate_network_key(&chainspecs[0].genesis_hash.to_vec(), Encryption::Sr25519));
+        let root_public = "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a";
+        assert!(enable_identity_network(root_public, &network_id_string_0, dbname).is_err(), "Network is already enabled");
+        disable_identity_network(root_public, &network_id_string_0, dbname).unwrap();
+        assert!(disable_identity_network(root_public, &network_id_string_0, dbname).is_err(), "Network is already disabled");
+        let identities = get_relevant_identities("Alice", &network_id_string_0, dbname).unwrap();
+        assert!(!identities.iter().any(|(_, details)| details.path == ""), "Root identity should not be available in disabled network");
+        enable_identity_network(root_public, &network_id_string_0, dbname).unwrap();
+        let identities = get_relevant_identities("Alice", &network_id_string_0, dbname).unwrap();
+        assert!(identities.iter().any(|(_, details)| details.path == ""), "Root identity should be available again");
+        try_create_seed("Bob", ENCRYPTION_NAME, "", 24, dbname).unwrap();
+        assert!(rename_seed("Alice", "Bob", dbname).is_err(), "Seed name Bob is already used");
+        assert!(rename_seed("Carol", "Dave", dbname).is_err(), "No identities for seed name Carol");
+        rename_seed("Alice", "Eve", dbname).unwrap();
+        assert!(get_relevant_identities("Alice", &network_id_string_0, dbname).unwrap().len() == 0);
+        assert!(get_relevant_identities("Eve", &network_id_string_0, dbname).unwrap().len() > 0);
+        let history_printed = print_history(dbname).unwrap();
+        assert!(history_printed.contains(r#""event":"identity_network_disabled""#));
+        assert!(history_printed.contains(r#""event":"identity_network_enabled""#));
+        assert!(history_printed.contains(r#"{"event":"seed_renamed","payload":{"old_seed_name":"Alice","new_seed_name":"Eve"}}"#), "Found: {}", history_printed);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+    #[test]
+    fn bulk_derivation_with_dry_run_and_collisions() {
+        let dbname = "tests/bulk_derivation_with_dry_run_and_collisions";
+        load_chainspecs(dbname).expect("create default database");
+        let chainspecs = get_default_chainspecs();
+        let network_id_string_0 = hex::encode(generate_network_key(&chainspecs[0].genesis_hash.to_vec(), Encryption::Sr25519));
+        let network_id_string_1 = hex::encode(generate_network_key(&chainspecs[1].genesis_hash.to_vec(), Encryption::Sr25519));
+        let networks = format!("{},{}", network_id_string_0, network_id_string_1);
+        let dry_run_print = try_create_addresses_bulk("stash {n}", "Alice", SEED, ENCRYPTION_NAME, "//stash//{0..2}", &networks, true, dbname).unwrap();
+        assert!(dry_run_print.contains(r#""path":"//stash//2","name":"stash 2""#), "Found: {}", dry_run_print);
+        assert!(dry_run_print.ends_with(r#""collisions":[]}"#), "Found: {}", dry_run_print);
+        assert!(get_relevant_identities("Alice", &network_id_string_0, dbname).unwrap().len() == 0, "Dry run should not create identities");
+        try_create_addresses_bulk("stash {n}", "Alice", SEED, ENCRYPTION_NAME, "//stash//{0..2}", &networks, false, dbname).unwrap();
+        let history_print = print_history(dbname).unwrap();
+        assert!(history_print.matches("identity_added").count() == 6, "Expected history entry with all created identities, found: {}", history_print);
+        let identities = get_relevant_identities("Alice", &network_id_string_1, dbname).unwrap();
+        assert!(identities.len() == 3);
+        for (address_key, _) in identities.iter() {
+            let public_key = hex::encode(reverse_address_key(address_key).unwrap().public_key);
+            assert!(dry_run_print.contains(&public_key), "Dry run public keys should match created identities");
+        }
+        try_create_address("stash 4", "Alice", SEED, ENCRYPTION_NAME, "//other", &network_id_string_0, false, dbname).unwrap();
+        let dry_run_print = try_create_addresses_bulk("stash {n}", "Alice", SEED, ENCRYPTION_NAME, "//stash//{3..5}", &networks, true, dbname).unwrap();
+        assert!(dry_run_print.contains(r#""collisions":[{"path":"//stash//4","name":"stash 4","error":"Identity with this name already exists"}]"#), "Found: {}", dry_run_print);
+        assert!(try_create_addresses_bulk("stash {n}", "Alice", SEED, ENCRYPTION_NAME, "//stash//{3..5}", &networks, false, dbname).is_err(), "Collisions found, nothing should be created");
+        assert!(get_relevant_identities("Alice", &network_id_string_0, dbname).unwrap().len() == 4, "Only the earlier created identities should be there");
+        assert!(try_create_addresses_bulk("stash {n}", "Alice", SEED, ENCRYPTION_NAME, "//stash//{5..3}", &networks, true, dbname).is_err());
+        assert!(try_create_addresses_bulk("stash {n}", "Alice", SEED, ENCRYPTION_NAME, "//stash", &networks, true, dbname).is_err());
+        assert!(try_create_addresses_bulk("stash {n}", "Alice", SEED, ENCRYPTION_NAME, "//stash//{0..1000}", &networks, true, dbname).is_err(), "Range exceeding the limit should be rejected");
+        fs::remove_dir_all(dbname).unwrap();
+    }
 
+    #[test]
+    fn watch_only_export_seed_and_selection() {
+        let dbname = "tests/watch_only_export_seed_and_selection";
+        load_chainspecs(dbname).expect("create default database");
+        try_create_seed("Alice", ENCRYPTION_NAME, SEED, 0, dbname).unwrap();
+        let chainspecs = get_default_chainspecs();
+        let network_id_string_0 = hex::encode(generate_network_key(&chainspecs[0].genesis_hash.to_vec(), Encryption::Sr25519));
+        let network_id_string_1 = hex::encode(generate_network_key(&chainspecs[1].genesis_hash.to_vec(), Encryption::Sr25519));
+
+        let database: Db = open(dbname).unwrap();
+        let seed_identities_number = get_seed_identities(&database, "Alice").unwrap().len();
+        drop(database);
+        let entries = seed_watch_only_entries("Alice", false, dbname).unwrap();
+        assert!(entries.len() == seed_identities_number, "All seed identities should be exported");
+        assert!(entries.iter().all(|x| x.path.is_none()), "Paths should not be exported unless requested");
+        let decoded = ContentWatchOnly::from_vec(&ContentWatchOnly::generate(&entries).to_vec()).entries().unwrap();
+        assert!(decoded.len() == entries.len());
+        assert!(seed_watch_only_entries("Bob", false, dbname).is_err(), "Unknown seed should not be exported");
+
+        let root_public_key = {
+            let seed_object = SeedObject {seed_name: String::from("Alice"), seed_phrase: SEED.to_string(), encryption: Encryption::Sr25519};
+            hex::encode(derive_public_key(&seed_object, "").unwrap())
+        };
+        let selection = format!("{}:{},{}:{}", root_public_key, network_id_string_0, root_public_key, network_id_string_1);
+        let entries = selected_watch_only_entries(&selection, true, dbname).unwrap();
+        assert!(entries.len() == 1, "Same identity in different networks should be grouped");
+        assert!(entries[0].networks.len() == 2);
+        assert!(entries[0].networks[0].genesis_hash == chainspecs[0].genesis_hash);
+        assert!(entries[0].path == Some(String::new()));
+        assert!(selected_watch_only_entries(&root_public_key, true, dbname).is_err(), "Selection without network key should be rejected");
+
+        let apng_hex = export_selected_watch_only(&selection, true, dbname).unwrap();
+        assert!(apng_hex.starts_with("89504e47"), "Expected png signature, found: {}", &apng_hex[..16]);
+
+        let mut unsupported_version = ContentWatchOnly::generate(&entries).to_vec();
+        unsupported_version[0] = 0;
+        assert!(ContentWatchOnly::from_vec(&unsupported_version).entries().is_err());
+        fs::remove_dir_all(dbname).unwrap();
+    }
+}
@@ -4,13 +4,13 @@ use std::fs;
 use anyhow;
 
 pub mod address_book;
-use address_book::load_address_book;
+use address_book::{load_address_book, migrate_address_book};
 
 pub mod metadata;
 use metadata::load_metadata;
 
 pub mod chainspecs;
-use chainspecs::{load_chainspecs, load_chainspecs_to_send, load_network_verifiers};
+use chainspecs::{load_chainspecs, load_chainspecs_to_send, load_network_verifiers, migrate_network_specs};
 
 pub mod error;
 use error::Error;
@@ -135,6 +135,19 @@ pub fn populate_hot (database_name: &str) -> anyhow::Result<()> {
     
 }
 
+/// Function to bring entries of "hot" database stored in earlier formats to current format:
+/// network specs and address book entries;
+/// outputs the number of migrated entries
+
+pub fn migrate_hot (database_name: &str) -> anyhow::Result<u32> {
+    
+    let specs_count = migrate_network_specs(database_name)?;
+    let address_book_count = migrate_address_book(database_name)?;
+    
+    Ok(specs_count + address_book_count)
+    
+}
+
 /// Function to re-populate default "hot" database with defaults.
 /// No metadata is added here, all metadata entries will come from
 /// meta_reading and/or generate_message
@@ -3,7 +3,7 @@ use definitions::history::{Event, Entry};
 use parity_scale_codec::{Decode, Encode};
 use anyhow;
 use chrono::Utc;
-use sled::{Db, Tree};
+use sled::{Batch, Db, Transactional, Tree, transaction::{ConflictableTransactionError, TransactionError}};
 
 use crate::helpers::{open_db, open_tree, clear_tree, flush_db, insert_into_tree};
 use crate::error::{Error, NotDecodeable};
@@ -65,14 +65,36 @@ pub fn enter_events(database_name: &str, events: Vec<Event>) -> anyhow::Result<(
 }
 
 pub fn enter_events_into_tree(history: &Tree, events: Vec<Event>) -> anyhow::Result<()> {
+    let (order_encoded, history_entry_encoded) = prep_history_entry(history, events);
+    insert_into_tree(order_encoded, history_entry_encoded, &history)?;
+    Ok(())
+}
+
+/// Function to make encoded key and value of new history entry with given events
+fn prep_history_entry(history: &Tree, events: Vec<Event>) -> (Vec<u8>, Vec<u8>) {
     let order = history.len() as Order;
     let timestamp = Utc::now().to_string();
     let history_entry = Entry {
         timestamp,
         events,
     };
-    insert_into_tree(order.encode(), history_entry.encode(), &history)?;
-    Ok(())
+    (order.encode(), history_entry.encode())
+}
+
+/// Function to apply batch to the tree and to add history entry with given events
+/// in single transaction, so that either both changes are written or none
+pub fn apply_batch_and_enter_events(tree: &Tree, batch: Batch, history: &Tree, events: Vec<Event>) -> anyhow::Result<()> {
+    let (order_encoded, history_entry_encoded) = prep_history_entry(history, events);
+    let transaction_result = (tree, history).transaction(|(tx_tree, tx_history)| {
+        tx_tree.apply_batch(&batch)?;
+        tx_history.insert(order_encoded.to_vec(), history_entry_encoded.to_vec())?;
+        Ok::<(), ConflictableTransactionError<()>>(())
+    });
+    match transaction_result {
+        Ok(()) => Ok(()),
+        Err(TransactionError::Storage(e)) => return Err(Error::InternalDatabaseError(e).show()),
+        Err(TransactionError::Abort(())) => unreachable!(),
+    }
 }
 
 pub fn history_entry_user(database_name: &str, string_from_user: String) -> anyhow::Result<()> {
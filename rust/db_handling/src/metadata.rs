@@ -2,11 +2,11 @@ use parity_scale_codec::Encode;
 use regex::Regex;
 use lazy_static::lazy_static;
 use constants::{METATREE, SPECSTREE};
-use definitions::metadata::NameVersioned;
+use definitions::metadata::{NameVersioned, is_compressed_meta};
 use anyhow;
 
 use crate::error::{Error, NotHex};
-use crate::helpers::{open_db, open_tree, flush_db, clear_tree, insert_into_tree, unhex, decode_chain_specs};
+use crate::helpers::{open_db, open_tree, flush_db, clear_tree, insert_into_tree, unhex, decode_chain_specs, meta_for_storage, meta_from_storage};
 
 
 
@@ -32,7 +32,7 @@ pub fn load_metadata (database_name: &str, metadata_contents: &str) -> anyhow::R
         let meta_hex = caps["meta"].to_string();
         
         let meta_to_store = unhex(&meta_hex, NotHex::DefaultMeta)?;
-        insert_into_tree(new.encode(), meta_to_store, &metadata)?;
+        insert_into_tree(new.encode(), meta_for_storage(&meta_to_store)?, &metadata)?;
     }
     
     flush_db(&database)?;
@@ -42,7 +42,8 @@ pub fn load_metadata (database_name: &str, metadata_contents: &str) -> anyhow::R
 
 /// Function to transfer metadata content hot database into cold database
 /// Checks that only networks with network specs already in "to" database are processed,
-/// so that no metadata without associated network specs enters the database
+/// so that no metadata without associated network specs enters the database;
+/// metadata is compressed in cold database regardless of the form it was stored in the hot one
 pub fn transfer_metadata (database_name_from: &str, database_name_to: &str) -> anyhow::Result<()> {
     
     let database_from = open_db(database_name_from)?;
@@ -56,7 +57,7 @@ pub fn transfer_metadata (database_name_from: &str, database_name_to: &str) -> a
             let network_specs = decode_chain_specs(network_specs_encoded, &network_key.to_vec())?;
             for y in metadata_from.scan_prefix(network_specs.name.encode()) {
                 if let Ok((key, value)) = y {
-                    insert_into_tree(key.to_vec(), value.to_vec(), &metadata_to)?;
+                    insert_into_tree(key.to_vec(), meta_for_storage(&meta_from_storage(&value)?)?, &metadata_to)?;
                 }
             }
         }
@@ -65,3 +66,76 @@ pub fn transfer_metadata (database_name_from: &str, database_name_to: &str) -> a
     Ok(())
     
 }
+
+
+/// Function to compress all metadata entries in the cold database stored before
+/// the compression was introduced; already compressed entries are left untouched;
+/// outputs the number of compressed entries
+pub fn compress_stored_metadata (database_name: &str) -> anyhow::Result<u32> {
+    
+    let database = open_db(database_name)?;
+    let metadata = open_tree(&database, METATREE)?;
+    
+    let mut count = 0;
+    for x in metadata.iter() {
+        if let Ok((versioned_name_encoded, meta_stored)) = x {
+            if !is_compressed_meta(&meta_stored) {
+                insert_into_tree(versioned_name_encoded.to_vec(), meta_for_storage(&meta_stored)?, &metadata)?;
+                count = count + 1;
+            }
+        }
+    }
+    flush_db(&database)?;
+    Ok(count)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::populate_cold;
+    use std::fs;
+    use sled::{Db, Tree, open};
+    
+    const METADATA_FILE: &str = "metadata_database.ts";
+    
+    #[test]
+    fn compressed_metadata_and_migration() {
+        let dbname = "tests/compressed_metadata_and_migration";
+        populate_cold(dbname, METADATA_FILE, true).unwrap();
+        
+        let versioned_name = NameVersioned {
+            name: String::from("westend"),
+            version: 9010,
+        };
+        let raw_meta = {
+            let database: Db = open(dbname).unwrap();
+            let metadata: Tree = database.open_tree(METATREE).unwrap();
+            for x in metadata.iter() {
+                let (_, meta_stored) = x.unwrap();
+                assert!(is_compressed_meta(&meta_stored), "Metadata entries from defaults should be stored compressed.");
+            }
+            let meta_stored = metadata.get(versioned_name.encode()).unwrap().unwrap();
+            let raw_meta = meta_from_storage(&meta_stored).unwrap();
+            assert!(raw_meta.starts_with(&[109, 101, 116, 97]), "Decompressed metadata should start with meta prefix.");
+            assert!(meta_stored.len() < raw_meta.len(), "Compressed metadata is not smaller than the raw one.");
+        // entry in old, uncompressed, format
+            metadata.insert(versioned_name.encode(), raw_meta.to_vec()).unwrap();
+            database.flush().unwrap();
+            raw_meta
+        };
+        
+        assert!(compress_stored_metadata(dbname).unwrap() == 1, "Expected exactly one entry to be compressed.");
+        assert!(compress_stored_metadata(dbname).unwrap() == 0, "Compressed entries should not be processed again.");
+        
+        {
+            let database: Db = open(dbname).unwrap();
+            let metadata: Tree = database.open_tree(METATREE).unwrap();
+            let meta_stored = metadata.get(versioned_name.encode()).unwrap().unwrap();
+            assert!(is_compressed_meta(&meta_stored), "Migrated entry is not compressed.");
+            assert!(meta_from_storage(&meta_stored).unwrap() == raw_meta, "Migrated entry decompresses into different metadata.");
+        }
+        
+        fs::remove_dir_all(dbname).unwrap();
+    }
+}
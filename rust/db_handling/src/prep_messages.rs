@@ -63,6 +63,9 @@ pub fn get_network_specs (network_name: &str, database_name: &str) -> anyhow::Re
                 secondary_color: network_specs.secondary_color,
                 title: network_specs.title,
                 unit: network_specs.unit,
+                relay_genesis_hash: network_specs.relay_genesis_hash,
+                parachain_id: network_specs.parachain_id,
+                tokens: network_specs.tokens,
             };
             Ok(network_specs_to_send)
         },
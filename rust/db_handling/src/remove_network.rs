@@ -1,11 +1,11 @@
-use constants::{ADDRTREE, HISTORY, METATREE, SPECSTREE, VERIFIERS};
-use definitions::{history::Event, metadata::{NameVersioned, MetaValuesDisplay}, network_specs::{NetworkKey}, users::{AddressDetails, IdentityHistory}};
+use constants::{ADDRTREE, ASSETS, HISTORY, METATREE, SPECSTREE, VERIFIERS};
+use definitions::{history::Event, metadata::{NameVersioned, MetaValuesDisplay}, network_specs::{AssetInfo, NetworkAssets, NetworkKey, generate_assets_key, generate_verifier_set_key, generate_verifier_rotation_key, show_assets}, users::{AddressDetails, IdentityHistory}};
 use parity_scale_codec::{Decode, Encode};
 use anyhow;
 use blake2_rfc::blake2b::blake2b;
 
 use crate::error::{Error, NotDecodeable, NotFound, NotHex};
-use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, remove_from_tree, unhex, decode_chain_specs, reverse_address_key, reverse_network_key, get_verifier, remove_verifier, genesis_hash_in_cold_db};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, remove_from_tree, unhex, decode_chain_specs, reverse_address_key, reverse_network_key, get_verifier, remove_verifier, genesis_hash_in_cold_db, meta_from_storage};
 use crate::manage_history::enter_events_into_tree;
 
 
@@ -17,6 +17,7 @@ pub fn remove_network_by_key (network_key: &NetworkKey, database_name: &str) ->
     let verifiers = open_tree(&database, VERIFIERS)?;
     let identities = open_tree(&database, ADDRTREE)?;
     let history = open_tree(&database, HISTORY)?;
+    let assets = open_tree(&database, ASSETS)?;
     
 // clean up the chainspecs tree
     let network_specs = match chainspecs.remove(&network_key) {
@@ -26,16 +27,42 @@ pub fn remove_network_by_key (network_key: &NetworkKey, database_name: &str) ->
     };
     flush_db(&database)?;
     
-// check if there are networks remaining that need same verifier, and if not, clean up verifiers tree
+// check if there are networks remaining that need same verifier, and if not, clean up verifiers tree,
+// including the verifier set and pending verifier rotation of the network if there are any
+    let genesis_hash_remains = genesis_hash_in_cold_db (network_specs.genesis_hash, &chainspecs)?;
     let network_verifier = {
-        if genesis_hash_in_cold_db (network_specs.genesis_hash, &chainspecs)? {get_verifier(network_specs.genesis_hash, &verifiers)?}
-        else {remove_verifier(network_specs.genesis_hash, &verifiers)?}
+        if genesis_hash_remains {get_verifier(network_specs.genesis_hash, &verifiers)?}
+        else {
+            remove_from_tree(generate_verifier_set_key(&network_specs.genesis_hash.to_vec()), &verifiers)?;
+            remove_from_tree(generate_verifier_rotation_key(&network_specs.genesis_hash.to_vec()), &verifiers)?;
+            remove_verifier(network_specs.genesis_hash, &verifiers)?
+        }
     };
     
 // record that in the history
     enter_events_into_tree(&history, vec![Event::NetworkRemoved(network_specs.show(&network_verifier))])?;
     flush_db(&database)?;
     
+// clean up the asset registry of the network, if no networks with same genesis hash remain
+    if !genesis_hash_remains {
+        match assets.remove(generate_assets_key(&network_specs.genesis_hash.to_vec())) {
+            Ok(Some(assets_encoded)) => {
+                let network_assets = match <Vec<AssetInfo>>::decode(&mut &assets_encoded[..]) {
+                    Ok(a) => a,
+                    Err(_) => return Err(Error::NotDecodeable(NotDecodeable::Assets).show()),
+                };
+                let network_assets_print = NetworkAssets {
+                    genesis_hash: &hex::encode(network_specs.genesis_hash),
+                    assets_line: show_assets(&network_assets),
+                }.show();
+                enter_events_into_tree(&history, vec![Event::AssetsRemoved(network_assets_print)])?;
+            },
+            Ok(None) => (),
+            Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+        }
+        flush_db(&database)?;
+    }
+    
 // clean up the existing metadata for this network (with various versions) in metadata tree
     let mut events: Vec<Event> = Vec::new();
     for x in metadata.scan_prefix(network_specs.name.encode()) {
@@ -45,7 +72,7 @@ pub fn remove_network_by_key (network_key: &NetworkKey, database_name: &str) ->
                 let meta_values_display = MetaValuesDisplay {
                     name: &versioned_name.name,
                     version: versioned_name.version,
-                    meta_hash: &hex::encode(blake2b(32, &[], &meta_stored).as_bytes()),
+                    meta_hash: &hex::encode(blake2b(32, &[], &meta_from_storage(&meta_stored)?).as_bytes()),
                 }.show();
                 events.push(Event::MetadataRemoved(meta_values_display));
             }
@@ -106,7 +133,7 @@ pub fn remove_metadata (network_name: &str, network_version: u32, database_name:
             let meta_values_display = MetaValuesDisplay {
                 name: &network_name,
                 version: network_version,
-                meta_hash: &hex::encode(blake2b(32, &[], &meta_stored).as_bytes()),
+                meta_hash: &hex::encode(blake2b(32, &[], &meta_from_storage(&meta_stored)?).as_bytes()),
             }.show();
             let events = vec![Event::MetadataRemoved(meta_values_display)];
             enter_events_into_tree(&history, events)?;
@@ -162,7 +189,7 @@ mod tests {
         }
         
         let history_printed = print_history_tree(&database).unwrap();
-        assert!(history_printed.contains(r#""events":[{"event":"database_initiated"}]"#) && history_printed.contains(r##""events":[{"event":"network_removed","payload":{"base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","order":"2","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","verifier":{"hex":"","encryption":"none"}}}]"##) && history_printed.contains(r#""events":[{"event":"metadata_removed","payload":{"specname":"westend","spec_version":"9000","meta_hash":"e80237ad8b2e92b72fcf6beb8f0e4ba4a21043a7115c844d91d6c4f981e469ce"}},{"event":"metadata_removed","payload":{"specname":"westend","spec_version":"9010","meta_hash":"70c99738c27fb32c87883f1c9c94ee454bf0b3d88e4a431a2bbfe1222b46ebdf"}}]"#) && history_printed.contains(r#"[{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"3efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34","path":"//westend","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a","path":"","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"64a31235d4bf9b37cfed3afa8aa60754675f9c4915430454d365c05112784d05","path":"//kusama","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"96129dcebc2e10f644e81fcf4269a663e521330084b1e447369087dec8017e04","path":"//rococo","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","path":"//Alice","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"f606519cb8726753885cd4d0f518804a69a5e0badf36fee70feadd8044081730","path":"//polkadot","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}}]"#), "Expected different history:\n{}", history_printed);
+        assert!(history_printed.contains(r#""events":[{"event":"database_initiated"}]"#) && history_printed.contains(r##""events":[{"event":"network_removed","payload":{"base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","order":"2","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","relay_genesis_hash":"","parachain_id":"","tokens":[],"verifier":{"hex":"","encryption":"none"}}}]"##) && history_printed.contains(r#""events":[{"event":"metadata_removed","payload":{"specname":"westend","spec_version":"9000","meta_hash":"e80237ad8b2e92b72fcf6beb8f0e4ba4a21043a7115c844d91d6c4f981e469ce"}},{"event":"metadata_removed","payload":{"specname":"westend","spec_version":"9010","meta_hash":"70c99738c27fb32c87883f1c9c94ee454bf0b3d88e4a431a2bbfe1222b46ebdf"}}]"#) && history_printed.contains(r#"[{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"3efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34","path":"//westend","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a","path":"","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"64a31235d4bf9b37cfed3afa8aa60754675f9c4915430454d365c05112784d05","path":"//kusama","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"96129dcebc2e10f644e81fcf4269a663e521330084b1e447369087dec8017e04","path":"//rococo","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","path":"//Alice","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}},{"event":"identity_removed","payload":{"seed_name":"Alice","encryption":"sr25519","public_key":"f606519cb8726753885cd4d0f518804a69a5e0badf36fee70feadd8044081730","path":"//polkadot","network_genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"}}]"#), "Expected different history:\n{}", history_printed);
         
         fs::remove_dir_all(dbname).unwrap();
     }
@@ -1,10 +1,10 @@
 use parity_scale_codec::Encode;
-use constants::{SETTREE, TYPES, GENERALVERIFIER};
-use definitions::{defaults::get_default_types, network_specs::Verifier};
+use constants::{SETTREE, TYPES, GENERALVERIFIER, RETENTIONPOLICY};
+use definitions::{defaults::get_default_types, metadata::RetentionPolicy, network_specs::Verifier};
 use anyhow;
 
 use crate::error::Error;
-use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, remove_from_tree};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, remove_from_tree, get_retention_policy};
 
 
 /// Load default types
@@ -36,3 +36,27 @@ pub fn set_general_verifier (database_name: &str, general_verifier: Verifier) ->
     Ok(())
 }
 
+/// Set metadata retention policy from policy line, e.g. `keep_all`, `keep_latest:2`, `drop_older_than:10`;
+/// policy is applied when new metadata is accepted
+
+pub fn set_retention_policy (policy_line: &str, database_name: &str) -> anyhow::Result<()> {
+    
+    let policy = match RetentionPolicy::from_line(policy_line) {
+        Ok(a) => a,
+        Err(e) => return Err(Error::RetentionPolicy(e.to_string()).show()),
+    };
+    let database = open_db(database_name)?;
+    let settings = open_tree(&database, SETTREE)?;
+    insert_into_tree(RETENTIONPOLICY.to_vec(), policy.encode(), &settings)?;
+    flush_db(&database)?;
+    Ok(())
+}
+
+/// Print current metadata retention policy
+
+pub fn print_retention_policy (database_name: &str) -> anyhow::Result<String> {
+    
+    let database = open_db(database_name)?;
+    let settings = open_tree(&database, SETTREE)?;
+    Ok(get_retention_policy(&settings)?.show())
+}
@@ -26,6 +26,9 @@ pub fn get_default_chainspecs() -> Vec<ChainSpecs> {
             secondary_color: String::from("#262626"),
             title: String::from("Kusama"),
             unit: String::from("KSM"),
+            relay_genesis_hash: None,
+            parachain_id: None,
+            tokens: Vec::new(),
     	},
 	ChainSpecs {
             base58prefix: 0,
@@ -40,6 +43,9 @@ pub fn get_default_chainspecs() -> Vec<ChainSpecs> {
             secondary_color: String::from("#262626"),
             title: String::from("Polkadot"),
             unit: String::from("DOT"),
+            relay_genesis_hash: None,
+            parachain_id: None,
+            tokens: Vec::new(),
     	},
 	ChainSpecs {
             base58prefix: 42,
@@ -54,6 +60,9 @@ pub fn get_default_chainspecs() -> Vec<ChainSpecs> {
             secondary_color: String::from("#262626"),
             title: String::from("Rococo"),
             unit: String::from("ROC"),
+            relay_genesis_hash: None,
+            parachain_id: None,
+            tokens: Vec::new(),
     	},
         ChainSpecs {
             base58prefix: 42,
@@ -68,6 +77,9 @@ pub fn get_default_chainspecs() -> Vec<ChainSpecs> {
             secondary_color: String::from("#262626"),
             title: String::from("Westend"),
             unit: String::from("WND"),
+            relay_genesis_hash: None,
+            parachain_id: None,
+            tokens: Vec::new(),
         },
     ]
 }
@@ -103,6 +115,9 @@ pub fn get_default_chainspecs_to_send() -> Vec<ChainSpecsToSend> {
             secondary_color: x.secondary_color.to_string(),
             title: x.title.to_string(),
             unit: x.unit.to_string(),
+            relay_genesis_hash: x.relay_genesis_hash,
+            parachain_id: x.parachain_id,
+            tokens: x.tokens.to_vec(),
         };
         specs_to_send.push(new);
     }
@@ -228,6 +243,9 @@ pub fn get_default_address_book() -> Vec<AddressBookEntry> {
             address: String::from("wss://kusama-rpc.polkadot.io"),
             encryption: Encryption::Sr25519,
             def: true,
+            relay_genesis_hash: None,
+            parachain_id: None,
+            fallback_addresses: Vec::new(),
         },
         AddressBookEntry {
             name: String::from("polkadot"),
@@ -235,6 +253,9 @@ pub fn get_default_address_book() -> Vec<AddressBookEntry> {
             address: String::from("wss://rpc.polkadot.io"),
             encryption: Encryption::Sr25519,
             def: true,
+            relay_genesis_hash: None,
+            parachain_id: None,
+            fallback_addresses: Vec::new(),
         },
         AddressBookEntry {
             name: String::from("rococo"),
@@ -245,6 +266,9 @@ pub fn get_default_address_book() -> Vec<AddressBookEntry> {
             address: String::from("wss://rococo-rpc.polkadot.io"),
             encryption: Encryption::Sr25519,
             def: true,
+            relay_genesis_hash: None,
+            parachain_id: None,
+            fallback_addresses: Vec::new(),
         },
         AddressBookEntry {
             name: String::from("westend"),
@@ -252,6 +276,9 @@ pub fn get_default_address_book() -> Vec<AddressBookEntry> {
             address: String::from("wss://westend-rpc.polkadot.io"),
             encryption: Encryption::Sr25519,
             def: true,
+            relay_genesis_hash: None,
+            parachain_id: None,
+            fallback_addresses: Vec::new(),
         },
     ]
 }
@@ -29,6 +29,18 @@ pub enum Event {
     SystemEntry(String),
     HistoryCleared,
     DatabaseInitiated,
+    MetadataVerifierSetAdded(String), // NetworkVerifierSet.show()
+    MetadataVerifierSetRemoved(String), // NetworkVerifierSet.show()
+    MetadataVerifierRotated(String), // NetworkVerifierRotation.show()
+    IdentityRenamed(String), // IdentityDetailsHistory.show()
+    IdentityTagAdded(String), // IdentityDetailsHistory.show()
+    IdentityTagRemoved(String), // IdentityDetailsHistory.show()
+    IdentityNotesUpdated(String), // IdentityDetailsHistory.show()
+    IdentityNetworkEnabled(String), // IdentityHistory.show()
+    IdentityNetworkDisabled(String), // IdentityHistory.show()
+    SeedRenamed(String), // SeedRenameHistory.show()
+    AssetsLoaded(String), // NetworkAssets.show()
+    AssetsRemoved(String), // NetworkAssets.show()
 }
 
 #[derive(Decode, Encode)]
@@ -67,6 +79,18 @@ impl Event {
             Event::SystemEntry(x) => format!("{{\"event\":\"system_entered_event\",\"payload\":\"{}\"}}", x),
             Event::HistoryCleared => String::from("{\"event\":\"history_cleared\"}"),
             Event::DatabaseInitiated => String::from("{\"event\":\"database_initiated\"}"),
+            Event::MetadataVerifierSetAdded(x) => format!("{{\"event\":\"metadata_verifier_set_added\",\"payload\":{{{}}}}}", x),
+            Event::MetadataVerifierSetRemoved(x) => format!("{{\"event\":\"metadata_verifier_set_removed\",\"payload\":{{{}}}}}", x),
+            Event::MetadataVerifierRotated(x) => format!("{{\"event\":\"metadata_verifier_rotated\",\"payload\":{{{}}}}}", x),
+            Event::IdentityRenamed(x) => format!("{{\"event\":\"identity_renamed\",\"payload\":{{{}}}}}", x),
+            Event::IdentityTagAdded(x) => format!("{{\"event\":\"identity_tag_added\",\"payload\":{{{}}}}}", x),
+            Event::IdentityTagRemoved(x) => format!("{{\"event\":\"identity_tag_removed\",\"payload\":{{{}}}}}", x),
+            Event::IdentityNotesUpdated(x) => format!("{{\"event\":\"identity_notes_updated\",\"payload\":{{{}}}}}", x),
+            Event::IdentityNetworkEnabled(x) => format!("{{\"event\":\"identity_network_enabled\",\"payload\":{{{}}}}}", x),
+            Event::IdentityNetworkDisabled(x) => format!("{{\"event\":\"identity_network_disabled\",\"payload\":{{{}}}}}", x),
+            Event::SeedRenamed(x) => format!("{{\"event\":\"seed_renamed\",\"payload\":{{{}}}}}", x),
+            Event::AssetsLoaded(x) => format!("{{\"event\":\"assets_loaded\",\"payload\":{{{}}}}}", x),
+            Event::AssetsRemoved(x) => format!("{{\"event\":\"assets_removed\",\"payload\":{{{}}}}}", x),
         }
     }
 }
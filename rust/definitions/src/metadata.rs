@@ -1,4 +1,5 @@
 use parity_scale_codec_derive::{Decode, Encode};
+use constants::{COMPRESSED_META_MARKER, META_COMPRESSION_LEVEL};
 use crate::network_specs::ChainSpecsToSend;
 use crate::crypto::Encryption;
 
@@ -82,5 +83,166 @@ pub struct AddressBookEntry {
     pub address: String,
     pub encryption: Encryption,
     pub def: bool,
+    pub relay_genesis_hash: Option<[u8; 32]>,
+    pub parachain_id: Option<u32>,
+    pub fallback_addresses: Vec<String>, // rpc endpoints to try, in order, if the one at `address` fails
 }
 
+impl AddressBookEntry {
+    /// Function to get all rpc endpoints of the network, in failover order
+    pub fn addresses(&self) -> Vec<String> {
+        [vec![self.address.to_string()], self.fallback_addresses.to_vec()].concat()
+    }
+}
+
+/// AddressBookEntry as encoded in the hot database before fallback rpc endpoints were added,
+/// used only to migrate the database entries stored in that form
+#[derive(Decode, Encode)]
+pub struct AddressBookEntryNoFallback {
+    pub name: String,
+    pub genesis_hash: [u8; 32],
+    pub address: String,
+    pub encryption: Encryption,
+    pub def: bool,
+    pub relay_genesis_hash: Option<[u8; 32]>,
+    pub parachain_id: Option<u32>,
+}
+
+impl AddressBookEntryNoFallback {
+    /// Function to get AddressBookEntry with no fallback rpc endpoints from entry stored without them
+    pub fn with_no_fallback(self) -> AddressBookEntry {
+        AddressBookEntry {
+            name: self.name,
+            genesis_hash: self.genesis_hash,
+            address: self.address,
+            encryption: self.encryption,
+            def: self.def,
+            relay_genesis_hash: self.relay_genesis_hash,
+            parachain_id: self.parachain_id,
+            fallback_addresses: Vec::new(),
+        }
+    }
+}
+
+/// AddressBookEntry as encoded in the hot database before parachain information was added,
+/// used only to migrate the database entries stored in that form
+#[derive(Decode, Encode)]
+pub struct AddressBookEntryNoParachain {
+    pub name: String,
+    pub genesis_hash: [u8; 32],
+    pub address: String,
+    pub encryption: Encryption,
+    pub def: bool,
+}
+
+impl AddressBookEntryNoParachain {
+    /// Function to get AddressBookEntry of network with no relay chain and no fallback rpc endpoints
+    /// from entry stored without parachain information
+    pub fn with_no_parachain(self) -> AddressBookEntry {
+        AddressBookEntry {
+            name: self.name,
+            genesis_hash: self.genesis_hash,
+            address: self.address,
+            encryption: self.encryption,
+            def: self.def,
+            relay_genesis_hash: None,
+            parachain_id: None,
+            fallback_addresses: Vec::new(),
+        }
+    }
+}
+
+
+/// Function to compress raw metadata before putting it into the database;
+/// compressed entry is marked with COMPRESSED_META_MARKER
+pub fn compress_meta (meta: &[u8]) -> Result<Vec<u8>, String> {
+    match zstd::encode_all(meta, META_COMPRESSION_LEVEL) {
+        Ok(a) => Ok([COMPRESSED_META_MARKER.to_vec(), a].concat()),
+        Err(e) => return Err(e.to_string()),
+    }
+}
+
+/// Function to get raw metadata from the database entry;
+/// entries without compression marker were stored before the compression was introduced
+/// and are returned as is
+pub fn decompress_meta (stored: &[u8]) -> Result<Vec<u8>, String> {
+    if is_compressed_meta(stored) {
+        match zstd::decode_all(&stored[COMPRESSED_META_MARKER.len()..]) {
+            Ok(a) => Ok(a),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    else {Ok(stored.to_vec())}
+}
+
+/// Function to check if the database entry contains compressed metadata
+pub fn is_compressed_meta (stored: &[u8]) -> bool {
+    stored.starts_with(COMPRESSED_META_MARKER)
+}
+
+/// Enum to store metadata retention policy on cold device,
+/// applied when new metadata is accepted:
+/// KeepAll - nothing is removed automatically,
+/// KeepLatest(n) - only n latest versions are kept for each network,
+/// DropOlderThan(x) - versions more than x below the newest known version are removed;
+/// just accepted metadata is never removed
+#[derive(Decode, Encode, PartialEq, Clone, Copy, Debug)]
+pub enum RetentionPolicy {
+    KeepAll,
+    KeepLatest(u32),
+    DropOlderThan(u32),
+}
+
+impl RetentionPolicy {
+    /// Function to parse retention policy line, e.g. `keep_all`, `keep_latest:2`, `drop_older_than:10`
+    pub fn from_line (line: &str) -> Result<Self, &'static str> {
+        let mut line_split = line.trim().splitn(2, ':');
+        let policy_name = line_split.next();
+        let number = match line_split.next() {
+            Some(a) => match a.trim().parse::<u32>() {
+                Ok(b) => Some(b),
+                Err(_) => return Err("Retention policy parameter should be a number."),
+            },
+            None => None,
+        };
+        match (policy_name, number) {
+            (Some("keep_all"), None) => Ok(RetentionPolicy::KeepAll),
+            (Some("keep_latest"), Some(0)) => return Err("At least one metadata version should be kept."),
+            (Some("keep_latest"), Some(n)) => Ok(RetentionPolicy::KeepLatest(n)),
+            (Some("drop_older_than"), Some(x)) => Ok(RetentionPolicy::DropOlderThan(x)),
+            _ => return Err("Unknown retention policy."),
+        }
+    }
+    /// Function to print retention policy
+    pub fn show (&self) -> String {
+        match &self {
+            RetentionPolicy::KeepAll => String::from("{\"policy\":\"keep_all\"}"),
+            RetentionPolicy::KeepLatest(n) => format!("{{\"policy\":\"keep_latest\",\"versions\":\"{}\"}}", n),
+            RetentionPolicy::DropOlderThan(x) => format!("{{\"policy\":\"drop_older_than\",\"versions\":\"{}\"}}", x),
+        }
+    }
+    /// Function to select stored metadata versions of the network to be removed
+    /// when the metadata with new_version is accepted;
+    /// stored_versions are the versions already in the database for the same network
+    pub fn versions_to_remove (&self, stored_versions: &Vec<u32>, new_version: u32) -> Vec<u32> {
+        match &self {
+            RetentionPolicy::KeepAll => Vec::new(),
+            RetentionPolicy::KeepLatest(n) => {
+                let mut all_versions = stored_versions.to_vec();
+                all_versions.push(new_version);
+                all_versions.sort_by(|a, b| b.cmp(a));
+                all_versions.dedup();
+                let mut kept: Vec<u32> = all_versions.into_iter().take(*n as usize).collect();
+                kept.push(new_version);
+                stored_versions.iter().filter(|x| !kept.contains(x)).map(|x| *x).collect()
+            },
+            RetentionPolicy::DropOlderThan(x) => {
+                let newest = match stored_versions.iter().max() {
+                    Some(a) if *a > new_version => *a,
+                    _ => new_version,
+                };
+                stored_versions.iter().filter(|a| (**a != new_version)&&(a.saturating_add(*x) < newest)).map(|a| *a).collect()
+            },
+        }
+    }
+}
@@ -4,7 +4,7 @@ use parity_scale_codec::Encode;
 use crate::crypto::Encryption;
 
 //TODO: rename fields to make them more clear
-#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug)]
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug, Clone)]
 pub struct ChainSpecs {
     pub base58prefix: u16,
     pub color: String,
@@ -18,6 +18,9 @@ pub struct ChainSpecs {
     pub secondary_color: String,
     pub title: String,
     pub unit: String,
+    pub relay_genesis_hash: Option<[u8; 32]>, // genesis hash of the relay chain, if the network is a parachain
+    pub parachain_id: Option<u32>, // parachain id within the relay chain
+    pub tokens: Vec<TokenInfo>, // all tokens of multi-token network, the first one is the native token; empty for single-token networks
     //TODO: add metadata signature parameters
 }
 
@@ -35,30 +38,236 @@ pub struct ChainSpecsToSend {
     pub secondary_color: String,
     pub title: String,
     pub unit: String,
+    pub relay_genesis_hash: Option<[u8; 32]>,
+    pub parachain_id: Option<u32>,
+    pub tokens: Vec<TokenInfo>,
+}
+
+/// Token of multi-token network, with decimals and unit used to display the balances in this token
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug, Clone)]
+pub struct TokenInfo {
+    pub decimals: u8,
+    pub unit: String,
+}
+
+/// ChainSpecs as encoded in the databases before parachain information was added,
+/// used only to migrate the database entries stored in that form
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug, Clone)]
+pub struct ChainSpecsNoParachain {
+    pub base58prefix: u16,
+    pub color: String,
+    pub decimals: u8,
+    pub encryption: Encryption,
+    pub genesis_hash: [u8; 32],
+    pub logo: String,
+    pub name: String,
+    pub order: u8,
+    pub path_id: String,
+    pub secondary_color: String,
+    pub title: String,
+    pub unit: String,
+}
+
+impl ChainSpecsNoParachain {
+    /// Function to get ChainSpecs of single-token network with no relay chain from entry stored without parachain information
+    pub fn with_no_parachain(self) -> ChainSpecs {
+        ChainSpecs {
+            base58prefix: self.base58prefix,
+            color: self.color,
+            decimals: self.decimals,
+            encryption: self.encryption,
+            genesis_hash: self.genesis_hash,
+            logo: self.logo,
+            name: self.name,
+            order: self.order,
+            path_id: self.path_id,
+            secondary_color: self.secondary_color,
+            title: self.title,
+            unit: self.unit,
+            relay_genesis_hash: None,
+            parachain_id: None,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+/// ChainSpecsToSend as encoded in the hot database and in add_network and add_specs messages
+/// before parachain information was added,
+/// used only to migrate the database entries and to read the messages generated in that form
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug, Clone)]
+pub struct ChainSpecsToSendNoParachain {
+    pub base58prefix: u16,
+    pub color: String,
+    pub decimals: u8,
+    pub encryption: Encryption,
+    pub genesis_hash: [u8; 32],
+    pub logo: String,
+    pub name: String,
+    pub path_id: String,
+    pub secondary_color: String,
+    pub title: String,
+    pub unit: String,
+}
+
+impl ChainSpecsToSendNoParachain {
+    /// Function to get ChainSpecsToSend of single-token network with no relay chain from entry stored without parachain information
+    pub fn with_no_parachain(self) -> ChainSpecsToSend {
+        ChainSpecsToSend {
+            base58prefix: self.base58prefix,
+            color: self.color,
+            decimals: self.decimals,
+            encryption: self.encryption,
+            genesis_hash: self.genesis_hash,
+            logo: self.logo,
+            name: self.name,
+            path_id: self.path_id,
+            secondary_color: self.secondary_color,
+            title: self.title,
+            unit: self.unit,
+            relay_genesis_hash: None,
+            parachain_id: None,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+/// ChainSpecs as encoded in the databases before multi-token networks were supported,
+/// used only to migrate the database entries stored in that form
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug, Clone)]
+pub struct ChainSpecsNoTokens {
+    pub base58prefix: u16,
+    pub color: String,
+    pub decimals: u8,
+    pub encryption: Encryption,
+    pub genesis_hash: [u8; 32],
+    pub logo: String,
+    pub name: String,
+    pub order: u8,
+    pub path_id: String,
+    pub secondary_color: String,
+    pub title: String,
+    pub unit: String,
+    pub relay_genesis_hash: Option<[u8; 32]>,
+    pub parachain_id: Option<u32>,
+}
+
+impl ChainSpecsNoTokens {
+    /// Function to get ChainSpecs of single-token network from entry stored without tokens
+    pub fn with_no_tokens(self) -> ChainSpecs {
+        ChainSpecs {
+            base58prefix: self.base58prefix,
+            color: self.color,
+            decimals: self.decimals,
+            encryption: self.encryption,
+            genesis_hash: self.genesis_hash,
+            logo: self.logo,
+            name: self.name,
+            order: self.order,
+            path_id: self.path_id,
+            secondary_color: self.secondary_color,
+            title: self.title,
+            unit: self.unit,
+            relay_genesis_hash: self.relay_genesis_hash,
+            parachain_id: self.parachain_id,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+/// ChainSpecsToSend as encoded in the hot database and in add_network and add_specs messages
+/// before multi-token networks were supported,
+/// used only to migrate the database entries and to read the messages generated in that form
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug, Clone)]
+pub struct ChainSpecsToSendNoTokens {
+    pub base58prefix: u16,
+    pub color: String,
+    pub decimals: u8,
+    pub encryption: Encryption,
+    pub genesis_hash: [u8; 32],
+    pub logo: String,
+    pub name: String,
+    pub path_id: String,
+    pub secondary_color: String,
+    pub title: String,
+    pub unit: String,
+    pub relay_genesis_hash: Option<[u8; 32]>,
+    pub parachain_id: Option<u32>,
+}
+
+impl ChainSpecsToSendNoTokens {
+    /// Function to get ChainSpecsToSend of single-token network from entry stored without tokens
+    pub fn with_no_tokens(self) -> ChainSpecsToSend {
+        ChainSpecsToSend {
+            base58prefix: self.base58prefix,
+            color: self.color,
+            decimals: self.decimals,
+            encryption: self.encryption,
+            genesis_hash: self.genesis_hash,
+            logo: self.logo,
+            name: self.name,
+            path_id: self.path_id,
+            secondary_color: self.secondary_color,
+            title: self.title,
+            unit: self.unit,
+            relay_genesis_hash: self.relay_genesis_hash,
+            parachain_id: self.parachain_id,
+            tokens: Vec::new(),
+        }
+    }
 }
 
 impl ChainSpecs {
     pub fn show(&self, verifier: &Verifier) -> String {
-        format!("\"base58prefix\":\"{}\",\"color\":\"{}\",\"decimals\":\"{}\",\"encryption\":\"{}\",\"genesis_hash\":\"{}\",\"logo\":\"{}\",\"name\":\"{}\",\"order\":\"{}\",\"path_id\":\"{}\",\"secondary_color\":\"{}\",\"title\":\"{}\",\"unit\":\"{}\",\"verifier\":{}", &self.base58prefix, &self.color, &self.decimals, &self.encryption.show(), hex::encode(&self.genesis_hash), &self.logo, &self.name, &self.order, &self.path_id, &self.secondary_color, &self.title, &self.unit, verifier.show_card())
+        format!("\"base58prefix\":\"{}\",\"color\":\"{}\",\"decimals\":\"{}\",\"encryption\":\"{}\",\"genesis_hash\":\"{}\",\"logo\":\"{}\",\"name\":\"{}\",\"order\":\"{}\",\"path_id\":\"{}\",\"secondary_color\":\"{}\",\"title\":\"{}\",\"unit\":\"{}\",{},{},\"verifier\":{}", &self.base58prefix, &self.color, &self.decimals, &self.encryption.show(), hex::encode(&self.genesis_hash), &self.logo, &self.name, &self.order, &self.path_id, &self.secondary_color, &self.title, &self.unit, show_parachain_info(&self.relay_genesis_hash, &self.parachain_id), show_tokens(&self.tokens), verifier.show_card())
+    }
+    /// Function to find the token of multi-token network by its unit, case-insensitive
+    pub fn find_token(&self, unit: &str) -> Option<&TokenInfo> {
+        self.tokens.iter().find(|x| x.unit.to_lowercase() == unit.to_lowercase())
     }
 }
 
 impl ChainSpecsToSend {
     pub fn show(&self) -> String {
-        format!("\"base58prefix\":\"{}\",\"color\":\"{}\",\"decimals\":\"{}\",\"encryption\":\"{}\",\"genesis_hash\":\"{}\",\"logo\":\"{}\",\"name\":\"{}\",\"path_id\":\"{}\",\"secondary_color\":\"{}\",\"title\":\"{}\",\"unit\":\"{}\"", &self.base58prefix, &self.color, &self.decimals, &self.encryption.show(), hex::encode(&self.genesis_hash), &self.logo, &self.name, &self.path_id, &self.secondary_color, &self.title, &self.unit)
+        format!("\"base58prefix\":\"{}\",\"color\":\"{}\",\"decimals\":\"{}\",\"encryption\":\"{}\",\"genesis_hash\":\"{}\",\"logo\":\"{}\",\"name\":\"{}\",\"path_id\":\"{}\",\"secondary_color\":\"{}\",\"title\":\"{}\",\"unit\":\"{}\",{},{}", &self.base58prefix, &self.color, &self.decimals, &self.encryption.show(), hex::encode(&self.genesis_hash), &self.logo, &self.name, &self.path_id, &self.secondary_color, &self.title, &self.unit, show_parachain_info(&self.relay_genesis_hash, &self.parachain_id), show_tokens(&self.tokens))
     }
 }
 
+/// Function to print relay chain genesis hash and parachain id for network specs;
+/// for networks that are not parachains both fields are printed empty
+pub fn show_parachain_info (relay_genesis_hash: &Option<[u8; 32]>, parachain_id: &Option<u32>) -> String {
+    let relay_line = match relay_genesis_hash {
+        Some(a) => hex::encode(a),
+        None => String::new(),
+    };
+    let parachain_line = match parachain_id {
+        Some(a) => a.to_string(),
+        None => String::new(),
+    };
+    format!("\"relay_genesis_hash\":\"{}\",\"parachain_id\":\"{}\"", relay_line, parachain_line)
+}
+
+/// Function to print the tokens of multi-token network;
+/// for single-token networks the list is empty
+pub fn show_tokens (tokens: &Vec<TokenInfo>) -> String {
+    let mut tokens_line = String::new();
+    for (i, x) in tokens.iter().enumerate() {
+        if i>0 {tokens_line.push_str(",")}
+        tokens_line.push_str(&format!("{{\"decimals\":\"{}\",\"unit\":\"{}\"}}", x.decimals, x.unit));
+    }
+    format!("\"tokens\":[{}]", tokens_line)
+}
+
 #[derive(Debug, parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
 pub struct ChainProperties {
     pub base58prefix: u16,
     pub decimals: u8,
     pub unit: String,
+    pub tokens: Vec<TokenInfo>,
 }
 
 /// Verifier for both network metadata and for types information,
 /// String is hexadecimal representation of verifier public key
-#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug)]
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug, Clone)]
 pub enum Verifier {
     Ed25519(String),
     Sr25519(String),
@@ -85,6 +294,47 @@ impl Verifier {
     }
 }
 
+/// Set of verifiers for network metadata,
+/// payload is accepted if it is signed by at least threshold number of distinct verifiers from the set
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug)]
+pub struct VerifierSet {
+    pub verifiers: Vec<Verifier>,
+    pub threshold: u32,
+}
+
+impl VerifierSet {
+    pub fn show_card(&self) -> String {
+        let mut verifiers_line = String::new();
+        for (i, x) in self.verifiers.iter().enumerate() {
+            if i>0 {verifiers_line.push_str(",")}
+            verifiers_line.push_str(&x.show_card());
+        }
+        format!("{{\"threshold\":\"{}\",\"verifiers\":[{}]}}", self.threshold, verifiers_line)
+    }
+    pub fn show_error(&self) -> String {
+        let mut verifiers_line = String::new();
+        for (i, x) in self.verifiers.iter().enumerate() {
+            if i>0 {verifiers_line.push_str("; ")}
+            verifiers_line.push_str(&x.show_error());
+        }
+        format!("threshold {} of verifiers {}", self.threshold, verifiers_line)
+    }
+    /// Function to check that the set could be used: threshold is not zero and does not exceed
+    /// the number of verifiers, all verifiers are actual public keys and are not repeated
+    pub fn is_valid(&self) -> bool {
+        if (self.threshold == 0)||(self.threshold as usize > self.verifiers.len()) {return false}
+        for (i, x) in self.verifiers.iter().enumerate() {
+            if x == &Verifier::None {return false}
+            if self.verifiers[..i].contains(x) {return false}
+        }
+        true
+    }
+    /// Function to count how many distinct verifiers from the set are among the signers
+    pub fn approvals(&self, signers: &Vec<Verifier>) -> u32 {
+        self.verifiers.iter().filter(|x| signers.contains(x)).count() as u32
+    }
+}
+
 /// Key for verifier tree, used to search who verifies the network on current device
 pub type VerifierKey = Vec<u8>;
 
@@ -93,6 +343,66 @@ pub fn generate_verifier_key (gen_hash: &Vec<u8>) -> VerifierKey {
     gen_hash.to_vec()
 }
 
+/// Function to generate key in verifier tree for verifier set of given network
+pub fn generate_verifier_set_key (gen_hash: &Vec<u8>) -> VerifierKey {
+    [b"verifier_set".to_vec(), gen_hash.to_vec()].concat()
+}
+
+/// Function to generate key in verifier tree for pending verifier rotation of given network
+pub fn generate_verifier_rotation_key (gen_hash: &Vec<u8>) -> VerifierKey {
+    [b"verifier_rotation".to_vec(), gen_hash.to_vec()].concat()
+}
+
+/// Function to generate key in assets tree for asset registry of given network
+pub fn generate_assets_key (gen_hash: &Vec<u8>) -> Vec<u8> {
+    gen_hash.to_vec()
+}
+
+/// Asset of pallet_assets from network asset registry,
+/// with symbol and decimals used to display the balances in this asset
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug, Clone)]
+pub struct AssetInfo {
+    pub asset_id: u32,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl AssetInfo {
+    pub fn show(&self) -> String {
+        format!("{{\"asset_id\":\"{}\",\"symbol\":\"{}\",\"decimals\":\"{}\"}}", self.asset_id, self.symbol, self.decimals)
+    }
+}
+
+/// Function to print asset registry of the network
+pub fn show_assets (assets: &Vec<AssetInfo>) -> String {
+    let mut assets_line = String::new();
+    for (i, x) in assets.iter().enumerate() {
+        if i>0 {assets_line.push_str(",")}
+        assets_line.push_str(&x.show());
+    }
+    format!("[{}]", assets_line)
+}
+
+/// Struct to store asset registry info for particular network, used in history logging
+pub struct NetworkAssets <'a> {
+    pub genesis_hash: &'a str,
+    pub assets_line: String,
+}
+
+impl <'a> NetworkAssets <'a> {
+    pub fn show(&self) -> String {
+        format!("\"genesis_hash\":\"{}\",\"assets\":{}", &self.genesis_hash, &self.assets_line)
+    }
+}
+
+/// Pending network verifier rotation, stored until the network receives
+/// metadata with version effective_from signed by the new verifier
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, PartialEq, Debug)]
+pub struct VerifierRotation {
+    pub new_verifier: Verifier,
+    pub effective_from: u32,
+}
+
 /// Struct to prepare verifier info for the database
 pub struct VerifierInfo {
     pub key: VerifierKey,
@@ -111,6 +421,36 @@ impl <'a> NetworkVerifier <'a> {
     }
 }
 
+/// Struct to store verifier set info for particular network, used in history logging
+pub struct NetworkVerifierSet <'a> {
+    pub verifier_key: &'a str,
+    pub verifier_set_line: String,
+}
+
+impl <'a> NetworkVerifierSet <'a> {
+    pub fn show(&self) -> String {
+        format!("\"specname\":\"{}\",\"verifier_set\":{}", &self.verifier_key, &self.verifier_set_line)
+    }
+}
+
+/// Struct to store verifier rotation info for particular network, used in history logging
+pub struct NetworkVerifierRotation <'a> {
+    pub verifier_key: &'a str,
+    pub old_verifier_line: String,
+    pub new_verifier_line: String,
+    pub effective_from: Option<u32>,
+}
+
+impl <'a> NetworkVerifierRotation <'a> {
+    pub fn show(&self) -> String {
+        let effective_from = match self.effective_from {
+            Some(a) => a.to_string(),
+            None => String::new(),
+        };
+        format!("\"specname\":\"{}\",\"old_verifier\":{},\"new_verifier\":{},\"effective_from\":\"{}\"", &self.verifier_key, &self.old_verifier_line, &self.new_verifier_line, effective_from)
+    }
+}
+
 /// Network identifier, used to search for network specs in the database
 pub type NetworkKey = Vec<u8>;
 
@@ -1,7 +1,9 @@
 use parity_scale_codec::{Decode, Encode};
 use parity_scale_codec_derive;
+use std::io::Read;
 
-use crate::{network_specs::ChainSpecsToSend, types::TypeEntry};
+use crate::{network_specs::{AssetInfo, ChainSpecsToSend, ChainSpecsToSendNoParachain, ChainSpecsToSendNoTokens, Verifier, VerifierSet}, types::TypeEntry, users::{DerivationEntry, WatchOnlyEntry}};
+use constants::{META_COMPRESSION_LEVEL, WATCH_ONLY_VERSION};
 
 /// Struct to process the content of qr codes with load_metadata messages
 pub struct ContentLoadMeta (Vec<u8>);
@@ -66,6 +68,20 @@ struct DecodedContentAddNetwork {
     specs: ChainSpecsToSend,
 }
 
+/// add_network content as generated before multi-token networks were supported
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
+struct DecodedContentAddNetworkNoTokens {
+    meta: Vec<u8>,
+    specs: ChainSpecsToSendNoTokens,
+}
+
+/// add_network content as generated before parachain information was added to network specs
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
+struct DecodedContentAddNetworkNoParachain {
+    meta: Vec<u8>,
+    specs: ChainSpecsToSendNoParachain,
+}
+
 impl ContentAddNetwork {
     /// Function to generate add_network content from metadata and network specs ChainSpecsToSend
     pub fn generate (meta: &Vec<u8>, specs: &ChainSpecsToSend) -> Self {
@@ -82,22 +98,21 @@ impl ContentAddNetwork {
     }
     /// Function to get metadata from add_network content
     pub fn meta (&self) -> Result<Vec<u8>, &'static str>  {
-        match <DecodedContentAddNetwork>::decode(&mut &self.0[..]) {
-            Ok(a) => Ok(a.meta),
-            Err(_) => return Err("add_network content could not be decoded")
-        }
+        Ok(self.meta_specs()?.0)
     }
     /// Function to get network specs ChainSpecsToSend from add_network content
     pub fn specs (&self) -> Result<ChainSpecsToSend, &'static str> {
-        match <DecodedContentAddNetwork>::decode(&mut &self.0[..]) {
-            Ok(a) => Ok(a.specs),
-            Err(_) => return Err("add_network content could not be decoded")
-        }
+        Ok(self.meta_specs()?.1)
     }
-    /// Function to decode add_network message and get both metadata and network specs as a tuple
+    /// Function to decode add_network message and get both metadata and network specs as a tuple;
+    /// messages generated with network specs in earlier formats are still accepted,
+    /// as single-token networks, and, if generated before parachain information was added, with no relay chain;
+    /// formats are tried from the newest one, as earlier format could be decoded from the later one
     pub fn meta_specs (&self) -> Result<(Vec<u8>, ChainSpecsToSend), &'static str> {
-        match <DecodedContentAddNetwork>::decode(&mut &self.0[..]) {
-            Ok(a) => Ok((a.meta, a.specs)),
+        if let Ok(a) = <DecodedContentAddNetwork>::decode(&mut &self.0[..]) {return Ok((a.meta, a.specs))}
+        if let Ok(a) = <DecodedContentAddNetworkNoTokens>::decode(&mut &self.0[..]) {return Ok((a.meta, a.specs.with_no_tokens()))}
+        match <DecodedContentAddNetworkNoParachain>::decode(&mut &self.0[..]) {
+            Ok(a) => Ok((a.meta, a.specs.with_no_parachain())),
             Err(_) => return Err("add_network content could not be decoded")
         }
     }
@@ -111,6 +126,36 @@ impl ContentAddNetwork {
 }
 
 
+/// Enum to describe errors in decompressing received qr payload content
+pub enum ContentDecompression {
+    Damaged,
+    TooLarge,
+}
+
+/// Function to compress load_metadata or add_network content for transfer;
+/// the compressed content is what gets signed
+pub fn compress_content (content: &[u8]) -> Result<Vec<u8>, String> {
+    match zstd::encode_all(content, META_COMPRESSION_LEVEL) {
+        Ok(a) => Ok(a),
+        Err(e) => return Err(e.to_string()),
+    }
+}
+
+/// Function to decompress received load_metadata or add_network content;
+/// decompression stops as soon as the output exceeds size_limit,
+/// so that the compression bomb could not exhaust the memory
+pub fn decompress_content (compressed: &[u8], size_limit: usize) -> Result<Vec<u8>, ContentDecompression> {
+    let decoder = match zstd::stream::read::Decoder::new(compressed) {
+        Ok(a) => a,
+        Err(_) => return Err(ContentDecompression::Damaged),
+    };
+    let mut out: Vec<u8> = Vec::new();
+    if let Err(_) = decoder.take(size_limit as u64 + 1).read_to_end(&mut out) {return Err(ContentDecompression::Damaged)}
+    if out.len() > size_limit {return Err(ContentDecompression::TooLarge)}
+    Ok(out)
+}
+
+
 /// Struct to process the content of qr codes with add_specs messages
 pub struct ContentAddSpecs (Vec<u8>);
 
@@ -119,6 +164,18 @@ struct DecodedContentAddSpecs {
     specs: ChainSpecsToSend,
 }
 
+/// add_specs content as generated before multi-token networks were supported
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
+struct DecodedContentAddSpecsNoTokens {
+    specs: ChainSpecsToSendNoTokens,
+}
+
+/// add_specs content as generated before parachain information was added to network specs
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
+struct DecodedContentAddSpecsNoParachain {
+    specs: ChainSpecsToSendNoParachain,
+}
+
 impl ContentAddSpecs {
     /// Function to generate add_specs content from network specs ChainSpecsToSend
     pub fn generate (specs: &ChainSpecsToSend) -> Self {
@@ -132,10 +189,13 @@ impl ContentAddSpecs {
     pub fn from_vec (vec: &Vec<u8>) -> Self {
         Self(vec.to_vec())
     }
-    /// Function to get network specs ChainSpecsToSend from add_specs content
+    /// Function to get network specs ChainSpecsToSend from add_specs content;
+    /// messages generated with network specs in earlier formats are still accepted, same as for add_network
     pub fn specs (&self) -> Result<ChainSpecsToSend, &'static str> {
-        match <DecodedContentAddSpecs>::decode(&mut &self.0[..]) {
-            Ok(a) => Ok(a.specs),
+        if let Ok(a) = <DecodedContentAddSpecs>::decode(&mut &self.0[..]) {return Ok(a.specs)}
+        if let Ok(a) = <DecodedContentAddSpecsNoTokens>::decode(&mut &self.0[..]) {return Ok(a.specs.with_no_tokens())}
+        match <DecodedContentAddSpecsNoParachain>::decode(&mut &self.0[..]) {
+            Ok(a) => Ok(a.specs.with_no_parachain()),
             Err(_) => return Err("add_specs content could not be decoded")
         }
     }
@@ -190,3 +250,186 @@ impl ContentLoadTypes {
     }
 }
 
+
+/// Struct to process the content of qr codes with set_verifiers messages
+pub struct ContentSetVerifiers (Vec<u8>);
+
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
+struct DecodedContentSetVerifiers {
+    genesis_hash: [u8; 32],
+    verifier_set: VerifierSet,
+}
+
+impl ContentSetVerifiers {
+    /// Function to generate set_verifiers content from network genesis hash and verifier set
+    pub fn generate (genesis_hash: &[u8; 32], verifier_set: VerifierSet) -> Self {
+        Self (
+            DecodedContentSetVerifiers {
+                genesis_hash: genesis_hash.to_owned(),
+                verifier_set,
+            }.encode()
+        )
+    }
+    /// Function to transform Vec<u8> into ContentSetVerifiers prior to processing
+    pub fn from_vec (vec: &Vec<u8>) -> Self {
+        Self(vec.to_vec())
+    }
+    /// Function to decode set_verifiers message and get both network genesis hash and verifier set as a tuple
+    pub fn genhash_set (&self) -> Result<([u8; 32], VerifierSet), &'static str> {
+        match <DecodedContentSetVerifiers>::decode(&mut &self.0[..]) {
+            Ok(a) => Ok((a.genesis_hash, a.verifier_set)),
+            Err(_) => return Err("set_verifiers content could not be decoded")
+        }
+    }
+    /// Function to export set_verifiers content into file
+    pub fn write (&self, filename: &str) -> Result<(), String> {
+        match std::fs::write(&filename, &self.0) {
+            Ok(_) => Ok(()),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+
+/// Struct to process the content of qr codes with rotate_verifier messages
+pub struct ContentRotateVerifier (Vec<u8>);
+
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
+struct DecodedContentRotateVerifier {
+    genesis_hash: [u8; 32],
+    new_verifier: Verifier,
+    effective_from: Option<u32>,
+}
+
+impl ContentRotateVerifier {
+    /// Function to generate rotate_verifier content from network genesis hash, new verifier,
+    /// and metadata version from which the new verifier is used (if any)
+    pub fn generate (genesis_hash: &[u8; 32], new_verifier: Verifier, effective_from: Option<u32>) -> Self {
+        Self (
+            DecodedContentRotateVerifier {
+                genesis_hash: genesis_hash.to_owned(),
+                new_verifier,
+                effective_from,
+            }.encode()
+        )
+    }
+    /// Function to transform Vec<u8> into ContentRotateVerifier prior to processing
+    pub fn from_vec (vec: &Vec<u8>) -> Self {
+        Self(vec.to_vec())
+    }
+    /// Function to decode rotate_verifier message and get network genesis hash, new verifier,
+    /// and metadata version from which the new verifier is used as a tuple
+    pub fn genhash_verifier_version (&self) -> Result<([u8; 32], Verifier, Option<u32>), &'static str> {
+        match <DecodedContentRotateVerifier>::decode(&mut &self.0[..]) {
+            Ok(a) => Ok((a.genesis_hash, a.new_verifier, a.effective_from)),
+            Err(_) => return Err("rotate_verifier content could not be decoded")
+        }
+    }
+    /// Function to export rotate_verifier content into file
+    pub fn write (&self, filename: &str) -> Result<(), String> {
+        match std::fs::write(&filename, &self.0) {
+            Ok(_) => Ok(()),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+
+/// Struct to process the content of qr codes with load_assets messages
+pub struct ContentLoadAssets (Vec<u8>);
+
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
+struct DecodedContentLoadAssets {
+    genesis_hash: [u8; 32],
+    assets: Vec<AssetInfo>,
+}
+
+impl ContentLoadAssets {
+    /// Function to generate load_assets content from network genesis hash and asset registry
+    pub fn generate (genesis_hash: &[u8; 32], assets: &Vec<AssetInfo>) -> Self {
+        Self (
+            DecodedContentLoadAssets {
+                genesis_hash: genesis_hash.to_owned(),
+                assets: assets.to_vec(),
+            }.encode()
+        )
+    }
+    /// Function to transform Vec<u8> into ContentLoadAssets prior to processing
+    pub fn from_vec (vec: &Vec<u8>) -> Self {
+        Self(vec.to_vec())
+    }
+    /// Function to decode load_assets message and get both network genesis hash and asset registry as a tuple
+    pub fn genhash_assets (&self) -> Result<([u8; 32], Vec<AssetInfo>), &'static str> {
+        match <DecodedContentLoadAssets>::decode(&mut &self.0[..]) {
+            Ok(a) => Ok((a.genesis_hash, a.assets)),
+            Err(_) => return Err("load_assets content could not be decoded")
+        }
+    }
+    /// Function to export load_assets content into file
+    pub fn write (&self, filename: &str) -> Result<(), String> {
+        match std::fs::write(&filename, &self.0) {
+            Ok(_) => Ok(()),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+
+/// Struct to process the content of qr codes with import_derivations messages
+pub struct ContentDerivations (Vec<u8>);
+
+impl ContentDerivations {
+    /// Function to generate import_derivations content from the list of derivations
+    pub fn generate (derivations: &Vec<DerivationEntry>) -> Self {
+        Self (derivations.encode())
+    }
+    /// Function to transform Vec<u8> into ContentDerivations prior to processing
+    pub fn from_vec (vec: &Vec<u8>) -> Self {
+        Self(vec.to_vec())
+    }
+    /// Function to decode import_derivations message and get the list of derivations
+    pub fn derivations (&self) -> Result<Vec<DerivationEntry>, &'static str> {
+        match <Vec<DerivationEntry>>::decode(&mut &self.0[..]) {
+            Ok(a) => Ok(a),
+            Err(_) => return Err("import_derivations content could not be decoded")
+        }
+    }
+    /// Function to export import_derivations content into file
+    pub fn write (&self, filename: &str) -> Result<(), String> {
+        match std::fs::write(&filename, &self.0) {
+            Ok(_) => Ok(()),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+
+/// Struct to process the content of watch-only multi-account export;
+/// content starts with format version byte, followed by encoded list of identities
+pub struct ContentWatchOnly (Vec<u8>);
+
+impl ContentWatchOnly {
+    /// Function to generate watch-only export content from the list of identities
+    pub fn generate (entries: &Vec<WatchOnlyEntry>) -> Self {
+        Self ([vec![WATCH_ONLY_VERSION], entries.encode()].concat())
+    }
+    /// Function to transform Vec<u8> into ContentWatchOnly prior to processing
+    pub fn from_vec (vec: &Vec<u8>) -> Self {
+        Self(vec.to_vec())
+    }
+    /// Function to get Vec<u8> to be turned into qr code
+    pub fn to_vec (&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+    /// Function to check format version and decode the list of identities
+    pub fn entries (&self) -> Result<Vec<WatchOnlyEntry>, &'static str> {
+        match self.0.get(0) {
+            Some(&WATCH_ONLY_VERSION) => match <Vec<WatchOnlyEntry>>::decode(&mut &self.0[1..]) {
+                Ok(a) => Ok(a),
+                Err(_) => return Err("watch-only export content could not be decoded"),
+            },
+            Some(_) => return Err("watch-only export format version is not supported"),
+            None => return Err("watch-only export content is empty"),
+        }
+    }
+}
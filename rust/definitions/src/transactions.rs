@@ -2,9 +2,9 @@ use parity_scale_codec_derive::{Decode, Encode};
 
 use crate::history::Event;
 use crate::metadata::NameVersioned;
-use crate::network_specs::{ChainSpecsToSend, Verifier, VerifierKey};
+use crate::network_specs::{AssetInfo, ChainSpecsToSend, Verifier, VerifierKey, VerifierSet};
 use crate::types::TypeEntry;
-use crate::users::AddressKey;
+use crate::users::{AddressKey, DerivationEntry};
 
 /// Enum to classify possible actions, and store corresponding information in the database
 #[derive(Decode, Encode)]
@@ -15,6 +15,10 @@ pub enum Transaction {
     UpdGeneralVerifier(UpdGeneralVerifier),
     LoadTypes(LoadTypes),
     AddNetwork(AddNetwork),
+    SetVerifiers(SetVerifiers),
+    RotateVerifier(RotateVerifier),
+    ImportDerivations(ImportDerivations),
+    LoadAssets(LoadAssets),
 }
 
 /// Struct to store sign_transaction action information
@@ -86,3 +90,35 @@ pub struct AddNetwork {
     pub history: Vec<Event>,
 }
 
+/// Struct to store set_verifiers action information
+#[derive(Decode, Encode)]
+pub struct SetVerifiers {
+    pub verifier_key: VerifierKey, // key in verifiers tree for the verifier set of the network
+    pub verifier_set: VerifierSet,
+    pub history: Vec<Event>,
+}
+
+/// Struct to store rotate_verifier action information
+#[derive(Decode, Encode)]
+pub struct RotateVerifier {
+    pub genesis_hash: [u8; 32],
+    pub old_verifier: Verifier,
+    pub new_verifier: Verifier,
+    pub effective_from: Option<u32>, // metadata version from which the new verifier is used, if None the rotation is immediate
+    pub history: Vec<Event>,
+}
+
+/// Struct to store import_derivations action information
+#[derive(Decode, Encode)]
+pub struct ImportDerivations {
+    pub derivations: Vec<DerivationEntry>,
+    pub history: Vec<Event>,
+}
+
+/// Struct to store load_assets action information
+#[derive(Decode, Encode)]
+pub struct LoadAssets {
+    pub genesis_hash: [u8; 32],
+    pub assets: Vec<AssetInfo>,
+    pub history: Vec<Event>,
+}
@@ -16,6 +16,85 @@ pub struct AddressDetails {
     pub name: String,
     pub network_id: Vec<NetworkKey>,
     pub encryption: Encryption,
+    pub tags: Vec<String>,
+    pub notes: String,
+}
+
+/// AddressDetails as encoded in the database before identity tags and notes were introduced,
+/// used only to migrate the database entries stored in that form
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, Debug)]
+pub struct AddressDetailsNoTags {
+    pub seed_name: String,
+    pub path: String,
+    pub has_pwd: bool,
+    pub name: String,
+    pub network_id: Vec<NetworkKey>,
+    pub encryption: Encryption,
+}
+
+impl AddressDetailsNoTags {
+    /// Function to get AddressDetails with no tags and empty notes from entry stored without them
+    pub fn with_no_tags(self) -> AddressDetails {
+        AddressDetails {
+            seed_name: self.seed_name,
+            path: self.path,
+            has_pwd: self.has_pwd,
+            name: self.name,
+            network_id: self.network_id,
+            encryption: self.encryption,
+            tags: Vec::new(),
+            notes: String::new(),
+        }
+    }
+}
+
+/// Function to escape the line entered by user, such as identity tag or notes,
+/// so that it could be printed as json string
+pub fn escape_json (line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    for c in line.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            a if (a as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", a as u32)),
+            a => out.push(a),
+        }
+    }
+    out
+}
+
+/// Struct to store single derivation from derivations list,
+/// as produced by hot side or exported from other device;
+/// path is without password part
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, Clone, Debug)]
+pub struct DerivationEntry {
+    pub path: String,
+    pub genesis_hash: [u8; 32],
+    pub encryption: Encryption,
+    pub public_key: Vec<u8>,
+    pub name: String,
+}
+
+/// Struct to store single identity in watch-only multi-account export;
+/// path is exported (without password part) only if the user opts in
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, Clone, Debug)]
+pub struct WatchOnlyEntry {
+    pub seed_name: String,
+    pub name: String,
+    pub public_key: Vec<u8>,
+    pub encryption: Encryption,
+    pub path: Option<String>,
+    pub networks: Vec<WatchOnlyNetwork>,
+}
+
+/// Struct to store network in which the watch-only identity is used
+#[derive(parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode, Clone, Debug)]
+pub struct WatchOnlyNetwork {
+    pub genesis_hash: [u8; 32],
+    pub base58prefix: u16,
 }
 
 /// Struct to move seed around
@@ -44,6 +123,37 @@ impl <'a> IdentityHistory <'a> {
 }
 
 
+/// Struct to store history entry for identity details update,
+/// i.e. identity renaming, tags and notes changes
+pub struct IdentityDetailsHistory <'a> {
+    pub seed_name: &'a str,
+    pub encryption: Encryption,
+    pub public_key: &'a str,
+    pub path: &'a str,
+    pub old_value: &'a str,
+    pub new_value: &'a str,
+}
+
+impl <'a> IdentityDetailsHistory <'a> {
+    pub fn show(&self) -> String {
+        format!("\"seed_name\":\"{}\",\"encryption\":\"{}\",\"public_key\":\"{}\",\"path\":\"{}\",\"old_value\":\"{}\",\"new_value\":\"{}\"", &self.seed_name, &self.encryption.show(), &self.public_key, &self.path, escape_json(&self.old_value), escape_json(&self.new_value))
+    }
+}
+
+
+/// Struct to store history entry for seed renaming
+pub struct SeedRenameHistory <'a> {
+    pub old_seed_name: &'a str,
+    pub new_seed_name: &'a str,
+}
+
+impl <'a> SeedRenameHistory <'a> {
+    pub fn show(&self) -> String {
+        format!("\"old_seed_name\":\"{}\",\"new_seed_name\":\"{}\"", &self.old_seed_name, &self.new_seed_name)
+    }
+}
+
+
 /// Network identifier, used to search for network specs in the database
 /// At this moment, vector made from public key
 pub type AddressKey = Vec<u8>;
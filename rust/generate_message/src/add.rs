@@ -1,28 +1,34 @@
-use constants::{ADDRESS_BOOK, HOT_DB_NAME, METATREE, SPECSTREEPREP};
+use constants::{ADDRESS_BOOK, METATREE, SPECSTREEPREP};
 use sled::{Tree, IVec};
 use anyhow;
 use db_handling::helpers::{open_db, open_tree};
 use parity_scale_codec::Encode;
 use definitions::crypto::Encryption;
 
-use crate::parser::{Instruction, Content, Set};
+use crate::config::MetadataVersions;
+use crate::parser::{Instruction, Content, NetworkFromFile, ParachainInfo, Set};
 use crate::metadata_db_utils::{add_new, prepare_metadata, write_metadata};
 use crate::error::{Error, NotFound};
-use crate::helpers::{decode_and_check_meta_entry, error_occured, network_specs_from_address_book_entry_encoded, get_from_tree, Write, update_db};
-use crate::metadata_shortcut::{MetaSpecsShortCut, meta_specs_shortcut};
+use crate::helpers::{decode_and_check_meta_entry, endpoints_for_url, error_occured, network_specs_from_address_book_entry_encoded, get_from_tree, Write, update_db};
+use crate::metadata_shortcut::{MetaSpecsShortCut, meta_specs_from_file, meta_specs_shortcut};
 use crate::output_prep::add_network_print;
 
 
 /// Function to generate `add_network` message ready for signing.
 /// Exact behavior is determined by the keys used.
+/// Hot database, the folder for output files and the number of metadata versions
+/// kept in the database are provided separately.
 
-pub fn gen_add_network (instruction: Instruction) -> anyhow::Result<()> {
+pub fn gen_add_network (instruction: Instruction, database_name: &str, folder: &str, metadata_versions: &MetadataVersions) -> anyhow::Result<()> {
     
-    let database = open_db(HOT_DB_NAME)?;
+    let database = open_db(database_name)?;
     let address_book = open_tree(&database, ADDRESS_BOOK)?;
     let metadata = open_tree(&database, METATREE)?;
     let chainspecs = open_tree(&database, SPECSTREEPREP)?;
     
+// relay chain and parachain id could be set only for networks processed through rpc calls
+    if let (Some(_), Set::F) = (&instruction.parachain_info, &instruction.set) {return Err(Error::NotSupported.show())}
+    
     match instruction.set {
         Set::F => {
             match instruction.content {
@@ -32,7 +38,7 @@ pub fn gen_add_network (instruction: Instruction) -> anyhow::Result<()> {
                         if address_book.len() == 0 {return Err(Error::AddressBookEmpty.show())}
                         for x in address_book.iter() {
                             if let Ok((_, address_book_entry_encoded)) = x {
-                                match network_f_a_element(address_book_entry_encoded, &chainspecs, &metadata) {
+                                match network_f_a_element(address_book_entry_encoded, &chainspecs, &metadata, folder) {
                                     Ok(()) => (),
                                     Err(e) => error_occured(e, instruction.pass_errors)?,
                                 }
@@ -43,7 +49,7 @@ pub fn gen_add_network (instruction: Instruction) -> anyhow::Result<()> {
                 },
                 Content::Name(name) => {
                     if let Some(_) = instruction.encryption_override {return Err(Error::NotSupported.show())}
-                    else {network_f_n(&name, &address_book, &chainspecs, &metadata)}
+                    else {network_f_n(&name, &address_book, &chainspecs, &metadata, folder)}
                 },
                 Content::Address(_) => return Err(Error::NotSupported.show()),
             }
@@ -53,7 +59,7 @@ pub fn gen_add_network (instruction: Instruction) -> anyhow::Result<()> {
                 Content::All => return Err(Error::NotSupported.show()),
                 Content::Name(_) => return Err(Error::NotSupported.show()),
                 Content::Address(address) => {
-                    if let Some(encryption) = instruction.encryption_override {network_d_u(&address, &address_book, &chainspecs, encryption)}
+                    if let Some(encryption) = instruction.encryption_override {network_d_u(&address, &address_book, &chainspecs, encryption, &instruction.parachain_info, folder)}
                     else {return Err(Error::NotSupported.show())}
                 },
             }
@@ -63,7 +69,7 @@ pub fn gen_add_network (instruction: Instruction) -> anyhow::Result<()> {
                 Content::All => return Err(Error::NotSupported.show()),
                 Content::Name(_) => return Err(Error::NotSupported.show()),
                 Content::Address(address) => {
-                    if let Some(encryption) = instruction.encryption_override {network_kpt_u(&address, &address_book, &chainspecs, &metadata, encryption, Write::OnlyNew)}
+                    if let Some(encryption) = instruction.encryption_override {network_kpt_u(&address, &address_book, &chainspecs, &metadata, encryption, Write::OnlyNew, &instruction.parachain_info, folder, metadata_versions)}
                     else {return Err(Error::NotSupported.show())}
                 },
             }
@@ -73,7 +79,7 @@ pub fn gen_add_network (instruction: Instruction) -> anyhow::Result<()> {
                 Content::All => return Err(Error::NotSupported.show()),
                 Content::Name(_) => return Err(Error::NotSupported.show()),
                 Content::Address(address) => {
-                    if let Some(encryption) = instruction.encryption_override {network_kpt_u(&address, &address_book, &chainspecs, &metadata, encryption, Write::None)}
+                    if let Some(encryption) = instruction.encryption_override {network_kpt_u(&address, &address_book, &chainspecs, &metadata, encryption, Write::None, &instruction.parachain_info, folder, metadata_versions)}
                     else {return Err(Error::NotSupported.show())}
                 },
             }
@@ -83,7 +89,7 @@ pub fn gen_add_network (instruction: Instruction) -> anyhow::Result<()> {
                 Content::All => return Err(Error::NotSupported.show()),
                 Content::Name(_) => return Err(Error::NotSupported.show()),
                 Content::Address(address) => {
-                    if let Some(encryption) = instruction.encryption_override {network_kpt_u(&address, &address_book, &chainspecs, &metadata, encryption, Write::All)}
+                    if let Some(encryption) = instruction.encryption_override {network_kpt_u(&address, &address_book, &chainspecs, &metadata, encryption, Write::All, &instruction.parachain_info, folder, metadata_versions)}
                     else {return Err(Error::NotSupported.show())}
                 },
             }
@@ -95,10 +101,10 @@ pub fn gen_add_network (instruction: Instruction) -> anyhow::Result<()> {
 /// Expected behavior:  
 /// get network specs for the entry,
 /// scan prefix in `metadata` database tree in search of network specname
-/// to get all versions available in the database (max 2),
+/// to get all versions available in the database,
 /// check meta_values integrity (network specname and spec_version),
 /// and print into `sign_me` output file.  
-fn network_f_a_element(address_book_entry_encoded: IVec, chainspecs: &Tree, metadata: &Tree) -> anyhow::Result<()> {
+fn network_f_a_element(address_book_entry_encoded: IVec, chainspecs: &Tree, metadata: &Tree, folder: &str) -> anyhow::Result<()> {
     let network_specs = network_specs_from_address_book_entry_encoded (address_book_entry_encoded, chainspecs)?;
     for x in metadata.scan_prefix(network_specs.name.encode()) {
         if let Ok(a) = x {
@@ -108,7 +114,7 @@ fn network_f_a_element(address_book_entry_encoded: IVec, chainspecs: &Tree, meta
                 specs: network_specs.to_owned(),
                 update: false,
             };
-            add_network_print(&specs_shortcut)?;
+            add_network_print(&specs_shortcut, folder)?;
         }
     }
     Ok(())
@@ -120,38 +126,50 @@ fn network_f_a_element(address_book_entry_encoded: IVec, chainspecs: &Tree, meta
 /// get from `address_book` the entry corresponding to the name, generate network key,
 /// with it find network specs in `chainspecs` database tree,
 /// scan prefix in `metadata` database tree in search of network specname
-/// to get all versions available in the database (max 2),
+/// to get all versions available in the database,
 /// check `meta_values` integrity (network specname and `spec_version`),
 /// and print into `sign_me` output file.  
-fn network_f_n(name: &str, address_book: &Tree, chainspecs: &Tree, metadata: &Tree) -> anyhow::Result<()> {
+fn network_f_n(name: &str, address_book: &Tree, chainspecs: &Tree, metadata: &Tree, folder: &str) -> anyhow::Result<()> {
     match get_from_tree (&name.encode(), address_book)? {
-        Some(address_book_entry_encoded) => network_f_a_element (address_book_entry_encoded, chainspecs, metadata),
+        Some(address_book_entry_encoded) => network_f_a_element (address_book_entry_encoded, chainspecs, metadata, folder),
         None => return Err(Error::NotFound(NotFound::AddressBookKey(name.to_string())).show()),
     }
 }
 
 /// Function to process `add_network -d -u url` run.
 /// Expected behavior:  
-/// fetch information from address, check it,
-/// and print into `sign_me` output file.
-fn network_d_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption) -> anyhow::Result<()> {
-    let shortcut = meta_specs_shortcut (address, address_book, chainspecs, encryption)?;
-    add_network_print(&shortcut)
+/// fetch information from address (or from the endpoints of the network known in `address_book`,
+/// in failover order), check it, and print into `sign_me` output file.
+fn network_d_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption, parachain_info: &Option<ParachainInfo>, folder: &str) -> anyhow::Result<()> {
+    let shortcut = meta_specs_shortcut (&endpoints_for_url(address, address_book)?, address_book, chainspecs, encryption, parachain_info)?;
+    add_network_print(&shortcut, folder)
 }
 
 /// Function to process `add_network -k -u url`, `add_network -p -u url`, `add_network -t -u url` and `add_network -u url` runs.
 /// Expected behavior:  
-/// fetch information from address, check it, update the database with it,
+/// fetch information from address (or from the endpoints of the network known in `address_book`,
+/// in failover order), check it, update the database with it,
 /// and print into `sign_me` output file if needed.
-fn network_kpt_u (address: &str, address_book: &Tree, chainspecs: &Tree, metadata: &Tree, encryption: Encryption, write: Write) -> anyhow::Result<()> {
-    let shortcut = meta_specs_shortcut (address, address_book, chainspecs, encryption)?;
-    if shortcut.update {update_db (address, &shortcut.specs, chainspecs, address_book)?}
+fn network_kpt_u (address: &str, address_book: &Tree, chainspecs: &Tree, metadata: &Tree, encryption: Encryption, write: Write, parachain_info: &Option<ParachainInfo>, folder: &str, metadata_versions: &MetadataVersions) -> anyhow::Result<()> {
+    let addresses = endpoints_for_url(address, address_book)?;
+    let shortcut = meta_specs_shortcut (&addresses, address_book, chainspecs, encryption, parachain_info)?;
+    if shortcut.update {update_db (&addresses, &shortcut.specs, chainspecs, address_book)?}
     let sorted_meta_values = prepare_metadata(&metadata)?;
-    let upd_sorted = add_new(&shortcut.meta_values, &sorted_meta_values)?;
+    let upd_sorted = add_new(&shortcut.meta_values, &sorted_meta_values, metadata_versions.for_network(&shortcut.meta_values.name))?;
     match write {
-        Write::All => add_network_print(&shortcut)?,
-        Write::OnlyNew => if upd_sorted.upd_done {add_network_print(&shortcut)?},
+        Write::All => add_network_print(&shortcut, folder)?,
+        Write::OnlyNew => if upd_sorted.upd_done {add_network_print(&shortcut, folder)?},
         Write::None => (),
     }
     write_metadata(upd_sorted.sorted, &metadata)
 }
+
+
+/// Function to process `add_network_from_file` run.
+/// Expected behavior:  
+/// read metadata from file, get network specs from metadata constants and provided values,
+/// and print into `sign_me` output file; no rpc calls are made and the database is not used.
+pub fn gen_add_network_from_file (network: NetworkFromFile, folder: &str) -> anyhow::Result<()> {
+    let shortcut = meta_specs_from_file(&network)?;
+    add_network_print(&shortcut, folder)
+}
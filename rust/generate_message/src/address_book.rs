@@ -0,0 +1,104 @@
+use constants::{ADDRESS_BOOK, SPECSTREEPREP};
+use sled::Tree;
+use anyhow;
+use db_handling::helpers::{open_db, open_tree, insert_into_tree};
+use parity_scale_codec::{Decode, Encode};
+use definitions::{metadata::AddressBookEntry, network_specs::generate_network_key};
+
+use crate::parser::{AddressBook, Remove};
+use crate::error::{Error, NotDecodeable, NotFound};
+use crate::helpers::{get_and_decode_chain_specs_to_send, get_from_tree, update_db};
+use crate::metadata_shortcut::meta_specs_shortcut;
+use crate::remove::remove_info;
+use crate::show::show_address_book;
+
+
+/// Function to view and edit address book entries in the hot database.
+/// Rpc endpoints are shared by all address book entries of the same network,
+/// so that all encryption varieties of the network are fetched from same endpoints.
+pub fn address_book_run (command: AddressBook, database_name: &str) -> anyhow::Result<()> {
+    match command {
+        AddressBook::List => show_address_book(database_name),
+        AddressBook::Add{addresses, encryption, parachain_info} => {
+            let database = open_db(database_name)?;
+            let address_book = open_tree(&database, ADDRESS_BOOK)?;
+            let chainspecs = open_tree(&database, SPECSTREEPREP)?;
+            let shortcut = meta_specs_shortcut(&addresses, &address_book, &chainspecs, encryption, &parachain_info)?;
+            if !shortcut.update {return Err(Error::SpecsInDb{name: shortcut.specs.title, encryption}.show())}
+            update_db(&addresses, &shortcut.specs, &chainspecs, &address_book)?;
+            set_network_addresses(&shortcut.specs.name, &addresses, &address_book)
+        },
+        AddressBook::Rename{title, new_title} => {
+            let database = open_db(database_name)?;
+            let address_book = open_tree(&database, ADDRESS_BOOK)?;
+            let chainspecs = open_tree(&database, SPECSTREEPREP)?;
+            if get_from_tree(&new_title.encode(), &address_book)?.is_some() {return Err(Error::AddressBookKeyInDb(new_title).show())}
+            let address_book_entry = get_address_book_entry(&title, &address_book)?;
+            let network_key = generate_network_key(&address_book_entry.genesis_hash.to_vec(), address_book_entry.encryption);
+            let mut network_specs = get_and_decode_chain_specs_to_send(&chainspecs, &network_key)?;
+            network_specs.title = new_title.to_string();
+            insert_into_tree(network_key, network_specs.encode(), &chainspecs)?;
+            if let Err(e) = address_book.remove(&title.encode()) {return Err(Error::InternalDatabaseError(e).show())}
+            insert_into_tree(new_title.encode(), address_book_entry.encode(), &address_book)
+        },
+        AddressBook::SetUrl{title, addresses} => {
+            let database = open_db(database_name)?;
+            let address_book = open_tree(&database, ADDRESS_BOOK)?;
+            let address_book_entry = get_address_book_entry(&title, &address_book)?;
+            set_network_addresses(&address_book_entry.name, &addresses, &address_book)
+        },
+        AddressBook::SetDefault(title) => {
+            let database = open_db(database_name)?;
+            let address_book = open_tree(&database, ADDRESS_BOOK)?;
+            let address_book_entry = get_address_book_entry(&title, &address_book)?;
+            for (key, mut x) in network_entries(&address_book_entry.name, &address_book)?.into_iter() {
+                x.def = key == title;
+                insert_into_tree(key.encode(), x.encode(), &address_book)?;
+            }
+            Ok(())
+        },
+        AddressBook::Remove(title) => remove_info(Remove::Title(title), database_name),
+    }
+}
+
+/// Function to get address book entry by network title
+fn get_address_book_entry (title: &str, address_book: &Tree) -> anyhow::Result<AddressBookEntry> {
+    match get_from_tree(&title.encode(), address_book)? {
+        Some(address_book_entry_encoded) => match <AddressBookEntry>::decode(&mut &address_book_entry_encoded[..]) {
+            Ok(a) => Ok(a),
+            Err(_) => return Err(Error::NotDecodeable(NotDecodeable::AddressBookEntry).show()),
+        },
+        None => return Err(Error::NotFound(NotFound::AddressBookKey(title.to_string())).show()),
+    }
+}
+
+/// Function to collect all address book entries of the network with given specname,
+/// together with their titles
+fn network_entries (name: &str, address_book: &Tree) -> anyhow::Result<Vec<(String, AddressBookEntry)>> {
+    let mut out: Vec<(String, AddressBookEntry)> = Vec::new();
+    for x in address_book.iter() {
+        if let Ok((title_encoded, address_book_entry_encoded)) = x {
+            let address_book_entry = match <AddressBookEntry>::decode(&mut &address_book_entry_encoded[..]) {
+                Ok(a) => a,
+                Err(_) => return Err(Error::NotDecodeable(NotDecodeable::AddressBookEntry).show()),
+            };
+            let title = match <String>::decode(&mut &title_encoded[..]) {
+                Ok(a) => a,
+                Err(_) => return Err(Error::NotDecodeable(NotDecodeable::AddressBookKey).show()),
+            };
+            if address_book_entry.name == name {out.push((title, address_book_entry))}
+        }
+    }
+    Ok(out)
+}
+
+/// Function to set rpc endpoints in failover order for all address book entries of the network
+fn set_network_addresses (name: &str, addresses: &[String], address_book: &Tree) -> anyhow::Result<()> {
+    if addresses.is_empty() {return Err(Error::NoEndpoints.show())}
+    for (title, mut x) in network_entries(name, address_book)?.into_iter() {
+        x.address = addresses[0].to_string();
+        x.fallback_addresses = addresses[1..].to_vec();
+        insert_into_tree(title.encode(), x.encode(), address_book)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use constants::ASSETLOAD;
+use definitions::qr_transfers::ContentLoadAssets;
+use anyhow;
+
+use crate::parser::LoadAssets;
+use crate::error::Error;
+
+/// Function to process `load_assets` run.
+/// Expected behavior:
+/// print network genesis hash and asset registry into `sign_me` output file;
+/// no rpc calls are made and the database is not used.
+pub fn gen_load_assets (load_assets: LoadAssets, folder: &str) -> anyhow::Result<()> {
+    let content = ContentLoadAssets::generate(&load_assets.genesis_hash, &load_assets.assets);
+    match content.write(&format!("{}/{}_{}", folder, ASSETLOAD, hex::encode(load_assets.genesis_hash))) {
+        Ok(_) => Ok(()),
+        Err(e) => return Err(Error::InputOutputError(e).show()),
+    }
+}
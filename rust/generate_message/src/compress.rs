@@ -0,0 +1,36 @@
+use definitions::qr_transfers::{ContentLoadMeta, ContentAddNetwork, compress_content};
+use anyhow;
+
+use crate::parser::{Compress, Msg};
+use crate::error::Error;
+
+/// Function to write compressed content of `load_metadata` or `add_network` payload,
+/// to be signed for `load_metadata_compressed` or `add_network_compressed` message.
+/// Compressed content is written next to the original payload, with `_compressed` added to the file name.
+/// Compression is deterministic, so the signature made for the compressed file matches
+/// the content compressed from the original payload in `make` and `sign`.
+
+pub fn compress_payload (compress: Compress, folder: &str) -> anyhow::Result<()> {
+    let filename = format!("{}/{}_compressed", folder, compress.payload);
+    let vec = match compress.msg {
+        Msg::LoadMetadata(vec) => {
+            if let Err(_) = ContentLoadMeta::from_vec(&vec).meta_genhash() {return Err(Error::NotLoadMetadata.show())}
+            vec
+        },
+        Msg::AddNetwork(vec) => {
+            if let Err(_) = ContentAddNetwork::from_vec(&vec).meta_specs() {return Err(Error::NotAddNetwork.show())}
+            vec
+        },
+        _ => return Err(Error::NotSupported.show()),
+    };
+    if let Err(e) = std::fs::write(&filename, compress_vec(&vec)?) {return Err(Error::InputOutputError(e.to_string()).show())}
+    Ok(())
+}
+
+/// Function to compress load_metadata or add_network content before signing
+pub fn compress_vec (vec: &Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    match compress_content(vec) {
+        Ok(a) => Ok(a),
+        Err(e) => return Err(Error::Compression(e).show()),
+    }
+}
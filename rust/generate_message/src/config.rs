@@ -0,0 +1,130 @@
+use constants::{EXPORT_FOLDER, FOLDER, HOT_DB_NAME};
+use definitions::crypto::Encryption;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow;
+
+use crate::error::Error;
+
+/// Workspace config file, looked for in current folder if no other file is set with `--config` key
+pub const CONFIG_FILE: &str = "generate_message.toml";
+
+/// Number of metadata versions kept in the hot database for each network, if not set in config file
+pub const METADATA_VERSIONS: usize = 2;
+
+/// Workspace settings used in generate_message runs
+pub struct Config {
+    pub database: String, // hot database
+    pub for_signing: String, // folder for payloads ready for signing, and for signatures and verifier keys
+    pub signed: String, // folder for signed messages
+    pub default_encryption: Option<Encryption>, // encryption for networks added by url without encryption key
+    pub endpoints: HashMap<String, String>, // rpc endpoints by name, to be used instead of url
+    pub metadata_versions: MetadataVersions,
+}
+
+/// Number of latest metadata versions kept in the hot database,
+/// same for all networks unless set for the network separately
+pub struct MetadataVersions {
+    pub default: usize,
+    pub networks: HashMap<String, usize>,
+}
+
+impl MetadataVersions {
+    /// Function to get the number of metadata versions kept for the network with given specname
+    pub fn for_network(&self, name: &str) -> usize {
+        match self.networks.get(name) {
+            Some(a) => *a,
+            None => self.default,
+        }
+    }
+}
+
+/// Config file content as it is written in TOML
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    database: Option<String>,
+    for_signing: Option<String>,
+    signed: Option<String>,
+    default_encryption: Option<String>,
+    #[serde(default)]
+    endpoints: HashMap<String, String>,
+    metadata_versions: Option<usize>,
+    #[serde(default)]
+    network_metadata_versions: HashMap<String, usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            database: HOT_DB_NAME.to_string(),
+            for_signing: FOLDER.to_string(),
+            signed: EXPORT_FOLDER.to_string(),
+            default_encryption: None,
+            endpoints: HashMap::new(),
+            metadata_versions: MetadataVersions {
+                default: METADATA_VERSIONS,
+                networks: HashMap::new(),
+            },
+        }
+    }
+}
+
+/// Function to get workspace settings.
+/// Config file set by user must exist; if no file is set, and there is no
+/// default config file in current folder, the default settings are used.
+/// Relative paths in config file are counted from the folder of the config file.
+pub fn load_config (filename: Option<&str>) -> anyhow::Result<Config> {
+    let filename = match filename {
+        Some(a) => a,
+        None => {
+            if Path::new(CONFIG_FILE).exists() {CONFIG_FILE}
+            else {return Ok(Config::default())}
+        },
+    };
+    let content = match std::fs::read_to_string(filename) {
+        Ok(a) => a,
+        Err(e) => return Err(config_error(filename, &e.to_string())),
+    };
+    let config_file: ConfigFile = match toml::from_str(&content) {
+        Ok(a) => a,
+        Err(e) => return Err(config_error(filename, &e.to_string())),
+    };
+    let base = Path::new(filename).parent().unwrap_or(Path::new(""));
+    let default = Config::default();
+    let default_encryption = match config_file.default_encryption {
+        Some(a) => match a.as_str() {
+            "ed25519" => Some(Encryption::Ed25519),
+            "sr25519" => Some(Encryption::Sr25519),
+            "ecdsa" => Some(Encryption::Ecdsa),
+            _ => return Err(config_error(filename, &format!("Unknown default encryption {}.", a))),
+        },
+        None => None,
+    };
+    let metadata_versions = MetadataVersions {
+        default: config_file.metadata_versions.unwrap_or(METADATA_VERSIONS),
+        networks: config_file.network_metadata_versions,
+    };
+    if (metadata_versions.default == 0)||metadata_versions.networks.values().any(|a| *a == 0) {return Err(config_error(filename, "At least one metadata version should be kept for each network."))}
+    Ok(Config {
+        database: config_path(base, config_file.database, default.database),
+        for_signing: config_path(base, config_file.for_signing, default.for_signing),
+        signed: config_path(base, config_file.signed, default.signed),
+        default_encryption,
+        endpoints: config_file.endpoints,
+        metadata_versions,
+    })
+}
+
+/// Function to get path from config file, relative to config file folder
+fn config_path (base: &Path, path: Option<String>, default: String) -> String {
+    match path {
+        Some(a) => base.join(a).to_string_lossy().to_string(),
+        None => default,
+    }
+}
+
+fn config_error (filename: &str, error: &str) -> anyhow::Error {
+    Error::Config{filename: filename.to_string(), error: error.to_string()}.show()
+}
@@ -0,0 +1,61 @@
+use constants::METATREE;
+use definitions::metadata::NameVersioned;
+use db_handling::helpers::{open_db, open_tree};
+use meta_reading::diff_metadata::diff_metadata;
+use anyhow;
+
+use crate::helpers::decode_and_check_meta_entry;
+use crate::parser::Diff;
+use crate::error::{Error, NotFound};
+
+/// Function to show the differences between two metadata versions of the network
+/// from the hot database: pallets and calls added or removed,
+/// calls with changed arguments and changed constants.
+/// If versions are not specified, the latest version is compared with the previous one.
+
+pub fn diff_versions (diff: Diff, database_name: &str) -> anyhow::Result<()> {
+
+    let database = open_db(database_name)?;
+    let metadata = open_tree(&database, METATREE)?;
+    let mut found: Vec<(u32, Vec<u8>)> = Vec::new();
+    for x in metadata.iter() {
+        if let Ok(a) = x {
+            let meta_values = decode_and_check_meta_entry(a)?;
+            if meta_values.name == diff.name {found.push((meta_values.version, meta_values.meta))}
+        }
+    }
+    found.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let to = match diff.to {
+        Some(a) => a,
+        None => match found.last() {
+            Some((a, _)) => *a,
+            None => return Err(Error::NotEnoughVersions(diff.name).show()),
+        },
+    };
+    let from = match diff.from {
+        Some(a) => a,
+        None => match found.iter().rev().find(|(a, _)| *a < to) {
+            Some((a, _)) => *a,
+            None => return Err(Error::NotEnoughVersions(diff.name).show()),
+        },
+    };
+    let old_meta = find_version(&found, &diff.name, from)?;
+    let new_meta = find_version(&found, &diff.name, to)?;
+
+    let metadata_diff = match diff_metadata(old_meta, new_meta) {
+        Ok(a) => a,
+        Err(e) => return Err(Error::Diff(e).show()),
+    };
+    println!("Changes in {} from version {} to version {}:", diff.name, from, to);
+    println!("{}", metadata_diff.show());
+    Ok(())
+}
+
+/// Function to select metadata of given version from the collected ones
+fn find_version <'a> (found: &'a Vec<(u32, Vec<u8>)>, name: &str, version: u32) -> anyhow::Result<&'a Vec<u8>> {
+    match found.iter().find(|(a, _)| *a == version) {
+        Some((_, meta)) => Ok(meta),
+        None => return Err(Error::NotFound(NotFound::NameVersioned(NameVersioned{name: name.to_string(), version})).show()),
+    }
+}
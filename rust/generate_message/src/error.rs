@@ -14,18 +14,21 @@ pub enum Error {
     TwoEntriesAddressEncryption{address: String, encryption: Encryption},
     TwoDefaultsAddress(String),
     SpecsInDb{name: String, encryption: Encryption},
+    AddressBookKeyInDb(String),
     UnexpectedGenesisHashFormat,
     FetchFailed{address: String, error: String},
+    NoEndpoints,
     BadNetworkProperties{address: String, error: String},
     Base58Changed(String),
     DecimalsChanged(String),
     UnitChanged(String),
+    TokensChanged(String),
     NameChanged(String),
+    ParachainInfoChanged(String),
     GenesisHashChanged{address: String, old_genesis_hash: [u8; 32], new_genesis_hash: [u8; 32]},
     NoEntriesExpected(String),
     DatabaseMetadata{name: String, version: u32, error: String},
     DatabaseMetadataMismatch{name1: String, version1: u32, name2: String, version2: u32},
-    DatabaseMetadataOverTwoEntries(String),
     DatabaseMetadataSameVersionTwice{name: String, version: u32},
     FetchedEarlierVersion{name: String, old_version: u32, new_version: u32},
     SameVersionDifferentMetadata{name: String, version: u32},
@@ -38,20 +41,28 @@ pub enum Error {
     NotAddNetwork,
     MessageNameMismatch{name_meta: String, name_specs: String},
     NotAddSpecs,
+    NotLoadAssets,
     WrongLengthPublicKey,
     WrongLengthSignature,
     BadSignature(Encryption),
+    SignatureForUncompressed,
     AliceKey(Encryption),
+    KeyFile{filename: String, error: String},
     Qr(String),
+    QrRead(String),
+    Compression(String),
+    Pruning(String),
+    Diff(String),
+    MetadataFile{filename: String, error: String},
+    ChainSpecFile{filename: String, error: String},
+    NoBase58Prefix(String),
+    NotEnoughVersions(String),
+    Inspect(InspectFailure),
+    Config{filename: String, error: String},
     NeedArgument(NeedArgument),
-    DoubleKey(DoubleKey),
-    OnlyOneNetworkId,
     BadArgument(BadArgument),
     NeedKey(NeedKey),
-    UnexpectedKeyArgumentSequence,
     Unexpected(Unexpected),
-    UnknownCommand,
-    NoCommand,
 }
 
 pub enum NotDecodeable {
@@ -63,6 +74,17 @@ pub enum NotDecodeable {
     SufficientCrypto,
 }
 
+pub enum InspectFailure {
+    File{filename: String, error: String},
+    NotSubstrate,
+    TooShort,
+    CryptoNotSupported(String),
+    MsgTypeNotSupported(String),
+    Signatures,
+    DecompressionDamaged,
+    DecompressedTooLarge,
+}
+
 pub enum NotFound {
     NetworkKey,
     AddressBookKey(String),
@@ -74,59 +96,27 @@ pub enum NotFound {
 pub enum NeedArgument {
     NetworkName,
     NetworkUrl,
-    CryptoKey,
-    Verifier,
-    VerifierHex,
-    VerifierFile,
-    Payload,
-    MsgType,
-    Signature,
-    SignatureHex,
-    SignatureFile,
-    Name,
-    SufficientCrypto,
-    SufficientCryptoHex,
-    SufficientCryptoFile,
-    Make,
-    Sign,
-    RemoveTitle,
-    RemoveName,
-    RemoveVersion,
-}
-
-pub enum DoubleKey {
-    Content,
-    Set,
-    CryptoOverride,
-    CryptoKey,
-    MsgType,
-    Verifier,
-    Payload,
-    Signature,
-    Name,
-    SufficientCrypto,
-    Remove,
 }
 
 pub enum NeedKey {
-    Show,
-    Content,
     Crypto,
-    Payload,
-    MsgType,
     SufficientCrypto,
     Signature,
     Verifier,
     Remove,
     RemoveVersion,
+    Relay,
+    ParachainId,
+    Decimals,
+    Unit,
+    Password,
+    Encryption,
 }
 
 pub enum BadArgument {
-    CryptoKey,
-    MsgType,
-    Verifier,
-    Signature,
-    SufficientCrypto,
+    Relay,
+    GenesisHash,
+    Asset,
 }
 
 pub enum Unexpected {
@@ -134,7 +124,11 @@ pub enum Unexpected {
     VerifierNoCrypto,
     SignatureNoCrypto,
     AliceSignature,
-    VersionFormat,
+    PropertiesWithChainSpec,
+    AliceNoDev,
+    KeyFileSignature,
+    PasswordNoKeyFile,
+    SufficientCryptoWithVerifier,
 }
 
 impl Error {
@@ -168,18 +162,21 @@ impl Error {
             Error::TwoEntriesAddressEncryption{address, encryption} => anyhow!("Database contains two entries for network with url {} and encryption {}.", address, encryption.show()),
             Error::TwoDefaultsAddress(url) => anyhow!("Database contains two default entries for network with url {}.", url),
             Error::SpecsInDb{name, encryption} => anyhow!("Network specs entry for {} and encryption {} is already in database.", name, encryption.show()),
+            Error::AddressBookKeyInDb(title) => anyhow!("Address book key {} is already in the database.", title),
             Error::UnexpectedGenesisHashFormat => anyhow!("Fetched genesis hash has unexpected format."),
             Error::FetchFailed{address, error} => anyhow!("Error processing rpc call at {}. {}", address, error),
+            Error::NoEndpoints => anyhow!("No rpc endpoints provided."),
             Error::BadNetworkProperties{address, error} => anyhow!("Error interpreting network properties fetched by rpc call at {}. {}", address, error),
             Error::Base58Changed(address) => anyhow!("Base58 prefix fetched by rpc call at {} differs from the one in the database.", address),
             Error::DecimalsChanged(address) => anyhow!("Decimals fetched by rpc call at {} differ from the one in the database.", address),
             Error::UnitChanged(address) => anyhow!("Unit fetched by rpc call at {} differs from the one in the database.", address),
+            Error::TokensChanged(address) => anyhow!("Tokens fetched by rpc call at {} differ from the ones in the database.", address),
             Error::NameChanged(address) => anyhow!("Network name, as derived from metadata fetched by rpc call at {} differs from the one in the database.", address),
+            Error::ParachainInfoChanged(address) => anyhow!("Relay chain and parachain id provided for network at {} differ from the ones in the database.", address),
             Error::GenesisHashChanged{address, old_genesis_hash, new_genesis_hash} => anyhow!("Genesis hash fetched by rpc call at {} differs from the one in the database. Old: {}, new: {}.", address, hex::encode(old_genesis_hash), hex::encode(new_genesis_hash)),
             Error::NoEntriesExpected(address) => anyhow!("No entries for address {} found in address book, however the entries with corresponding network are found. Database needs attention.", address),
             Error::DatabaseMetadata{name, version, error} => anyhow!("Error in metadata entry {}{} from database. {}", name, version, error),
             Error::DatabaseMetadataMismatch{name1, version1, name2, version2} => anyhow!("Error in metadata entry {}{} from database. Metadata decodes into {}{}", name1, version1, name2, version2),
-            Error::DatabaseMetadataOverTwoEntries(name) => anyhow!("More than two entries for network {} in database.", name),
             Error::DatabaseMetadataSameVersionTwice{name, version} => anyhow!("Two entries in the database for {} version {}.", name, version),
             Error::FetchedEarlierVersion{name, old_version, new_version} => anyhow!("For {} the fetched version {} is lower than the latest version in the database {}.", name, new_version, old_version),
             Error::SameVersionDifferentMetadata{name, version} => anyhow!("Fetched metadata for {}{} differs from the one in the database.", name, version),
@@ -192,90 +189,80 @@ impl Error {
             Error::NotAddNetwork => anyhow!("Provided message has no add_network content."),
             Error::MessageNameMismatch{name_meta, name_specs} => anyhow!("Network name in metadata {} does not match the name in network specs {}.", name_meta, name_specs),
             Error::NotAddSpecs => anyhow!("Provided message has no add_specs content."),
+            Error::NotLoadAssets => anyhow!("Provided message has no load_assets content."),
             Error::WrongLengthPublicKey => anyhow!("Provided verifier public key has wrong length."),
             Error::WrongLengthSignature => anyhow!("Provided signature has wrong length."),
             Error::BadSignature(x) => anyhow!("Bad {} signature.", x.show()),
+            Error::SignatureForUncompressed => anyhow!("Signature is made for uncompressed content. Compressed message should be signed over compressed content, as written by `compress` command."),
             Error::AliceKey(x) => anyhow!("Error generating Alice key for {} encryption.", x.show()),
+            Error::KeyFile{filename, error} => anyhow!("Error loading signing key from file {}. {}", filename, error),
             Error::Qr(e) => anyhow!("Error generating apng qr code. {}", e),
+            Error::QrRead(e) => anyhow!("Error reading qr code. {}", e),
+            Error::Compression(e) => anyhow!("Error compressing message content. {}", e),
+            Error::Pruning(e) => anyhow!("Error pruning metadata. {}", e),
+            Error::Diff(e) => anyhow!("Error comparing metadata. {}", e),
+            Error::MetadataFile{filename, error} => anyhow!("Error decoding metadata from file {}. {}", filename, error),
+            Error::ChainSpecFile{filename, error} => anyhow!("Error reading network properties from chain spec file {}. {}", filename, error),
+            Error::NoBase58Prefix(filename) => anyhow!("Metadata from file {} has no SS58Prefix constant, base58 prefix must be provided with `--base58` key.", filename),
+            Error::NotEnoughVersions(name) => anyhow!("Database has less than two metadata versions for {}, nothing to compare.", name),
+            Error::Inspect(x) => {
+                match x {
+                    InspectFailure::File{filename, error} => anyhow!("Unable to read payload from file {}. {}", filename, error),
+                    InspectFailure::NotSubstrate => anyhow!("Payload does not start with 53, only Substrate payloads are expected."),
+                    InspectFailure::TooShort => anyhow!("Payload is too short for declared encryption."),
+                    InspectFailure::CryptoNotSupported(x) => anyhow!("Encryption code {} in payload is not supported.", x),
+                    InspectFailure::MsgTypeNotSupported(x) => anyhow!("Message type code {} in payload is not supported for inspection.", x),
+                    InspectFailure::Signatures => anyhow!("Unable to decode signatures of multi-signed payload."),
+                    InspectFailure::DecompressionDamaged => anyhow!("Unable to decompress message content."),
+                    InspectFailure::DecompressedTooLarge => anyhow!("Decompressed message content exceeds allowed size."),
+                }
+            },
+            Error::Config{filename, error} => anyhow!("Error reading workspace config file {}. {}", filename, error),
             Error::NeedArgument(x) => {
                 let insert = match x {
                     NeedArgument::NetworkName => "`-n`",
                     NeedArgument::NetworkUrl => "`-u`",
-                    NeedArgument::CryptoKey => "`-crypto`",
-                    NeedArgument::MsgType => "`-msgtype`",
-                    NeedArgument::Verifier => "`-verifier`",
-                    NeedArgument::VerifierHex => "`-verifier -hex`",
-                    NeedArgument::VerifierFile => "`-verifier -file`",
-                    NeedArgument::Payload => "`-payload`",
-                    NeedArgument::Signature => "`-signature`",
-                    NeedArgument::SignatureHex => "`-signature -hex`",
-                    NeedArgument::SignatureFile => "`-signature -file`",
-                    NeedArgument::Name => "`-name`",
-                    NeedArgument::SufficientCrypto => "`-sufficient`",
-                    NeedArgument::SufficientCryptoHex => "`-sufficient -hex`",
-                    NeedArgument::SufficientCryptoFile => "`-sufficient -file`",
-                    NeedArgument::Make => "make",
-                    NeedArgument::Sign => "sign",
-                    NeedArgument::RemoveTitle => "`-remove -title`",
-                    NeedArgument::RemoveName => "`-remove -name`",
-                    NeedArgument::RemoveVersion => "`-remove -name *** -version`"
                 };
                 anyhow!("{} must be followed by an agrument.", insert)
             },
-            Error::DoubleKey(x) => {
-                let insert = match x {
-                    DoubleKey::Content => "content",
-                    DoubleKey::Set => "set",
-                    DoubleKey::CryptoOverride => "encryption override",
-                    DoubleKey::CryptoKey => "`-crypto`",
-                    DoubleKey::MsgType => "`-msgtype`",
-                    DoubleKey::Verifier => "`-verifier`",
-                    DoubleKey::Payload => "`-payload`",
-                    DoubleKey::Signature => "`-signature`",
-                    DoubleKey::Name => "`-name`",
-                    DoubleKey::SufficientCrypto => "`-sufficient`",
-                    DoubleKey::Remove => "`-remove`",
-                };
-                anyhow!("More than one entry for {} key is not allowed.", insert)
-            },
             Error::NeedKey(x) => {
                 let insert = match x {
-                    NeedKey::Show => "`show`",
-                    NeedKey::Content => "content",
-                    NeedKey::Crypto => "`-crypto`",
-                    NeedKey::Payload => "`-payload`",
-                    NeedKey::MsgType => "`-msgtype`",
-                    NeedKey::SufficientCrypto => "`-sufficient`",
-                    NeedKey::Signature => "`-signature`",
-                    NeedKey::Verifier => "`-verifier`",
-                    NeedKey::Remove => "`-title` or `-name`",
-                    NeedKey::RemoveVersion => "`-version`",
+                    NeedKey::Crypto => "`--crypto`",
+                    NeedKey::SufficientCrypto => "`--sufficient-hex`, `--sufficient-file` or `--sufficient-qr`",
+                    NeedKey::Signature => "`--signature-hex` or `--signature-file`",
+                    NeedKey::Verifier => "`--verifier-hex`, `--verifier-file`, `--verifier-keystore`, `--verifier-secret` or `--verifier-alice`",
+                    NeedKey::Remove => "`--title` or `--name`",
+                    NeedKey::RemoveVersion => "`--version`",
+                    NeedKey::Relay => "`--relay`",
+                    NeedKey::ParachainId => "`--para`",
+                    NeedKey::Decimals => "`--decimals`",
+                    NeedKey::Unit => "`--unit`",
+                    NeedKey::Password => "`--password-env` or `--password-stdin`",
+                    NeedKey::Encryption => "`--ed25519`, `--sr25519` or `--ecdsa`",
                 };
                 anyhow!("Expected {} key to be used.", insert)
             },
-            Error::OnlyOneNetworkId => anyhow!("Only one network identifier is allowed."),
             Error::BadArgument(x) => {
                 let insert = match x {
-                    BadArgument::CryptoKey => "`-crypto`",
-                    BadArgument::MsgType => "`-msgtype`",
-                    BadArgument::Verifier => "`-verifier`",
-                    BadArgument::Signature => "`-signature`",
-                    BadArgument::SufficientCrypto => "`-sufficient`",
+                    BadArgument::Relay => "`--relay`",
+                    BadArgument::GenesisHash => "`--genesis`",
+                    BadArgument::Asset => "`--asset`",
                 };
                 anyhow!("Invalid argument after {} key.", insert)
             },
-            Error::UnexpectedKeyArgumentSequence => anyhow!("Unexpected key and argument sequence."),
             Error::Unexpected(x) => {
                 match x {
                     Unexpected::KeyAContent => anyhow!("Key -a is used to process all, name or url was not expected."),
-                    Unexpected::VerifierNoCrypto => anyhow!("No verifier entry was expected for `-crypto none` sequence."),
-                    Unexpected::SignatureNoCrypto => anyhow!("No singature entry was expected for `-crypto none` sequence."),
+                    Unexpected::VerifierNoCrypto => anyhow!("No verifier entry was expected for `--crypto none` sequence."),
+                    Unexpected::SignatureNoCrypto => anyhow!("No singature entry was expected for `--crypto none` sequence."),
                     Unexpected::AliceSignature => anyhow!("No signature was expected for verifier Alice."),
-                    Unexpected::VersionFormat => anyhow!("Unexpected version format."),
+                    Unexpected::AliceNoDev => anyhow!("Alice key could be used only with `--dev` flag."),
+                    Unexpected::KeyFileSignature => anyhow!("No signature was expected for verifier key loaded from file."),
+                    Unexpected::PasswordNoKeyFile => anyhow!("Password is expected only for verifier key loaded from keystore or secret file."),
+                    Unexpected::SufficientCryptoWithVerifier => anyhow!("Keys `--crypto`, verifier, signature and password keys are not expected together with sufficient crypto keys, verifier is taken from sufficient crypto."),
+                    Unexpected::PropertiesWithChainSpec => anyhow!("Keys `--decimals`, `--unit` and `--base58` are not expected together with `--chainspec`, network properties are taken from chain spec."),
                 }
             },
-            Error::UnknownCommand => anyhow!("Unknown command."),
-            Error::NoCommand => anyhow!("No command."),
         }
     }
 }
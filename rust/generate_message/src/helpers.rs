@@ -1,6 +1,6 @@
 use sled::{IVec, Tree};
 use anyhow;
-use definitions::{crypto::Encryption, metadata::{AddressBookEntry, MetaValues, NameVersioned, VersionDecoded}, network_specs::{ChainSpecsToSend, generate_network_key, NetworkKey}};
+use definitions::{crypto::Encryption, metadata::{AddressBookEntry, MetaValues, NameVersioned, VersionDecoded, decompress_meta}, network_specs::{ChainSpecsToSend, generate_network_key, NetworkKey}};
 use meta_reading::decode_metadata::get_meta_const;
 use db_handling::helpers::insert_into_tree;
 use parity_scale_codec::{Decode, Encode};
@@ -38,13 +38,19 @@ pub fn decode_chain_specs_to_send(chain_specs_to_send_encoded: IVec, network_key
     }
 }
 
-/// Function to decode and check for integrity an entry from metadata database
-pub fn decode_and_check_meta_entry ((versioned_name_encoded, meta): (IVec, IVec)) -> anyhow::Result<MetaValues> {
+/// Function to decode and check for integrity an entry from metadata database;
+/// compressed metadata entries are decompressed
+pub fn decode_and_check_meta_entry ((versioned_name_encoded, meta_stored): (IVec, IVec)) -> anyhow::Result<MetaValues> {
 // decode what is in the key
     let name_versioned = match NameVersioned::decode(&mut &versioned_name_encoded[..]) {
         Ok(a) => a,
         Err(_) => return Err(Error::NotDecodeable(NotDecodeable::DatabaseVersionedName).show()),
     };
+// get raw metadata
+    let meta = match decompress_meta(&meta_stored) {
+        Ok(a) => a,
+        Err(e) => return Err(Error::DatabaseMetadata{name: name_versioned.name, version: name_versioned.version, error: e}.show()),
+    };
 // check the database for corruption
     let version_vector = match get_meta_const(&meta.to_vec()) {
         Ok(a) => a,
@@ -59,7 +65,7 @@ pub fn decode_and_check_meta_entry ((versioned_name_encoded, meta): (IVec, IVec)
     Ok(MetaValues {
         name: name_versioned.name,
         version: name_versioned.version,
-        meta: meta.to_vec(),
+        meta,
     })
 }
 
@@ -75,16 +81,20 @@ pub fn network_specs_from_address_book_entry_encoded (address_book_entry_encoded
     Ok(network_specs)
 }
 
-/// Function to update chainspecs and address_book trees of the database
-pub fn update_db (address: &str, network_specs: &ChainSpecsToSend, chainspecs: &Tree, address_book: &Tree) -> anyhow::Result<()> {
+/// Function to update chainspecs and address_book trees of the database;
+/// addresses are rpc endpoints of the network in failover order
+pub fn update_db (addresses: &[String], network_specs: &ChainSpecsToSend, chainspecs: &Tree, address_book: &Tree) -> anyhow::Result<()> {
     insert_into_tree(generate_network_key(&network_specs.genesis_hash.to_vec(), network_specs.encryption), network_specs.encode(), chainspecs)?;
     let address_book_new_key = network_specs.title.encode();
     let address_book_new_entry_encoded = AddressBookEntry {
         name: network_specs.name.to_string(),
         genesis_hash: network_specs.genesis_hash,
-        address: address.to_string(),
+        address: addresses[0].to_string(),
         encryption: network_specs.encryption,
         def: false,
+        relay_genesis_hash: network_specs.relay_genesis_hash,
+        parachain_id: network_specs.parachain_id,
+        fallback_addresses: addresses[1..].to_vec(),
     }.encode();
     insert_into_tree(address_book_new_key, address_book_new_entry_encoded, address_book)?;
     Ok(())
@@ -103,7 +113,8 @@ pub enum Write {
     None, // -p key
 }
 
-/// Function to filter address_book entries by url address
+/// Function to filter address_book entries by url address,
+/// fallback addresses are searched as well
 pub fn filter_address_book_by_url (address: &str, address_book: &Tree) -> anyhow::Result<Vec<AddressBookEntry>> {
     let mut out: Vec<AddressBookEntry> = Vec::new();
     for x in address_book.iter() {
@@ -112,12 +123,22 @@ pub fn filter_address_book_by_url (address: &str, address_book: &Tree) -> anyhow
                 Ok(a) => a,
                 Err(_) => return Err(Error::NotDecodeable(NotDecodeable::AddressBookEntry).show()),
             };
-            if new_address_book_entry.address == address {out.push(new_address_book_entry)}
+            if new_address_book_entry.addresses().iter().any(|a| a == address) {out.push(new_address_book_entry)}
         }
     }
     Ok(out)
 }
 
+/// Function to get rpc endpoints in failover order for url address:
+/// if the address is known in address_book, all endpoints of the network are used,
+/// if not, only the address itself
+pub fn endpoints_for_url (address: &str, address_book: &Tree) -> anyhow::Result<Vec<String>> {
+    match filter_address_book_by_url(address, address_book)?.first() {
+        Some(a) => Ok(a.addresses()),
+        None => Ok(vec![address.to_string()]),
+    }
+}
+
 /// Struct to store indices (id found) for correct encryption and for default entry
 struct Indices {
     index_correct_encryption: Option<usize>,
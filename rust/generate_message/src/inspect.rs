@@ -0,0 +1,225 @@
+use blake2_rfc::blake2b::blake2b;
+use constants::MAX_DECOMPRESSED_CONTENT;
+use definitions::{crypto::{Encryption, SufficientCrypto}, metadata::VersionDecoded, network_specs::{Verifier, show_assets}, qr_transfers::{ContentLoadTypes, ContentLoadMeta, ContentAddNetwork, ContentAddSpecs, ContentLoadAssets, ContentDecompression, decompress_content}};
+use meta_reading::decode_metadata::get_meta_const;
+use qr_reader_pc::run_with_file;
+use sp_core::{Pair, ed25519, sr25519, ecdsa};
+use std::convert::TryInto;
+use parity_scale_codec::{Decode, Encode};
+use anyhow;
+
+use crate::error::{Error, InspectFailure};
+
+const PNG_SIGNATURE: &[u8] = &[137, 80, 78, 71, 13, 10, 26, 10];
+
+/// Struct to store the checked signature information:
+/// all verifiers that signed the message, and the message itself
+struct PassedCrypto {
+    signers: Vec<Verifier>,
+    message: Vec<u8>,
+}
+
+/// Function to process `inspect` run.
+/// Expected behavior:
+/// read the signed payload from hex text file or from png/apng qr code file,
+/// check the signature(s) and print the decoded payload content;
+/// any unexpected content results in error.
+pub fn inspect (filename: &str, folder: &str) -> anyhow::Result<()> {
+    let data = read_payload(&format!("{}/{}", folder, filename))?;
+    println!("{}", inspect_payload(&data)?);
+    Ok(())
+}
+
+/// Function to read the payload from file:
+/// png and apng files are read as qr codes, other files are expected to contain hex string
+fn read_payload (path: &str) -> anyhow::Result<Vec<u8>> {
+    let content = match std::fs::read(path) {
+        Ok(a) => a,
+        Err(e) => return Err(Error::Inspect(InspectFailure::File{filename: path.to_string(), error: e.to_string()}).show()),
+    };
+    let hex_line = {
+        if content.starts_with(PNG_SIGNATURE) {
+            match run_with_file(path) {
+                Ok(a) => a,
+                Err(e) => return Err(Error::Inspect(InspectFailure::File{filename: path.to_string(), error: e.to_string()}).show()),
+            }
+        }
+        else {
+            match String::from_utf8(content) {
+                Ok(a) => a.trim().trim_start_matches("0x").to_string(),
+                Err(_) => return Err(Error::Inspect(InspectFailure::File{filename: path.to_string(), error: String::from("File is neither png nor text.")}).show()),
+            }
+        }
+    };
+    match hex::decode(&hex_line) {
+        Ok(a) => Ok(a),
+        Err(_) => return Err(Error::Inspect(InspectFailure::File{filename: path.to_string(), error: String::from("Payload is not in hex format.")}).show()),
+    }
+}
+
+/// Function to check the signed payload and make printable description of it;
+/// signatures are checked same way as in Signer, and the content is decoded
+/// according to the message type in prelude
+pub fn inspect_payload (data: &[u8]) -> anyhow::Result<String> {
+    if data.len() < 3 {return Err(Error::Inspect(InspectFailure::TooShort).show())}
+    if data[0] != 0x53 {return Err(Error::Inspect(InspectFailure::NotSubstrate).show())}
+    let passed_crypto = check_crypto(data)?;
+    let verifier_line = {
+        if passed_crypto.signers.len() == 0 {String::from("Verifier: none")}
+        else {
+            let signers: Vec<String> = passed_crypto.signers.iter().map(|x| x.show_error()).collect();
+            format!("Verifier: {}", signers.join("; "))
+        }
+    };
+    let content_line = match data[2] {
+        0x80 => inspect_load_metadata(&passed_crypto.message)?,
+        0x84 => format!("compressed {}", inspect_load_metadata(&decompress(&passed_crypto.message)?)?),
+        0x81 => inspect_load_types(&passed_crypto.message)?,
+        0xc0 => inspect_add_network(&passed_crypto.message)?,
+        0xc2 => format!("compressed {}", inspect_add_network(&decompress(&passed_crypto.message)?)?),
+        0xc1 => inspect_add_specs(&passed_crypto.message)?,
+        0x85 => inspect_load_assets(&passed_crypto.message)?,
+        a => return Err(Error::Inspect(InspectFailure::MsgTypeNotSupported(hex::encode([a]))).show()),
+    };
+    Ok(format!("Message type: {}\n{}", content_line, verifier_line))
+}
+
+/// Function to check the signature(s) in payload;
+/// single signature for ed25519, sr25519 and ecdsa, several signatures as Vec<SufficientCrypto>,
+/// or no signature at all
+fn check_crypto (data: &[u8]) -> anyhow::Result<PassedCrypto> {
+    match data[1] {
+        0x00 => {
+            if data.len() < 99 {return Err(Error::Inspect(InspectFailure::TooShort).show())}
+            let into_pubkey: [u8;32] = data[3..35].try_into().expect("fixed size should fit in array");
+            let message = data[35..data.len()-64].to_vec();
+            let into_signature: [u8;64] = data[data.len()-64..].try_into().expect("fixed size should fit in array");
+            let verifier = verify(&SufficientCrypto::Ed25519{public_key: into_pubkey, signature: into_signature}, &message)?;
+            Ok(PassedCrypto{signers: vec![verifier], message})
+        },
+        0x01 => {
+            if data.len() < 99 {return Err(Error::Inspect(InspectFailure::TooShort).show())}
+            let into_pubkey: [u8;32] = data[3..35].try_into().expect("fixed size should fit in array");
+            let message = data[35..data.len()-64].to_vec();
+            let into_signature: [u8;64] = data[data.len()-64..].try_into().expect("fixed size should fit in array");
+            let verifier = verify(&SufficientCrypto::Sr25519{public_key: into_pubkey, signature: into_signature}, &message)?;
+            Ok(PassedCrypto{signers: vec![verifier], message})
+        },
+        0x02 => {
+            if data.len() < 101 {return Err(Error::Inspect(InspectFailure::TooShort).show())}
+            let into_pubkey: [u8;33] = data[3..36].try_into().expect("fixed size should fit in array");
+            let message = data[36..data.len()-65].to_vec();
+            let into_signature: [u8;65] = data[data.len()-65..].try_into().expect("fixed size should fit in array");
+            let verifier = verify(&SufficientCrypto::Ecdsa{public_key: into_pubkey, signature: into_signature}, &message)?;
+            Ok(PassedCrypto{signers: vec![verifier], message})
+        },
+        0x03 => {
+            let mut remaining = &data[3..];
+            let signatures = match <Vec<SufficientCrypto>>::decode(&mut remaining) {
+                Ok(a) => a,
+                Err(_) => return Err(Error::Inspect(InspectFailure::Signatures).show()),
+            };
+            if signatures.len() == 0 {return Err(Error::Inspect(InspectFailure::Signatures).show())}
+            let message = remaining.to_vec();
+            let mut signers: Vec<Verifier> = Vec::new();
+            for x in signatures.iter() {
+                let verifier = verify(x, &message)?;
+                if !signers.contains(&verifier) {signers.push(verifier)}
+            }
+            Ok(PassedCrypto{signers, message})
+        },
+        0xff => Ok(PassedCrypto{signers: Vec::new(), message: data[3..].to_vec()}),
+        a => return Err(Error::Inspect(InspectFailure::CryptoNotSupported(hex::encode([a]))).show()),
+    }
+}
+
+/// Function to check single signature, outputs the verifier if the signature is good
+fn verify (sufficient_crypto: &SufficientCrypto, message: &Vec<u8>) -> anyhow::Result<Verifier> {
+    match sufficient_crypto {
+        SufficientCrypto::Ed25519 {public_key, signature} => {
+            let pubkey = ed25519::Public::from_raw(public_key.to_owned());
+            let sign = ed25519::Signature::from_raw(signature.to_owned());
+            if ed25519::Pair::verify(&sign, message, &pubkey) {Ok(Verifier::Ed25519(hex::encode(public_key)))}
+            else {return Err(Error::BadSignature(Encryption::Ed25519).show())}
+        },
+        SufficientCrypto::Sr25519 {public_key, signature} => {
+            let pubkey = sr25519::Public::from_raw(public_key.to_owned());
+            let sign = sr25519::Signature::from_raw(signature.to_owned());
+            if sr25519::Pair::verify(&sign, message, &pubkey) {Ok(Verifier::Sr25519(hex::encode(public_key)))}
+            else {return Err(Error::BadSignature(Encryption::Sr25519).show())}
+        },
+        SufficientCrypto::Ecdsa {public_key, signature} => {
+            let pubkey = ecdsa::Public::from_raw(public_key.to_owned());
+            let sign = ecdsa::Signature::from_raw(signature.to_owned());
+            if ecdsa::Pair::verify(&sign, message, &pubkey) {Ok(Verifier::Ecdsa(hex::encode(public_key)))}
+            else {return Err(Error::BadSignature(Encryption::Ecdsa).show())}
+        },
+    }
+}
+
+/// Function to decompress load_metadata or add_network content
+fn decompress (message: &Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    match decompress_content(message, MAX_DECOMPRESSED_CONTENT) {
+        Ok(a) => Ok(a),
+        Err(ContentDecompression::Damaged) => return Err(Error::Inspect(InspectFailure::DecompressionDamaged).show()),
+        Err(ContentDecompression::TooLarge) => return Err(Error::Inspect(InspectFailure::DecompressedTooLarge).show()),
+    }
+}
+
+/// Function to get network name and version from metadata
+fn name_version (meta: &Vec<u8>) -> anyhow::Result<VersionDecoded> {
+    match get_meta_const(meta) {
+        Ok(version_vector) => {
+            match VersionDecoded::decode(&mut &version_vector[..]) {
+                Ok(a) => Ok(a),
+                Err(_) => return Err(Error::DamagedMetadata.show()),
+            }
+        },
+        Err(_) => return Err(Error::DamagedMetadata.show()),
+    }
+}
+
+/// Function to describe load_metadata content
+fn inspect_load_metadata (message: &Vec<u8>) -> anyhow::Result<String> {
+    let (meta, genesis_hash) = match ContentLoadMeta::from_vec(message).meta_genhash() {
+        Ok(a) => a,
+        Err(_) => return Err(Error::NotLoadMetadata.show()),
+    };
+    let version = name_version(&meta)?;
+    Ok(format!("load_metadata\nNetwork: {}, version: {}, metadata hash: {}, genesis hash: {}", version.specname, version.spec_version, hex::encode(blake2b(32, &[], &meta).as_bytes()), hex::encode(genesis_hash)))
+}
+
+/// Function to describe add_network content
+fn inspect_add_network (message: &Vec<u8>) -> anyhow::Result<String> {
+    let (meta, network_specs) = match ContentAddNetwork::from_vec(message).meta_specs() {
+        Ok(a) => a,
+        Err(_) => return Err(Error::NotAddNetwork.show()),
+    };
+    let version = name_version(&meta)?;
+    if version.specname != network_specs.name {return Err(Error::MessageNameMismatch{name_meta: version.specname, name_specs: network_specs.name}.show())}
+    Ok(format!("add_network\nNetwork: {}, version: {}, metadata hash: {}\nSpecs: {{{}}}", version.specname, version.spec_version, hex::encode(blake2b(32, &[], &meta).as_bytes()), network_specs.show()))
+}
+
+/// Function to describe add_specs content
+fn inspect_add_specs (message: &Vec<u8>) -> anyhow::Result<String> {
+    match ContentAddSpecs::from_vec(message).specs() {
+        Ok(network_specs) => Ok(format!("add_specs\nNetwork: {}\nSpecs: {{{}}}", network_specs.name, network_specs.show())),
+        Err(_) => return Err(Error::NotAddSpecs.show()),
+    }
+}
+
+/// Function to describe load_assets content
+fn inspect_load_assets (message: &Vec<u8>) -> anyhow::Result<String> {
+    match ContentLoadAssets::from_vec(message).genhash_assets() {
+        Ok((genesis_hash, assets)) => Ok(format!("load_assets\nGenesis hash: {}\nAssets: {}", hex::encode(genesis_hash), show_assets(&assets))),
+        Err(_) => return Err(Error::NotLoadAssets.show()),
+    }
+}
+
+/// Function to describe load_types content
+fn inspect_load_types (message: &Vec<u8>) -> anyhow::Result<String> {
+    match ContentLoadTypes::from_vec(message).types() {
+        Ok(types) => Ok(format!("load_types\nTypes: {} entries, types hash: {}", types.len(), hex::encode(blake2b(32, &[], &types.encode()).as_bytes()))),
+        Err(_) => return Err(Error::NotLoadTypes.show()),
+    }
+}
@@ -0,0 +1,204 @@
+use schnorrkel::SecretKey;
+use scrypt::{scrypt, Params};
+use serde_json::Value;
+use sp_core::{Pair, ed25519, sr25519, ecdsa};
+use std::io::BufRead;
+use xsalsa20poly1305::{XSalsa20Poly1305, aead::{Aead, NewAead, generic_array::GenericArray}};
+use zeroize::Zeroizing;
+use anyhow;
+
+use crate::error::Error;
+use crate::parser::{KeyFile, KeyFileKind, PasswordSource};
+
+/// Constants of polkadot-js keystore format
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+const SALT_LENGTH: usize = 32;
+const SCRYPT_LENGTH: usize = SALT_LENGTH + 4 + 4 + 4; // salt, N, p, r
+const NONCE_LENGTH: usize = 24;
+
+/// Secret key material loaded from file, zeroized on drop
+enum Secret {
+    Uri{uri: Zeroizing<String>, password: Option<Zeroizing<String>>}, // from subkey secret file
+    Raw{secret_key: Zeroizing<Vec<u8>>, public_key: Vec<u8>}, // from decrypted keystore
+}
+
+/// Function to get ed25519 key pair from key file
+pub fn ed25519_pair (key_file: &KeyFile) -> anyhow::Result<ed25519::Pair> {
+    match load_secret(key_file, "ed25519")? {
+        Secret::Uri{uri, password} => match ed25519::Pair::from_string(&uri, password.as_ref().map(|a| a.as_str())) {
+            Ok(a) => Ok(a),
+            Err(e) => return Err(key_file_error(key_file, &format!("{:?}", e))),
+        },
+        Secret::Raw{secret_key, public_key} => {
+            if secret_key.len() < 32 {return Err(key_file_error(key_file, "Secret key is too short."))}
+            let pair = match ed25519::Pair::from_seed_slice(&secret_key[..32]) {
+                Ok(a) => a,
+                Err(e) => return Err(key_file_error(key_file, &format!("{:?}", e))),
+            };
+            check_public(key_file, pair.public().as_ref(), &public_key)?;
+            Ok(pair)
+        },
+    }
+}
+
+/// Function to get sr25519 key pair from key file;
+/// polkadot-js keystore has sr25519 secret key in ed25519-compatible form
+pub fn sr25519_pair (key_file: &KeyFile) -> anyhow::Result<sr25519::Pair> {
+    match load_secret(key_file, "sr25519")? {
+        Secret::Uri{uri, password} => match sr25519::Pair::from_string(&uri, password.as_ref().map(|a| a.as_str())) {
+            Ok(a) => Ok(a),
+            Err(e) => return Err(key_file_error(key_file, &format!("{:?}", e))),
+        },
+        Secret::Raw{secret_key, public_key} => {
+            let pair = match secret_key.len() {
+                32 => match sr25519::Pair::from_seed_slice(&secret_key) {
+                    Ok(a) => a,
+                    Err(e) => return Err(key_file_error(key_file, &format!("{:?}", e))),
+                },
+                _ => match SecretKey::from_ed25519_bytes(&secret_key) {
+                    Ok(a) => sr25519::Pair::from(a),
+                    Err(e) => return Err(key_file_error(key_file, &e.to_string())),
+                },
+            };
+            check_public(key_file, pair.public().as_ref(), &public_key)?;
+            Ok(pair)
+        },
+    }
+}
+
+/// Function to get ecdsa key pair from key file
+pub fn ecdsa_pair (key_file: &KeyFile) -> anyhow::Result<ecdsa::Pair> {
+    match load_secret(key_file, "ecdsa")? {
+        Secret::Uri{uri, password} => match ecdsa::Pair::from_string(&uri, password.as_ref().map(|a| a.as_str())) {
+            Ok(a) => Ok(a),
+            Err(e) => return Err(key_file_error(key_file, &format!("{:?}", e))),
+        },
+        Secret::Raw{secret_key, public_key} => {
+            if secret_key.len() < 32 {return Err(key_file_error(key_file, "Secret key is too short."))}
+            let pair = match ecdsa::Pair::from_seed_slice(&secret_key[..32]) {
+                Ok(a) => a,
+                Err(e) => return Err(key_file_error(key_file, &format!("{:?}", e))),
+            };
+            check_public(key_file, pair.public().as_ref(), &public_key)?;
+            Ok(pair)
+        },
+    }
+}
+
+/// Function to load secret from key file, with password from the selected source
+fn load_secret (key_file: &KeyFile, encryption: &str) -> anyhow::Result<Secret> {
+    let content = match std::fs::read_to_string(&key_file.path) {
+        Ok(a) => Zeroizing::new(a),
+        Err(e) => return Err(key_file_error(key_file, &e.to_string())),
+    };
+    let password = read_password(key_file)?;
+    match key_file.kind {
+        KeyFileKind::Secret => Ok(Secret::Uri{uri: Zeroizing::new(content.trim().to_string()), password}),
+        KeyFileKind::Keystore => {
+            let password = match password {
+                Some(a) => a,
+                None => return Err(key_file_error(key_file, "Password is needed to decrypt keystore.")),
+            };
+            decode_keystore(&content, &password, encryption).map_err(|e| key_file_error(key_file, &e))
+        },
+    }
+}
+
+/// Function to read the password from environment variable or from stdin
+fn read_password (key_file: &KeyFile) -> anyhow::Result<Option<Zeroizing<String>>> {
+    match &key_file.password {
+        PasswordSource::None => Ok(None),
+        PasswordSource::Env(variable) => match std::env::var(variable) {
+            Ok(a) => Ok(Some(Zeroizing::new(a))),
+            Err(e) => return Err(key_file_error(key_file, &format!("Unable to read password from environment variable {}. {}", variable, e))),
+        },
+        PasswordSource::Stdin => {
+            println!("Enter password for {}:", key_file.path);
+            let mut line = Zeroizing::new(String::new());
+            if let Err(e) = std::io::stdin().lock().read_line(&mut line) {return Err(key_file_error(key_file, &format!("Unable to read password from stdin. {}", e)))}
+            Ok(Some(Zeroizing::new(line.trim_end_matches(&['\r', '\n'][..]).to_string())))
+        },
+    }
+}
+
+/// Function to decrypt polkadot-js json keystore (version 3, scrypt and xsalsa20-poly1305),
+/// and get the secret key and the public key from pkcs8 content
+fn decode_keystore (content: &str, password: &str, encryption: &str) -> Result<Secret, String> {
+    let keystore: Value = serde_json::from_str(content).map_err(|e| format!("Keystore is not valid json. {}", e))?;
+    let encoding = match keystore.get("encoding") {
+        Some(a) => a,
+        None => return Err(String::from("No encoding information in keystore.")),
+    };
+    let version = encoding.get("version").and_then(|a| a.as_str()).unwrap_or("");
+    if version != "3" {return Err(format!("Keystore version {} is not supported.", version))}
+    let content_types: Vec<&str> = match encoding.get("content").and_then(|a| a.as_array()) {
+        Some(a) => a.iter().filter_map(|x| x.as_str()).collect(),
+        None => return Err(String::from("No content information in keystore.")),
+    };
+    if !content_types.contains(&"pkcs8") {return Err(String::from("Keystore content is not pkcs8."))}
+    if !content_types.contains(&encryption) {return Err(format!("Keystore has no {} key.", encryption))}
+    let encryption_types: Vec<&str> = match encoding.get("type").and_then(|a| a.as_array()) {
+        Some(a) => a.iter().filter_map(|x| x.as_str()).collect(),
+        None => return Err(String::from("No encryption type information in keystore.")),
+    };
+    let encoded = match keystore.get("encoded").and_then(|a| a.as_str()) {
+        Some(a) => match base64::decode(a) {
+            Ok(b) => b,
+            Err(_) => return Err(String::from("Encoded keystore content is not base64.")),
+        },
+        None => return Err(String::from("No encoded content in keystore.")),
+    };
+    let decrypted = {
+        if encryption_types.contains(&"xsalsa20-poly1305") {
+            if !encryption_types.contains(&"scrypt") {return Err(String::from("Only scrypt keystores are supported."))}
+            decrypt(&encoded, password)?
+        }
+        else {Zeroizing::new(encoded)}
+    };
+    if !decrypted.starts_with(&PKCS8_HEADER) {return Err(String::from("Invalid pkcs8 header in keystore content."))}
+    let body = &decrypted[PKCS8_HEADER.len()..];
+// secret key is 64 bytes long for sr25519 and ed25519, older keystores have 32 bytes seed
+    let secret_length = {
+        if body.len() >= 64 + PKCS8_DIVIDER.len() && body[64..64 + PKCS8_DIVIDER.len()] == PKCS8_DIVIDER {64}
+        else if body.len() >= 32 + PKCS8_DIVIDER.len() && body[32..32 + PKCS8_DIVIDER.len()] == PKCS8_DIVIDER {32}
+        else {return Err(String::from("Invalid pkcs8 divider in keystore content."))}
+    };
+    Ok(Secret::Raw{
+        secret_key: Zeroizing::new(body[..secret_length].to_vec()),
+        public_key: body[secret_length + PKCS8_DIVIDER.len()..].to_vec(),
+    })
+}
+
+/// Function to decrypt keystore content: the content starts with scrypt salt and parameters,
+/// followed by xsalsa20-poly1305 nonce and the encrypted data
+fn decrypt (encoded: &[u8], password: &str) -> Result<Zeroizing<Vec<u8>>, String> {
+    if encoded.len() < SCRYPT_LENGTH + NONCE_LENGTH {return Err(String::from("Encoded keystore content is too short."))}
+    let salt = &encoded[..SALT_LENGTH];
+    let n = u32::from_le_bytes([encoded[32], encoded[33], encoded[34], encoded[35]]);
+    let p = u32::from_le_bytes([encoded[36], encoded[37], encoded[38], encoded[39]]);
+    let r = u32::from_le_bytes([encoded[40], encoded[41], encoded[42], encoded[43]]);
+    if !n.is_power_of_two() {return Err(String::from("Invalid scrypt parameters in keystore."))}
+    let params = match Params::new(n.trailing_zeros() as u8, r, p) {
+        Ok(a) => a,
+        Err(_) => return Err(String::from("Invalid scrypt parameters in keystore.")),
+    };
+    let mut key = Zeroizing::new([0u8; 32]);
+    if let Err(_) = scrypt(password.as_bytes(), salt, &params, &mut key[..]) {return Err(String::from("Unable to derive key from password."))}
+    let cipher = XSalsa20Poly1305::new(GenericArray::from_slice(&key[..]));
+    let nonce = GenericArray::from_slice(&encoded[SCRYPT_LENGTH..SCRYPT_LENGTH + NONCE_LENGTH]);
+    match cipher.decrypt(nonce, &encoded[SCRYPT_LENGTH + NONCE_LENGTH..]) {
+        Ok(a) => Ok(Zeroizing::new(a)),
+        Err(_) => return Err(String::from("Unable to decrypt keystore, wrong password or damaged keystore.")),
+    }
+}
+
+/// Function to check that the public key of the loaded pair matches the one recorded in keystore
+fn check_public (key_file: &KeyFile, public_key: &[u8], recorded: &[u8]) -> anyhow::Result<()> {
+    if public_key != recorded {return Err(key_file_error(key_file, "Public key derived from secret key does not match the public key in keystore."))}
+    Ok(())
+}
+
+fn key_file_error (key_file: &KeyFile, error: &str) -> anyhow::Error {
+    Error::KeyFile{filename: key_file.path.to_string(), error: error.to_string()}.show()
+}
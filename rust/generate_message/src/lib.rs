@@ -1,14 +1,26 @@
 use anyhow;
-use db_handling::default_hot;
+use db_handling::{migrate_hot, populate_hot};
+use structopt::StructOpt;
 
 mod add;
-    use add::gen_add_network;
+    use add::{gen_add_network, gen_add_network_from_file};
+mod address_book;
+    use address_book::address_book_run;
+mod assets;
+    use assets::gen_load_assets;
+mod compress;
+    use compress::compress_payload;
+pub mod config;
+    use config::Config;
 mod error;
 mod helpers;
+mod inspect;
+    use inspect::inspect;
+mod keystore;
 mod load;
-    use load::gen_load_meta;
+    use load::{gen_load_meta, gen_load_meta_from_file};
 pub mod parser;
-    use parser::{Command, Show};
+    use parser::{Cli, Command, Show};
 mod remove;
     use remove::remove_info;
 mod show;
@@ -22,26 +34,50 @@ mod metadata_shortcut;
 mod output_prep;
 mod make_message;
     use make_message::make_message;
+mod prune;
+    use prune::prune_payload;
+mod diff;
+    use diff::diff_versions;
+mod tests;
 
 
-/// Function to process incoming command as interpreted by parser
+/// Function to process incoming command as interpreted by parser,
+/// with the database and the folders from workspace config;
+/// hot database entries stored in earlier formats are migrated before the database is used
 
-pub fn full_run (command: Command) -> anyhow::Result<()> {
+pub fn full_run (command: Command, config: &Config) -> anyhow::Result<()> {
+    
+    match command {
+        Command::Show(_) | Command::Types | Command::Load(_) | Command::Add(_) | Command::Specs(_) | Command::Remove(_) | Command::Diff(_) | Command::AddressBook(_) => {migrate_hot(&config.database)?;},
+        _ => (),
+    }
     
     match command {
         Command::Show(x) => {
             match x {
-                Show::Database => show_database(),
-                Show::AddressBook => show_address_book(),
+                Show::Database => show_database(&config.database),
+                Show::AddressBook => show_address_book(&config.database),
             }
         },
-        Command::Types => gen_types(),
-        Command::Load(instruction) => gen_load_meta(instruction),
-        Command::Add(instruction) => gen_add_network(instruction),
-        Command::Specs(instruction) => gen_add_specs(instruction),
-        Command::Make(make) => make_message(make),
-        Command::Remove(info) => remove_info(info),
-        Command::RestoreDefaults => default_hot(),
+        Command::Types => gen_types(&config.database, &config.for_signing),
+        Command::Load(instruction) => gen_load_meta(instruction, &config.database, &config.for_signing, &config.metadata_versions),
+        Command::Add(instruction) => gen_add_network(instruction, &config.database, &config.for_signing, &config.metadata_versions),
+        Command::Specs(instruction) => gen_add_specs(instruction, &config.database, &config.for_signing),
+        Command::Make(make) => make_message(make, &config.signed),
+        Command::Remove(info) => remove_info(info, &config.database),
+        Command::Prune(prune) => prune_payload(prune, &config.for_signing),
+        Command::Compress(compress) => compress_payload(compress, &config.for_signing),
+        Command::Diff(diff) => diff_versions(diff, &config.database),
+        Command::NetworkFromFile(network) => gen_add_network_from_file(network, &config.for_signing),
+        Command::LoadFromFile(load) => gen_load_meta_from_file(load, &config.for_signing),
+        Command::Assets(load_assets) => gen_load_assets(load_assets, &config.for_signing),
+        Command::Inspect(filename) => inspect(&filename, &config.signed),
+        Command::AddressBook(x) => address_book_run(x, &config.database),
+        Command::RestoreDefaults => populate_hot(&config.database),
+        Command::Completions(shell) => {
+            Cli::clap().gen_completions_to("generate_message", shell, &mut std::io::stdout());
+            Ok(())
+        },
     }
 }
 
@@ -1,41 +1,45 @@
-use constants::{ADDRESS_BOOK, HOT_DB_NAME, METATREE};
+use constants::{ADDRESS_BOOK, METATREE};
 use sled::Tree;
 use anyhow;
 use db_handling::helpers::{open_db, open_tree};
 use parity_scale_codec::{Decode, Encode};
 use definitions::metadata::AddressBookEntry;
 
-use crate::parser::{Instruction, Content, Set};
+use crate::config::MetadataVersions;
+use crate::parser::{Instruction, Content, LoadFromFile, Set};
 use crate::metadata_db_utils::{add_new, SortedMetaValues, prepare_metadata, write_metadata};
 use crate::error::{Error, NotFound, NotDecodeable};
-use crate::helpers::{decode_and_check_meta_entry, error_occured, Write};
-use crate::metadata_shortcut::{MetaShortCut, meta_shortcut};
+use crate::helpers::{decode_and_check_meta_entry, endpoints_for_url, error_occured, Write};
+use crate::metadata_shortcut::{MetaShortCut, meta_shortcut, meta_shortcut_from_file};
 use crate::output_prep::load_meta_print;
 
 
 /// Function to generate `load_metadata` message ready for signing.
 /// Exact behavior is determined by the keys used.
+/// Hot database, the folder for output files and the number of metadata versions
+/// kept in the database are provided separately.
 
-pub fn gen_load_meta (instruction: Instruction) -> anyhow::Result<()> {
+pub fn gen_load_meta (instruction: Instruction, database_name: &str, folder: &str, metadata_versions: &MetadataVersions) -> anyhow::Result<()> {
     
-    let database = open_db(HOT_DB_NAME)?;
+    let database = open_db(database_name)?;
     let address_book = open_tree(&database, ADDRESS_BOOK)?;
     let metadata = open_tree(&database, METATREE)?;
     if let Some(_) = instruction.encryption_override {return Err(Error::NotSupported.show())}
+    if let Some(_) = instruction.parachain_info {return Err(Error::NotSupported.show())}
     match instruction.set {
         Set::F => {
             match instruction.content {
                 Content::All => {
                     let set = get_address_book_set(&address_book)?;
                     for x in set.iter() {
-                        match meta_f_a_element (x, &metadata) {
+                        match meta_f_a_element (x, &metadata, folder) {
                             Ok(()) => (),
                             Err(e) => error_occured(e, instruction.pass_errors)?,
                         }
                     }
                     Ok(())
                 },
-                Content::Name(name) => meta_f_n (&name, &address_book, &metadata),
+                Content::Name(name) => meta_f_n (&name, &address_book, &metadata, folder),
                 Content::Address(_) => return Err(Error::NotSupported.show()),
             }
         },
@@ -44,38 +48,38 @@ pub fn gen_load_meta (instruction: Instruction) -> anyhow::Result<()> {
                 Content::All => {
                     let set = get_address_book_set(&address_book)?;
                     for x in set.iter() {
-                        match meta_d_a_element (x) {
+                        match meta_d_a_element (x, folder) {
                             Ok(()) => (),
                             Err(e) => error_occured(e, instruction.pass_errors)?,
                         }
                     }
                     Ok(())
                 },
-                Content::Name(name) => meta_d_n (&name, &address_book),
-                Content::Address(address) => meta_d_u (&address),
+                Content::Name(name) => meta_d_n (&name, &address_book, folder),
+                Content::Address(address) => meta_d_u (&address, &address_book, folder),
             }
         },
         Set::K => {
             let write = Write::OnlyNew;
             match instruction.content {
-                Content::All => meta_kpt_a(&address_book, &metadata, &write, instruction.pass_errors),
-                Content::Name(name) => meta_kpt_n (&name, &write, &address_book, &metadata),
+                Content::All => meta_kpt_a(&address_book, &metadata, &write, instruction.pass_errors, folder, metadata_versions),
+                Content::Name(name) => meta_kpt_n (&name, &write, &address_book, &metadata, folder, metadata_versions),
                 Content::Address(_) => return Err(Error::NotSupported.show()),
             }
         },
         Set::P => {
             let write = Write::None;
             match instruction.content {
-                Content::All => meta_kpt_a(&address_book, &metadata, &write, instruction.pass_errors),
-                Content::Name(name) => meta_kpt_n (&name, &write, &address_book, &metadata),
+                Content::All => meta_kpt_a(&address_book, &metadata, &write, instruction.pass_errors, folder, metadata_versions),
+                Content::Name(name) => meta_kpt_n (&name, &write, &address_book, &metadata, folder, metadata_versions),
                 Content::Address(_) => return Err(Error::NotSupported.show()),
             }
         },
         Set::T => {
             let write = Write::All;
             match instruction.content {
-                Content::All => meta_kpt_a(&address_book, &metadata, &write, instruction.pass_errors),
-                Content::Name(name) => meta_kpt_n (&name, &write, &address_book, &metadata),
+                Content::All => meta_kpt_a(&address_book, &metadata, &write, instruction.pass_errors, folder, metadata_versions),
+                Content::Name(name) => meta_kpt_n (&name, &write, &address_book, &metadata, folder, metadata_versions),
                 Content::Address(_) => return Err(Error::NotSupported.show()),
             }
         },
@@ -85,10 +89,10 @@ pub fn gen_load_meta (instruction: Instruction) -> anyhow::Result<()> {
 /// Function to process an individual address book entry in `load_metadata -f -a` run.
 /// Expected behavior:  
 /// scan prefix in `metadata` database tree in search of network specname
-/// to get all versions available in the database (max 2),
+/// to get all versions available in the database,
 /// check meta_values integrity (network specname and spec_version),
 /// and print into `sign_me` output file.  
-fn meta_f_a_element (set_element: &NameHashAddress, metadata: &Tree) -> anyhow::Result<()> {
+fn meta_f_a_element (set_element: &NameHashAddress, metadata: &Tree, folder: &str) -> anyhow::Result<()> {
     for x in metadata.scan_prefix(set_element.name.encode()) {
         if let Ok((versioned_name_encoded, meta)) = x {
             let meta_values = decode_and_check_meta_entry((versioned_name_encoded, meta))?;
@@ -96,7 +100,7 @@ fn meta_f_a_element (set_element: &NameHashAddress, metadata: &Tree) -> anyhow::
                 meta_values,
                 genesis_hash: set_element.genesis_hash,
             };
-            load_meta_print(&shortcut)?;
+            load_meta_print(&shortcut, folder)?;
         }
     }
     Ok(())
@@ -110,20 +114,20 @@ fn meta_f_a_element (set_element: &NameHashAddress, metadata: &Tree) -> anyhow::
 /// go through `address_book` and collect all unique NameHashAddress entries,
 /// search through this set for the entry corresponding to the requested name,
 /// scan prefix in `metadata` database tree in search of network specname
-/// to get all versions available in the database (max 2),
+/// to get all versions available in the database,
 /// check `meta_values` integrity (network specname and `spec_version`),
 /// and print into `sign_me` output file.  
-fn meta_f_n (name: &str, address_book: &Tree, metadata: &Tree) -> anyhow::Result<()> {
-    meta_f_a_element(&search_name(name, address_book)?, metadata)
+fn meta_f_n (name: &str, address_book: &Tree, metadata: &Tree, folder: &str) -> anyhow::Result<()> {
+    meta_f_a_element(&search_name(name, address_book)?, metadata, folder)
 }
 
 /// Function to process individual address book entry in `load_metadata -d -a` run.
 /// Expected behavior:  
 /// fetch information from address, check it,
 /// and print into `sign_me` output file.  
-fn meta_d_a_element (set_element: &NameHashAddress) -> anyhow::Result<()> {
+fn meta_d_a_element (set_element: &NameHashAddress, folder: &str) -> anyhow::Result<()> {
     let shortcut = shortcut_set_element(set_element)?;
-    load_meta_print(&shortcut)
+    load_meta_print(&shortcut, folder)
 }
 
 /// Function to process `load_metadata -d -n name` run.
@@ -132,17 +136,18 @@ fn meta_d_a_element (set_element: &NameHashAddress) -> anyhow::Result<()> {
 /// search through this set for the entry corresponding to the requested name,
 /// fetch information from address, check it,
 /// and print into `sign_me` output file.
-fn meta_d_n (name: &str, address_book: &Tree) -> anyhow::Result<()> {
-    meta_d_a_element(&search_name(name, address_book)?)
+fn meta_d_n (name: &str, address_book: &Tree, folder: &str) -> anyhow::Result<()> {
+    meta_d_a_element(&search_name(name, address_book)?, folder)
 }
 
 /// Function to process `load_metadata -d -u url` run.
 /// Expected behavior:  
-/// fetch information from address, check it,
+/// fetch information from address (or, if the address is known in `address_book`,
+/// from the endpoints of the network in failover order), check it,
 /// and print into `sign_me` output file.
-fn meta_d_u (address: &str) -> anyhow::Result<()> {
-    let shortcut = meta_shortcut(address)?;
-    load_meta_print(&shortcut)
+fn meta_d_u (address: &str, address_book: &Tree, folder: &str) -> anyhow::Result<()> {
+    let shortcut = meta_shortcut(&endpoints_for_url(address, address_book)?)?;
+    load_meta_print(&shortcut, folder)
 }
 
 /// Function to process `load_metadata -k -a`, `load_metadata -p -a`, `load_metadata -t -a`, `load_metadata -a` runs.
@@ -150,14 +155,14 @@ fn meta_d_u (address: &str) -> anyhow::Result<()> {
 /// go through `address_book` and collect all unique NameHashAddress entries,
 /// collect known metadata from database and sort it, clear metadata from database,
 /// process each element and update sorted metadata set (so that there are
-/// max 2 most recent metadata entries for each network),
+/// no more than `metadata_versions` most recent metadata entries for each network),
 /// record resulting metadata into database.
 /// `write` determines which `sign_me` files are produced.
-fn meta_kpt_a (address_book: &Tree, metadata: &Tree, write: &Write, pass_errors: bool) -> anyhow::Result<()> {
+fn meta_kpt_a (address_book: &Tree, metadata: &Tree, write: &Write, pass_errors: bool, folder: &str, metadata_versions: &MetadataVersions) -> anyhow::Result<()> {
     let set = get_address_book_set(&address_book)?;
     let mut sorted_meta_values = prepare_metadata(&metadata)?;
     for x in set.iter() {
-        sorted_meta_values = match meta_kpt_a_element (x, write, &sorted_meta_values) {
+        sorted_meta_values = match meta_kpt_a_element (x, write, &sorted_meta_values, folder, metadata_versions) {
             Ok(a) => a,
             Err(e) => {
                 error_occured(e, pass_errors)?;
@@ -174,12 +179,12 @@ fn meta_kpt_a (address_book: &Tree, metadata: &Tree, write: &Write, pass_errors:
 /// fetch information from address, check it,
 /// insert in the sorted `meta_values`,
 /// and print into `sign_me` output file depending on value of `write`.
-fn meta_kpt_a_element (set_element: &NameHashAddress, write: &Write, sorted_meta_values: &SortedMetaValues) -> anyhow::Result<SortedMetaValues> {
+fn meta_kpt_a_element (set_element: &NameHashAddress, write: &Write, sorted_meta_values: &SortedMetaValues, folder: &str, metadata_versions: &MetadataVersions) -> anyhow::Result<SortedMetaValues> {
     let shortcut = shortcut_set_element(set_element)?;
-    let upd_sorted = add_new(&shortcut.meta_values, sorted_meta_values)?;
+    let upd_sorted = add_new(&shortcut.meta_values, sorted_meta_values, metadata_versions.for_network(&set_element.name))?;
     match write {
-        Write::All => load_meta_print(&shortcut)?,
-        Write::OnlyNew => if upd_sorted.upd_done {load_meta_print(&shortcut)?},
+        Write::All => load_meta_print(&shortcut, folder)?,
+        Write::OnlyNew => if upd_sorted.upd_done {load_meta_print(&shortcut, folder)?},
         Write::None => (),
     }
     Ok(upd_sorted.sorted)
@@ -194,18 +199,28 @@ fn meta_kpt_a_element (set_element: &NameHashAddress, write: &Write, sorted_meta
 /// fetch information from address, check it, insert into sorted metadata
 /// and print into `sign_me` output file depending on `write` value,
 /// record resulting metadata into database.
-fn meta_kpt_n (name: &str, write: &Write, address_book: &Tree, metadata: &Tree) -> anyhow::Result<()> {
+fn meta_kpt_n (name: &str, write: &Write, address_book: &Tree, metadata: &Tree, folder: &str, metadata_versions: &MetadataVersions) -> anyhow::Result<()> {
     let mut sorted_meta_values = prepare_metadata(&metadata)?;
-    sorted_meta_values = meta_kpt_a_element(&search_name(name, address_book)?, write, &sorted_meta_values)?;
+    sorted_meta_values = meta_kpt_a_element(&search_name(name, address_book)?, write, &sorted_meta_values, folder, metadata_versions)?;
     write_metadata(sorted_meta_values, &metadata)
 }
 
-/// Struct to collect network specname, genesis hash and fetching address from address book
+/// Struct to collect network specname, genesis hash and fetching addresses
+/// in failover order from address book
 #[derive(PartialEq)]
 struct NameHashAddress {
     name: String,
     genesis_hash: [u8; 32],
-    address: String,
+    addresses: Vec<String>,
+}
+
+/// Function to process `load_metadata_from_file` run.
+/// Expected behavior:  
+/// read metadata from file and print it with provided genesis hash into `sign_me` output file;
+/// no rpc calls are made and the database is not used.
+pub fn gen_load_meta_from_file (load: LoadFromFile, folder: &str) -> anyhow::Result<()> {
+    let shortcut = meta_shortcut_from_file(&load.meta_file, load.genesis_hash)?;
+    load_meta_print(&shortcut, folder)
 }
 
 /// Function to collect a vector of unique NameHashAddress entries from address book
@@ -221,13 +236,13 @@ fn get_address_book_set (address_book: &Tree) -> anyhow::Result<Vec<NameHashAddr
             for a in set.iter() {
                 if a.name == address_book_entry.name {
                     if a.genesis_hash != address_book_entry.genesis_hash {return Err(Error::TwoGenHash(address_book_entry.name).show())}
-                    if a.address != address_book_entry.address {return Err(Error::TwoAddresses(address_book_entry.name).show())}
+                    if a.addresses != address_book_entry.addresses() {return Err(Error::TwoAddresses(address_book_entry.name).show())}
                 }
             }
             let new = NameHashAddress{
                 name: address_book_entry.name.to_string(),
                 genesis_hash: address_book_entry.genesis_hash,
-                address: address_book_entry.address.to_string(),
+                addresses: address_book_entry.addresses(),
             };
             if !set.contains(&new) {set.push(new)}
         }
@@ -255,13 +270,14 @@ fn search_name (name: &str, address_book: &Tree) -> anyhow::Result<NameHashAddre
 }
 
 /// Function to process individual NameHashAddress entry:
-/// do fetch, check fetched metadata for version,
+/// do fetch with endpoints in failover order, check fetched metadata for version,
 /// check that genesis hash and network name are same in address book and in fetch
 /// output MetaShortCut value
 fn shortcut_set_element (set_element: &NameHashAddress) -> anyhow::Result<MetaShortCut> {
-    let shortcut = meta_shortcut(&set_element.address)?;
-    if shortcut.meta_values.name != set_element.name {return Err(Error::NameChanged(set_element.address.to_string()).show())}
-    if shortcut.genesis_hash != set_element.genesis_hash {return Err(Error::GenesisHashChanged{address: set_element.address.to_string(), old_genesis_hash: set_element.genesis_hash, new_genesis_hash: shortcut.genesis_hash}.show())}
+    let shortcut = meta_shortcut(&set_element.addresses)?;
+    let address = set_element.addresses.join(", ");
+    if shortcut.meta_values.name != set_element.name {return Err(Error::NameChanged(address).show())}
+    if shortcut.genesis_hash != set_element.genesis_hash {return Err(Error::GenesisHashChanged{address, old_genesis_hash: set_element.genesis_hash, new_genesis_hash: shortcut.genesis_hash}.show())}
     Ok(shortcut)
 }
 
@@ -1,14 +1,22 @@
 use std::env;
-use generate_message::{full_run, parser::Command};
+use structopt::StructOpt;
+use generate_message::{config::load_config, full_run, parser::{Cli, Command, legacy_args}};
 
 fn main() -> Result<(), String> {
 
-    let command = match Command::new(env::args()) {
+    let cli = Cli::from_iter(legacy_args(env::args()));
+    
+    let config = match load_config(cli.config.as_deref()) {
+        Ok(x) => x,
+        Err(e) => return Err(format!("Error reading config. {}", e)),
+    };
+    
+    let command = match Command::new(cli.command, &config) {
         Ok(x) => x,
         Err(e) => return Err(format!("Error parsing arguments. {}", e)),
     };
     
-    match full_run(command) {
+    match full_run(command, &config) {
         Ok(()) => Ok(()),
         Err(e) => return Err(format!("Application error. {}", e)),
     }
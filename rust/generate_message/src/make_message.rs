@@ -1,5 +1,4 @@
-use constants::EXPORT_FOLDER;
-use definitions::{crypto::Encryption, metadata::VersionDecoded, qr_transfers::{ContentLoadTypes, ContentLoadMeta, ContentAddNetwork, ContentAddSpecs}};
+use definitions::{crypto::Encryption, metadata::VersionDecoded, qr_transfers::{ContentLoadTypes, ContentLoadMeta, ContentAddNetwork, ContentAddSpecs, ContentLoadAssets}};
 use meta_reading::decode_metadata::get_meta_const;
 use hex;
 use sp_core::{Pair, ed25519, sr25519, ecdsa};
@@ -8,65 +7,103 @@ use qrcode_rtx::transform_into_qr_apng;
 use parity_scale_codec::Decode;
 use anyhow;
 
+use crate::compress::compress_vec;
 use crate::parser::{Make, Goal, Crypto, VerifierKind, Msg};
 use crate::error::Error;
+use crate::keystore::{ed25519_pair, sr25519_pair, ecdsa_pair};
 
 const ALICE_WORDS: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk//Alice";
 
+/// Function to check load_metadata content and get name stub for output file
+fn check_load_metadata (vec: &Vec<u8>) -> anyhow::Result<String> {
+    match ContentLoadMeta::from_vec(vec).meta() {
+        Ok(meta) => {
+            match get_meta_const(&meta) {
+                Ok(version_vector) => {
+                    match VersionDecoded::decode(&mut &version_vector[..]) {
+                        Ok(version) => Ok(format!("load_metadata_{}V{}", version.specname, version.spec_version)),
+                        Err(_) => {return Err(Error::DamagedMetadata.show())},
+                    }
+                },
+                Err(_) => {return Err(Error::DamagedMetadata.show())},
+            }
+        },
+        Err(_) => {return Err(Error::NotLoadMetadata.show())},
+    }
+}
+
+/// Function to check add_network content and get name stub for output file
+fn check_add_network (vec: &Vec<u8>) -> anyhow::Result<String> {
+    match ContentAddNetwork::from_vec(vec).meta_specs() {
+        Ok((meta, network_specs)) => {
+            match get_meta_const(&meta) {
+                Ok(version_vector) => {
+                    match VersionDecoded::decode(&mut &version_vector[..]) {
+                        Ok(version) => {
+                            if version.specname != network_specs.name {return Err(Error::MessageNameMismatch{name_meta: version.specname, name_specs: network_specs.name}.show())}
+                            Ok(format!("add_network_{}V{}", version.specname, version.spec_version))
+                        },
+                        Err(_) => {return Err(Error::DamagedMetadata.show())},
+                    }
+                },
+                Err(_) => {return Err(Error::DamagedMetadata.show())},
+            }
+        },
+        Err(_) => {return Err(Error::NotAddNetwork.show())},
+    }
+}
+
+/// Function to report signature that does not match the message;
+/// signature made for uncompressed content of compressed message is reported separately
+fn signature_error (encryption: Encryption, matches_uncompressed: bool) -> anyhow::Error {
+    if matches_uncompressed {Error::SignatureForUncompressed.show()}
+    else {Error::BadSignature(encryption).show()}
+}
+
 /// Function to generate signed message.
 /// Exact behavior is determined by the keys used.
+/// Compressed load_metadata and add_network messages are compressed before signing,
+/// i.e. the signature is made for compressed content, as written by `compress` command.
+/// Output files are written into the provided folder.
 
-pub fn make_message (make: Make) -> anyhow::Result<()> {
+pub fn make_message (make: Make, folder: &str) -> anyhow::Result<()> {
 
 // check message content for consistency
-    let (message, name_stub, msg_type_code) = match make.msg {
+    let (message, name_stub, msg_type_code, uncompressed) = match make.msg {
         Msg::LoadTypes(vec) => {
             match ContentLoadTypes::from_vec(&vec).types() {
-                Ok(_) => (vec, String::from("load_types"), "81"),
+                Ok(_) => (vec, String::from("load_types"), "81", None),
                 Err(_) => {return Err(Error::NotLoadTypes.show())},
             }
         },
         Msg::LoadMetadata(vec) => {
-            match ContentLoadMeta::from_vec(&vec).meta() {
-                Ok(meta) => {
-                    match get_meta_const(&meta) {
-                        Ok(version_vector) => {
-                            match VersionDecoded::decode(&mut &version_vector[..]) {
-                                Ok(version) => (vec, format!("load_metadata_{}V{}", version.specname, version.spec_version), "80"),
-                                Err(_) => {return Err(Error::DamagedMetadata.show())},
-                            }
-                        },
-                        Err(_) => {return Err(Error::DamagedMetadata.show())},
-                    }
-                },
-                Err(_) => {return Err(Error::NotLoadMetadata.show())},
-            }
+            let name_stub = check_load_metadata(&vec)?;
+            (vec, name_stub, "80", None)
+        },
+        Msg::LoadMetadataCompressed(vec) => {
+            let name_stub = format!("{}_compressed", check_load_metadata(&vec)?);
+            (compress_vec(&vec)?, name_stub, "84", Some(vec))
         },
         Msg::AddNetwork(vec) => {
-            match ContentAddNetwork::from_vec(&vec).meta_specs() {
-                Ok((meta, network_specs)) => {
-                    match get_meta_const(&meta) {
-                        Ok(version_vector) => {
-                            match VersionDecoded::decode(&mut &version_vector[..]) {
-                                Ok(version) => {
-                                    if version.specname != network_specs.name {return Err(Error::MessageNameMismatch{name_meta: version.specname, name_specs: network_specs.name}.show())}
-                                    (vec, format!("add_network_{}V{}", version.specname, version.spec_version), "c0")
-                                },
-                                Err(_) => {return Err(Error::DamagedMetadata.show())},
-                            }
-                        },
-                        Err(_) => {return Err(Error::DamagedMetadata.show())},
-                    }
-                },
-                Err(_) => {return Err(Error::NotAddNetwork.show())},
-            }
+            let name_stub = check_add_network(&vec)?;
+            (vec, name_stub, "c0", None)
+        },
+        Msg::AddNetworkCompressed(vec) => {
+            let name_stub = format!("{}_compressed", check_add_network(&vec)?);
+            (compress_vec(&vec)?, name_stub, "c2", Some(vec))
         },
         Msg::AddSpecs(vec) => {
             match ContentAddSpecs::from_vec(&vec).specs() {
-                Ok(network_specs) => (vec, format!("add_specs_{}", network_specs.name), "c1"),
+                Ok(network_specs) => (vec, format!("add_specs_{}", network_specs.name), "c1", None),
                 Err(_) => {return Err(Error::NotAddSpecs.show())},
             }
         },
+        Msg::LoadAssets(vec) => {
+            match ContentLoadAssets::from_vec(&vec).genhash_assets() {
+                Ok((genesis_hash, _)) => (vec, format!("load_assets_{}", hex::encode(genesis_hash)), "85", None),
+                Err(_) => {return Err(Error::NotLoadAssets.show())},
+            }
+        },
     };
     
 // processing crypto information
@@ -100,7 +137,13 @@ pub fn make_message (make: Make) -> anyhow::Result<()> {
                         let complete_message = [hex::decode(prelude).expect("known value"), pubkey.to_vec(), message, sign.0.to_vec()].concat();
                         (complete_message, name_stub)
                     }
-                    else {return Err(Error::BadSignature(Encryption::Ed25519).show())}
+                    else {return Err(signature_error(Encryption::Ed25519, uncompressed.as_ref().map_or(false, |a| ed25519::Pair::verify(&sign, a, &pubkey))))}
+                },
+                VerifierKind::KeyFile(key_file) => {
+                    let pair = ed25519_pair(&key_file)?;
+                    let signature = pair.sign(&message[..]).0.to_vec();
+                    let complete_message = [hex::decode(prelude).expect("known value"), pair.public().to_vec(), message, signature].concat();
+                    (complete_message, name_stub)
                 },
             }
         },
@@ -132,7 +175,13 @@ pub fn make_message (make: Make) -> anyhow::Result<()> {
                         let complete_message = [hex::decode(prelude).expect("known value"), pubkey.to_vec(), message, sign.0.to_vec()].concat();
                         (complete_message, name_stub)
                     }
-                    else {return Err(Error::BadSignature(Encryption::Sr25519).show())}
+                    else {return Err(signature_error(Encryption::Sr25519, uncompressed.as_ref().map_or(false, |a| sr25519::Pair::verify(&sign, a, &pubkey))))}
+                },
+                VerifierKind::KeyFile(key_file) => {
+                    let pair = sr25519_pair(&key_file)?;
+                    let signature = pair.sign(&message[..]).0.to_vec();
+                    let complete_message = [hex::decode(prelude).expect("known value"), pair.public().to_vec(), message, signature].concat();
+                    (complete_message, name_stub)
                 },
             }
         },
@@ -164,7 +213,13 @@ pub fn make_message (make: Make) -> anyhow::Result<()> {
                         let complete_message = [hex::decode(prelude).expect("known value"), pubkey.0.to_vec(), message, sign.0.to_vec()].concat();
                         (complete_message, name_stub)
                     }
-                    else {return Err(Error::BadSignature(Encryption::Ecdsa).show())}
+                    else {return Err(signature_error(Encryption::Ecdsa, uncompressed.as_ref().map_or(false, |a| ecdsa::Pair::verify(&sign, a, &pubkey))))}
+                },
+                VerifierKind::KeyFile(key_file) => {
+                    let pair = ecdsa_pair(&key_file)?;
+                    let signature = pair.sign(&message[..]).0.to_vec();
+                    let complete_message = [hex::decode(prelude).expect("known value"), pair.public().0.to_vec(), message, signature].concat();
+                    (complete_message, name_stub)
                 },
             }
         },
@@ -177,8 +232,8 @@ pub fn make_message (make: Make) -> anyhow::Result<()> {
     };
     
     let output_name = match make.name {
-        Some(a) => format!("{}/{}", EXPORT_FOLDER, a),
-        None => format!("{}/{}", EXPORT_FOLDER, complete_name),
+        Some(a) => format!("{}/{}", folder, a),
+        None => format!("{}/{}", folder, complete_name),
     };
     
     match make.goal {
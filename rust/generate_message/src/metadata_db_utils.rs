@@ -21,53 +21,30 @@ fn read_metadata_database (metadata: &Tree) -> anyhow::Result<Vec<MetaValues>> {
 
 
 /// Struct used to sort the metadata entries:
-/// newer has newest MetaValues entry from the database,
-/// older has older MetaValues entry from the database
+/// for each network, MetaValues entries from the database are ordered
+/// from the newest version to the oldest one
 pub struct SortedMetaValues {
-    pub newer: Vec<MetaValues>,
-    pub older: Vec<MetaValues>,
+    pub networks: Vec<Vec<MetaValues>>,
 }
 
 
-/// Function to sort the metavalues into set of newest ones and set of older ones,
-/// with maximum one older version for each of the networks;
-/// at the moment it is agreed to have no more than two entries for each of the networks,
-/// function throws error if finds the third one
+/// Function to sort the metavalues by network, each network gets the list of its versions,
+/// from the newest to the oldest;
+/// function throws error if finds same version of the network twice
 fn sort_metavalues (meta_values: Vec<MetaValues>) -> anyhow::Result<SortedMetaValues> {
-    let mut newer: Vec<MetaValues> = Vec::new();
-    let mut older: Vec<MetaValues> = Vec::new();
-    for x in meta_values.iter() {
-        let mut found_in_new = false;
-        let mut num_new = None;
-        for (i, y) in newer.iter().enumerate() {
-            if x.name == y.name {
-                for z in older.iter() {
-                    if x.name == z.name {return Err(Error::DatabaseMetadataOverTwoEntries(x.name.to_string()).show())}
-                }
-                found_in_new = true;
-                if x.version < y.version {
-                    older.push(x.to_owned());
-                }
-                else {
-                    if x.version == y.version {return Err(Error::DatabaseMetadataSameVersionTwice{name: x.name.to_string(), version: x.version}.show())}
-                    else {
-                        num_new = Some(i);
-                    }
-                }
-            break;
-            }
-        }
-        if !found_in_new {
-            newer.push(x.to_owned());
-        }
-        if let Some(i) = num_new {
-            older.push(newer.remove(i));
-            newer.push(x.to_owned());
+    let mut networks: Vec<Vec<MetaValues>> = Vec::new();
+    for x in meta_values.into_iter() {
+        match networks.iter_mut().find(|y| y[0].name == x.name) {
+            Some(versions) => {
+                if versions.iter().any(|y| y.version == x.version) {return Err(Error::DatabaseMetadataSameVersionTwice{name: x.name.to_string(), version: x.version}.show())}
+                let position = versions.iter().position(|y| y.version < x.version).unwrap_or(versions.len());
+                versions.insert(position, x);
+            },
+            None => networks.push(vec![x]),
         }
     }
     Ok(SortedMetaValues{
-        newer,
-        older,
+        networks,
     })
 }
 
@@ -81,73 +58,40 @@ pub struct UpdSortedMetaValues {
 
 /// Function to add new MetaValues entry to SortedMetaValues
 /// If the fetched metadata is good and has later version than the ones in SortedMetaValues,
-/// it is added to newer group of metavalues, any previous value from newer is moved to older,
-/// if there was any value in older, it gets kicked out.
+/// it is added as the newest version of the network, and only `keep` newest versions
+/// of the network are kept, older ones get kicked out.
 /// flag upd_done indicates if any update was done to the SortedMetaValues
-pub fn add_new (new: &MetaValues, sorted: &SortedMetaValues) -> anyhow::Result<UpdSortedMetaValues> {
-    let mut upd_done = false;
-    let mut num_new = None;
-    let mut num_old = None;
-    let mut found_in_newer = false;
-    for (i, x) in sorted.newer.iter().enumerate() {
-        if &new.name == &x.name {
-            found_in_newer = true;
+pub fn add_new (new: &MetaValues, sorted: &SortedMetaValues, keep: usize) -> anyhow::Result<UpdSortedMetaValues> {
+    let mut networks = sorted.networks.to_vec();
+    let upd_done = match networks.iter_mut().find(|x| x[0].name == new.name) {
+        Some(versions) => {
+            let x = &versions[0];
             if new.version < x.version {return Err(Error::FetchedEarlierVersion{name: x.name.to_string(), old_version: x.version, new_version: new.version}.show())}
-            else {
-                if new.version == x.version {
-                    if new.meta != x.meta {
-                        let mut sus1: Vec<u8> = Vec::new();
-                        let mut sus2: Vec<u8> = Vec::new();
-                        for a in 0..x.meta.len() {
-                            if new.meta[a] != x.meta[a] {
-                                println!("Suspicious number {}", a);
-                                sus1.push(new.meta[a]);
-                                sus2.push(x.meta[a]);
-                            }
-                        }
-                        println!("new: {:?}, in db: {:?}", sus1, sus2);
-                        return Err(Error::SameVersionDifferentMetadata{name: new.name.to_string(), version: new.version}.show())
-                    }
-                }
-                else {
-                    num_new = Some(i);
-                    for (j, y) in sorted.older.iter().enumerate() {
-                        if &x.name == &y.name {
-                            num_old = Some(j);
-                            break;
-                        }
-                    }
+            if new.version == x.version {
+                if new.meta != x.meta {
+                    return Err(Error::SameVersionDifferentMetadata{name: new.name.to_string(), version: new.version}.show())
                 }
+                false
             }
-        }
-    }
-    let mut sorted_output = SortedMetaValues {
-        newer: sorted.newer.to_vec(),
-        older: sorted.older.to_vec(),
+            else {
+                versions.insert(0, new.to_owned());
+                versions.truncate(keep);
+                true
+            }
+        },
+        None => {
+            networks.push(vec![new.to_owned()]);
+            true
+        },
     };
-    if !found_in_newer {
-        upd_done = true;
-        sorted_output.newer.push(new.to_owned());
-    }
-    else {
-        if let Some(j) = num_old {
-            upd_done = true;
-            sorted_output.older.remove(j);
-        }
-        if let Some(i) = num_new {
-            upd_done = true;
-            sorted_output.older.push(sorted_output.newer.remove(i));
-            sorted_output.newer.push(new.to_owned());
-        }
-    }
     Ok(UpdSortedMetaValues{
-        sorted: sorted_output,
+        sorted: SortedMetaValues{networks},
         upd_done,
     })
 }
 
-/// Function to collect metadata from metadata tree of the database, clear that tree,
-/// and sort the metadata into newer and older subsets
+/// Function to collect metadata from metadata tree of the database
+/// and sort the metadata into ordered list of versions for each network
 pub fn prepare_metadata (metadata: &Tree) -> anyhow::Result<SortedMetaValues> {
     let known_metavalues = read_metadata_database(metadata)?;
     sort_metavalues(known_metavalues)
@@ -156,9 +100,7 @@ pub fn prepare_metadata (metadata: &Tree) -> anyhow::Result<SortedMetaValues> {
 /// Function to write sorted metadata into the database
 pub fn write_metadata (sorted_meta_values: SortedMetaValues, metadata: &Tree) -> anyhow::Result<()> {
     clear_tree(&metadata)?;
-    let mut all_meta = sorted_meta_values.newer;
-    all_meta.extend(sorted_meta_values.older);
-    for x in all_meta.iter() {
+    for x in sorted_meta_values.networks.iter().flatten() {
         let versioned_name = NameVersioned {
             name: x.name.to_string(),
             version: x.version,
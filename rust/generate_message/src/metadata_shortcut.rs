@@ -1,13 +1,14 @@
 use sled::Tree;
-use meta_reading::{decode_metadata::decode_version, fetch_metadata::{fetch_info, fetch_info_with_chainspecs}, interpret_chainspecs::interpret_properties};
+use meta_reading::{decode_metadata::decode_version, fetch_metadata::{fetch_info, fetch_info_with_chainspecs, FileSource, MetadataSource}, interpret_chainspecs::{interpret_properties_with_meta, properties_from_metadata}};
 use constants::{COLOR, SECONDARY_COLOR};
 use definitions::{crypto::Encryption, metadata::MetaValues, network_specs::ChainSpecsToSend};
 use std::convert::TryInto;
 use db_handling::{helpers::unhex, error::NotHex};
 use anyhow;
 
-use crate::error::{Error, NotDecodeable};
+use crate::error::{Error, NeedKey, NotDecodeable};
 use crate::helpers::{genesis_hash_in_hot_db, filter_address_book_by_url, process_indices};
+use crate::parser::{NetworkFromFile, ParachainInfo};
 
 
 /// Struct to store MetaValues and genesis hash for network
@@ -16,14 +17,11 @@ pub struct MetaShortCut {
     pub genesis_hash: [u8; 32],
 }
 
-/// Function to process address as &str, fetch metadata and genesis hash for it,
-/// and output MetaShortCut value in case of success
-pub fn meta_shortcut (address: &str) -> anyhow::Result<MetaShortCut> {
+/// Function to process rpc endpoints of the network in failover order,
+/// fetch metadata and genesis hash, and output MetaShortCut value in case of success
+pub fn meta_shortcut (addresses: &[String]) -> anyhow::Result<MetaShortCut> {
 
-    let new_info = match fetch_info(address) {
-        Ok(a) => a,
-        Err(e) => return Err(Error::FetchFailed{address: address.to_string(), error: e.to_string()}.show()),
-    };
+    let (new_info, address) = fetch_with_failover(addresses, fetch_info)?;
     let genesis_hash = get_genesis_hash(&new_info.genesis_hash)?;
     let meta_values = match decode_version(&new_info.meta) {
         Ok(a) => a,
@@ -44,22 +42,24 @@ pub struct MetaSpecsShortCut {
 }
 
 
-/// Function to process address as &str, fetch metadata, genesis hash, and chainspecs
-/// for it, and output MetaSpecsShortCut value in case of success
-pub fn meta_specs_shortcut (address: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption) -> anyhow::Result<MetaSpecsShortCut> {
+/// Function to process rpc endpoints of the network in failover order, fetch metadata,
+/// genesis hash, and chainspecs, and output MetaSpecsShortCut value in case of success;
+/// parachain info, if provided, is set in new network specs and checked against the existing ones
+pub fn meta_specs_shortcut (addresses: &[String], address_book: &Tree, chainspecs: &Tree, encryption: Encryption, parachain_info: &Option<ParachainInfo>) -> anyhow::Result<MetaSpecsShortCut> {
 
+    let (new_info, address) = fetch_with_failover(addresses, fetch_info_with_chainspecs)?;
+    let address = address.as_str();
     let entries = filter_address_book_by_url(address, address_book)?;
-    let new_info = match fetch_info_with_chainspecs(address) {
-        Ok(a) => a,
-        Err(e) => return Err(Error::FetchFailed{address: address.to_string(), error: e.to_string()}.show()),
-    };
     let genesis_hash = get_genesis_hash(&new_info.genesis_hash)?;
     let meta_values = match decode_version(&new_info.meta) {
         Ok(a) => a,
         Err(e) => return Err(Error::NotDecodeable(NotDecodeable::FetchedMetadata{address: address.to_string(), error: e.to_string()}).show())
     };
-    let new_properties = match interpret_properties(&new_info.properties) {
-        Ok(a) => a,
+    let new_properties = match interpret_properties_with_meta(&new_info.properties, &meta_values.meta) {
+        Ok(a) => {
+            for x in a.warnings.iter() {println!("Warning for network at {}. {}", address, x)}
+            a.properties
+        },
         Err(e) => return Err(Error::BadNetworkProperties{address: address.to_string(), error: e.to_string()}.show()),
     };
     if entries.len() == 0 {
@@ -76,6 +76,9 @@ pub fn meta_specs_shortcut (address: &str, address_book: &Tree, chainspecs: &Tre
             secondary_color: SECONDARY_COLOR.to_string(),
             title: format!("{}-{}", meta_values.name, encryption.show()),
             unit: new_properties.unit.to_string(),
+            relay_genesis_hash: parachain_info.as_ref().map(|a| a.relay_genesis_hash),
+            parachain_id: parachain_info.as_ref().map(|a| a.parachain_id),
+            tokens: new_properties.tokens,
         };
         Ok(MetaSpecsShortCut{
             meta_values,
@@ -88,7 +91,11 @@ pub fn meta_specs_shortcut (address: &str, address_book: &Tree, chainspecs: &Tre
         if specs.base58prefix != new_properties.base58prefix {return Err(Error::Base58Changed(address.to_string()).show())}
         if specs.decimals != new_properties.decimals {return Err(Error::DecimalsChanged(address.to_string()).show())}
         if specs.unit != new_properties.unit {return Err(Error::UnitChanged(address.to_string()).show())}
+        if specs.tokens != new_properties.tokens {return Err(Error::TokensChanged(address.to_string()).show())}
         if specs.name != meta_values.name {return Err(Error::NameChanged(address.to_string()).show())}
+        if let Some(a) = parachain_info {
+            if (specs.relay_genesis_hash != Some(a.relay_genesis_hash))||(specs.parachain_id != Some(a.parachain_id)) {return Err(Error::ParachainInfoChanged(address.to_string()).show())}
+        }
         // ChainSpecsToSend are good, can use them
         Ok(MetaSpecsShortCut{
             meta_values,
@@ -98,6 +105,128 @@ pub fn meta_specs_shortcut (address: &str, address_book: &Tree, chainspecs: &Tre
     }
 }
 
+/// Function to make MetaShortCut from metadata file and genesis hash, without rpc calls;
+/// metadata file could be binary `.scale` file or hex file
+pub fn meta_shortcut_from_file (meta_file: &str, genesis_hash: [u8; 32]) -> anyhow::Result<MetaShortCut> {
+    let source = match FileSource::new(meta_file, &genesis_hash, None) {
+        Ok(a) => a,
+        Err(e) => return Err(Error::MetadataFile{filename: meta_file.to_string(), error: e.to_string()}.show()),
+    };
+    let meta_values = meta_values_from_source(&source, meta_file)?;
+    Ok(MetaShortCut{
+        meta_values,
+        genesis_hash,
+    })
+}
+
+/// Function to make MetaSpecsShortCut from metadata file and genesis hash, without rpc calls;
+/// if chain spec file is provided, network properties are taken from it,
+/// same way as the properties fetched through rpc call;
+/// if not, base58 prefix is taken from metadata constants, decimals and unit are not recorded
+/// in metadata and must be provided;
+/// provided base58 prefix is used only if metadata has no `SS58Prefix` constant,
+/// and is reported if it differs from the one in metadata
+pub fn meta_specs_from_file (network: &NetworkFromFile) -> anyhow::Result<MetaSpecsShortCut> {
+    let source = match FileSource::new(&network.meta_file, &network.genesis_hash, network.chain_spec_file.as_deref()) {
+        Ok(a) => a,
+        Err(e) => {
+            let filename = match &network.chain_spec_file {
+                Some(a) => format!("{} or {}", network.meta_file, a),
+                None => network.meta_file.to_string(),
+            };
+            return Err(Error::MetadataFile{filename, error: e.to_string()}.show())
+        },
+    };
+    let meta_values = meta_values_from_source(&source, &network.meta_file)?;
+    let (base58prefix, decimals, unit, tokens) = match &network.chain_spec_file {
+        Some(chain_spec_file) => {
+            let properties = match source.fetch_properties() {
+                Ok(a) => a,
+                Err(e) => return Err(Error::ChainSpecFile{filename: chain_spec_file.to_string(), error: e.to_string()}.show()),
+            };
+            match interpret_properties_with_meta(&properties, &meta_values.meta) {
+                Ok(a) => {
+                    for x in a.warnings.iter() {println!("Warning for network {}. {}", meta_values.name, x)}
+                    (a.properties.base58prefix, a.properties.decimals, a.properties.unit, a.properties.tokens)
+                },
+                Err(e) => return Err(Error::ChainSpecFile{filename: chain_spec_file.to_string(), error: e.to_string()}.show()),
+            }
+        },
+        None => {
+            let meta_properties = match properties_from_metadata(&meta_values.meta) {
+                Ok(a) => a,
+                Err(e) => return Err(Error::MetadataFile{filename: network.meta_file.to_string(), error: e}.show()),
+            };
+            let base58prefix = match (meta_properties.base58prefix, network.base58prefix) {
+                (Some(a), Some(b)) => {
+                    if a != b {println!("Warning for network {}. Base58 prefix {} provided differs from base58 prefix {} in metadata constants. Metadata value is used.", meta_values.name, b, a)}
+                    a
+                },
+                (Some(a), None) => a,
+                (None, Some(b)) => b,
+                (None, None) => return Err(Error::NoBase58Prefix(network.meta_file.to_string()).show()),
+            };
+            let decimals = match network.decimals {
+                Some(a) => a,
+                None => return Err(Error::NeedKey(NeedKey::Decimals).show()),
+            };
+            let unit = match &network.unit {
+                Some(a) => a.to_string(),
+                None => return Err(Error::NeedKey(NeedKey::Unit).show()),
+            };
+            (base58prefix, decimals, unit, Vec::new())
+        },
+    };
+    let specs = ChainSpecsToSend {
+        base58prefix,
+        color: COLOR.to_string(),
+        decimals,
+        encryption: network.encryption,
+        genesis_hash: network.genesis_hash,
+        logo: meta_values.name.to_string(),
+        name: meta_values.name.to_string(),
+        path_id: format!("//{}", meta_values.name),
+        secondary_color: SECONDARY_COLOR.to_string(),
+        title: format!("{}-{}", meta_values.name, network.encryption.show()),
+        unit,
+        relay_genesis_hash: network.parachain_info.as_ref().map(|a| a.relay_genesis_hash),
+        parachain_id: network.parachain_info.as_ref().map(|a| a.parachain_id),
+        tokens,
+    };
+    Ok(MetaSpecsShortCut{
+        meta_values,
+        specs,
+        update: false,
+    })
+}
+
+/// Helper function to get MetaValues from file source
+fn meta_values_from_source (source: &FileSource, meta_file: &str) -> anyhow::Result<MetaValues> {
+    let meta = match source.fetch_meta() {
+        Ok(a) => a,
+        Err(e) => return Err(Error::MetadataFile{filename: meta_file.to_string(), error: e.to_string()}.show()),
+    };
+    match decode_version(&meta) {
+        Ok(a) => Ok(a),
+        Err(e) => return Err(Error::MetadataFile{filename: meta_file.to_string(), error: e.to_string()}.show()),
+    }
+}
+
+/// Helper function to make rpc call at the endpoints in failover order,
+/// until one of them succeeds; outputs fetched information and the endpoint used
+fn fetch_with_failover <T> (addresses: &[String], fetch: fn(&str) -> Result<T, Box<dyn std::error::Error>>) -> anyhow::Result<(T, String)> {
+    for (i, address) in addresses.iter().enumerate() {
+        match fetch(address) {
+            Ok(a) => return Ok((a, address.to_string())),
+            Err(e) => {
+                if i+1 == addresses.len() {return Err(Error::FetchFailed{address: address.to_string(), error: e.to_string()}.show())}
+                println!("Rpc call at {} failed. {} Trying next endpoint.", address, e);
+            },
+        }
+    }
+    Err(Error::NoEndpoints.show())
+}
+
 /// Helper function to interpret freshly fetched genesis hash
 fn get_genesis_hash (fetched_genesis_hash: &str) -> anyhow::Result<[u8; 32]> {
     let genesis_hash_vec = unhex(fetched_genesis_hash, NotHex::GenesisHash)?;
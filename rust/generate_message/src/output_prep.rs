@@ -9,9 +9,9 @@ use crate::error::Error;
 
 /// Function to print in standardly named file a plaintext output ready for signing
 /// for `load_meta` type of message.
-/// Input is MetaShortCut.
-pub fn load_meta_print (shortcut: &MetaShortCut) -> anyhow::Result<()> {
-    let filename = format!("{}_{}V{}", LOAD, shortcut.meta_values.name, shortcut.meta_values.version);
+/// Input is MetaShortCut and the folder for output file.
+pub fn load_meta_print (shortcut: &MetaShortCut, folder: &str) -> anyhow::Result<()> {
+    let filename = format!("{}/{}_{}V{}", folder, LOAD, shortcut.meta_values.name, shortcut.meta_values.version);
     let content = ContentLoadMeta::generate(&shortcut.meta_values.meta, &shortcut.genesis_hash);
     match content.write(&filename) {
         Ok(_) => Ok(()),
@@ -22,9 +22,9 @@ pub fn load_meta_print (shortcut: &MetaShortCut) -> anyhow::Result<()> {
 
 /// Function to print in standardly named file a plaintext output ready for signing
 /// for `add_network` type of message.
-/// Input is MetaSpecsShortCut.
-pub fn add_network_print (shortcut: &MetaSpecsShortCut) -> anyhow::Result<()> {
-    let filename = format!("{}_{}V{}", ADD, shortcut.meta_values.name, shortcut.meta_values.version);
+/// Input is MetaSpecsShortCut and the folder for output file.
+pub fn add_network_print (shortcut: &MetaSpecsShortCut, folder: &str) -> anyhow::Result<()> {
+    let filename = format!("{}/{}_{}V{}", folder, ADD, shortcut.meta_values.name, shortcut.meta_values.version);
     let content = ContentAddNetwork::generate(&shortcut.meta_values.meta, &shortcut.specs);
     match content.write(&filename) {
         Ok(_) => Ok(()),
@@ -33,9 +33,11 @@ pub fn add_network_print (shortcut: &MetaSpecsShortCut) -> anyhow::Result<()> {
 }
 
 
-/// Function to print `add_specs`
-pub fn print_specs (network_specs: &ChainSpecsToSend) -> anyhow::Result<()> {
-    let filename = format!("{}_{}_{}", SPECS, network_specs.name, network_specs.encryption.show());
+/// Function to print in standardly named file a plaintext output ready for signing
+/// for `add_specs` type of message.
+/// Input is ChainSpecsToSend and the folder for output file.
+pub fn print_specs (network_specs: &ChainSpecsToSend, folder: &str) -> anyhow::Result<()> {
+    let filename = format!("{}/{}_{}_{}", folder, SPECS, network_specs.name, network_specs.encryption.show());
     let content = ContentAddSpecs::generate(network_specs);
     match content.write(&filename) {
         Ok(_) => Ok(()),
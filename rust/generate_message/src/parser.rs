@@ -1,18 +1,431 @@
-use std::env;
-use constants::FOLDER;
-use definitions::crypto::{Encryption, SufficientCrypto};
+use std::convert::TryInto;
+use definitions::{crypto::{Encryption, SufficientCrypto}, network_specs::AssetInfo};
 use parity_scale_codec::Decode;
+use structopt::{StructOpt, clap::{AppSettings, ArgGroup, Shell}};
 use anyhow;
 use db_handling::{helpers::unhex, error::NotHex};
+use qr_reader_pc::run_with_file;
 
-use crate::error::{Error, NotDecodeable, NeedArgument, DoubleKey, NeedKey, BadArgument, Unexpected};
+use crate::config::Config;
+use crate::error::{Error, NotDecodeable, NeedArgument, NeedKey, BadArgument, Unexpected};
 
 /// Expected typical run commands:
-/// `$ cargo run show database`
-/// `$ cargo run show address_book`
+/// `$ cargo run show --database`
+/// `$ cargo run show --address-book`
 /// `$ cargo run load_metadata -n westend`
-/// `$ cargo run add_specs -d -n -ed25519 westend`
-/// `$ cargo run add_network -u wss://unknown-network.eu -ecdsa`
+/// `$ cargo run add_specs -d -n --ed25519 westend`
+/// `$ cargo run add_network -u wss://unknown-network.eu --ecdsa`
+/// `$ cargo run add_specs -u wss://some-parachain.eu --sr25519 --relay 0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3 --para 2000`
+/// `$ cargo run inspect --payload add_network_kusamaV9070_Alice.txt`
+/// `$ cargo run load_assets --genesis 0x48239ef607d7928874027a43a67689209727dfb3d3dc5e5b03a39bdc2eda771a --asset 1984:USDt:6`
+/// `$ cargo run address_book set_url westend wss://westend-rpc.polkadot.io wss://westend.api.onfinality.io/public-ws`
+/// `$ cargo run completions bash`
+/// Keys in earlier form, with single dash, i.e. `-crypto` or `-verifier -hex`, are still accepted,
+/// see `legacy_args`.
+
+
+/// Command line interface, as parsed by structopt
+#[derive(StructOpt)]
+#[structopt(name = "generate_message", about = "Generate and sign update messages for Signer", global_settings = &[AppSettings::VersionlessSubcommands])]
+pub struct Cli {
+    /// Workspace config file, by default `generate_message.toml` in current folder is used, if it exists
+    #[structopt(long = "config", global = true)]
+    pub config: Option<String>,
+    #[structopt(subcommand)]
+    pub command: CliCommand,
+}
+
+#[derive(StructOpt)]
+pub enum CliCommand {
+    /// Show metadata or address book entries from the hot database
+    #[structopt(name = "show")]
+    Show(ShowArgs),
+    /// Make load_types payload from the hot database
+    #[structopt(name = "load_types")]
+    LoadTypes,
+    /// Make load_metadata payload(s)
+    #[structopt(name = "load_metadata")]
+    LoadMetadata(SetArgs),
+    /// Make add_network payload(s)
+    #[structopt(name = "add_network")]
+    AddNetwork(SetArgs),
+    /// Make add_specs payload(s)
+    #[structopt(name = "add_specs")]
+    AddSpecs(SetArgs),
+    /// Make signed message from payload, verifier and signature
+    #[structopt(name = "make")]
+    Make(MakeArgs),
+    /// Make signed message from payload and sufficient crypto produced by Signer
+    #[structopt(name = "sign")]
+    Sign(SignArgs),
+    /// Remove network or metadata entry from the hot database
+    #[structopt(name = "remove")]
+    Remove(RemoveArgs),
+    /// Make load_assets payload with asset registry of the network
+    #[structopt(name = "load_assets")]
+    LoadAssets(LoadAssetsArgs),
+    /// Make pruned copy of load_metadata or add_network payload
+    #[structopt(name = "prune")]
+    Prune(PruneArgs),
+    /// Write compressed content of load_metadata or add_network payload, for signing compressed message
+    #[structopt(name = "compress")]
+    Compress(CompressArgs),
+    /// Show changes between two metadata versions from the hot database
+    #[structopt(name = "diff")]
+    Diff(DiffArgs),
+    /// Make add_network payload from metadata file, without rpc calls
+    #[structopt(name = "add_network_from_file")]
+    NetworkFromFile(NetworkFromFileArgs),
+    /// Make load_metadata payload from metadata file, without rpc calls
+    #[structopt(name = "load_metadata_from_file")]
+    LoadFromFile(LoadFromFileArgs),
+    /// Check signature(s) and content of signed message
+    #[structopt(name = "inspect")]
+    Inspect(InspectArgs),
+    /// View and edit address book entries in the hot database
+    #[structopt(name = "address_book")]
+    AddressBook(AddressBookArgs),
+    /// Restore the hot database to its default form
+    #[structopt(name = "restore_defaults")]
+    RestoreDefaults,
+    /// Print shell completion script
+    #[structopt(name = "completions")]
+    Completions {
+        #[structopt(possible_values = &Shell::variants())]
+        shell: Shell,
+    },
+}
+
+#[derive(StructOpt)]
+#[structopt(group = ArgGroup::with_name("show").required(true))]
+pub struct ShowArgs {
+    /// Show metadata entries
+    #[structopt(long = "database", group = "show")]
+    database: bool,
+    /// Show address book entries
+    #[structopt(long = "address-book", group = "show")]
+    address_book: bool,
+}
+
+/// Keys for `load_metadata`, `add_network` and `add_specs`
+#[derive(StructOpt)]
+#[structopt(group = ArgGroup::with_name("set"), group = ArgGroup::with_name("content").required(true), group = ArgGroup::with_name("encryption"))]
+pub struct SetArgs {
+    /// Make rpc calls, do not update the database, produce all output files
+    #[structopt(short = "d", group = "set")]
+    d: bool,
+    /// Make no rpc calls, produce all output files from the database
+    #[structopt(short = "f", group = "set")]
+    f: bool,
+    /// Update the database through rpc calls, produce output files only for updated entries
+    #[structopt(short = "k", group = "set")]
+    k: bool,
+    /// Update the database through rpc calls, produce no output files
+    #[structopt(short = "p", group = "set")]
+    p: bool,
+    /// Update the database through rpc calls, produce all output files (default)
+    #[structopt(short = "t", group = "set")]
+    t: bool,
+    /// Process all networks from the address book
+    #[structopt(short = "a", group = "content")]
+    all: bool,
+    /// Process network with given name
+    #[structopt(short = "n", group = "content")]
+    name: bool,
+    /// Process network at given url, or at endpoint with given name from config file
+    #[structopt(short = "u", group = "content")]
+    url: bool,
+    /// Stop on first error when processing all networks
+    #[structopt(short = "s")]
+    stop: bool,
+    /// Set ed25519 encryption
+    #[structopt(long = "ed25519", group = "encryption")]
+    ed25519: bool,
+    /// Set sr25519 encryption
+    #[structopt(long = "sr25519", group = "encryption")]
+    sr25519: bool,
+    /// Set ecdsa encryption
+    #[structopt(long = "ecdsa", group = "encryption")]
+    ecdsa: bool,
+    /// Genesis hash of relay chain, for parachain added by url
+    #[structopt(long = "relay")]
+    relay: Option<String>,
+    /// Parachain id, for parachain added by url
+    #[structopt(long = "para")]
+    para: Option<u32>,
+    /// Network name or url
+    network: Option<String>,
+}
+
+#[derive(StructOpt)]
+#[structopt(group = ArgGroup::with_name("goal"), group = ArgGroup::with_name("verifier"), group = ArgGroup::with_name("signature"), group = ArgGroup::with_name("password"), group = ArgGroup::with_name("sufficient"))]
+pub struct MakeArgs {
+    /// Produce only apng qr code
+    #[structopt(long = "qr", group = "goal")]
+    qr: bool,
+    /// Produce only text file
+    #[structopt(long = "text", group = "goal")]
+    text: bool,
+    /// Verifier encryption, or `none` for message without verifier
+    #[structopt(long = "crypto", possible_values = &["ed25519", "sr25519", "ecdsa", "none"])]
+    crypto: Option<String>,
+    /// Message type
+    #[structopt(long = "msgtype", possible_values = MSG_TYPES)]
+    msgtype: String,
+    /// Verifier public key as hex
+    #[structopt(long = "verifier-hex", group = "verifier")]
+    verifier_hex: Option<String>,
+    /// File with verifier public key, in for_signing folder
+    #[structopt(long = "verifier-file", group = "verifier")]
+    verifier_file: Option<String>,
+    /// Polkadot-js keystore to sign with
+    #[structopt(long = "verifier-keystore", group = "verifier")]
+    verifier_keystore: Option<String>,
+    /// Subkey secret file to sign with
+    #[structopt(long = "verifier-secret", group = "verifier")]
+    verifier_secret: Option<String>,
+    /// Sign with Alice key, only with `--dev`
+    #[structopt(long = "verifier-alice", group = "verifier")]
+    verifier_alice: bool,
+    /// Signature as hex
+    #[structopt(long = "signature-hex", group = "signature")]
+    signature_hex: Option<String>,
+    /// File with signature, in for_signing folder
+    #[structopt(long = "signature-file", group = "signature")]
+    signature_file: Option<String>,
+    /// Sufficient crypto from Signer as hex
+    #[structopt(long = "sufficient-hex", group = "sufficient")]
+    sufficient_hex: Option<String>,
+    /// File with sufficient crypto from Signer, in for_signing folder
+    #[structopt(long = "sufficient-file", group = "sufficient")]
+    sufficient_file: Option<String>,
+    /// Png qr code with sufficient crypto from Signer, in for_signing folder
+    #[structopt(long = "sufficient-qr", group = "sufficient")]
+    sufficient_qr: Option<String>,
+    /// Environment variable with password for key file
+    #[structopt(long = "password-env", group = "password")]
+    password_env: Option<String>,
+    /// Read password for key file from stdin
+    #[structopt(long = "password-stdin", group = "password")]
+    password_stdin: bool,
+    /// Allow Alice key
+    #[structopt(long = "dev")]
+    dev: bool,
+    /// Payload file, in for_signing folder
+    #[structopt(long = "payload")]
+    payload: String,
+    /// Name for output files, in signed folder
+    #[structopt(long = "name")]
+    name: Option<String>,
+}
+
+#[derive(StructOpt)]
+#[structopt(group = ArgGroup::with_name("goal"), group = ArgGroup::with_name("sufficient"))]
+pub struct SignArgs {
+    /// Produce only apng qr code
+    #[structopt(long = "qr", group = "goal")]
+    qr: bool,
+    /// Produce only text file
+    #[structopt(long = "text", group = "goal")]
+    text: bool,
+    /// Message type
+    #[structopt(long = "msgtype", possible_values = MSG_TYPES)]
+    msgtype: String,
+    /// Sufficient crypto from Signer as hex
+    #[structopt(long = "sufficient-hex", group = "sufficient")]
+    sufficient_hex: Option<String>,
+    /// File with sufficient crypto from Signer, in for_signing folder
+    #[structopt(long = "sufficient-file", group = "sufficient")]
+    sufficient_file: Option<String>,
+    /// Png qr code with sufficient crypto from Signer, in for_signing folder
+    #[structopt(long = "sufficient-qr", group = "sufficient")]
+    sufficient_qr: Option<String>,
+    /// Payload file, in for_signing folder
+    #[structopt(long = "payload")]
+    payload: String,
+    /// Name for output files, in signed folder
+    #[structopt(long = "name")]
+    name: Option<String>,
+}
+
+#[derive(StructOpt)]
+#[structopt(group = ArgGroup::with_name("remove").required(true))]
+pub struct RemoveArgs {
+    /// Network title from the address book, to remove network specs and address book entry
+    #[structopt(long = "title", group = "remove")]
+    title: Option<String>,
+    /// Network specname, to remove metadata of given version
+    #[structopt(long = "name", group = "remove")]
+    name: Option<String>,
+    /// Metadata version to remove
+    #[structopt(long = "version", requires = "name")]
+    version: Option<u32>,
+}
+
+#[derive(StructOpt)]
+pub struct PruneArgs {
+    /// Message type of payload
+    #[structopt(long = "msgtype", possible_values = &["load_metadata", "add_network"])]
+    msgtype: String,
+    /// Payload file, in for_signing folder
+    #[structopt(long = "payload")]
+    payload: String,
+}
+
+#[derive(StructOpt)]
+pub struct CompressArgs {
+    /// Message type of payload
+    #[structopt(long = "msgtype", possible_values = &["load_metadata", "add_network"])]
+    msgtype: String,
+    /// Payload file, in for_signing folder
+    #[structopt(long = "payload")]
+    payload: String,
+}
+
+#[derive(StructOpt)]
+pub struct DiffArgs {
+    /// Network specname
+    #[structopt(long = "name")]
+    name: String,
+    /// Older version, by default the one preceding the newer version
+    #[structopt(long = "from")]
+    from: Option<u32>,
+    /// Newer version, by default the latest one in the database
+    #[structopt(long = "to")]
+    to: Option<u32>,
+}
+
+#[derive(StructOpt)]
+#[structopt(group = ArgGroup::with_name("encryption").required(true))]
+pub struct NetworkFromFileArgs {
+    /// Metadata file, .scale or hex
+    #[structopt(long = "meta")]
+    meta: String,
+    /// Network genesis hash as hex
+    #[structopt(long = "genesis")]
+    genesis: String,
+    /// Set ed25519 encryption
+    #[structopt(long = "ed25519", group = "encryption")]
+    ed25519: bool,
+    /// Set sr25519 encryption
+    #[structopt(long = "sr25519", group = "encryption")]
+    sr25519: bool,
+    /// Set ecdsa encryption
+    #[structopt(long = "ecdsa", group = "encryption")]
+    ecdsa: bool,
+    /// Network decimals
+    #[structopt(long = "decimals")]
+    decimals: Option<u8>,
+    /// Network unit
+    #[structopt(long = "unit")]
+    unit: Option<String>,
+    /// Base58 prefix, if metadata has no SS58Prefix constant
+    #[structopt(long = "base58")]
+    base58: Option<u16>,
+    /// Chain spec json file to take network properties from
+    #[structopt(long = "chainspec")]
+    chainspec: Option<String>,
+    /// Genesis hash of relay chain, for parachain
+    #[structopt(long = "relay")]
+    relay: Option<String>,
+    /// Parachain id
+    #[structopt(long = "para")]
+    para: Option<u32>,
+}
+
+#[derive(StructOpt)]
+pub struct LoadFromFileArgs {
+    /// Metadata file, .scale or hex
+    #[structopt(long = "meta")]
+    meta: String,
+    /// Network genesis hash as hex
+    #[structopt(long = "genesis")]
+    genesis: String,
+}
+
+#[derive(StructOpt)]
+pub struct LoadAssetsArgs {
+    /// Network genesis hash as hex
+    #[structopt(long = "genesis")]
+    genesis: String,
+    /// Asset as `asset_id:symbol:decimals`, key is repeated for each asset in registry
+    #[structopt(long = "asset", required = true, number_of_values = 1)]
+    asset: Vec<String>,
+}
+
+#[derive(StructOpt)]
+pub struct InspectArgs {
+    /// Signed message file, hex text or png qr code, in signed folder
+    #[structopt(long = "payload")]
+    payload: String,
+}
+
+#[derive(StructOpt)]
+pub enum AddressBookArgs {
+    /// List address book entries
+    #[structopt(name = "list")]
+    List,
+    /// Add network at given url(s) to the address book, network specs are fetched through rpc call
+    #[structopt(name = "add")]
+    Add(AddressBookAddArgs),
+    /// Change title of address book entry and network specs
+    #[structopt(name = "rename")]
+    Rename {
+        /// Network title
+        title: String,
+        /// New network title
+        new_title: String,
+    },
+    /// Set rpc endpoints of the network, in failover order
+    #[structopt(name = "set_url")]
+    SetUrl {
+        /// Network title
+        title: String,
+        /// Rpc endpoints, or endpoint names from config file
+        #[structopt(required = true)]
+        urls: Vec<String>,
+    },
+    /// Make the entry default one among the entries of its network
+    #[structopt(name = "set_default")]
+    SetDefault {
+        /// Network title
+        title: String,
+    },
+    /// Remove address book entry and network specs
+    #[structopt(name = "remove")]
+    Remove {
+        /// Network title
+        title: String,
+    },
+}
+
+#[derive(StructOpt)]
+#[structopt(group = ArgGroup::with_name("encryption"))]
+pub struct AddressBookAddArgs {
+    /// Set ed25519 encryption
+    #[structopt(long = "ed25519", group = "encryption")]
+    ed25519: bool,
+    /// Set sr25519 encryption
+    #[structopt(long = "sr25519", group = "encryption")]
+    sr25519: bool,
+    /// Set ecdsa encryption
+    #[structopt(long = "ecdsa", group = "encryption")]
+    ecdsa: bool,
+    /// Genesis hash of relay chain, for parachain
+    #[structopt(long = "relay")]
+    relay: Option<String>,
+    /// Parachain id
+    #[structopt(long = "para")]
+    para: Option<u32>,
+    /// Rpc endpoints in failover order, or endpoint names from config file
+    #[structopt(required = true)]
+    urls: Vec<String>,
+}
+
+/// Multi-letter keys used earlier with single dash
+const LEGACY_KEYS: &[&str] = &["-database", "-address_book", "-ed25519", "-sr25519", "-ecdsa", "-relay", "-para", "-qr", "-text", "-crypto", "-msgtype", "-payload", "-name", "-password-env", "-password-stdin", "-title", "-version", "-from", "-to", "-meta", "-genesis", "-decimals", "-unit", "-base58", "-chainspec"];
+
+const MSG_TYPES: &[&str] = &["load_types", "load_metadata", "load_metadata_compressed", "add_network", "add_network_compressed", "add_specs", "load_assets"];
 
 
 /// Enum to describe the incoming command contents
@@ -24,7 +437,16 @@ pub enum Command {
     Specs(Instruction),
     Make(Make),
     Remove(Remove),
+    Prune(Prune),
+    Compress(Compress),
+    Diff(Diff),
+    NetworkFromFile(NetworkFromFile),
+    LoadFromFile(LoadFromFile),
+    Assets(LoadAssets),
+    Inspect(String),
+    AddressBook(AddressBook),
     RestoreDefaults,
+    Completions(Shell),
 }
 
 pub enum Show {
@@ -37,6 +459,14 @@ pub struct Instruction {
     pub content: Content,
     pub pass_errors: bool,
     pub encryption_override: Option<Encryption>,
+    pub parachain_info: Option<ParachainInfo>,
+}
+
+/// Relay chain genesis hash and parachain id, to be set in network specs
+/// of a parachain when adding it through `-u` key
+pub struct ParachainInfo {
+    pub relay_genesis_hash: [u8; 32],
+    pub parachain_id: u32,
 }
 
 pub enum Content {
@@ -76,32 +506,42 @@ pub enum Crypto {
 pub enum VerifierKind {
     Alice,
     Normal {verifier_public_key: Vec<u8>, signature: Vec<u8>},
+    KeyFile(KeyFile),
 }
 
-pub enum Msg {
-    LoadTypes(Vec<u8>),
-    LoadMetadata(Vec<u8>),
-    AddNetwork(Vec<u8>),
-    AddSpecs(Vec<u8>),
+/// File with the secret key to sign the message with, and the source of the password for it
+pub struct KeyFile {
+    pub kind: KeyFileKind,
+    pub path: String,
+    pub password: PasswordSource,
+}
+
+pub enum KeyFileKind {
+    Keystore, // polkadot-js encrypted json keystore
+    Secret, // subkey secret file, with secret phrase or seed, possibly with derivations
 }
 
-enum CryptoType {
-    Ed25519,
-    Sr25519,
-    Ecdsa,
+pub enum PasswordSource {
     None,
+    Env(String),
+    Stdin,
 }
 
-enum MsgType {
-    LoadTypes,
-    LoadMetadata,
-    AddNetwork,
-    AddSpecs
+pub enum Msg {
+    LoadTypes(Vec<u8>),
+    LoadMetadata(Vec<u8>),
+    LoadMetadataCompressed(Vec<u8>),
+    AddNetwork(Vec<u8>),
+    AddNetworkCompressed(Vec<u8>),
+    AddSpecs(Vec<u8>),
+    LoadAssets(Vec<u8>),
 }
 
 enum VerKey {
     Hex(String),
     File(String),
+    Keystore(String),
+    Secret(String),
     Alice,
 }
 
@@ -110,560 +550,483 @@ enum Entry {
     File(String),
 }
 
+enum SufficientEntry {
+    Hex(String),
+    File(String),
+    Qr(String),
+}
+
+/// Payload with load_metadata or add_network content to prune the metadata in,
+/// and payload file name in for_signing folder
+pub struct Prune {
+    pub msg: Msg,
+    pub payload: String,
+}
+
+/// Payload with load_metadata or add_network content to compress,
+/// and payload file name in for_signing folder
+pub struct Compress {
+    pub msg: Msg,
+    pub payload: String,
+}
+
+/// Network specname and versions of metadata from the database to compare;
+/// if versions are not specified, two latest versions are compared
+pub struct Diff {
+    pub name: String,
+    pub from: Option<u32>,
+    pub to: Option<u32>,
+}
+
+/// Information to make `add_network` message from metadata file and genesis hash,
+/// without rpc calls;
+/// network properties are either provided directly or read from chain spec file
+pub struct NetworkFromFile {
+    pub meta_file: String,
+    pub genesis_hash: [u8; 32],
+    pub encryption: Encryption,
+    pub decimals: Option<u8>,
+    pub unit: Option<String>,
+    pub base58prefix: Option<u16>,
+    pub chain_spec_file: Option<String>,
+    pub parachain_info: Option<ParachainInfo>,
+}
+
+/// Information to make `load_metadata` message from metadata file and genesis hash,
+/// without rpc calls
+pub struct LoadFromFile {
+    pub meta_file: String,
+    pub genesis_hash: [u8; 32],
+}
+
+/// Information to make `load_assets` message, i.e. network genesis hash
+/// and asset registry of the network, without rpc calls
+pub struct LoadAssets {
+    pub genesis_hash: [u8; 32],
+    pub assets: Vec<AssetInfo>,
+}
+
 pub enum Remove {
     Title(String),
     SpecNameVersion{name: String, version: u32},
 }
 
+/// Address book changes, entries are addressed by network title;
+/// rpc endpoints are in failover order
+pub enum AddressBook {
+    List,
+    Add{addresses: Vec<String>, encryption: Encryption, parachain_info: Option<ParachainInfo>},
+    Rename{title: String, new_title: String},
+    SetUrl{title: String, addresses: Vec<String>},
+    SetDefault(String),
+    Remove(String),
+}
+
 impl Command {
-    /// FUnction to interpret command line input
-    pub fn new(mut args: env::Args) -> anyhow::Result<Command> {
-        args.next();
-
-        match args.next() {
-            Some(arg) => {
-                match arg.as_str() {
-                    "show" => {
-                        match args.next() {
-                            Some(show) => match show.as_str() {
-                                "-database" => Ok(Command::Show(Show::Database)),
-                                "-address_book" => Ok(Command::Show(Show::AddressBook)),
-                                _ => {return Err(Error::UnexpectedKeyArgumentSequence.show())},
-                            },
-                            None => return Err(Error::NeedKey(NeedKey::Show).show())
-                        }
+    /// Function to interpret parsed command line input;
+    /// files are read from for_signing folder, and missing encryption and
+    /// rpc endpoint names are taken from workspace config
+    pub fn new(cli_command: CliCommand, config: &Config) -> anyhow::Result<Command> {
+        match cli_command {
+            CliCommand::Show(x) => {
+                if x.database {Ok(Command::Show(Show::Database))}
+                else {Ok(Command::Show(Show::AddressBook))}
+            },
+            CliCommand::LoadTypes => Ok(Command::Types),
+            CliCommand::LoadMetadata(x) => Ok(Command::Load(x.instruction(None)?)),
+            CliCommand::AddNetwork(x) => {
+                let default_encryption = match (x.f, x.url) {
+                    (false, true) => config.default_encryption,
+                    _ => None,
+                };
+                Ok(Command::Add(x.with_endpoints(config).instruction(default_encryption)?))
+            },
+            CliCommand::AddSpecs(x) => {
+                let default_encryption = match (x.f, x.all) {
+                    (false, false) => config.default_encryption,
+                    _ => None,
+                };
+                Ok(Command::Specs(x.with_endpoints(config).instruction(default_encryption)?))
+            },
+            CliCommand::Make(x) => {
+                let goal = goal(x.qr, x.text);
+                let sufficient_crypto_found = sufficient_entry(x.sufficient_hex, x.sufficient_file, x.sufficient_qr);
+                let verifier_found = {
+                    if let Some(h) = x.verifier_hex {Some(VerKey::Hex(h))}
+                    else if let Some(f) = x.verifier_file {Some(VerKey::File(f))}
+                    else if let Some(f) = x.verifier_keystore {Some(VerKey::Keystore(f))}
+                    else if let Some(f) = x.verifier_secret {Some(VerKey::Secret(f))}
+                    else if x.verifier_alice {Some(VerKey::Alice)}
+                    else {None}
+                };
+                let signature_found = {
+                    if let Some(h) = x.signature_hex {Some(Entry::Hex(h))}
+                    else if let Some(f) = x.signature_file {Some(Entry::File(f))}
+                    else {None}
+                };
+                let password_found = {
+                    if let Some(v) = x.password_env {Some(PasswordSource::Env(v))}
+                    else if x.password_stdin {Some(PasswordSource::Stdin)}
+                    else {None}
+                };
+            // finalize what was parsed for `make`
+                if let Some(_) = sufficient_crypto_found {
+                    if x.crypto.is_some()||verifier_found.is_some()||signature_found.is_some()||password_found.is_some() {return Err(Error::Unexpected(Unexpected::SufficientCryptoWithVerifier).show())}
+                }
+                if let Some(VerKey::Alice) = verifier_found {
+                    if !x.dev {return Err(Error::Unexpected(Unexpected::AliceNoDev).show())}
+                }
+                match verifier_found {
+                    Some(VerKey::Keystore(_)) => {
+                        if let None = password_found {return Err(Error::NeedKey(NeedKey::Password).show())}
                     },
-                    "load_types" => Ok(Command::Types),
-                    "load_metadata"|"add_network"|"add_specs" => {
-                        let mut set_key = None;
-                        let mut content_key = None;
-                        let mut pass_errors = true;
-                        let mut name = None;
-                        let mut encryption_override_key = None;
-                        
-                        loop {
-                            match args.next() {
-                                Some(x) => {
-                                    if x.starts_with("-") {
-                                        match x.as_str() {
-                                            "-a"|"-n"|"-u" => {
-                                                match content_key {
-                                                    Some(_) => {return Err(Error::DoubleKey(DoubleKey::Content).show())},
-                                                    None => {content_key = Some(x)}
-                                                }
-                                            },
-                                            "-d"|"-f"|"-k"|"-p"|"-t" => {
-                                                match set_key {
-                                                    Some(_) => {return Err(Error::DoubleKey(DoubleKey::Set).show())},
-                                                    None => {set_key = Some(x)}
-                                                }
-                                            },
-                                            "-s" => {pass_errors = false},
-                                            "-ed25519"|"-sr25519"|"-ecdsa" => {
-                                                match encryption_override_key {
-                                                    Some(_) =>  {return Err(Error::DoubleKey(DoubleKey::CryptoOverride).show())},
-                                                    None => {encryption_override_key = Some(x)}
-                                                }
-                                            },
-                                            _ => {return Err(Error::UnexpectedKeyArgumentSequence.show())},
-                                        }
-                                    }
-                                    else {
-                                        match name {
-                                            Some(_) => return Err(Error::OnlyOneNetworkId.show()),
-                                            None => {name = Some(x)}
-                                        }
-                                    }
-                                },
-                                None => break,
-                            }
-                        }
-
-                        let set = match set_key {
-                            Some(x) => {
-                                match x.as_str() {
-                                    "-d" => Set::D,
-                                    "-f" => Set::F,
-                                    "-k" => Set::K,
-                                    "-p" => Set::P,
-                                    "-t" => Set::T,
-                                    _ => unreachable!(),
-                                }
-                            },
-                            None => Set::T,
-                        };
-                        
-                        let encryption_override = match encryption_override_key {
-                            Some(x) => {
-                                match x.as_str() {
-                                    "-ed25519" => Some(Encryption::Ed25519),
-                                    "-sr25519" => Some(Encryption::Sr25519),
-                                    "-ecdsa" => Some(Encryption::Ecdsa),
-                                    _ => unreachable!(),
-                                }
-                            },
-                            None => None,
-                        };
-                        
-                        let content = match content_key {
-                            Some(x) => {
-                                match x.as_str() {
-                                    "-a" => {
-                                        if let Some(_) = name {return Err(Error::Unexpected(Unexpected::KeyAContent).show())}
-                                        Content::All
-                                    },
-                                    "-n" => {
-                                        match name {
-                                            Some(n) => Content::Name(n),
-                                            None => return Err(Error::NeedArgument(NeedArgument::NetworkName).show())
-                                        }
-                                    },
-                                    "-u" => {
-                                        match name {
-                                            Some(a) => Content::Address(a),
-                                            None => return Err(Error::NeedArgument(NeedArgument::NetworkUrl).show())
-                                        }
-                                    },
-                                    _ => unreachable!(),
-                                }
+                    Some(VerKey::Secret(_)) => (),
+                    _ => {
+                        if let Some(_) = password_found {return Err(Error::Unexpected(Unexpected::PasswordNoKeyFile).show())}
+                    },
+                }
+                let password = password_found.unwrap_or(PasswordSource::None);
+                let crypto = match (x.crypto, sufficient_crypto_found) {
+                    (_, Some(s)) => process_sufficient_crypto(s, &config.for_signing)?,
+                    (Some(c), None) => {
+                        match c.as_str() {
+                            "ed25519" => Crypto::Ed25519(process_verifier_and_signature (verifier_found, signature_found, password, &config.for_signing)?),
+                            "sr25519" => Crypto::Sr25519(process_verifier_and_signature (verifier_found, signature_found, password, &config.for_signing)?),
+                            "ecdsa" => Crypto::Ecdsa(process_verifier_and_signature (verifier_found, signature_found, password, &config.for_signing)?),
+                            "none" => {
+                                if let Some(_) = verifier_found {return Err(Error::Unexpected(Unexpected::VerifierNoCrypto).show())}
+                                if let Some(_) = signature_found {return Err(Error::Unexpected(Unexpected::SignatureNoCrypto).show())}
+                                Crypto::None
                             },
-                            None => {return Err(Error::NeedKey(NeedKey::Content).show())}
-                        };
-                        
-                        let instruction = Instruction {
-                            set,
-                            content,
-                            pass_errors,
-                            encryption_override,
-                        };
-                        
-                        match arg.as_str() {
-                            "load_metadata" => Ok(Command::Load(instruction)),
-                            "add_network" => Ok(Command::Add(instruction)),
-                            "add_specs" => Ok(Command::Specs(instruction)),
                             _ => unreachable!(),
                         }
                     },
-                    "make" => {
-                        let mut goal = Goal::Both; // default option for `make`
-                        let mut args = args.peekable();
-                        match args.peek() {
-                            Some(x) => {
-                                match x.as_str() {
-                                    "-qr" => {
-                                        goal = Goal::Qr;
-                                        args.next();
-                                    },
-                                    "-text" => {
-                                        goal = Goal::Text;
-                                        args.next();
-                                    },
-                                    _ => (),
-                                }
-                            },
-                            None => {return Err(Error::NeedArgument(NeedArgument::Make).show())}
-                        }
-                        let mut crypto_type_found = None;
-                        let mut msg_type_found = None;
-                        let mut verifier_found = None;
-                        let mut payload_found = None;
-                        let mut signature_found = None;
-                        let mut name = None; // default option for `make`
-                        loop {
-                            match args.next() {
-                                Some(x) => {
-                                    match x.as_str() {
-                                        "-crypto" => {
-                                            if let Some(_) = crypto_type_found {return Err(Error::DoubleKey(DoubleKey::CryptoKey).show())}
-                                            crypto_type_found = match args.next() {
-                                                Some(x) => {
-                                                    match x.as_str() {
-                                                        "ed25519" => Some(CryptoType::Ed25519),
-                                                        "sr25519" => Some(CryptoType::Sr25519),
-                                                        "ecdsa" => Some(CryptoType::Ecdsa),
-                                                        "none" => Some(CryptoType::None),
-                                                        _ => {return Err(Error::BadArgument(BadArgument::CryptoKey).show())}
-                                                    }
-                                                },
-                                                None => {return Err(Error::NeedArgument(NeedArgument::CryptoKey).show())},
-                                            };
-                                        },
-                                        "-msgtype" => {
-                                            if let Some(_) = msg_type_found {return Err(Error::DoubleKey(DoubleKey::MsgType).show())}
-                                            msg_type_found = match args.next() {
-                                                Some(x) => {
-                                                    match x.as_str() {
-                                                        "load_types" => Some(MsgType::LoadTypes),
-                                                        "load_metadata" => Some(MsgType::LoadMetadata),
-                                                        "add_network" => Some(MsgType::AddNetwork),
-                                                        "add_specs" => Some(MsgType::AddSpecs),
-                                                        _ => {return Err(Error::BadArgument(BadArgument::MsgType).show())}
-                                                    }
-                                                },
-                                                None => {return Err(Error::NeedArgument(NeedArgument::MsgType).show())},
-                                            };
-                                        },
-                                        "-verifier" => {
-                                            if let Some(_) = verifier_found {return Err(Error::DoubleKey(DoubleKey::Verifier).show())}
-                                            verifier_found = match args.next() {
-                                                Some(x) => {
-                                                    match x.as_str() {
-                                                        "-hex" => {
-                                                            match args.next() {
-                                                                Some(h) => Some(VerKey::Hex(h.to_string())),
-                                                                None => {return Err(Error::NeedArgument(NeedArgument::VerifierHex).show())},
-                                                            }
-                                                        },
-                                                        "-file" => {
-                                                            match args.next() {
-                                                                Some(f) => Some(VerKey::File(f.to_string())),
-                                                                None => {return Err(Error::NeedArgument(NeedArgument::VerifierFile).show())},
-                                                            }
-                                                        },
-                                                        "Alice" => Some(VerKey::Alice),
-                                                        _ => {return Err(Error::BadArgument(BadArgument::Verifier).show())}
-                                                    }
-                                                },
-                                                None => {return Err(Error::NeedArgument(NeedArgument::Verifier).show())},
-                                            };
-                                        },
-                                        "-payload" => {
-                                            if let Some(_) = payload_found {return Err(Error::DoubleKey(DoubleKey::Payload).show())}
-                                            payload_found = match args.next() {
-                                                Some(x) => Some(x.to_string()),
-                                                None => {return Err(Error::NeedArgument(NeedArgument::Payload).show())},
-                                            };
-                                        },
-                                        "-signature" => {
-                                            if let Some(_) = signature_found {return Err(Error::DoubleKey(DoubleKey::Signature).show())}
-                                            signature_found = match args.next() {
-                                                Some(x) => {
-                                                    match x.as_str() {
-                                                        "-hex" => {
-                                                            match args.next() {
-                                                                Some(h) => Some(Entry::Hex(h.to_string())),
-                                                                None => {return Err(Error::NeedArgument(NeedArgument::SignatureHex).show())},
-                                                            }
-                                                        },
-                                                        "-file" => {
-                                                            match args.next() {
-                                                                Some(f) => Some(Entry::File(f.to_string())),
-                                                                None => {return Err(Error::NeedArgument(NeedArgument::SignatureFile).show())},
-                                                            }
-                                                        },
-                                                        _ => {return Err(Error::BadArgument(BadArgument::Signature).show())}
-                                                    }
-                                                },
-                                                None => {return Err(Error::NeedArgument(NeedArgument::Signature).show())},
-                                            }
-                                        },
-                                        "-name" => {
-                                            if let Some(_) = name {return Err(Error::DoubleKey(DoubleKey::Name).show())}
-                                            name = match args.next() {
-                                                Some(x) => Some(x.to_string()),
-                                                None => {return Err(Error::NeedArgument(NeedArgument::Name).show())},
-                                            };
-                                        },
-                                        _ => {return Err(Error::UnexpectedKeyArgumentSequence.show())},
-                                    }
-                                },
-                                None => break,
-                            }
-                        }
-                    // finalize what was parsed for `make`
-                        let crypto = match crypto_type_found {
-                            Some(x) => {
-                                match x {
-                                    CryptoType::Ed25519 => Crypto::Ed25519(process_verifier_and_signature (verifier_found, signature_found)?),
-                                    CryptoType::Sr25519 => Crypto::Sr25519(process_verifier_and_signature (verifier_found, signature_found)?),
-                                    CryptoType::Ecdsa => Crypto::Ecdsa(process_verifier_and_signature (verifier_found, signature_found)?),
-                                    CryptoType::None => {
-                                        if let Some(_) = verifier_found {return Err(Error::Unexpected(Unexpected::VerifierNoCrypto).show())}
-                                        if let Some(_) = signature_found {return Err(Error::Unexpected(Unexpected::SignatureNoCrypto).show())}
-                                        Crypto::None
-                                    },
-                                }
-                            },
-                            None => {return Err(Error::NeedKey(NeedKey::Crypto).show())},
-                        };
-                        let payload = match payload_found {
-                            Some(x) => {
-                                let filename = format!("{}/{}", FOLDER, x);
-                                match std::fs::read(&filename) {
-                                    Ok(a) => a,
-                                    Err(e) => {return Err(Error::InputOutputError(e.to_string()).show())} 
-                                }
-                            },
-                            None => {return Err(Error::NeedKey(NeedKey::Payload).show())},
-                        };
-                        let msg = match msg_type_found {
-                            Some(x) => {
-                                match x {
-                                    MsgType::LoadTypes => Msg::LoadTypes(payload),
-                                    MsgType::LoadMetadata => Msg::LoadMetadata(payload),
-                                    MsgType::AddNetwork => Msg::AddNetwork(payload),
-                                    MsgType::AddSpecs => Msg::AddSpecs(payload),
-                                }
-                            },
-                            None => {return Err(Error::NeedKey(NeedKey::MsgType).show())},
-                        };
-                        let make = Make {
-                            goal,
-                            crypto,
-                            msg,
-                            name,
+                    (None, None) => {return Err(Error::NeedKey(NeedKey::Crypto).show())},
+                };
+                let msg = msg(&x.msgtype, read_file(&config.for_signing, &x.payload)?);
+                Ok(Command::Make(Make{goal, crypto, msg, name: x.name}))
+            },
+            CliCommand::Sign(x) => {
+                let goal = goal(x.qr, x.text);
+                let crypto = match sufficient_entry(x.sufficient_hex, x.sufficient_file, x.sufficient_qr) {
+                    Some(s) => process_sufficient_crypto(s, &config.for_signing)?,
+                    None => {return Err(Error::NeedKey(NeedKey::SufficientCrypto).show())},
+                };
+                let msg = msg(&x.msgtype, read_file(&config.for_signing, &x.payload)?);
+                Ok(Command::Make(Make{goal, crypto, msg, name: x.name}))
+            },
+            CliCommand::Remove(x) => {
+                match (x.title, x.name, x.version) {
+                    (Some(title), _, _) => Ok(Command::Remove(Remove::Title(title))),
+                    (None, Some(name), Some(version)) => Ok(Command::Remove(Remove::SpecNameVersion{name, version})),
+                    (None, Some(_), None) => {return Err(Error::NeedKey(NeedKey::RemoveVersion).show())},
+                    (None, None, _) => {return Err(Error::NeedKey(NeedKey::Remove).show())},
+                }
+            },
+            CliCommand::Prune(x) => {
+                let msg = msg(&x.msgtype, read_file(&config.for_signing, &x.payload)?);
+                Ok(Command::Prune(Prune{msg, payload: x.payload}))
+            },
+            CliCommand::Compress(x) => {
+                let msg = msg(&x.msgtype, read_file(&config.for_signing, &x.payload)?);
+                Ok(Command::Compress(Compress{msg, payload: x.payload}))
+            },
+            CliCommand::Diff(x) => Ok(Command::Diff(Diff{name: x.name, from: x.from, to: x.to})),
+            CliCommand::NetworkFromFile(x) => {
+                let parachain_info = parachain_info(x.relay, x.para)?;
+                if let Some(_) = x.chainspec {
+                    if x.decimals.is_some()||x.unit.is_some()||x.base58.is_some() {return Err(Error::Unexpected(Unexpected::PropertiesWithChainSpec).show())}
+                }
+                else {
+                    if let None = x.decimals {return Err(Error::NeedKey(NeedKey::Decimals).show())}
+                    if let None = x.unit {return Err(Error::NeedKey(NeedKey::Unit).show())}
+                }
+                let network_from_file = NetworkFromFile {
+                    meta_file: x.meta,
+                    genesis_hash: genesis_hash(&x.genesis, BadArgument::GenesisHash)?,
+                    encryption: encryption(x.ed25519, x.sr25519, x.ecdsa).expect("encryption key is required"),
+                    decimals: x.decimals,
+                    unit: x.unit,
+                    base58prefix: x.base58,
+                    chain_spec_file: x.chainspec,
+                    parachain_info,
+                };
+                Ok(Command::NetworkFromFile(network_from_file))
+            },
+            CliCommand::LoadFromFile(x) => {
+                let load_from_file = LoadFromFile {
+                    meta_file: x.meta,
+                    genesis_hash: genesis_hash(&x.genesis, BadArgument::GenesisHash)?,
+                };
+                Ok(Command::LoadFromFile(load_from_file))
+            },
+            CliCommand::LoadAssets(x) => {
+                let mut assets: Vec<AssetInfo> = Vec::new();
+                for line in x.asset.iter() {
+                    let new = asset(line)?;
+                    if assets.iter().any(|a| a.asset_id == new.asset_id) {return Err(Error::BadArgument(BadArgument::Asset).show())}
+                    assets.push(new);
+                }
+                let load_assets = LoadAssets {
+                    genesis_hash: genesis_hash(&x.genesis, BadArgument::GenesisHash)?,
+                    assets,
+                };
+                Ok(Command::Assets(load_assets))
+            },
+            CliCommand::Inspect(x) => Ok(Command::Inspect(x.payload)),
+            CliCommand::AddressBook(x) => {
+                let address_book = match x {
+                    AddressBookArgs::List => AddressBook::List,
+                    AddressBookArgs::Add(a) => {
+                        let encryption = match encryption(a.ed25519, a.sr25519, a.ecdsa).or(config.default_encryption) {
+                            Some(e) => e,
+                            None => {return Err(Error::NeedKey(NeedKey::Encryption).show())},
                         };
-                        Ok(Command::Make(make))
+                        AddressBook::Add{addresses: endpoints(a.urls, config), encryption, parachain_info: parachain_info(a.relay, a.para)?}
                     },
-                    "sign" => {
-                        let mut goal = Goal::Both; // default option for `sign`
-                        let mut args = args.peekable();
-                        match args.peek() {
-                            Some(x) => {
-                                match x.as_str() {
-                                    "-qr" => {
-                                        goal = Goal::Qr;
-                                        args.next();
-                                    },
-                                    "-text" => {
-                                        goal = Goal::Text;
-                                        args.next();
-                                    },
-                                    _ => (),
-                                }
-                            },
-                            None => {return Err(Error::NeedArgument(NeedArgument::Sign).show())}
-                        }
-                        let mut sufficient_crypto_found = None;
-                        let mut msg_type_found = None;
-                        let mut payload_found = None;
-                        let mut name = None; // default option for `sign`
-                        loop {
-                            match args.next() {
-                                Some(x) => {
-                                    match x.as_str() {
-                                        "-sufficient" => {
-                                            if let Some(_) = sufficient_crypto_found {return Err(Error::DoubleKey(DoubleKey::SufficientCrypto).show())}
-                                            sufficient_crypto_found = match args.next() {
-                                                Some(x) => {
-                                                    match x.as_str() {
-                                                        "-hex" => {
-                                                            match args.next() {
-                                                                Some(h) => Some(Entry::Hex(h.to_string())),
-                                                                None => {return Err(Error::NeedArgument(NeedArgument::SufficientCryptoHex).show())},
-                                                            }
-                                                        },
-                                                        "-file" => {
-                                                            match args.next() {
-                                                                Some(f) => Some(Entry::File(f.to_string())),
-                                                                None => {return Err(Error::NeedArgument(NeedArgument::SufficientCryptoFile).show())},
-                                                            }
-                                                        },
-                                                        _ => {return Err(Error::BadArgument(BadArgument::SufficientCrypto).show())}
-                                                    }
-                                                },
-                                                None => {return Err(Error::NeedArgument(NeedArgument::SufficientCrypto).show())},
-                                            };
-                                        },
-                                        "-msgtype" => {
-                                            if let Some(_) = msg_type_found {return Err(Error::DoubleKey(DoubleKey::MsgType).show())}
-                                            msg_type_found = match args.next() {
-                                                Some(x) => {
-                                                    match x.as_str() {
-                                                        "load_types" => Some(MsgType::LoadTypes),
-                                                        "load_metadata" => Some(MsgType::LoadMetadata),
-                                                        "add_network" => Some(MsgType::AddNetwork),
-                                                        "add_specs" => Some(MsgType::AddSpecs),
-                                                        _ => {return Err(Error::BadArgument(BadArgument::MsgType).show())}
-                                                    }
-                                                },
-                                                None => {return Err(Error::NeedArgument(NeedArgument::MsgType).show())},
-                                            };
-                                        },
-                                        "-payload" => {
-                                            if let Some(_) = payload_found {return Err(Error::DoubleKey(DoubleKey::Payload).show())}
-                                            payload_found = match args.next() {
-                                                Some(x) => Some(x.to_string()),
-                                                None => {return Err(Error::NeedArgument(NeedArgument::Payload).show())},
-                                            };
-                                        },
-                                        "-name" => {
-                                            if let Some(_) = name {return Err(Error::DoubleKey(DoubleKey::Name).show())}
-                                            name = match args.next() {
-                                                Some(x) => Some(x.to_string()),
-                                                None => {return Err(Error::NeedArgument(NeedArgument::Name).show())},
-                                            };
-                                        },
-                                        _ => {return Err(Error::UnexpectedKeyArgumentSequence.show())},
-                                    }
-                                },
-                                None => break,
-                            }
-                        }
-                    // finalize command parsed with "sign"
-                        let crypto = match sufficient_crypto_found {
-                            Some(x) => {
-                                let sufficient_crypto_vector = match x {
-                                    Entry::Hex(h) => unhex(&h, NotHex::SufficientCrypto)?,
-                                    Entry::File(f) => {
-                                        let filename = format!("{}/{}", FOLDER, f);
-                                        match std::fs::read(&filename) {
-                                            Ok(a) => a,
-                                            Err(e) => {return Err(Error::InputOutputError(e.to_string()).show())},
-                                        }
-                                    },
-                                };
-                                let sufficient_crypto = match <SufficientCrypto>::decode(&mut &sufficient_crypto_vector[..]) {
-                                    Ok(a) => a,
-                                    Err(_) => {return Err(Error::NotDecodeable(NotDecodeable::SufficientCrypto).show())},
-                                };
-                                match sufficient_crypto {
-                                    SufficientCrypto::Ed25519 {public_key, signature} => {
-                                        Crypto::Ed25519(VerifierKind::Normal {verifier_public_key: public_key.to_vec(), signature: signature.to_vec()})
-                                    },
-                                    SufficientCrypto::Sr25519 {public_key, signature} => {
-                                        Crypto::Sr25519(VerifierKind::Normal {verifier_public_key: public_key.to_vec(), signature: signature.to_vec()})
-                                    },
-                                    SufficientCrypto::Ecdsa {public_key, signature} => {
-                                        Crypto::Ecdsa(VerifierKind::Normal {verifier_public_key: public_key.to_vec(), signature: signature.to_vec()})
-                                    },
-                                }
-                            },
-                            None => {return Err(Error::NeedKey(NeedKey::SufficientCrypto).show())},
-                        };
-                        let payload = match payload_found {
-                            Some(x) => {
-                                let filename = format!("{}/{}", FOLDER, x);
-                                match std::fs::read(&filename) {
-                                    Ok(a) => a,
-                                    Err(e) => {return Err(Error::InputOutputError(e.to_string()).show())},
-                                }
-                            },
-                            None => {return Err(Error::NeedKey(NeedKey::Payload).show())},
-                        };
-                        let msg = match msg_type_found {
-                            Some(x) => {
-                                match x {
-                                    MsgType::LoadTypes => Msg::LoadTypes(payload),
-                                    MsgType::LoadMetadata => Msg::LoadMetadata(payload),
-                                    MsgType::AddNetwork => Msg::AddNetwork(payload),
-                                    MsgType::AddSpecs => Msg::AddSpecs(payload),
-                                }
-                            },
-                            None => {return Err(Error::NeedKey(NeedKey::MsgType).show())},
-                        };
-                        let make = Make {
-                            goal,
-                            crypto,
-                            msg,
-                            name,
-                        };
-                        Ok(Command::Make(make))
-                    },                
-                    "remove" => {
-                        let mut info_found = None;
-                        loop {
-                            match args.next() {
-                                Some(a) => {
-                                    match a.as_str() {
-                                        "-title" => {
-                                            if let Some(_) = info_found {return Err(Error::DoubleKey(DoubleKey::Remove).show())}
-                                            info_found = match args.next() {
-                                                Some(b) => Some(Remove::Title(b.to_string())),
-                                                None => {return Err(Error::NeedArgument(NeedArgument::RemoveTitle).show())},
-                                            };
-                                        },
-                                        "-name" => {
-                                            if let Some(_) = info_found {return Err(Error::DoubleKey(DoubleKey::Remove).show())}
-                                            info_found = match args.next() {
-                                                Some(b) => {
-                                                    let name = b.to_string();
-                                                    match args.next() {
-                                                        Some(c) => {
-                                                            match c.as_str() {
-                                                                "-version" => {
-                                                                    match args.next() {
-                                                                        Some(d) => {
-                                                                            match d.parse::<u32> () {
-                                                                                Ok(version) => Some(Remove::SpecNameVersion{name, version}),
-                                                                                Err(_) => {return Err(Error::Unexpected(Unexpected::VersionFormat).show())},
-                                                                            }
-                                                                        },
-                                                                        None => {return Err(Error::NeedArgument(NeedArgument::RemoveVersion).show())}
-                                                                    }
-                                                                }
-                                                                _ => {return Err(Error::UnexpectedKeyArgumentSequence.show())},
-                                                            }
-                                                        },
-                                                        None => {return Err(Error::NeedKey(NeedKey::RemoveVersion).show())},
-                                                    }
-                                                },
-                                                None => {return Err(Error::NeedArgument(NeedArgument::RemoveName).show())},
-                                            };
-                                        },
-                                        _ => {return Err(Error::UnexpectedKeyArgumentSequence.show())},
-                                    }
-                                },
-                                None => break,
-                            }
-                        }
-                        match info_found {
-                            Some(x) => Ok(Command::Remove(x)),
-                            None => {return Err(Error::NeedKey(NeedKey::Remove).show())}
-                        }                        
+                    AddressBookArgs::Rename{title, new_title} => AddressBook::Rename{title, new_title},
+                    AddressBookArgs::SetUrl{title, urls} => AddressBook::SetUrl{title, addresses: endpoints(urls, config)},
+                    AddressBookArgs::SetDefault{title} => AddressBook::SetDefault(title),
+                    AddressBookArgs::Remove{title} => AddressBook::Remove(title),
+                };
+                Ok(Command::AddressBook(address_book))
+            },
+            CliCommand::RestoreDefaults => Ok(Command::RestoreDefaults),
+            CliCommand::Completions{shell} => Ok(Command::Completions(shell)),
+        }
+    }
+}
+
+impl SetArgs {
+    /// Function to replace the endpoint name after `-u` key with the url
+    /// of this endpoint from workspace config
+    fn with_endpoints(mut self, config: &Config) -> Self {
+        if self.url {
+            if let Some(url) = self.network.as_ref().and_then(|a| config.endpoints.get(a)) {self.network = Some(url.to_string())}
+        }
+        self
+    }
+
+    /// Function to make instruction for `load_metadata`, `add_network` and `add_specs`,
+    /// with encryption to use if no encryption key was set
+    fn instruction(self, default_encryption: Option<Encryption>) -> anyhow::Result<Instruction> {
+        let set = {
+            if self.d {Set::D}
+            else if self.f {Set::F}
+            else if self.k {Set::K}
+            else if self.p {Set::P}
+            else {Set::T}
+        };
+        let content = {
+            if self.all {
+                if let Some(_) = self.network {return Err(Error::Unexpected(Unexpected::KeyAContent).show())}
+                Content::All
+            }
+            else if self.name {
+                match self.network {
+                    Some(n) => Content::Name(n),
+                    None => return Err(Error::NeedArgument(NeedArgument::NetworkName).show()),
+                }
+            }
+            else {
+                match self.network {
+                    Some(a) => Content::Address(a),
+                    None => return Err(Error::NeedArgument(NeedArgument::NetworkUrl).show()),
+                }
+            }
+        };
+        let encryption_override = match encryption(self.ed25519, self.sr25519, self.ecdsa) {
+            Some(a) => Some(a),
+            None => default_encryption,
+        };
+        Ok(Instruction {
+            set,
+            content,
+            pass_errors: !self.stop,
+            encryption_override,
+            parachain_info: parachain_info(self.relay, self.para)?,
+        })
+    }
+}
+
+/// Function to translate the command line arguments in earlier form into current one,
+/// so that existing scripts keep working:
+/// multi-letter keys with single dash get double dash (`-crypto` to `--crypto`,
+/// `-address_book` to `--address-book`), and nested keys are joined
+/// (`-verifier -hex` to `--verifier-hex`, `-verifier Alice` to `--verifier-alice`)
+pub fn legacy_args <I: Iterator<Item = String>> (args: I) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-verifier"|"-signature"|"-sufficient" => {
+                let nested = match args.peek().map(|a| a.as_str()) {
+                    Some("-hex")|Some("-file")|Some("-qr")|Some("-keystore")|Some("-secret") => args.next().map(|a| a[1..].to_string()),
+                    Some("Alice") => {
+                        args.next();
+                        Some(String::from("alice"))
                     },
-                    "restore_defaults" => Ok(Command::RestoreDefaults),
-                    _ => return Err(Error::UnknownCommand.show()),
+                    _ => None,
+                };
+                match nested {
+                    Some(a) => out.push(format!("-{}-{}", arg, a)),
+                    None => out.push(format!("-{}", arg)),
                 }
             },
-            None => return Err(Error::NoCommand.show()),
+            _ => {
+                if LEGACY_KEYS.contains(&arg.as_str()) {out.push(format!("-{}", arg.replace("_", "-")))}
+                else {out.push(arg)}
+            },
         }
     }
+    out
+}
+
+/// Function to replace endpoint names with urls of these endpoints from workspace config
+fn endpoints (urls: Vec<String>, config: &Config) -> Vec<String> {
+    urls.into_iter().map(|a| config.endpoints.get(&a).map(|b| b.to_string()).unwrap_or(a)).collect()
+}
+
+fn goal (qr: bool, text: bool) -> Goal {
+    if qr {Goal::Qr}
+    else if text {Goal::Text}
+    else {Goal::Both}
+}
+
+fn encryption (ed25519: bool, sr25519: bool, ecdsa: bool) -> Option<Encryption> {
+    if ed25519 {Some(Encryption::Ed25519)}
+    else if sr25519 {Some(Encryption::Sr25519)}
+    else if ecdsa {Some(Encryption::Ecdsa)}
+    else {None}
+}
+
+fn msg (msgtype: &str, payload: Vec<u8>) -> Msg {
+    match msgtype {
+        "load_types" => Msg::LoadTypes(payload),
+        "load_metadata" => Msg::LoadMetadata(payload),
+        "load_metadata_compressed" => Msg::LoadMetadataCompressed(payload),
+        "add_network" => Msg::AddNetwork(payload),
+        "add_network_compressed" => Msg::AddNetworkCompressed(payload),
+        "add_specs" => Msg::AddSpecs(payload),
+        "load_assets" => Msg::LoadAssets(payload),
+        _ => unreachable!(),
+    }
+}
+
+fn sufficient_entry (hex: Option<String>, file: Option<String>, qr: Option<String>) -> Option<SufficientEntry> {
+    if let Some(h) = hex {Some(SufficientEntry::Hex(h))}
+    else if let Some(f) = file {Some(SufficientEntry::File(f))}
+    else if let Some(f) = qr {Some(SufficientEntry::Qr(f))}
+    else {None}
+}
+
+fn parachain_info (relay: Option<String>, para: Option<u32>) -> anyhow::Result<Option<ParachainInfo>> {
+    match (relay, para) {
+        (Some(relay), Some(parachain_id)) => Ok(Some(ParachainInfo{relay_genesis_hash: genesis_hash(&relay, BadArgument::Relay)?, parachain_id})),
+        (Some(_), None) => {return Err(Error::NeedKey(NeedKey::ParachainId).show())},
+        (None, Some(_)) => {return Err(Error::NeedKey(NeedKey::Relay).show())},
+        (None, None) => Ok(None),
+    }
 }
 
+fn genesis_hash (hex_line: &str, bad_argument: BadArgument) -> anyhow::Result<[u8; 32]> {
+    match unhex(hex_line, NotHex::GenesisHash)?.try_into() {
+        Ok(a) => Ok(a),
+        Err(_) => {return Err(Error::BadArgument(bad_argument).show())},
+    }
+}
+
+/// Function to get asset from `asset_id:symbol:decimals` line
+fn asset (line: &str) -> anyhow::Result<AssetInfo> {
+    let parts: Vec<&str> = line.split(':').collect();
+    if parts.len() != 3 {return Err(Error::BadArgument(BadArgument::Asset).show())}
+    let asset_id = match parts[0].parse::<u32>() {
+        Ok(a) => a,
+        Err(_) => {return Err(Error::BadArgument(BadArgument::Asset).show())},
+    };
+    if parts[1].is_empty() {return Err(Error::BadArgument(BadArgument::Asset).show())}
+    let decimals = match parts[2].parse::<u8>() {
+        Ok(a) => a,
+        Err(_) => {return Err(Error::BadArgument(BadArgument::Asset).show())},
+    };
+    Ok(AssetInfo{asset_id, symbol: parts[1].to_string(), decimals})
+}
+
+/// Function to read file from the folder
+fn read_file (folder: &str, filename: &str) -> anyhow::Result<Vec<u8>> {
+    match std::fs::read(&format!("{}/{}", folder, filename)) {
+        Ok(a) => Ok(a),
+        Err(e) => {return Err(Error::InputOutputError(e.to_string()).show())},
+    }
+}
+
+
+fn process_verifier_and_signature (verifier_found: Option<VerKey>, signature_found: Option<Entry>, password: PasswordSource, folder: &str) -> anyhow::Result<VerifierKind> {
 
-fn process_verifier_and_signature (verifier_found: Option<VerKey>, signature_found: Option<Entry>) -> anyhow::Result<VerifierKind> {
-    
     match verifier_found {
         Some(VerKey::Hex(x)) => {
             let verifier_public_key = unhex(&x, NotHex::PublicKey)?;
             let signature = match signature_found {
                 Some(Entry::Hex(t)) => unhex(&t, NotHex::Signature)?,
-                Some(Entry::File(t)) => {
-                    let signature_filename = format!("{}/{}", FOLDER, t);
-                    match std::fs::read(&signature_filename) {
-                        Ok(a) => a,
-                        Err(e) => {return Err(Error::InputOutputError(e.to_string()).show())},
-                    }
-                },
+                Some(Entry::File(t)) => read_file(folder, &t)?,
                 None => {return Err(Error::NeedKey(NeedKey::Signature).show())},
             };
             Ok(VerifierKind::Normal{verifier_public_key, signature})
         },
         Some(VerKey::File(x)) => {
-            let verifier_filename = format!("{}/{}", FOLDER, x);
-            let verifier_public_key = match std::fs::read(&verifier_filename) {
-                Ok(a) => a,
-                Err(e) => {return Err(Error::InputOutputError(e.to_string()).show())},
-            };
+            let verifier_public_key = read_file(folder, &x)?;
             let signature = match signature_found {
                 Some(Entry::Hex(t)) => unhex(&t, NotHex::Signature)?,
-                Some(Entry::File(t)) => {
-                    let signature_filename = format!("{}/{}", FOLDER, t);
-                    match std::fs::read(&signature_filename) {
-                        Ok(a) => a,
-                        Err(e) => {return Err(Error::InputOutputError(e.to_string()).show())},
-                    }
-                },
+                Some(Entry::File(t)) => read_file(folder, &t)?,
                 None => {return Err(Error::NeedKey(NeedKey::Signature).show())},
             };
             Ok(VerifierKind::Normal{verifier_public_key, signature})
         },
+        Some(VerKey::Keystore(path)) => {
+            if let Some(_) = signature_found {return Err(Error::Unexpected(Unexpected::KeyFileSignature).show())}
+            Ok(VerifierKind::KeyFile(KeyFile{kind: KeyFileKind::Keystore, path, password}))
+        },
+        Some(VerKey::Secret(path)) => {
+            if let Some(_) = signature_found {return Err(Error::Unexpected(Unexpected::KeyFileSignature).show())}
+            Ok(VerifierKind::KeyFile(KeyFile{kind: KeyFileKind::Secret, path, password}))
+        },
         Some(VerKey::Alice) => {
             if let Some(_) = signature_found {return Err(Error::Unexpected(Unexpected::AliceSignature).show())}
             Ok(VerifierKind::Alice)
         },
         None => {return Err(Error::NeedKey(NeedKey::Verifier).show())},
     }
-    
+
+}
+
+
+/// Function to get verifier public key and signature from SufficientCrypto;
+/// signature is checked against the assembled message later, in `make_message`
+fn process_sufficient_crypto (entry: SufficientEntry, folder: &str) -> anyhow::Result<Crypto> {
+    let sufficient_crypto_vector = match entry {
+        SufficientEntry::Hex(h) => unhex(&h, NotHex::SufficientCrypto)?,
+        SufficientEntry::File(f) => read_file(folder, &f)?,
+        SufficientEntry::Qr(f) => {
+            let filename = format!("{}/{}", folder, f);
+            match run_with_file(&filename) {
+                Ok(a) => unhex(&a, NotHex::SufficientCrypto)?,
+                Err(e) => {return Err(Error::QrRead(e.to_string()).show())},
+            }
+        },
+    };
+    let sufficient_crypto = match <SufficientCrypto>::decode(&mut &sufficient_crypto_vector[..]) {
+        Ok(a) => a,
+        Err(_) => {return Err(Error::NotDecodeable(NotDecodeable::SufficientCrypto).show())},
+    };
+    match sufficient_crypto {
+        SufficientCrypto::Ed25519 {public_key, signature} => {
+            Ok(Crypto::Ed25519(VerifierKind::Normal {verifier_public_key: public_key.to_vec(), signature: signature.to_vec()}))
+        },
+        SufficientCrypto::Sr25519 {public_key, signature} => {
+            Ok(Crypto::Sr25519(VerifierKind::Normal {verifier_public_key: public_key.to_vec(), signature: signature.to_vec()}))
+        },
+        SufficientCrypto::Ecdsa {public_key, signature} => {
+            Ok(Crypto::Ecdsa(VerifierKind::Normal {verifier_public_key: public_key.to_vec(), signature: signature.to_vec()}))
+        },
+    }
 }
@@ -0,0 +1,44 @@
+use definitions::qr_transfers::{ContentLoadMeta, ContentAddNetwork};
+use meta_reading::prune_metadata::{PrunedMetadata, prune_metadata};
+use anyhow;
+
+use crate::parser::{Prune, Msg};
+use crate::error::Error;
+
+/// Function to prune metadata in `load_metadata` or `add_network` payload ready for signing.
+/// Pruned payload is written next to the original one, with `_pruned` added to the file name,
+/// and can be used in `make` and `sign` same way as the original one.
+
+pub fn prune_payload (prune: Prune, folder: &str) -> anyhow::Result<()> {
+    let filename = format!("{}/{}_pruned", folder, prune.payload);
+    match prune.msg {
+        Msg::LoadMetadata(vec) => {
+            let (meta, genesis_hash) = match ContentLoadMeta::from_vec(&vec).meta_genhash() {
+                Ok(a) => a,
+                Err(_) => {return Err(Error::NotLoadMetadata.show())},
+            };
+            let pruned = prune_meta(&meta)?;
+            if let Err(e) = ContentLoadMeta::generate(&pruned.meta, &genesis_hash).write(&filename) {return Err(Error::InputOutputError(e).show())}
+            println!("{}", pruned.show());
+        },
+        Msg::AddNetwork(vec) => {
+            let (meta, network_specs) = match ContentAddNetwork::from_vec(&vec).meta_specs() {
+                Ok(a) => a,
+                Err(_) => {return Err(Error::NotAddNetwork.show())},
+            };
+            let pruned = prune_meta(&meta)?;
+            if let Err(e) = ContentAddNetwork::generate(&pruned.meta, &network_specs).write(&filename) {return Err(Error::InputOutputError(e).show())}
+            println!("{}", pruned.show());
+        },
+        _ => return Err(Error::NotSupported.show()),
+    }
+    Ok(())
+}
+
+/// Function to prune metadata with crate error
+fn prune_meta (meta: &Vec<u8>) -> anyhow::Result<PrunedMetadata> {
+    match prune_metadata(meta) {
+        Ok(a) => Ok(a),
+        Err(e) => return Err(Error::Pruning(e).show()),
+    }
+}
@@ -1,4 +1,4 @@
-use constants::{ADDRESS_BOOK, HOT_DB_NAME, METATREE, SPECSTREEPREP};
+use constants::{ADDRESS_BOOK, METATREE, SPECSTREEPREP};
 use sled::IVec;
 use anyhow;
 use db_handling::helpers::{open_db, open_tree};
@@ -11,9 +11,9 @@ use crate::helpers::specname_in_db;
 
 
 /// Function to remove information from the database.
-pub fn remove_info (info: Remove) -> anyhow::Result<()> {
+pub fn remove_info (info: Remove, database_name: &str) -> anyhow::Result<()> {
     
-    let database = open_db(HOT_DB_NAME)?;
+    let database = open_db(database_name)?;
     let metadata = open_tree(&database, METATREE)?;
     
     match info {
@@ -1,15 +1,16 @@
 use parity_scale_codec::Decode;
-use constants::{ADDRESS_BOOK, HOT_DB_NAME, METATREE};
+use constants::{ADDRESS_BOOK, METATREE};
 use definitions::metadata::AddressBookEntry;
+use meta_reading::decode_metadata::decode_metadata_v15;
 use db_handling::helpers::{open_db, open_tree};
 use anyhow;
 
 use crate::helpers::decode_and_check_meta_entry;
 use crate::error::{Error, NotDecodeable};
 
-pub fn show_database() -> anyhow::Result<()> {
+pub fn show_database(database_name: &str) -> anyhow::Result<()> {
     
-    let database = open_db(HOT_DB_NAME)?;
+    let database = open_db(database_name)?;
     let metadata = open_tree(&database, METATREE)?;
     if metadata.len() == 0 {return Err(Error::MetadataEmpty.show())}
     println!("Database has metadata information for following networks:");
@@ -17,15 +18,16 @@ pub fn show_database() -> anyhow::Result<()> {
         if let Ok(a) = x {
             let meta_values = decode_and_check_meta_entry(a)?;
             println!("\t{} {}", meta_values.name, meta_values.version);
+            if let Ok(metadata_v15) = decode_metadata_v15(&meta_values.meta) {println!("\t\tmetadata V15, {}", metadata_v15.show_extras())}
         }
     }
     Ok(())
 }
 
 
-pub fn show_address_book() -> anyhow::Result<()> {
+pub fn show_address_book(database_name: &str) -> anyhow::Result<()> {
     
-    let database = open_db(HOT_DB_NAME)?;
+    let database = open_db(database_name)?;
     let address_book = open_tree(&database, ADDRESS_BOOK)?;
     
     if address_book.len() == 0 {return Err(Error::AddressBookEmpty.show())}
@@ -40,8 +42,16 @@ pub fn show_address_book() -> anyhow::Result<()> {
                 Ok(a) => a,
                 Err(_) => return Err(Error::NotDecodeable(NotDecodeable::AddressBookKey).show()),
             };
-            if address_book_entry.def {println!("\t{} at {}, encryption {} (default)", title, address_book_entry.address, address_book_entry.encryption.show());}
-            else {println!("\t{} at {}, encryption {}", title, address_book_entry.address, address_book_entry.encryption.show());}
+            let parachain_line = match (address_book_entry.relay_genesis_hash, address_book_entry.parachain_id) {
+                (Some(relay_genesis_hash), Some(parachain_id)) => format!(", parachain {} of relay chain with genesis hash {}", parachain_id, hex::encode(relay_genesis_hash)),
+                _ => String::new(),
+            };
+            let fallback_line = match address_book_entry.fallback_addresses.len() {
+                0 => String::new(),
+                _ => format!(" (fallback {})", address_book_entry.fallback_addresses.join(", ")),
+            };
+            if address_book_entry.def {println!("\t{} at {}{}, encryption {}{} (default)", title, address_book_entry.address, fallback_line, address_book_entry.encryption.show(), parachain_line);}
+            else {println!("\t{} at {}{}, encryption {}{}", title, address_book_entry.address, fallback_line, address_book_entry.encryption.show(), parachain_line);}
         }
     }
     Ok(())
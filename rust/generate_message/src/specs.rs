@@ -1,26 +1,36 @@
-use constants::{ADDRESS_BOOK, HOT_DB_NAME, SPECSTREEPREP};
+use constants::{ADDRESS_BOOK, SPECSTREEPREP};
 use sled::{IVec, Tree};
 use parity_scale_codec::{Encode, Decode};
 use definitions::{crypto::Encryption, metadata::AddressBookEntry, network_specs::generate_network_key};
 use db_handling::helpers::{open_db, open_tree};
 use anyhow;
 
-use crate::parser::{Instruction, Content, Set};
+use crate::parser::{Instruction, Content, ParachainInfo, Set};
 use crate::metadata_shortcut::meta_specs_shortcut;
 use crate::output_prep::print_specs;
 use crate::error::{Error, NotDecodeable, NotFound};
-use crate::helpers::{decode_chain_specs_to_send, get_and_decode_chain_specs_to_send, get_from_tree, network_specs_from_address_book_entry_encoded, error_occured, filter_address_book_by_url, process_indices, update_db};
+use crate::helpers::{decode_chain_specs_to_send, endpoints_for_url, get_and_decode_chain_specs_to_send, get_from_tree, network_specs_from_address_book_entry_encoded, error_occured, filter_address_book_by_url, process_indices, update_db};
 
 
 /// Function to generate `add_specs` message ready for signing.
 /// Exact behavior is determined by the keys used.
+/// Hot database and the folder for output files are provided separately.
 
-pub fn gen_add_specs (instruction: Instruction) -> anyhow::Result<()> {
+pub fn gen_add_specs (instruction: Instruction, database_name: &str, folder: &str) -> anyhow::Result<()> {
     
-    let database = open_db(HOT_DB_NAME)?;
+    let database = open_db(database_name)?;
     let address_book = open_tree(&database, ADDRESS_BOOK)?;
     let chainspecs = open_tree(&database, SPECSTREEPREP)?;
     
+// relay chain and parachain id could be set only for networks processed through rpc calls
+    if let Some(_) = instruction.parachain_info {
+        match (&instruction.set, &instruction.content) {
+            (Set::F, _) => return Err(Error::NotSupported.show()),
+            (_, Content::Address(_)) => (),
+            _ => return Err(Error::NotSupported.show()),
+        }
+    }
+    
     match instruction.set {
         Set::F => {
             match instruction.content {
@@ -30,7 +40,7 @@ pub fn gen_add_specs (instruction: Instruction) -> anyhow::Result<()> {
                         if address_book.len() == 0 {return Err(Error::AddressBookEmpty.show())}
                         for x in address_book.iter() {
                             if let Ok((_, address_book_entry_encoded)) = x {
-                                match specs_f_a_element(address_book_entry_encoded, &chainspecs) {
+                                match specs_f_a_element(address_book_entry_encoded, &chainspecs, folder) {
                                     Ok(()) => (),
                                     Err(e) => error_occured(e, instruction.pass_errors)?,
                                 }
@@ -40,10 +50,10 @@ pub fn gen_add_specs (instruction: Instruction) -> anyhow::Result<()> {
                     Ok(())
                 },
                 Content::Name(name) => {
-                    specs_f_n(&name, &address_book, &chainspecs, instruction.encryption_override)
+                    specs_f_n(&name, &address_book, &chainspecs, instruction.encryption_override, folder)
                 },
                 Content::Address(address) => {
-                    specs_f_u(&address, &address_book, &chainspecs, instruction.encryption_override)
+                    specs_f_u(&address, &address_book, &chainspecs, instruction.encryption_override, folder)
                 },
             }
         },
@@ -52,7 +62,7 @@ pub fn gen_add_specs (instruction: Instruction) -> anyhow::Result<()> {
                 Content::All => return Err(Error::NotSupported.show()),
                 Content::Name(_) => return Err(Error::NotSupported.show()),
                 Content::Address(address) => {
-                    if let Some(encryption) = instruction.encryption_override {specs_d_u(&address, &address_book, &chainspecs, encryption)}
+                    if let Some(encryption) = instruction.encryption_override {specs_d_u(&address, &address_book, &chainspecs, encryption, &instruction.parachain_info, folder)}
                     else {return Err(Error::NotSupported.show())}
                 },
             }
@@ -62,11 +72,11 @@ pub fn gen_add_specs (instruction: Instruction) -> anyhow::Result<()> {
             match instruction.content {
                 Content::All => return Err(Error::NotSupported.show()),
                 Content::Name(name) => {
-                    if let Some(encryption) = instruction.encryption_override {specs_pt_n(&name, &address_book, &chainspecs, encryption, false)}
+                    if let Some(encryption) = instruction.encryption_override {specs_pt_n(&name, &address_book, &chainspecs, encryption, false, folder)}
                     else {return Err(Error::NotSupported.show())}
                 },
                 Content::Address(address) => {
-                    if let Some(encryption) = instruction.encryption_override {specs_pt_u(&address, &address_book, &chainspecs, encryption, false)}
+                    if let Some(encryption) = instruction.encryption_override {specs_pt_u(&address, &address_book, &chainspecs, encryption, false, &instruction.parachain_info, folder)}
                     else {return Err(Error::NotSupported.show())}
                 },
             }
@@ -75,11 +85,11 @@ pub fn gen_add_specs (instruction: Instruction) -> anyhow::Result<()> {
             match instruction.content {
                 Content::All => return Err(Error::NotSupported.show()),
                 Content::Name(name) => {
-                    if let Some(encryption) = instruction.encryption_override {specs_pt_n(&name, &address_book, &chainspecs, encryption, true)}
+                    if let Some(encryption) = instruction.encryption_override {specs_pt_n(&name, &address_book, &chainspecs, encryption, true, folder)}
                     else {return Err(Error::NotSupported.show())}
                 },
                 Content::Address(address) => {
-                    if let Some(encryption) = instruction.encryption_override {specs_pt_u(&address, &address_book, &chainspecs, encryption, true)}
+                    if let Some(encryption) = instruction.encryption_override {specs_pt_u(&address, &address_book, &chainspecs, encryption, true, &instruction.parachain_info, folder)}
                     else {return Err(Error::NotSupported.show())}
                 },
             }
@@ -90,9 +100,9 @@ pub fn gen_add_specs (instruction: Instruction) -> anyhow::Result<()> {
 /// Function to process individual address book entry in `add_specs -f -a` run.
 /// Expected behavior:  
 /// generate network key, by network key find network specs in `chainspecs` database tree, print into `sign_me` output file.  
-fn specs_f_a_element (address_book_entry_encoded: IVec, chainspecs: &Tree) -> anyhow::Result<()> {
+fn specs_f_a_element (address_book_entry_encoded: IVec, chainspecs: &Tree, folder: &str) -> anyhow::Result<()> {
     let network_specs = network_specs_from_address_book_entry_encoded (address_book_entry_encoded, chainspecs)?;
-    print_specs(&network_specs)
+    print_specs(&network_specs, folder)
 }
 
 /// Function to process `add_specs -f -n name` run.
@@ -102,7 +112,7 @@ fn specs_f_a_element (address_book_entry_encoded: IVec, chainspecs: &Tree) -> an
 /// Expected behavior:  
 /// get from `address_book` the entry corresponding to the name, generate network key,
 /// with it find network specs in `chainspecs` database tree, print into `sign_me` output file.  
-fn specs_f_n (name: &str, address_book: &Tree, chainspecs: &Tree, encryption_override: Option<Encryption>) -> anyhow::Result<()> {
+fn specs_f_n (name: &str, address_book: &Tree, chainspecs: &Tree, encryption_override: Option<Encryption>, folder: &str) -> anyhow::Result<()> {
     let mut network_specs = match get_from_tree (&name.encode(), address_book)? {
         Some(address_book_entry_encoded) => network_specs_from_address_book_entry_encoded (address_book_entry_encoded, chainspecs)?,
         None => return Err(Error::NotFound(NotFound::AddressBookKey(name.to_string())).show()),
@@ -111,9 +121,9 @@ fn specs_f_n (name: &str, address_book: &Tree, chainspecs: &Tree, encryption_ove
         Some(encryption) => {
             network_specs.encryption = encryption;
             network_specs.title = format!("{}-{}", network_specs.name, encryption.show());
-            print_specs(&network_specs)
+            print_specs(&network_specs, folder)
         }
-        None => print_specs(&network_specs)
+        None => print_specs(&network_specs, folder)
     }
 }
 
@@ -127,19 +137,19 @@ fn specs_f_n (name: &str, address_book: &Tree, chainspecs: &Tree, encryption_ove
 /// generate network key with old encryption, and with it find network specs in `chainspecs` database tree,
 /// generate modified network specs (if not in case (1)) set with encryption override,
 /// print into `sign_me` output file.
-fn specs_f_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption_override: Option<Encryption>) -> anyhow::Result<()> {
+fn specs_f_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption_override: Option<Encryption>, folder: &str) -> anyhow::Result<()> {
     let entries = filter_address_book_by_url(address, address_book)?;
     if entries.len() == 0 {return Err(Error::NotFound(NotFound::Url(address.to_string())).show())}
     match encryption_override {
         Some(encryption) => {
             let network_specs = process_indices(&entries, chainspecs, encryption)?.0;
-            print_specs(&network_specs)
+            print_specs(&network_specs, folder)
         },
         None => {
             for x in entries.iter() {
                 let network_key = generate_network_key(&x.genesis_hash.to_vec(), x.encryption);
                 let network_specs = get_and_decode_chain_specs_to_send(&chainspecs, &network_key)?;
-                print_specs(&network_specs)?;
+                print_specs(&network_specs, folder)?;
             }
             Ok(())
         }
@@ -150,10 +160,11 @@ fn specs_f_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption_o
 /// Expected behavior:  
 /// go through address book in the database and search for given address;
 /// if no entries found, do fetch (throw error if chainspecs turn up in the database), print `sign_me` file;
-/// if entries found, search for appropriate network specs to modify, and print `sign_me` file.
-fn specs_d_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption) -> anyhow::Result<()> {
-    let shortcut = meta_specs_shortcut (address, address_book, chainspecs, encryption)?;
-    print_specs(&shortcut.specs)
+/// if entries found, do fetch with the endpoints of the network in failover order,
+/// search for appropriate network specs to modify, and print `sign_me` file.
+fn specs_d_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption, parachain_info: &Option<ParachainInfo>, folder: &str) -> anyhow::Result<()> {
+    let shortcut = meta_specs_shortcut (&endpoints_for_url(address, address_book)?, address_book, chainspecs, encryption, parachain_info)?;
+    print_specs(&shortcut.specs, folder)
 }
 
 
@@ -171,7 +182,7 @@ fn specs_d_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption:
 /// (1) modify ChainSpecsToSent#1 (encryption and title fields) and insert in `chainspecs` tree with NetworkKey#2,
 /// (2) modify AddressBookEntry#1 (encryption and `def = false`) and insert in `address_book` tree with encoded `name-encryption` as a key  
 /// and print `sign_me` file according to the key;
-fn specs_pt_n(name: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption, printing: bool) -> anyhow::Result<()> {
+fn specs_pt_n(name: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption, printing: bool, folder: &str) -> anyhow::Result<()> {
     match get_from_tree (&name.encode(), address_book)? {
         Some(address_book_entry_encoded) => {
             let address_book_entry = match <AddressBookEntry>::decode(&mut &address_book_entry_encoded[..]) {
@@ -181,7 +192,7 @@ fn specs_pt_n(name: &str, address_book: &Tree, chainspecs: &Tree, encryption: En
             let network_key_existing = generate_network_key(&address_book_entry.genesis_hash.to_vec(), address_book_entry.encryption);
             let network_specs_existing = get_and_decode_chain_specs_to_send(&chainspecs, &network_key_existing)?;
             if address_book_entry.encryption == encryption {
-                if printing {print_specs(&network_specs_existing)}
+                if printing {print_specs(&network_specs_existing, folder)}
                 else {return Err(Error::SpecsInDb{name: name.to_string(), encryption}.show())}
             }
             else {
@@ -189,7 +200,7 @@ fn specs_pt_n(name: &str, address_book: &Tree, chainspecs: &Tree, encryption: En
                 match get_from_tree(&network_key_possible, chainspecs)? {
                     Some(a) => {
                         let network_specs_found = decode_chain_specs_to_send (a, &network_key_possible)?;
-                        if printing {print_specs(&network_specs_found)}
+                        if printing {print_specs(&network_specs_found, folder)}
                         else {return Err(Error::SpecsInDb{name: name.to_string(), encryption}.show())}
                     },
                     None => {
@@ -197,8 +208,8 @@ fn specs_pt_n(name: &str, address_book: &Tree, chainspecs: &Tree, encryption: En
                         let mut network_specs = network_specs_existing;
                         network_specs.encryption = encryption;
                         network_specs.title = format!("{}-{}", network_specs.name, encryption.show());
-                        update_db (&address_book_entry.address, &network_specs, chainspecs, address_book)?;
-                        if printing {print_specs(&network_specs)}
+                        update_db (&address_book_entry.addresses(), &network_specs, chainspecs, address_book)?;
+                        if printing {print_specs(&network_specs, folder)}
                         else {Ok(())}
                     },
                 }
@@ -212,11 +223,13 @@ fn specs_pt_n(name: &str, address_book: &Tree, chainspecs: &Tree, encryption: En
 /// Expected behavior:  
 /// get from address book set of entries corresponding to given url address;
 /// if no entries found, the network is new, and network specs are fetched;
-/// if there are entries, search for appropriate network specs to modify, print `sign_me` file according to the key and update the database.
-fn specs_pt_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption, printing: bool) -> anyhow::Result<()> {
-    let shortcut = meta_specs_shortcut (address, address_book, chainspecs, encryption)?;
-    if shortcut.update {update_db (address, &shortcut.specs, chainspecs, address_book)?}
-    if printing {print_specs(&shortcut.specs)?}
+/// if there are entries, network specs are fetched with the endpoints of the network in failover order,
+/// search for appropriate network specs to modify, print `sign_me` file according to the key and update the database.
+fn specs_pt_u(address: &str, address_book: &Tree, chainspecs: &Tree, encryption: Encryption, printing: bool, parachain_info: &Option<ParachainInfo>, folder: &str) -> anyhow::Result<()> {
+    let addresses = endpoints_for_url(address, address_book)?;
+    let shortcut = meta_specs_shortcut (&addresses, address_book, chainspecs, encryption, parachain_info)?;
+    if shortcut.update {update_db (&addresses, &shortcut.specs, chainspecs, address_book)?}
+    if printing {print_specs(&shortcut.specs, folder)?}
     Ok(())
 }
 
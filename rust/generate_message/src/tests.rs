@@ -0,0 +1,548 @@
+/// Tests for load_metadata and add_network runs with all setting keys,
+/// rpc calls are made to local stub server with recorded westend9070 results;
+/// messages generated from metadata and chain spec files are tested without rpc calls,
+/// load_assets message is generated, signed and inspected,
+/// signed payloads are inspected from Signer test files,
+/// command line in earlier and current form is interpreted with workspace config,
+/// sufficient crypto from Signer is accepted as hex and as qr file and checked against the message,
+/// compressed content is written for signing and signature for uncompressed content is refused,
+/// address book entries are edited and rpc endpoints are used in failover order,
+/// configured number of metadata versions is kept.
+/// Separated hot test databases and output folders are created during the tests,
+/// and removed after test is performed, so the test can run in parallel
+
+#[cfg(test)]
+mod tests {
+    use crate::add::{gen_add_network, gen_add_network_from_file};
+    use crate::address_book::address_book_run;
+    use crate::assets::gen_load_assets;
+    use crate::compress::compress_payload;
+    use crate::config::{Config, load_config};
+    use crate::full_run;
+    use crate::inspect::{inspect, inspect_payload};
+    use crate::keystore::{ed25519_pair, sr25519_pair};
+    use crate::load::{gen_load_meta, gen_load_meta_from_file};
+    use crate::make_message::make_message;
+    use crate::metadata_db_utils::{SortedMetaValues, add_new};
+    use crate::parser::{AddressBook, Cli, Command, Compress, Content, Crypto, Goal, Instruction, KeyFile, KeyFileKind, LoadAssets, LoadFromFile, Make, Msg, NetworkFromFile, PasswordSource, Set, VerifierKind, legacy_args};
+    use constants::{ADDRESS_BOOK, METATREE};
+    use db_handling::populate_hot;
+    use definitions::defaults::get_default_address_book;
+    use definitions::{crypto::{Encryption, SufficientCrypto}, metadata::{AddressBookEntry, AddressBookEntryNoFallback, AddressBookEntryNoParachain, MetaValues}, network_specs::AssetInfo};
+    use meta_reading::{fetch_metadata::MockSource, stub_server::start_stub_server};
+    use parity_scale_codec::{Decode, Encode};
+    use qrcode_rtx::transform_into_qr_apng;
+    use sp_core::Pair;
+    use std::fs;
+    use structopt::StructOpt;
+
+    const WESTEND_RECORDING: &str = "../meta_reading/for_tests/rpc_westend9070";
+    const WESTEND_META: &str = "../meta_reading/for_tests/westend9070";
+    const WESTEND_CHAIN_SPEC: &str = "../meta_reading/for_tests/chain_spec_westend.json";
+    const SIGNED_PAYLOADS: &str = "../transaction_parsing/for_tests";
+    const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+    const ALICE_ED25519: &str = "88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee";
+    const LOAD_FILE: &str = "sign_me_load_metadata_westendV9070";
+    const ADD_FILE: &str = "sign_me_add_network_westendV9070";
+    const DEAD_ENDPOINT: &str = "http://127.0.0.1:1";
+
+    /// Function to make hot test database with westend as the only address book entry,
+    /// with the address of the stub server, and empty output folder
+    fn prepare (dbname: &str, folder: &str) -> String {
+        let address = start_stub_server(MockSource::from_recording(WESTEND_RECORDING).unwrap()).unwrap().address;
+        populate_hot(dbname).unwrap();
+        let database = sled::open(dbname).unwrap();
+        let address_book = database.open_tree(ADDRESS_BOOK).unwrap();
+        let mut westend = None;
+        for x in address_book.iter() {
+            if let Ok((_, address_book_entry_encoded)) = x {
+                let address_book_entry = <AddressBookEntry>::decode(&mut &address_book_entry_encoded[..]).unwrap();
+                if address_book_entry.name == "westend" {westend = Some(address_book_entry)}
+            }
+        }
+        let mut westend = westend.unwrap();
+        westend.address = address.to_string();
+        address_book.clear().unwrap();
+        address_book.insert(westend.name.encode(), westend.encode()).unwrap();
+        database.flush().unwrap();
+        fs::create_dir_all(folder).unwrap();
+        address
+    }
+
+    fn instruction (set: Set, content: Content, encryption_override: Option<Encryption>) -> Instruction {
+        Instruction {
+            set,
+            content,
+            pass_errors: false,
+            encryption_override,
+            parachain_info: None,
+        }
+    }
+
+    fn metadata_entries (dbname: &str) -> usize {
+        let database = sled::open(dbname).unwrap();
+        let metadata = database.open_tree(METATREE).unwrap();
+        metadata.len()
+    }
+
+    /// Function to check if the output file is there, and to remove it
+    fn output_produced (folder: &str, filename: &str) -> bool {
+        let path = format!("{}/{}", folder, filename);
+        let out = fs::metadata(&path).is_ok();
+        if out {fs::remove_file(&path).unwrap()}
+        out
+    }
+
+    #[test]
+    fn load_metadata_all_keys() {
+        let dbname = "for_tests/load_metadata_all_keys";
+        let folder = "for_tests/load_metadata_all_keys_files";
+        let address = prepare(dbname, folder);
+
+        gen_load_meta(instruction(Set::D, Content::Address(address.to_string()), None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, LOAD_FILE), "-d -u key should produce output file.");
+        assert!(metadata_entries(dbname) == 0, "-d key should not update the database.");
+
+        gen_load_meta(instruction(Set::D, Content::Name(String::from("westend")), None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, LOAD_FILE), "-d -n key should produce output file.");
+
+        gen_load_meta(instruction(Set::P, Content::All, None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(!output_produced(folder, LOAD_FILE), "-p key should not produce output file.");
+        assert!(metadata_entries(dbname) == 1, "-p key should update the database.");
+
+        gen_load_meta(instruction(Set::K, Content::All, None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(!output_produced(folder, LOAD_FILE), "-k key should not produce output file for metadata already in the database.");
+
+        gen_load_meta(instruction(Set::T, Content::Name(String::from("westend")), None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, LOAD_FILE), "-t key should produce output file.");
+        assert!(metadata_entries(dbname) == 1, "Same metadata should not be added twice.");
+
+        gen_load_meta(instruction(Set::F, Content::All, None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, LOAD_FILE), "-f -a key should produce output file from the database.");
+
+        gen_load_meta(instruction(Set::F, Content::Name(String::from("westend")), None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, LOAD_FILE), "-f -n key should produce output file from the database.");
+
+        assert!(gen_load_meta(instruction(Set::F, Content::Address(address.to_string()), None), dbname, folder, &Config::default().metadata_versions).is_err(), "-f -u key is not supported.");
+
+        fs::remove_dir_all(dbname).unwrap();
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn add_network_all_keys() {
+        let dbname = "for_tests/add_network_all_keys";
+        let folder = "for_tests/add_network_all_keys_files";
+        let address = prepare(dbname, folder);
+
+        gen_add_network(instruction(Set::D, Content::Address(address.to_string()), Some(Encryption::Sr25519)), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, ADD_FILE), "-d -u key should produce output file.");
+        assert!(metadata_entries(dbname) == 0, "-d key should not update the database.");
+
+        gen_add_network(instruction(Set::P, Content::Address(address.to_string()), Some(Encryption::Ed25519)), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(!output_produced(folder, ADD_FILE), "-p key should not produce output file.");
+        assert!(metadata_entries(dbname) == 1, "-p key should update the database.");
+        {
+            let database = sled::open(dbname).unwrap();
+            let address_book = database.open_tree(ADDRESS_BOOK).unwrap();
+            assert!(address_book.len() == 2, "New address book entry expected for network with new encryption.");
+        }
+
+        gen_add_network(instruction(Set::K, Content::Address(address.to_string()), Some(Encryption::Sr25519)), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(!output_produced(folder, ADD_FILE), "-k key should not produce output file for metadata already in the database.");
+
+        gen_add_network(instruction(Set::T, Content::Address(address.to_string()), Some(Encryption::Sr25519)), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, ADD_FILE), "-t key should produce output file.");
+
+        gen_add_network(instruction(Set::F, Content::All, None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, ADD_FILE), "-f -a key should produce output file from the database.");
+
+        gen_add_network(instruction(Set::F, Content::Name(String::from("westend")), None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, ADD_FILE), "-f -n key should produce output file from the database.");
+
+        assert!(gen_add_network(instruction(Set::D, Content::Address(address.to_string()), None), dbname, folder, &Config::default().metadata_versions).is_err(), "-u key without encryption is not supported.");
+
+        fs::remove_dir_all(dbname).unwrap();
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn messages_from_files() {
+        let folder = "for_tests/messages_from_files_files";
+        fs::create_dir_all(folder).unwrap();
+        let genesis_hash = [0; 32];
+
+        gen_load_meta_from_file(LoadFromFile{meta_file: WESTEND_META.to_string(), genesis_hash}, folder).unwrap();
+        assert!(output_produced(folder, LOAD_FILE), "load_metadata_from_file should produce output file.");
+
+        let network = NetworkFromFile {
+            meta_file: WESTEND_META.to_string(),
+            genesis_hash,
+            encryption: Encryption::Sr25519,
+            decimals: None,
+            unit: None,
+            base58prefix: None,
+            chain_spec_file: Some(WESTEND_CHAIN_SPEC.to_string()),
+            parachain_info: None,
+        };
+        gen_add_network_from_file(network, folder).unwrap();
+        assert!(output_produced(folder, ADD_FILE), "add_network_from_file with chain spec should produce output file.");
+
+        let network = NetworkFromFile {
+            meta_file: WESTEND_META.to_string(),
+            genesis_hash,
+            encryption: Encryption::Sr25519,
+            decimals: None,
+            unit: None,
+            base58prefix: None,
+            chain_spec_file: None,
+            parachain_info: None,
+        };
+        assert!(gen_add_network_from_file(network, folder).is_err(), "add_network_from_file without chain spec needs decimals and unit.");
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    fn payload_from_file (filename: &str) -> Vec<u8> {
+        hex::decode(fs::read_to_string(format!("{}/{}", SIGNED_PAYLOADS, filename)).unwrap().trim()).unwrap()
+    }
+
+    #[test]
+    fn inspect_signed_payloads() {
+        let description = inspect_payload(&payload_from_file("network_metadata_westendV9070_Alice.txt")).unwrap();
+        assert!(description.starts_with("Message type: load_metadata\nNetwork: westend, version: 9070, metadata hash: e281fbc53168a6b87d1ea212923811f4c083e7be7d18df4b8527b9532e5f5fec"), "Unexpected description:\n{}", description);
+        assert!(description.ends_with("Verifier: public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519"), "Unexpected description:\n{}", description);
+
+        let description = inspect_payload(&payload_from_file("network_metadata_westendV9070_Alice_Bob.txt")).unwrap();
+        assert!(description.contains("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")&&description.contains("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"), "Both verifiers expected:\n{}", description);
+
+        let description = inspect_payload(&payload_from_file("add_network_westendV9090_unverified.txt")).unwrap();
+        assert!(description.starts_with("Message type: add_network\nNetwork: westend, version: 9090"), "Unexpected description:\n{}", description);
+        assert!(description.ends_with("Verifier: none"), "Unexpected description:\n{}", description);
+
+        let description = inspect_payload(&payload_from_file("types_info_None.txt")).unwrap();
+        assert!(description.starts_with("Message type: load_types"), "Unexpected description:\n{}", description);
+
+        let description = inspect_payload(&payload_from_file("load_assets_westend_Alice.txt")).unwrap();
+        assert!(description.starts_with("Message type: load_assets\nGenesis hash: e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e\nAssets: [{\"asset_id\":\"1984\",\"symbol\":\"USDt\",\"decimals\":\"6\"}]"), "Unexpected description:\n{}", description);
+
+        let mut damaged = payload_from_file("network_metadata_westendV9070_Alice.txt");
+        let last = damaged.len()-1;
+        damaged[last] ^= 1;
+        assert!(inspect_payload(&damaged).is_err(), "Bad signature should be reported.");
+
+        assert!(inspect_payload(&payload_from_file("set_verifiers_westend_Alice.txt")).is_err(), "Only make-produced message types are inspected.");
+
+        assert!(inspect("network_metadata_westendV9070_Alice.txt", SIGNED_PAYLOADS).is_ok(), "Hex text file should be inspected.");
+    }
+
+    #[test]
+    fn inspect_qr_file() {
+        let folder = "for_tests/inspect_qr_file";
+        fs::create_dir_all(folder).unwrap();
+        transform_into_qr_apng(&payload_from_file("types_info_Alice.txt"), &format!("{}/types_info_Alice.png", folder)).unwrap();
+        assert!(inspect("types_info_Alice.png", folder).is_ok(), "Apng qr file should be inspected.");
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    fn key_file (kind: KeyFileKind, path: &str, password: PasswordSource) -> KeyFile {
+        KeyFile {
+            kind,
+            path: path.to_string(),
+            password,
+        }
+    }
+
+    #[test]
+    fn keys_from_files() {
+        std::env::set_var("KEYS_FROM_FILES_PASSWORD", "correct horse");
+        std::env::set_var("KEYS_FROM_FILES_WRONG_PASSWORD", "battery staple");
+
+        let pair = sr25519_pair(&key_file(KeyFileKind::Keystore, "for_tests/alice_sr25519_keystore.json", PasswordSource::Env(String::from("KEYS_FROM_FILES_PASSWORD")))).unwrap();
+        assert!(hex::encode(pair.public()) == ALICE, "Alice sr25519 key expected from keystore.");
+
+        let pair = ed25519_pair(&key_file(KeyFileKind::Keystore, "for_tests/alice_ed25519_keystore.json", PasswordSource::Env(String::from("KEYS_FROM_FILES_PASSWORD")))).unwrap();
+        assert!(hex::encode(pair.public()) == ALICE_ED25519, "Alice ed25519 key expected from keystore.");
+
+        assert!(sr25519_pair(&key_file(KeyFileKind::Keystore, "for_tests/alice_sr25519_keystore.json", PasswordSource::Env(String::from("KEYS_FROM_FILES_WRONG_PASSWORD")))).is_err(), "Wrong password should be reported.");
+        assert!(sr25519_pair(&key_file(KeyFileKind::Keystore, "for_tests/alice_ed25519_keystore.json", PasswordSource::Env(String::from("KEYS_FROM_FILES_PASSWORD")))).is_err(), "Keystore with key of other encryption should be reported.");
+        assert!(sr25519_pair(&key_file(KeyFileKind::Keystore, "for_tests/alice_sr25519_keystore.json", PasswordSource::Env(String::from("KEYS_FROM_FILES_NO_SUCH_VARIABLE")))).is_err(), "Missing password should be reported.");
+
+        let pair = sr25519_pair(&key_file(KeyFileKind::Secret, "for_tests/alice_secret", PasswordSource::None)).unwrap();
+        assert!(hex::encode(pair.public()) == ALICE, "Alice sr25519 key expected from secret file.");
+    }
+
+    fn command (line: &str, config_file: Option<&str>) -> anyhow::Result<Command> {
+        let cli = Cli::from_iter_safe(legacy_args(line.split(' ').map(|a| a.to_string()))).unwrap();
+        Command::new(cli.command, &load_config(config_file).unwrap())
+    }
+
+    #[test]
+    fn command_line_with_config() {
+        let config = load_config(Some("for_tests/workspace_config.toml")).unwrap();
+        assert!(config.database == "for_tests/workspace/database_hot", "Relative path should be counted from config file folder.");
+        assert!(config.signed == "/tmp/signed", "Absolute path should be kept.");
+        assert!(load_config(Some("for_tests/no_such_config.toml")).is_err(), "Missing config file should be reported.");
+        assert!((config.metadata_versions.for_network("westend") == 3)&&(config.metadata_versions.for_network("kusama") == 5), "Number of metadata versions from config expected.");
+
+        match command("generate_message add_specs -d -u westend", Some("for_tests/workspace_config.toml")).unwrap() {
+            Command::Specs(Instruction{set: Set::D, content: Content::Address(address), encryption_override: Some(Encryption::Ed25519), ..}) => assert!(address == "wss://westend-rpc.polkadot.io", "Endpoint from config expected."),
+            _ => panic!("Default encryption and endpoint from config expected."),
+        }
+        match command("generate_message add_network -k -u wss://example.io -ecdsa", Some("for_tests/workspace_config.toml")).unwrap() {
+            Command::Add(Instruction{set: Set::K, encryption_override: Some(Encryption::Ecdsa), ..}) => (),
+            _ => panic!("Encryption key should override default encryption."),
+        }
+        match command("generate_message load_metadata -a -s", None).unwrap() {
+            Command::Load(Instruction{set: Set::T, content: Content::All, pass_errors: false, encryption_override: None, ..}) => (),
+            _ => panic!("Default settings expected."),
+        }
+        match command("generate_message address_book add westend wss://westend.example.io", Some("for_tests/workspace_config.toml")).unwrap() {
+            Command::AddressBook(AddressBook::Add{addresses, encryption: Encryption::Ed25519, parachain_info: None}) => assert!(addresses == vec![String::from("wss://westend-rpc.polkadot.io"), String::from("wss://westend.example.io")], "Endpoints in given order expected, with names replaced by urls from config."),
+            _ => panic!("Address book entry with default encryption from config expected."),
+        }
+        assert!(command("generate_message address_book add wss://westend.example.io", None).is_err(), "Missing encryption should be reported.");
+        assert!(command("generate_message make -crypto sr25519 -msgtype load_types -verifier Alice -payload sign_me_load_types", None).is_err(), "Alice key without --dev should be reported.");
+        assert!(command("generate_message load_metadata -a westend", None).is_err(), "Network name with -a should be reported.");
+        assert!(Cli::from_iter_safe(legacy_args("generate_message make --text -sufficient -qr signature.png -msgtype load_types -payload sign_me_load_types".split(' ').map(|a| a.to_string()))).is_ok(), "Keys in earlier and current form should be accepted together.");
+    }
+
+    #[test]
+    fn sufficient_crypto_commands() {
+        let folder = "for_tests/sufficient_crypto_commands";
+        fs::create_dir_all(folder).unwrap();
+        let config_file = format!("{}/config.toml", folder);
+        fs::write(&config_file, "for_signing = \".\"\nsigned = \".\"\n").unwrap();
+
+        // load_types content without prelude, verifier public key and signature
+        let signed_types = payload_from_file("types_info_Alice.txt");
+        let message = signed_types[35..signed_types.len()-64].to_vec();
+        fs::write(format!("{}/sign_me_load_types", folder), &message).unwrap();
+
+        let alice = sr25519_pair(&key_file(KeyFileKind::Secret, "for_tests/alice_secret", PasswordSource::None)).unwrap();
+        let sufficient_crypto = SufficientCrypto::Sr25519{public_key: alice.public().0, signature: alice.sign(&message).0};
+        let other_sufficient_crypto = SufficientCrypto::Sr25519{public_key: alice.public().0, signature: alice.sign(b"some other message").0};
+        transform_into_qr_apng(&sufficient_crypto.encode(), &format!("{}/signature.png", folder)).unwrap();
+
+        match command(&format!("generate_message make -sufficient -hex {} -msgtype load_types -payload sign_me_load_types", hex::encode(sufficient_crypto.encode())), Some(&config_file)).unwrap() {
+            Command::Make(make) => {
+                match make.crypto {
+                    Crypto::Sr25519(VerifierKind::Normal{ref verifier_public_key, ..}) => assert!(hex::encode(verifier_public_key) == ALICE, "Alice public key expected from sufficient crypto."),
+                    _ => panic!("Sr25519 verifier from sufficient crypto expected."),
+                }
+                make_message(make, folder).unwrap();
+            },
+            _ => panic!("make command expected."),
+        }
+        assert!(fs::read_to_string(format!("{}/load_types.txt", folder)).unwrap().starts_with("5301"), "Signed load_types message expected.");
+
+        match command("generate_message make --text -sufficient -qr signature.png -msgtype load_types -payload sign_me_load_types --name from_qr", Some(&config_file)).unwrap() {
+            Command::Make(make) => {
+                assert!(matches!(make.crypto, Crypto::Sr25519(VerifierKind::Normal{..})), "Sr25519 verifier from qr sufficient crypto expected.");
+                make_message(make, folder).unwrap();
+            },
+            _ => panic!("make command expected."),
+        }
+        assert!(output_produced(folder, "from_qr.txt"), "Message signed with sufficient crypto from qr expected.");
+
+        match command(&format!("generate_message make -sufficient -hex {} -msgtype load_types -payload sign_me_load_types", hex::encode(other_sufficient_crypto.encode())), Some(&config_file)).unwrap() {
+            Command::Make(make) => assert!(make_message(make, folder).is_err(), "Sufficient crypto signed over other message should be rejected."),
+            _ => panic!("make command expected."),
+        }
+        assert!(command("generate_message make -sufficient -hex 0102 -msgtype load_types -payload sign_me_load_types", Some(&config_file)).is_err(), "Damaged sufficient crypto should be reported.");
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn compressed_payload_signing() {
+        let folder = "for_tests/compressed_payload_signing";
+        fs::create_dir_all(folder).unwrap();
+        gen_load_meta_from_file(LoadFromFile{meta_file: WESTEND_META.to_string(), genesis_hash: [0; 32]}, folder).unwrap();
+        let payload = fs::read(format!("{}/{}", folder, LOAD_FILE)).unwrap();
+
+        compress_payload(Compress{msg: Msg::LoadMetadata(payload.to_vec()), payload: LOAD_FILE.to_string()}, folder).unwrap();
+        let compressed = fs::read(format!("{}/{}_compressed", folder, LOAD_FILE)).unwrap();
+        assert!(compressed.len() < payload.len(), "Compressed content expected to be shorter.");
+        assert!(compress_payload(Compress{msg: Msg::LoadTypes(payload.to_vec()), payload: LOAD_FILE.to_string()}, folder).is_err(), "Only load_metadata and add_network payloads should be compressed.");
+
+        let alice = sr25519_pair(&key_file(KeyFileKind::Secret, "for_tests/alice_secret", PasswordSource::None)).unwrap();
+        let crypto = |message: &[u8]| Crypto::Sr25519(VerifierKind::Normal{verifier_public_key: alice.public().to_vec(), signature: alice.sign(message).0.to_vec()});
+
+        make_message(Make{goal: Goal::Text, crypto: crypto(&compressed), msg: Msg::LoadMetadataCompressed(payload.to_vec()), name: None}, folder).unwrap();
+        let signed = hex::decode(fs::read_to_string(format!("{}/load_metadata_westendV9070_compressed.txt", folder)).unwrap()).unwrap();
+        assert!(signed.starts_with(&[0x53, 0x01, 0x84]), "Compressed load_metadata prelude expected.");
+        assert!(inspect_payload(&signed).is_ok(), "Message signed over compressed content should be inspected.");
+
+        let error = make_message(Make{goal: Goal::Text, crypto: crypto(&payload), msg: Msg::LoadMetadataCompressed(payload.to_vec()), name: None}, folder).unwrap_err();
+        assert!(error.to_string().starts_with("Signature is made for uncompressed content."), "Unexpected error: {}", error);
+        let error = make_message(Make{goal: Goal::Text, crypto: crypto(b"some other message"), msg: Msg::LoadMetadataCompressed(payload), name: None}, folder).unwrap_err();
+        assert!(error.to_string() == "Bad sr25519 signature.", "Unexpected error: {}", error);
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    /// Function to get address book entries with their titles
+    fn address_book_entries (dbname: &str) -> Vec<(String, AddressBookEntry)> {
+        let database = sled::open(dbname).unwrap();
+        let address_book = database.open_tree(ADDRESS_BOOK).unwrap();
+        address_book.iter().map(|x| {
+            let (title_encoded, address_book_entry_encoded) = x.unwrap();
+            (<String>::decode(&mut &title_encoded[..]).unwrap(), <AddressBookEntry>::decode(&mut &address_book_entry_encoded[..]).unwrap())
+        }).collect()
+    }
+
+    #[test]
+    fn address_book_with_failover() {
+        let dbname = "for_tests/address_book_with_failover";
+        let folder = "for_tests/address_book_with_failover_files";
+        let address = prepare(dbname, folder);
+
+        address_book_run(AddressBook::SetUrl{title: String::from("westend"), addresses: vec![DEAD_ENDPOINT.to_string(), address.to_string()]}, dbname).unwrap();
+        gen_load_meta(instruction(Set::D, Content::Name(String::from("westend")), None), dbname, folder, &Config::default().metadata_versions).unwrap();
+        assert!(output_produced(folder, LOAD_FILE), "Fallback endpoint should be used if the first one fails.");
+
+        gen_add_network(instruction(Set::P, Content::Address(address.to_string()), Some(Encryption::Ed25519)), dbname, folder, &Config::default().metadata_versions).unwrap();
+        let entries = address_book_entries(dbname);
+        assert!(entries.len() == 2, "New address book entry expected for network with new encryption.");
+        for (_, x) in entries.iter() {
+            assert!(x.addresses() == vec![DEAD_ENDPOINT.to_string(), address.to_string()], "All entries of the network should have same endpoints.");
+        }
+
+        address_book_run(AddressBook::SetDefault(String::from("westend-ed25519")), dbname).unwrap();
+        for (title, x) in address_book_entries(dbname).iter() {
+            assert!(x.def == (title == "westend-ed25519"), "Only one default entry expected for the network.");
+        }
+
+        assert!(address_book_run(AddressBook::Rename{title: String::from("westend-ed25519"), new_title: String::from("westend")}, dbname).is_err(), "Renaming into existing title should be reported.");
+        address_book_run(AddressBook::Rename{title: String::from("westend-ed25519"), new_title: String::from("westend-ed")}, dbname).unwrap();
+        assert!(address_book_entries(dbname).iter().any(|(title, _)| title == "westend-ed"), "Renamed entry expected.");
+
+        address_book_run(AddressBook::SetUrl{title: String::from("westend-ed"), addresses: vec![address.to_string()]}, dbname).unwrap();
+        for (_, x) in address_book_entries(dbname).iter() {
+            assert!(x.address == address && x.fallback_addresses.is_empty(), "Endpoints should be changed for all entries of the network.");
+        }
+
+        address_book_run(AddressBook::Remove(String::from("westend-ed")), dbname).unwrap();
+        assert!(address_book_entries(dbname).len() == 1, "Entry should be removed.");
+        assert!(address_book_run(AddressBook::SetDefault(String::from("westend-ed")), dbname).is_err(), "Missing entry should be reported.");
+
+        fs::remove_dir_all(dbname).unwrap();
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn address_book_in_earlier_formats() {
+        let dbname = "for_tests/address_book_in_earlier_formats";
+        populate_hot(dbname).unwrap();
+        {
+            let database = sled::open(dbname).unwrap();
+            let address_book = database.open_tree(ADDRESS_BOOK).unwrap();
+        // default entries as stored before fallback rpc endpoints were added
+            for x in get_default_address_book().into_iter() {
+                let entry = AddressBookEntryNoFallback {
+                    name: x.name.to_string(),
+                    genesis_hash: x.genesis_hash,
+                    address: x.address.to_string(),
+                    encryption: x.encryption,
+                    def: x.def,
+                    relay_genesis_hash: x.relay_genesis_hash,
+                    parachain_id: x.parachain_id,
+                };
+                address_book.insert(x.name.encode(), entry.encode()).unwrap();
+            }
+        // entry added by user with add_specs, as stored before parachain information was added
+            let entry = AddressBookEntryNoParachain {
+                name: String::from("westend"),
+                genesis_hash: [1; 32],
+                address: String::from("wss://example.io"),
+                encryption: Encryption::Ed25519,
+                def: false,
+            };
+            address_book.insert(String::from("westend-ed25519").encode(), entry.encode()).unwrap();
+            database.flush().unwrap();
+        }
+        let config = Config {
+            database: dbname.to_string(),
+            ..Config::default()
+        };
+        full_run(Command::AddressBook(AddressBook::SetUrl{title: String::from("kusama"), addresses: vec![String::from("wss://kusama.example.io"), String::from("wss://kusama-rpc.polkadot.io")]}), &config).unwrap();
+        let entries = address_book_entries(dbname);
+        assert!(entries.len() == get_default_address_book().len() + 1, "All entries expected to be kept.");
+        for (title, x) in entries.iter() {
+            match title.as_str() {
+                "kusama" => assert!(x.addresses() == vec![String::from("wss://kusama.example.io"), String::from("wss://kusama-rpc.polkadot.io")], "Endpoints should be changed."),
+                "westend-ed25519" => assert!((x.address == "wss://example.io")&&x.fallback_addresses.is_empty()&&x.relay_genesis_hash.is_none()&&(x.encryption == Encryption::Ed25519), "Entry added by user expected to be kept."),
+                _ => assert!(x.fallback_addresses.is_empty(), "No fallback endpoints expected."),
+            }
+        }
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    fn meta_values (version: u32) -> MetaValues {
+        MetaValues {
+            name: String::from("westend"),
+            version,
+            meta: version.to_le_bytes().to_vec(),
+        }
+    }
+
+    fn versions (sorted: &SortedMetaValues) -> Vec<u32> {
+        sorted.networks[0].iter().map(|a| a.version).collect()
+    }
+
+    #[test]
+    fn metadata_versions_kept() {
+        let mut sorted = SortedMetaValues{networks: Vec::new()};
+        for version in 9070..9075 {
+            let upd_sorted = add_new(&meta_values(version), &sorted, 3).unwrap();
+            assert!(upd_sorted.upd_done, "New version should be added.");
+            sorted = upd_sorted.sorted;
+        }
+        assert!(versions(&sorted) == vec![9074, 9073, 9072], "Only 3 latest versions should be kept, newest first.");
+        assert!(!add_new(&meta_values(9074), &sorted, 3).unwrap().upd_done, "Same metadata should not be added twice.");
+        assert!(add_new(&meta_values(9071), &sorted, 3).is_err(), "Earlier version should be reported.");
+        let shorter = MetaValues{meta: vec![0], ..meta_values(9074)};
+        match add_new(&shorter, &sorted, 3) {
+            Ok(_) => panic!("Different metadata with same version should be reported."),
+            Err(e) => assert!(e.to_string() == "Fetched metadata for westend9074 differs from the one in the database.", "Unexpected error: {}", e),
+        }
+        let upd_sorted = add_new(&meta_values(9075), &sorted, 1).unwrap();
+        assert!(versions(&upd_sorted.sorted) == vec![9075], "Only latest version should be kept.");
+    }
+
+    #[test]
+    fn load_assets_message() {
+        let folder = "for_tests/load_assets_message";
+        fs::create_dir_all(folder).unwrap();
+        let westend_genesis_hash = "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+        let assets = vec![
+            AssetInfo{asset_id: 1984, symbol: String::from("USDt"), decimals: 6},
+            AssetInfo{asset_id: 1337, symbol: String::from("USDC"), decimals: 6},
+        ];
+
+        match command(&format!("generate_message load_assets --genesis 0x{} --asset 1984:USDt:6 --asset 1337:USDC:6", westend_genesis_hash), None).unwrap() {
+            Command::Assets(a) => assert!((hex::encode(a.genesis_hash) == westend_genesis_hash)&&(a.assets == assets), "Genesis hash and assets in given order expected."),
+            _ => panic!("load_assets command expected."),
+        }
+        assert!(command(&format!("generate_message load_assets --genesis 0x{} --asset 1984:USDt:6 --asset 1984:USDC:6", westend_genesis_hash), None).is_err(), "Same asset id twice should be reported.");
+        assert!(command(&format!("generate_message load_assets --genesis 0x{} --asset 1984:USDt", westend_genesis_hash), None).is_err(), "Asset without decimals should be reported.");
+        assert!(command(&format!("generate_message load_assets --genesis 0x{} --asset USDt:1984:6", westend_genesis_hash), None).is_err(), "Asset id should be a number.");
+
+        let mut genesis_hash = [0; 32];
+        genesis_hash.copy_from_slice(&hex::decode(westend_genesis_hash).unwrap());
+        gen_load_assets(LoadAssets{genesis_hash, assets}, folder).unwrap();
+        let payload = fs::read(format!("{}/sign_me_load_assets_{}", folder, westend_genesis_hash)).unwrap();
+
+        make_message(Make{goal: Goal::Text, crypto: Crypto::Sr25519(VerifierKind::Alice), msg: Msg::LoadAssets(payload), name: None}, folder).unwrap();
+        let signed = hex::decode(fs::read_to_string(format!("{}/load_assets_{}_Alice.txt", folder, westend_genesis_hash)).unwrap()).unwrap();
+        assert!(signed.starts_with(&[0x53, 0x01, 0x85]), "load_assets prelude expected.");
+        let description = inspect_payload(&signed).unwrap();
+        assert!(description.starts_with("Message type: load_assets\nGenesis hash: e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e\nAssets: [{\"asset_id\":\"1984\",\"symbol\":\"USDt\",\"decimals\":\"6\"},{\"asset_id\":\"1337\",\"symbol\":\"USDC\",\"decimals\":\"6\"}]"), "Unexpected description:\n{}", description);
+        assert!(description.ends_with(&format!("Verifier: public key: {}, encryption: sr25519", ALICE)), "Unexpected description:\n{}", description);
+
+        assert!(make_message(Make{goal: Goal::Text, crypto: Crypto::None, msg: Msg::LoadAssets(vec![1, 2, 3]), name: None}, folder).is_err(), "Damaged load_assets content should be reported.");
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+}
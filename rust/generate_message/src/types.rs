@@ -1,12 +1,14 @@
-use constants::{HOT_DB_NAME, TYLO};
+use constants::TYLO;
 use db_handling::prep_messages::prep_types;
 
 use crate::error::Error;
 
-pub fn gen_types() -> anyhow::Result<()> {
+/// Function to generate `load_types` message ready for signing,
+/// from the types in hot database, into the folder for output files.
+pub fn gen_types(database_name: &str, folder: &str) -> anyhow::Result<()> {
     
-    let content = prep_types(HOT_DB_NAME)?;
-    match content.write(TYLO) {
+    let content = prep_types(database_name)?;
+    match content.write(&format!("{}/{}", folder, TYLO)) {
         Ok(_) => Ok(()),
         Err(e) => return Err(Error::InputOutputError(e).show()),
     }
@@ -3,6 +3,41 @@ use parity_scale_codec::Decode;
 use frame_metadata::{RuntimeMetadata, decode_different::DecodeDifferent};
 use definitions::metadata::{MetaValues, VersionDecoded};
 
+use crate::metadata_v15::RuntimeMetadataV15;
+
+
+/// Function to decode runtime metadata, with `meta` prefix already cut off;
+/// RuntimeMetadataV15 is transformed into RuntimeMetadataV14
+/// (RuntimeMetadata enum does not yet have V15 variant),
+/// all other versions are decoded as is
+
+pub fn decode_runtime_metadata (meta_body: &[u8]) -> Result<RuntimeMetadata, &'static str> {
+    match meta_body.get(0) {
+        Some(15) => match RuntimeMetadataV15::decode(&mut &meta_body[1..]) {
+            Ok(a) => Ok(RuntimeMetadata::V14(a.to_v14())),
+            Err(_) => return Err("Unable to decode runtime metadata"),
+        },
+        _ => match RuntimeMetadata::decode(&mut &meta_body[..]) {
+            Ok(a) => Ok(a),
+            Err(_) => return Err("Unable to decode runtime metadata"),
+        },
+    }
+}
+
+
+/// Function to decode RuntimeMetadataV15 as is, with `meta` prefix,
+/// to access the information absent in RuntimeMetadataV14
+/// (outer enums, runtime api and custom values)
+
+pub fn decode_metadata_v15 (meta_unhex: &Vec<u8>) -> Result<RuntimeMetadataV15, &'static str> {
+    if !meta_unhex.starts_with(&vec![109, 101, 116, 97]) {return Err("No 'meta' starting sequence in metadata")}
+    if meta_unhex.get(4) != Some(&15) {return Err("RuntimeMetadata version is not V15")}
+    match RuntimeMetadataV15::decode(&mut &meta_unhex[5..]) {
+        Ok(a) => Ok(a),
+        Err(_) => return Err("Unable to decode runtime metadata"),
+    }
+}
+
 
 /// Function to search metadata as RuntimeMetadataV12 for system block,
 /// and then find version entry within it
@@ -82,6 +117,62 @@ pub fn get_meta_const_light (meta_back: &RuntimeMetadata) -> Result<Vec<u8>, &'s
 }
 
 
+/// Function to search metadata for system block,
+/// and then find constant with given name within it;
+/// outputs None if the constant is not there
+
+pub fn get_system_constant (meta_back: &RuntimeMetadata, constant_name: &str) -> Result<Option<Vec<u8>>, &'static str> {
+    match meta_back {
+        RuntimeMetadata::V12(metadata_v12) => {
+            if let DecodeDifferent::Decoded(meta_vector) = &metadata_v12.modules {
+                for x in meta_vector.iter() {
+                    if x.name==DecodeDifferent::Encode("System") {
+                        if let DecodeDifferent::Decoded(constants_vector) = &x.constants {
+                            for y in constants_vector.iter() {
+                                if let (DecodeDifferent::Decoded(name), DecodeDifferent::Decoded(fin)) = (&y.name, &y.value) {
+                                    if name == constant_name {return Ok(Some(fin.to_vec()))}
+                                }
+                            }
+                        }
+                        return Ok(None)
+                    }
+                }
+            }
+            Err("No system block found")
+        },
+        RuntimeMetadata::V13(metadata_v13) => {
+            if let DecodeDifferent::Decoded(meta_vector) = &metadata_v13.modules {
+                for x in meta_vector.iter() {
+                    if x.name==DecodeDifferent::Encode("System") {
+                        if let DecodeDifferent::Decoded(constants_vector) = &x.constants {
+                            for y in constants_vector.iter() {
+                                if let (DecodeDifferent::Decoded(name), DecodeDifferent::Decoded(fin)) = (&y.name, &y.value) {
+                                    if name == constant_name {return Ok(Some(fin.to_vec()))}
+                                }
+                            }
+                        }
+                        return Ok(None)
+                    }
+                }
+            }
+            Err("No system block found")
+        },
+        RuntimeMetadata::V14(metadata_v14) => {
+            for x in metadata_v14.pallets.iter() {
+                if x.name == "System" {
+                    for y in x.constants.iter() {
+                        if y.name == constant_name {return Ok(Some(y.value.to_vec()))}
+                    }
+                    return Ok(None)
+                }
+            }
+            Err("No system block found")
+        },
+        _ => return Err("RuntimeMetadata version incompatible"),
+    }
+}
+
+
 /// Function to decode metadata hex string into RuntimeMetadataV12,
 /// then search for system block in it, and find version entry within it
 
@@ -90,10 +181,7 @@ pub fn get_meta_const (meta_unhex: &Vec<u8>) -> Result<Vec<u8>, &'static str> {
     if !meta_unhex.starts_with(&vec![109, 101, 116, 97]) {return Err("No 'meta' starting sequence in metadata")}
     if meta_unhex[4] < 12 {return Err("RuntimeMetadata version incompatible");}
     
-    let meta_back = match RuntimeMetadata::decode(&mut &meta_unhex[4..]) {
-        Ok(x) => x,
-        Err(_) => return Err("Unable to decode runtime metadata"),
-    };
+    let meta_back = decode_runtime_metadata(&meta_unhex[4..])?;
     
     get_meta_const_light(&meta_back)
     
@@ -209,6 +297,55 @@ mod tests {
         assert!(meta_values.version == 9010, "Unexpected network name: {}", meta_values.version);
     }
     
+    fn metadata_v15_westmint() -> Vec<u8> {
+        use crate::metadata_v15::{CustomValueMetadata, ExtrinsicMetadataV15, OuterEnums, PalletMetadataV15, RuntimeApiMetadata};
+        use frame_metadata::v14::PalletConstantMetadata;
+        use parity_scale_codec::Encode;
+        use scale_info::{meta_type, Registry};
+        use std::collections::BTreeMap;
+        
+        let mut registry = Registry::new();
+        let unit_ty = registry.register_type(&meta_type::<()>());
+        let bytes_ty = registry.register_type(&meta_type::<Vec<u8>>());
+        let version = (String::from("westmint"), String::from("westmint"), 1u32, 9150u32, 0u32, Vec::<(u8, u32)>::new(), 5u32).encode();
+        let system = PalletMetadataV15 {
+            name: String::from("System"),
+            storage: None,
+            calls: None,
+            event: None,
+            constants: vec![PalletConstantMetadata {name: String::from("Version"), ty: bytes_ty.clone(), value: version, docs: Vec::new()}],
+            error: None,
+            index: 0,
+            docs: Vec::new(),
+        };
+        let mut custom = BTreeMap::new();
+        custom.insert(String::from("asset_id"), CustomValueMetadata {ty: unit_ty.clone(), value: Vec::new()});
+        let metadata_v15 = RuntimeMetadataV15 {
+            types: registry.into(),
+            pallets: vec![system],
+            extrinsic: ExtrinsicMetadataV15 {version: 4, address_ty: unit_ty.clone(), call_ty: unit_ty.clone(), signature_ty: unit_ty.clone(), extra_ty: unit_ty.clone(), signed_extensions: Vec::new()},
+            ty: unit_ty.clone(),
+            apis: vec![RuntimeApiMetadata {name: String::from("Core"), methods: Vec::new(), docs: Vec::new()}],
+            outer_enums: OuterEnums {call_enum_ty: unit_ty.clone(), event_enum_ty: unit_ty.clone(), error_enum_ty: unit_ty},
+            custom,
+        };
+        [vec![109, 101, 116, 97, 15], metadata_v15.encode()].concat()
+    }
+    
+    #[test]
+    fn westmint_v15() {
+        let meta = metadata_v15_westmint();
+        let meta_values = decode_version(&hex::encode(&meta)).unwrap();
+        assert!(meta_values.name == String::from("westmint"), "Unexpected network name: {}", meta_values.name);
+        assert!(meta_values.version == 9150, "Unexpected network version: {}", meta_values.version);
+        match decode_runtime_metadata(&meta[4..]).unwrap() {
+            RuntimeMetadata::V14(metadata_v14) => assert!(metadata_v14.pallets[0].name == "System", "Unexpected pallet name."),
+            _ => panic!("Metadata V15 expected to be transformed into V14."),
+        }
+        let extras = decode_metadata_v15(&meta).unwrap().show_extras();
+        assert!(extras == "runtime apis: Core (0 methods); custom values: asset_id", "Unexpected metadata V15 extras: {}", extras);
+    }
+    
     #[test]
     fn edgeware() {
         let meta = read_to_string("for_tests/edgeware").unwrap();
@@ -0,0 +1,331 @@
+use frame_metadata::{RuntimeMetadata, decode_different::{DecodeDifferent, DecodeDifferentStr}, v12::RuntimeMetadataV12, v13::RuntimeMetadataV13, v14::RuntimeMetadataV14};
+use scale_info::{PortableRegistry, TypeDef};
+
+use crate::decode_metadata::decode_runtime_metadata;
+
+/// Depth up to which the types of call arguments are expanded when compared;
+/// deeper types are compared by path only
+const TYPE_DEPTH: u32 = 4;
+
+/// Struct to store the differences between two metadata versions of the same network
+#[derive(Debug, PartialEq)]
+pub struct MetadataDiff {
+    pub pallets_added: Vec<String>,
+    pub pallets_removed: Vec<String>,
+    pub calls_added: Vec<String>,
+    pub calls_removed: Vec<String>,
+    pub calls_changed: Vec<String>,
+    pub constants_changed: Vec<ConstantChange>,
+}
+
+/// Struct to store changed constant value, constant name is given as `Pallet.Constant`
+#[derive(Debug, PartialEq)]
+pub struct ConstantChange {
+    pub name: String,
+    pub old_value: Vec<u8>,
+    pub new_value: Vec<u8>,
+}
+
+/// Struct to store pallet information relevant for comparison,
+/// same for all metadata versions
+struct PalletSummary {
+    name: String,
+    calls: Vec<CallSummary>,
+    constants: Vec<(String, Vec<u8>)>,
+}
+
+/// Struct to store call name with argument names and types
+struct CallSummary {
+    name: String,
+    arguments: Vec<(String, String)>,
+}
+
+impl MetadataDiff {
+    /// Function to check if no differences were found
+    pub fn is_empty(&self) -> bool {
+        self.pallets_added.is_empty() && self.pallets_removed.is_empty() && self.calls_added.is_empty() && self.calls_removed.is_empty() && self.calls_changed.is_empty() && self.constants_changed.is_empty()
+    }
+    /// Function to show the differences as plain text, for command line
+    pub fn show(&self) -> String {
+        if self.is_empty() {return String::from("No differences in pallets, calls and constants.")}
+        let mut lines: Vec<String> = Vec::new();
+        for (title, set) in [("Pallets added", &self.pallets_added), ("Pallets removed", &self.pallets_removed), ("Calls added", &self.calls_added), ("Calls removed", &self.calls_removed), ("Calls with changed arguments", &self.calls_changed)].iter() {
+            if !set.is_empty() {lines.push(format!("{}: {}", title, set.join(", ")))}
+        }
+        if !self.constants_changed.is_empty() {
+            let constants: Vec<String> = self.constants_changed.iter().map(|x| format!("{} 0x{} -> 0x{}", x.name, hex::encode(&x.old_value), hex::encode(&x.new_value))).collect();
+            lines.push(format!("Constants changed: {}", constants.join(", ")));
+        }
+        lines.join("\n")
+    }
+    /// Function to show the differences as json fields, for cards
+    pub fn show_card(&self) -> String {
+        let constants: Vec<String> = self.constants_changed.iter().map(|x| format!("{{\"constant\":\"{}\",\"old_value\":\"{}\",\"new_value\":\"{}\"}}", x.name, hex::encode(&x.old_value), hex::encode(&x.new_value))).collect();
+        format!("\"pallets_added\":{},\"pallets_removed\":{},\"calls_added\":{},\"calls_removed\":{},\"calls_changed\":{},\"constants_changed\":[{}]", json_list(&self.pallets_added), json_list(&self.pallets_removed), json_list(&self.calls_added), json_list(&self.calls_removed), json_list(&self.calls_changed), constants.join(","))
+    }
+}
+
+fn json_list (list: &Vec<String>) -> String {
+    let entries: Vec<String> = list.iter().map(|x| format!("\"{}\"", x)).collect();
+    format!("[{}]", entries.join(","))
+}
+
+
+/// Function to find the differences between old and new metadata:
+/// pallets and calls added or removed, calls with changed arguments
+/// and changed constants;
+/// metadata is expected with `meta` prefix, versions of old and new metadata could differ,
+/// although comparing call arguments between V13 and V14 is meaningless
+/// as argument types are recorded differently
+
+pub fn diff_metadata (old: &Vec<u8>, new: &Vec<u8>) -> Result<MetadataDiff, String> {
+    let old_pallets = summary(old)?;
+    let new_pallets = summary(new)?;
+
+    let mut diff = MetadataDiff {
+        pallets_added: Vec::new(),
+        pallets_removed: Vec::new(),
+        calls_added: Vec::new(),
+        calls_removed: Vec::new(),
+        calls_changed: Vec::new(),
+        constants_changed: Vec::new(),
+    };
+
+    for old_pallet in old_pallets.iter() {
+        match new_pallets.iter().find(|x| x.name == old_pallet.name) {
+            Some(new_pallet) => {
+                for old_call in old_pallet.calls.iter() {
+                    match new_pallet.calls.iter().find(|x| x.name == old_call.name) {
+                        Some(new_call) => {
+                            if new_call.arguments != old_call.arguments {diff.calls_changed.push(format!("{}.{}", old_pallet.name, old_call.name))}
+                        },
+                        None => diff.calls_removed.push(format!("{}.{}", old_pallet.name, old_call.name)),
+                    }
+                }
+                for new_call in new_pallet.calls.iter() {
+                    if !old_pallet.calls.iter().any(|x| x.name == new_call.name) {diff.calls_added.push(format!("{}.{}", new_pallet.name, new_call.name))}
+                }
+                for (name, old_value) in old_pallet.constants.iter() {
+                // version constant is always different
+                    if (old_pallet.name == "System") && (name == "Version") {continue}
+                    if let Some((_, new_value)) = new_pallet.constants.iter().find(|(x, _)| x == name) {
+                        if new_value != old_value {
+                            diff.constants_changed.push(ConstantChange {
+                                name: format!("{}.{}", old_pallet.name, name),
+                                old_value: old_value.to_vec(),
+                                new_value: new_value.to_vec(),
+                            })
+                        }
+                    }
+                }
+            },
+            None => diff.pallets_removed.push(old_pallet.name.to_string()),
+        }
+    }
+    for new_pallet in new_pallets.iter() {
+        if !old_pallets.iter().any(|x| x.name == new_pallet.name) {diff.pallets_added.push(new_pallet.name.to_string())}
+    }
+    Ok(diff)
+}
+
+
+/// Function to collect pallets summary from metadata with `meta` prefix
+fn summary (meta: &Vec<u8>) -> Result<Vec<PalletSummary>, String> {
+    if !meta.starts_with(&vec![109, 101, 116, 97]) {return Err(String::from("No 'meta' starting sequence in metadata"))}
+    match meta.get(4) {
+        Some(a) => if *a < 12 {return Err(String::from("RuntimeMetadata version incompatible"))},
+        None => return Err(String::from("No 'meta' starting sequence in metadata")),
+    }
+    match decode_runtime_metadata(&meta[4..]).map_err(|e| e.to_string())? {
+        RuntimeMetadata::V12(metadata_v12) => summary_v12(&metadata_v12),
+        RuntimeMetadata::V13(metadata_v13) => summary_v13(&metadata_v13),
+        RuntimeMetadata::V14(metadata_v14) => summary_v14(&metadata_v14),
+        _ => Err(String::from("RuntimeMetadata version incompatible")),
+    }
+}
+
+
+/// Function to collect pallets summary for metadata V12,
+/// call arguments types are recorded in metadata as strings
+fn summary_v12 (metadata_v12: &RuntimeMetadataV12) -> Result<Vec<PalletSummary>, String> {
+    let mut out = Vec::new();
+    if let DecodeDifferent::Decoded(modules) = &metadata_v12.modules {
+        for x in modules.iter() {
+            let mut calls = Vec::new();
+            if let Some(DecodeDifferent::Decoded(a)) = &x.calls {
+                for y in a.iter() {
+                    let mut arguments = Vec::new();
+                    if let DecodeDifferent::Decoded(b) = &y.arguments {
+                        for z in b.iter() {arguments.push((decoded_string(&z.name), decoded_string(&z.ty)))}
+                    }
+                    calls.push(CallSummary{name: decoded_string(&y.name), arguments});
+                }
+            }
+            let mut constants = Vec::new();
+            if let DecodeDifferent::Decoded(a) = &x.constants {
+                for y in a.iter() {
+                    if let DecodeDifferent::Decoded(b) = &y.value {constants.push((decoded_string(&y.name), b.to_vec()))}
+                }
+            }
+            out.push(PalletSummary {
+                name: decoded_string(&x.name),
+                calls,
+                constants,
+            })
+        }
+        Ok(out)
+    }
+    else {return Err(String::from("Pallets could not be read"))}
+}
+
+
+/// Function to collect pallets summary for metadata V13,
+/// call arguments types are recorded in metadata as strings
+fn summary_v13 (metadata_v13: &RuntimeMetadataV13) -> Result<Vec<PalletSummary>, String> {
+    let mut out = Vec::new();
+    if let DecodeDifferent::Decoded(modules) = &metadata_v13.modules {
+        for x in modules.iter() {
+            let mut calls = Vec::new();
+            if let Some(DecodeDifferent::Decoded(a)) = &x.calls {
+                for y in a.iter() {
+                    let mut arguments = Vec::new();
+                    if let DecodeDifferent::Decoded(b) = &y.arguments {
+                        for z in b.iter() {arguments.push((decoded_string(&z.name), decoded_string(&z.ty)))}
+                    }
+                    calls.push(CallSummary{name: decoded_string(&y.name), arguments});
+                }
+            }
+            let mut constants = Vec::new();
+            if let DecodeDifferent::Decoded(a) = &x.constants {
+                for y in a.iter() {
+                    if let DecodeDifferent::Decoded(b) = &y.value {constants.push((decoded_string(&y.name), b.to_vec()))}
+                }
+            }
+            out.push(PalletSummary {
+                name: decoded_string(&x.name),
+                calls,
+                constants,
+            })
+        }
+        Ok(out)
+    }
+    else {return Err(String::from("Pallets could not be read"))}
+}
+
+fn decoded_string (entry: &DecodeDifferentStr) -> String {
+    match entry {
+        DecodeDifferent::Decoded(a) => a.to_string(),
+        DecodeDifferent::Encode(a) => a.to_string(),
+    }
+}
+
+
+/// Function to collect pallet summary for metadata V14,
+/// call arguments types are described by expanding types registry entries
+fn summary_v14 (metadata_v14: &RuntimeMetadataV14) -> Result<Vec<PalletSummary>, String> {
+    let mut out = Vec::new();
+    for x in metadata_v14.pallets.iter() {
+        let mut calls = Vec::new();
+        if let Some(a) = &x.calls {
+            let calls_type = match metadata_v14.types.resolve(a.ty.id()) {
+                Some(b) => b,
+                None => return Err(format!("Type {} could not be resolved", a.ty.id())),
+            };
+            if let TypeDef::Variant(b) = calls_type.type_def() {
+                for y in b.variants().iter() {
+                    let mut arguments = Vec::new();
+                    for z in y.fields().iter() {
+                        let argument_name = match z.name() {
+                            Some(c) => c.to_string(),
+                            None => String::new(),
+                        };
+                        arguments.push((argument_name, describe_type(z.ty().id(), &metadata_v14.types, TYPE_DEPTH)));
+                    }
+                    calls.push(CallSummary{name: y.name().to_string(), arguments});
+                }
+            }
+        }
+        let constants = x.constants.iter().map(|y| (y.name.to_string(), y.value.to_vec())).collect();
+        out.push(PalletSummary {
+            name: x.name.to_string(),
+            calls,
+            constants,
+        })
+    }
+    Ok(out)
+}
+
+
+/// Function to describe type from types registry as string;
+/// type ids are not stable between metadata versions, so types are compared by description
+fn describe_type (id: u32, types: &PortableRegistry, depth: u32) -> String {
+    let current_type = match types.resolve(id) {
+        Some(a) => a,
+        None => return String::from("?"),
+    };
+    let path = current_type.path().segments().join("::");
+    if depth == 0 {return path}
+    let inner = match current_type.type_def() {
+        TypeDef::Composite(x) => {
+            let fields: Vec<String> = x.fields().iter().map(|y| describe_field(y.name(), y.ty().id(), types, depth)).collect();
+            format!("{{{}}}", fields.join(","))
+        },
+        TypeDef::Variant(x) => {
+            let variants: Vec<String> = x.variants().iter().map(|y| {
+                let fields: Vec<String> = y.fields().iter().map(|z| describe_field(z.name(), z.ty().id(), types, depth)).collect();
+                format!("{}({})", y.name(), fields.join(","))
+            }).collect();
+            format!("{{{}}}", variants.join("|"))
+        },
+        TypeDef::Sequence(x) => format!("Vec<{}>", describe_type(x.type_param().id(), types, depth-1)),
+        TypeDef::Array(x) => format!("[{};{}]", describe_type(x.type_param().id(), types, depth-1), x.len()),
+        TypeDef::Tuple(x) => {
+            let fields: Vec<String> = x.fields().iter().map(|y| describe_type(y.id(), types, depth-1)).collect();
+            format!("({})", fields.join(","))
+        },
+        TypeDef::Primitive(x) => format!("{:?}", x),
+        TypeDef::Compact(x) => format!("Compact<{}>", describe_type(x.type_param().id(), types, depth-1)),
+        TypeDef::BitSequence(x) => format!("BitVec<{},{}>", describe_type(x.bit_store_type().id(), types, depth-1), describe_type(x.bit_order_type().id(), types, depth-1)),
+        TypeDef::Range(x) => format!("Range<{}>", describe_type(x.index_type().id(), types, depth-1)),
+    };
+    format!("{}{}", path, inner)
+}
+
+fn describe_field (name: Option<&String>, id: u32, types: &PortableRegistry, depth: u32) -> String {
+    match name {
+        Some(a) => format!("{}:{}", a, describe_type(id, types, depth-1)),
+        None => describe_type(id, types, depth-1),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::meta_from_file;
+
+    #[test]
+    fn same_metadata_no_diff() {
+        let meta = meta_from_file("for_tests/westend9070");
+        let diff = diff_metadata(&meta, &meta).unwrap();
+        assert!(diff.is_empty(), "Unexpected differences: {}", diff.show());
+    }
+
+    #[test]
+    fn westend9033_to_westend9070() {
+        let old = meta_from_file("for_tests/westend9033");
+        let new = meta_from_file("for_tests/westend9070");
+        let diff = diff_metadata(&old, &new).unwrap();
+        assert!(!diff.is_empty(), "No differences found between westend9033 and westend9070.");
+        let reverse = diff_metadata(&new, &old).unwrap();
+        assert!(reverse.pallets_added == diff.pallets_removed, "Pallets added and removed do not match in reverse diff.");
+        assert!(reverse.calls_added == diff.calls_removed, "Calls added and removed do not match in reverse diff.");
+    }
+
+    #[test]
+    fn too_short_metadata() {
+        let meta = meta_from_file("for_tests/westend9070");
+        assert!(diff_metadata(&b"meta".to_vec(), &meta).is_err(), "Metadata with only prefix should be reported.");
+        assert!(diff_metadata(&meta, &b"meta".to_vec()).is_err(), "Metadata with only prefix should be reported.");
+    }
+}
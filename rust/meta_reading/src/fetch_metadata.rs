@@ -1,10 +1,15 @@
 use jsonrpsee_types::{
-    JsonValue, 
+    JsonValue,
     v2::params::JsonRpcParams,
     traits::Client,
 };
-use jsonrpsee_ws_client::WsClientBuilder;
+use jsonrpsee_http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee_ws_client::{WsClient, WsClientBuilder};
+use parity_scale_codec::Decode;
 use serde_json::{value::Number, map::Map};
+use tokio::runtime::Runtime;
+
+use crate::interpret_chainspecs::properties_from_chain_spec;
 
 pub struct FetchedInfo {
     pub meta: String,
@@ -22,53 +27,301 @@ pub struct FetchedChainSpecs {
     pub properties: Map<String, JsonValue>,
 }
 
-/// Function to fetch the metadata as String and genesis hash as String from given address,
-/// actually fetches stuff, is slow
 
-#[tokio::main]
-pub async fn fetch_info(str_address: &str) -> Result<FetchedInfo, Box<dyn std::error::Error>> {
-    let client = WsClientBuilder::default().build(str_address).await?;
-    let response: JsonValue = client.request("state_getMetadata", JsonRpcParams::NoParams).await?;
-    let meta = match response {
-        JsonValue::String(x) => x,
-        _ => return Err(Box::from("Unexpected metadata format")),
-    };
-    let response: JsonValue = client.request("chain_getBlockHash", JsonRpcParams::ArrayRef(&[JsonValue::Number(Number::from(0 as u8))])).await?;
-    let genesis_hash = match response {
-        JsonValue::String(x) => x,
-        _ => return Err(Box::from("Unexpected genesis hash format")),
-    };
+/// Trait for the sources of network information: metadata, genesis hash and properties.
+/// Sources differ only in the way the rpc requests are made;
+/// the information is fetched and checked same way for all sources.
+pub trait MetadataSource {
+
+    /// Function to make rpc request with given method and params, and get the result
+    fn request (&self, method: &str, params: &[JsonValue]) -> Result<JsonValue, Box<dyn std::error::Error>>;
+
+    /// Function to fetch the metadata as String:
+    /// metadata V15 is requested first through runtime api call,
+    /// if the runtime api is not available or the node has no metadata V15,
+    /// the metadata is requested through `state_getMetadata`
+    fn fetch_meta (&self) -> Result<String, Box<dyn std::error::Error>> {
+        let params = [JsonValue::String(String::from("Metadata_metadata_at_version")), JsonValue::String(String::from("0x0f000000"))];
+        if let Ok(JsonValue::String(x)) = self.request("state_call", &params) {
+        // runtime api returns encoded Option<Vec<u8>>
+            if let Ok(encoded) = hex::decode(x.trim_start_matches("0x")) {
+                if let Ok(Some(meta_v15)) = <Option<Vec<u8>>>::decode(&mut &encoded[..]) {
+                    return Ok(format!("0x{}", hex::encode(meta_v15)))
+                }
+            }
+        }
+        match self.request("state_getMetadata", &[])? {
+            JsonValue::String(x) => Ok(x),
+            _ => return Err(Box::from("Unexpected metadata format")),
+        }
+    }
+
+    /// Function to fetch the genesis hash as String
+    fn fetch_genesis_hash (&self) -> Result<String, Box<dyn std::error::Error>> {
+        match self.request("chain_getBlockHash", &[JsonValue::Number(Number::from(0 as u8))])? {
+            JsonValue::String(x) => Ok(x),
+            _ => return Err(Box::from("Unexpected genesis hash format")),
+        }
+    }
+
+    /// Function to fetch the network properties
+    fn fetch_properties (&self) -> Result<Map<String, JsonValue>, Box<dyn std::error::Error>> {
+        match self.request("system_properties", &[])? {
+            JsonValue::Object(x) => Ok(x),
+            _ => return Err(Box::from("Unexpected system properties format")),
+        }
+    }
+}
+
+
+/// Source making rpc calls through WebSocket connection,
+/// connection is established once and is used for all requests
+pub struct WsSource {
+    client: WsClient,
+    runtime: Runtime,
+}
+
+impl WsSource {
+    /// Function to connect to the node at given address
+    pub fn connect (address: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let runtime = Runtime::new()?;
+        let client = runtime.block_on(WsClientBuilder::default().build(address))?;
+        Ok(Self{client, runtime})
+    }
+}
+
+impl MetadataSource for WsSource {
+    fn request (&self, method: &str, params: &[JsonValue]) -> Result<JsonValue, Box<dyn std::error::Error>> {
+        Ok(self.runtime.block_on(self.client.request::<JsonValue>(method, rpc_params(params)))?)
+    }
+}
+
+
+/// Source making rpc calls through http
+pub struct HttpSource {
+    client: HttpClient,
+    runtime: Runtime,
+}
+
+impl HttpSource {
+    /// Function to set up http client for the node at given address
+    pub fn new (address: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let runtime = Runtime::new()?;
+        let client = HttpClientBuilder::default().build(address)?;
+        Ok(Self{client, runtime})
+    }
+}
+
+impl MetadataSource for HttpSource {
+    fn request (&self, method: &str, params: &[JsonValue]) -> Result<JsonValue, Box<dyn std::error::Error>> {
+        Ok(self.runtime.block_on(self.client.request::<JsonValue>(method, rpc_params(params)))?)
+    }
+}
+
+
+/// Source without rpc calls, answering requests with previously recorded results;
+/// params of the requests are not checked
+pub struct MockSource {
+    recorded: Map<String, JsonValue>,
+}
+
+impl MockSource {
+    /// Function to make MockSource from method names and recorded results
+    pub fn new (recorded: Map<String, JsonValue>) -> Self {
+        Self{recorded}
+    }
+    /// Function to make MockSource from recording directory:
+    /// each file in the directory is named after the rpc method and contains the result,
+    /// either as json, or, for results that are strings, as plain text
+    pub fn from_recording (dir: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut recorded = Map::new();
+        for x in std::fs::read_dir(dir)? {
+            let path = x?.path();
+            let method = match path.file_name().and_then(|a| a.to_str()) {
+                Some(a) => a.to_string(),
+                None => return Err(Box::from(format!("Unexpected file name in recording {}", dir))),
+            };
+            let content = std::fs::read_to_string(&path)?;
+            let result = match serde_json::from_str::<JsonValue>(&content) {
+                Ok(a) => a,
+                Err(_) => JsonValue::String(content.trim().to_string()),
+            };
+            recorded.insert(method, result);
+        }
+        Ok(Self{recorded})
+    }
+}
+
+impl MetadataSource for MockSource {
+    fn request (&self, method: &str, _params: &[JsonValue]) -> Result<JsonValue, Box<dyn std::error::Error>> {
+        match self.recorded.get(method) {
+            Some(a) => Ok(a.to_owned()),
+            None => return Err(Box::from(format!("No recorded result for method {}", method))),
+        }
+    }
+}
+
+
+/// Source without rpc calls, reading network information from files:
+/// metadata from binary `.scale` file or from hex file,
+/// network properties from `properties` of chain spec json file;
+/// genesis hash is not recorded in these files and is provided separately
+pub struct FileSource {
+    meta: String,
+    genesis_hash: String,
+    properties: Option<Map<String, JsonValue>>,
+}
+
+impl FileSource {
+    /// Function to make FileSource from metadata file, genesis hash and optional chain spec file
+    pub fn new (meta_file: &str, genesis_hash: &[u8; 32], chain_spec_file: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        let meta = read_metadata_file(meta_file)?;
+        let properties = match chain_spec_file {
+            Some(a) => Some(properties_from_chain_spec(&std::fs::read_to_string(a)?)?),
+            None => None,
+        };
+        Ok(Self{
+            meta,
+            genesis_hash: format!("0x{}", hex::encode(genesis_hash)),
+            properties,
+        })
+    }
+}
+
+impl MetadataSource for FileSource {
+    fn request (&self, method: &str, _params: &[JsonValue]) -> Result<JsonValue, Box<dyn std::error::Error>> {
+        match method {
+            "state_getMetadata" => Ok(JsonValue::String(self.meta.to_string())),
+            "chain_getBlockHash" => Ok(JsonValue::String(self.genesis_hash.to_string())),
+            "system_properties" => match &self.properties {
+                Some(a) => Ok(JsonValue::Object(a.to_owned())),
+                None => return Err(Box::from("No chain spec file provided, network properties are not known.")),
+            },
+            _ => return Err(Box::from(format!("Method {} is not available for files", method))),
+        }
+    }
+}
+
+
+/// Function to read metadata from file as hex String with `0x` prefix;
+/// file could be binary, with metadata starting with `meta` prefix
+/// or with metadata SCALE-encoded as Vec<u8> (as returned by runtime api),
+/// or could contain metadata in hex format
+pub fn read_metadata_file (filename: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let content = std::fs::read(filename)?;
+    if content.starts_with(b"meta") {return Ok(format!("0x{}", hex::encode(content)))}
+    if let Ok(a) = std::str::from_utf8(&content) {
+        if let Ok(meta) = hex::decode(a.trim().trim_start_matches("0x")) {
+            if meta.starts_with(b"meta") {return Ok(format!("0x{}", hex::encode(meta)))}
+        }
+    }
+    if let Ok(meta) = <Vec<u8>>::decode(&mut &content[..]) {
+        if meta.starts_with(b"meta") {return Ok(format!("0x{}", hex::encode(meta)))}
+    }
+    Err(Box::from("No metadata with 'meta' starting sequence found in file."))
+}
+
+
+/// Function to select metadata source for address:
+/// http source for `http://` and `https://` addresses, WebSocket source otherwise
+pub fn source_for_address (address: &str) -> Result<Box<dyn MetadataSource>, Box<dyn std::error::Error>> {
+    if address.starts_with("http://")||address.starts_with("https://") {Ok(Box::new(HttpSource::new(address)?))}
+    else {Ok(Box::new(WsSource::connect(address)?))}
+}
+
+
+/// Function to fetch the metadata as String and genesis hash as String from given source
+pub fn fetch_info_from_source (source: &dyn MetadataSource) -> Result<FetchedInfo, Box<dyn std::error::Error>> {
     Ok(FetchedInfo{
-        meta,
-        genesis_hash,
+        meta: source.fetch_meta()?,
+        genesis_hash: source.fetch_genesis_hash()?,
+    })
+}
+
+/// Function to fetch the metadata as String, genesis hash as String, and network specs from given source
+pub fn fetch_info_with_chainspecs_from_source (source: &dyn MetadataSource) -> Result<FetchedInfoWithChainSpecs, Box<dyn std::error::Error>> {
+    Ok(FetchedInfoWithChainSpecs{
+        meta: source.fetch_meta()?,
+        genesis_hash: source.fetch_genesis_hash()?,
+        properties: source.fetch_properties()?,
     })
 }
 
+/// Function to fetch the metadata as String and genesis hash as String from given address,
+/// actually fetches stuff, is slow
+pub fn fetch_info(str_address: &str) -> Result<FetchedInfo, Box<dyn std::error::Error>> {
+    fetch_info_from_source(source_for_address(str_address)?.as_ref())
+}
+
 /// Function to fetch the metadata as String, genesis hash as String, and network specs from given address,
 /// actually fetches stuff, is slow
+pub fn fetch_info_with_chainspecs(str_address: &str) -> Result<FetchedInfoWithChainSpecs, Box<dyn std::error::Error>> {
+    fetch_info_with_chainspecs_from_source(source_for_address(str_address)?.as_ref())
+}
 
-#[tokio::main]
-pub async fn fetch_info_with_chainspecs(str_address: &str) -> Result<FetchedInfoWithChainSpecs, Box<dyn std::error::Error>> {
-    let client = WsClientBuilder::default().build(str_address).await?;
-    let response: JsonValue = client.request("state_getMetadata", JsonRpcParams::NoParams).await?;
-    let meta = match response {
-        JsonValue::String(x) => x,
-        _ => return Err(Box::from("Unexpected metadata format")),
-    };
-    let response: JsonValue = client.request("chain_getBlockHash", JsonRpcParams::ArrayRef(&[JsonValue::Number(Number::from(0 as u8))])).await?;
-    let genesis_hash = match response {
-        JsonValue::String(x) => x,
-        _ => return Err(Box::from("Unexpected genesis hash format")),
-    };
-    let response: JsonValue = client.request("system_properties", JsonRpcParams::NoParams).await?;
-    let properties = match response {
-        JsonValue::Object(x) => x,
-        _ => return Err(Box::from("Unexpected system properties format")),
-    };
-    Ok(FetchedInfoWithChainSpecs{
-        meta,
-        genesis_hash,
-        properties,
-    })
+
+/// Helper function to make rpc params from slice of values
+fn rpc_params (params: &[JsonValue]) -> JsonRpcParams {
+    if params.is_empty() {JsonRpcParams::NoParams}
+    else {JsonRpcParams::ArrayRef(params)}
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::start_stub_server;
+    use std::convert::TryInto;
+
+    const WESTEND_RECORDING: &str = "for_tests/rpc_westend9070";
+
+    #[test]
+    fn mock_source_fetches_recorded_info() {
+        let source = MockSource::from_recording(WESTEND_RECORDING).unwrap();
+        let fetched = fetch_info_with_chainspecs_from_source(&source).unwrap();
+        assert!(fetched.meta == std::fs::read_to_string("for_tests/westend9070").unwrap().trim(), "Metadata falls back to state_getMetadata.");
+        assert!(fetched.genesis_hash == "0xe143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e", "Unexpected genesis hash: {}", fetched.genesis_hash);
+        assert!(fetched.properties.get("tokenSymbol") == Some(&JsonValue::from("WND")), "Unexpected properties: {:?}", fetched.properties);
+    }
+
+    #[test]
+    fn mock_source_unknown_method() {
+        let source = MockSource::new(Map::new());
+        match source.fetch_genesis_hash() {
+            Ok(_) => panic!("Empty recording should not produce genesis hash."),
+            Err(e) => assert!(e.to_string() == "No recorded result for method chain_getBlockHash", "Unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn file_source_from_scale_and_chain_spec() {
+        let scale_file = "for_tests/file_source_westend9070.scale";
+        let meta_hex = std::fs::read_to_string("for_tests/westend9070").unwrap();
+        std::fs::write(scale_file, hex::decode(meta_hex.trim().trim_start_matches("0x")).unwrap()).unwrap();
+        let genesis_hash: [u8; 32] = hex::decode("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap().try_into().unwrap();
+        let source = FileSource::new(scale_file, &genesis_hash, Some("for_tests/chain_spec_westend.json")).unwrap();
+        let fetched = fetch_info_with_chainspecs_from_source(&source).unwrap();
+        std::fs::remove_file(scale_file).unwrap();
+        assert!(fetched.meta == meta_hex.trim(), "Metadata from .scale file differs from the one in hex file.");
+        assert!(fetched.genesis_hash == "0xe143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e", "Unexpected genesis hash: {}", fetched.genesis_hash);
+        assert!(fetched.properties.get("ss58Format") == Some(&JsonValue::from(42)), "Unexpected properties: {:?}", fetched.properties);
+    }
+
+    #[test]
+    fn file_source_without_chain_spec() {
+        let source = FileSource::new("for_tests/westend9070", &[0; 32], None).unwrap();
+        assert!(source.fetch_meta().is_ok(), "Metadata expected from hex file.");
+        assert!(source.fetch_properties().is_err(), "No properties expected without chain spec.");
+    }
+
+    #[test]
+    fn http_source_with_stub_server() {
+        let server = start_stub_server(MockSource::from_recording(WESTEND_RECORDING).unwrap()).unwrap();
+        let fetched = fetch_info(&server.address).unwrap();
+        let mock = fetch_info_from_source(&MockSource::from_recording(WESTEND_RECORDING).unwrap()).unwrap();
+        assert!(fetched.meta == mock.meta, "Metadata fetched through http differs from recorded one.");
+        assert!(fetched.genesis_hash == mock.genesis_hash, "Genesis hash fetched through http differs from recorded one.");
+        let errors = server.errors();
+        assert!(errors.is_empty(), "Stub server errors: {:?}", errors);
+    }
+}
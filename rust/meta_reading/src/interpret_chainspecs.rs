@@ -1,56 +1,245 @@
 use serde_json::map::Map;
 use jsonrpsee_types::JsonValue;
 use std::convert::TryInto;
-use definitions::network_specs::ChainProperties;
+use definitions::network_specs::{ChainProperties, TokenInfo};
+
+use crate::decode_metadata::{decode_runtime_metadata, get_system_constant};
+
+/// Network properties found in metadata constants;
+/// decimals and unit are not recorded in metadata
+pub struct MetaProperties {
+    pub base58prefix: Option<u16>,
+}
+
+/// Network properties fetched via rpc call, cross-checked with metadata constants,
+/// with the list of found mismatches
+pub struct CheckedProperties {
+    pub properties: ChainProperties,
+    pub warnings: Vec<String>,
+}
+
 
 /// Function to interpret network properties fetched via rpc call
 
 pub fn interpret_properties (x: &Map<String, JsonValue>) -> Result<ChainProperties, Box<dyn std::error::Error>> {
-    let base58prefix: u16 = match x.get("ss58Format") {
+    let base58prefix = match get_base58prefix(x)? {
+        Some(a) => a,
+        None => return Err(Box::from("No base58prefix fetched.")),
+    };
+    let (decimals, unit, tokens) = get_tokens(x)?;
+    Ok(ChainProperties {
+        base58prefix,
+        decimals,
+        unit,
+        tokens,
+    })
+}
+
+
+/// Function to interpret network properties fetched via rpc call
+/// together with the metadata constants:
+/// base58 prefix from `System` pallet constant `SS58Prefix` is enforced by the runtime,
+/// and is used instead of fetched one, if the values are different, the mismatch is reported;
+/// if the base58 prefix is not fetched, the value from metadata is used
+
+pub fn interpret_properties_with_meta (x: &Map<String, JsonValue>, meta: &Vec<u8>) -> Result<CheckedProperties, Box<dyn std::error::Error>> {
+    let meta_properties = properties_from_metadata(meta)?;
+    let mut warnings: Vec<String> = Vec::new();
+    let base58prefix = match (meta_properties.base58prefix, get_base58prefix(x)?) {
+        (Some(a), Some(b)) => {
+            if a != b {warnings.push(format!("Base58 prefix {} fetched via rpc call differs from base58 prefix {} in metadata constants. Metadata value is used.", b, a))}
+            a
+        },
+        (Some(a), None) => a,
+        (None, Some(b)) => b,
+        (None, None) => return Err(Box::from("No base58prefix fetched or found in metadata.")),
+    };
+    let (decimals, unit, tokens) = get_tokens(x)?;
+    Ok(CheckedProperties {
+        properties: ChainProperties {
+            base58prefix,
+            decimals,
+            unit,
+            tokens,
+        },
+        warnings,
+    })
+}
+
+
+/// Function to read network properties from metadata constants;
+/// metadata is expected with `meta` prefix
+
+pub fn properties_from_metadata (meta: &Vec<u8>) -> Result<MetaProperties, String> {
+    if !meta.starts_with(&vec![109, 101, 116, 97]) {return Err(String::from("No 'meta' starting sequence in metadata"))}
+    match meta.get(4) {
+        Some(a) => if *a < 12 {return Err(String::from("RuntimeMetadata version incompatible"))},
+        None => return Err(String::from("No 'meta' starting sequence in metadata")),
+    }
+    let meta_back = decode_runtime_metadata(&meta[4..]).map_err(|e| e.to_string())?;
+// SS58Prefix is u16 in newer runtimes, u8 in older ones
+    let base58prefix = match get_system_constant(&meta_back, "SS58Prefix")? {
+        Some(a) => match a.len() {
+            1 => Some(a[0] as u16),
+            2 => Some(u16::from_le_bytes([a[0], a[1]])),
+            _ => return Err(String::from("Unexpected SS58Prefix format in metadata constants")),
+        },
+        None => None,
+    };
+    Ok(MetaProperties {
+        base58prefix,
+    })
+}
+
+
+/// Function to get network properties from chain spec json,
+/// properties are recorded in the same format as in `system_properties` rpc call
+
+pub fn properties_from_chain_spec (chain_spec: &str) -> Result<Map<String, JsonValue>, Box<dyn std::error::Error>> {
+    let chain_spec: JsonValue = serde_json::from_str(chain_spec)?;
+    match chain_spec.get("properties") {
+        Some(JsonValue::Object(a)) => Ok(a.to_owned()),
+        Some(_) => return Err(Box::from("Unexpected properties format in chain spec.")),
+        None => return Err(Box::from("No properties in chain spec.")),
+    }
+}
+
+
+fn get_base58prefix (x: &Map<String, JsonValue>) -> Result<Option<u16>, Box<dyn std::error::Error>> {
+    match x.get("ss58Format") {
         Some(a) => {
             match a {
                 JsonValue::Number(b) => {
                     match b.as_u64() {
-                        Some(c) => {
-                            c.try_into()?
-                        },
+                        Some(c) => Ok(Some(c.try_into()?)),
                         None => return Err(Box::from("Expected base58prefix to convert into u64.")),
                     }
                 },
+                JsonValue::Null => Ok(None),
                 _ => return Err(Box::from("Unsupported base58prefix format.")),
             }
         },
-        None => return Err(Box::from("No base58prefix fetched.")),
+        None => Ok(None),
+    }
+}
+
+/// Function to collect the network tokens from fetched properties:
+/// single-token networks have numeric `tokenDecimals` and string `tokenSymbol`,
+/// multi-token networks have arrays of same length, the first token is the native one;
+/// the list of tokens is kept only for multi-token networks
+fn get_tokens (x: &Map<String, JsonValue>) -> Result<(u8, String, Vec<TokenInfo>), Box<dyn std::error::Error>> {
+    let decimals = match get_decimals(x)? {
+        Some(a) => a,
+        None => return Err(Box::from("No decimals fetched.")),
     };
-    let decimals: u8 = match x.get("tokenDecimals") {
+    let units = match get_unit(x)? {
+        Some(a) => a,
+        None => return Err(Box::from("No unit fetched.")),
+    };
+    if decimals.len() != units.len() {return Err(Box::from("Decimals and units fetched are of different length."))}
+    if decimals.len() == 0 {return Err(Box::from("No decimals fetched."))}
+    let tokens = match decimals.len() {
+        1 => Vec::new(),
+        _ => decimals.iter().zip(units.iter()).map(|(decimals, unit)| TokenInfo{decimals: *decimals, unit: unit.to_string()}).collect(),
+    };
+    Ok((decimals[0], units[0].to_string(), tokens))
+}
+
+fn get_decimals (x: &Map<String, JsonValue>) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+    match x.get("tokenDecimals") {
         Some(a) => {
             match a {
-                JsonValue::Number(b) => {
-                    match b.as_u64() {
-                        Some(c) => {
-                            c.try_into()?
-                        },
-                        None => return Err(Box::from("Expected decimals to convert into u64.")),
-                    }
+                JsonValue::Number(_) => Ok(Some(vec![decimals_from_number(a)?])),
+                JsonValue::Array(b) => {
+                    let mut out: Vec<u8> = Vec::new();
+                    for c in b.iter() {out.push(decimals_from_number(c)?)}
+                    Ok(Some(out))
                 },
                 _ => return Err(Box::from("Unsupported decimals format.")),
             }
         },
-        None => return Err(Box::from("No decimals fetched.")),
-    };
-    let unit = match x.get("tokenSymbol") {
+        None => Ok(None),
+    }
+}
+
+fn decimals_from_number (a: &JsonValue) -> Result<u8, Box<dyn std::error::Error>> {
+    match a {
+        JsonValue::Number(b) => {
+            match b.as_u64() {
+                Some(c) => Ok(c.try_into()?),
+                None => return Err(Box::from("Expected decimals to convert into u64.")),
+            }
+        },
+        _ => return Err(Box::from("Unsupported decimals format.")),
+    }
+}
+
+fn get_unit (x: &Map<String, JsonValue>) -> Result<Option<Vec<String>>, Box<dyn std::error::Error>> {
+    match x.get("tokenSymbol") {
         Some(a) => {
             match a {
-                JsonValue::String(b) => b,
+                JsonValue::String(b) => Ok(Some(vec![b.to_string()])),
+                JsonValue::Array(b) => {
+                    let mut out: Vec<String> = Vec::new();
+                    for c in b.iter() {
+                        match c {
+                            JsonValue::String(d) => out.push(d.to_string()),
+                            _ => return Err(Box::from("Unsupported unit format.")),
+                        }
+                    }
+                    Ok(Some(out))
+                },
                 _ => return Err(Box::from("Unsupported unit format.")),
             }
         },
-        None => return Err(Box::from("No unit fetched.")),
-    };
-    Ok(ChainProperties {
-        base58prefix,
-        decimals,
-        unit: unit.to_string(),
-    })
+        None => Ok(None),
+    }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::meta_from_file;
+
+    fn rpc_properties (ss58_format: u64) -> Map<String, JsonValue> {
+        let mut properties = Map::new();
+        properties.insert(String::from("ss58Format"), JsonValue::from(ss58_format));
+        properties.insert(String::from("tokenDecimals"), JsonValue::from(12));
+        properties.insert(String::from("tokenSymbol"), JsonValue::from("WND"));
+        properties
+    }
+
+    #[test]
+    fn base58prefix_from_metadata() {
+        assert!(properties_from_metadata(&meta_from_file("for_tests/westend9070")).unwrap().base58prefix == Some(42), "Expected base58 prefix 42 for westend9070, recorded as u16.");
+        assert!(properties_from_metadata(&meta_from_file("for_tests/westend9030")).unwrap().base58prefix == Some(42), "Expected base58 prefix 42 for westend9030, recorded as u8.");
+        assert!(properties_from_metadata(&meta_from_file("for_tests/kusama9090")).unwrap().base58prefix == Some(2), "Expected base58 prefix 2 for kusama9090.");
+        assert!(properties_from_metadata(&b"meta".to_vec()).is_err(), "Metadata with only prefix should be reported.");
+    }
+
+    #[test]
+    fn rpc_properties_checked_with_metadata() {
+        let meta = meta_from_file("for_tests/westend9070");
+        let checked = interpret_properties_with_meta(&rpc_properties(42), &meta).unwrap();
+        assert!(checked.warnings.is_empty(), "Unexpected warnings: {:?}", checked.warnings);
+        let checked = interpret_properties_with_meta(&rpc_properties(0), &meta).unwrap();
+        assert!(checked.properties.base58prefix == 42, "Base58 prefix from metadata was expected to be used.");
+        assert!(checked.warnings.len() == 1, "Expected mismatch warning, got: {:?}", checked.warnings);
+    }
+
+    #[test]
+    fn multi_token_properties() {
+        let mut properties = Map::new();
+        properties.insert(String::from("ss58Format"), JsonValue::from(8));
+        properties.insert(String::from("tokenDecimals"), JsonValue::from(vec![12, 12, 10]));
+        properties.insert(String::from("tokenSymbol"), JsonValue::from(vec!["KAR", "KUSD", "KSM"]));
+        let chain_properties = interpret_properties(&properties).unwrap();
+        assert!((chain_properties.decimals == 12) && (chain_properties.unit == "KAR"), "Expected first token to be the native one.");
+        assert!(chain_properties.tokens.len() == 3, "Expected all tokens in list.");
+        assert!(chain_properties.tokens[2] == TokenInfo{decimals: 10, unit: String::from("KSM")}, "Unexpected token: {:?}", chain_properties.tokens[2]);
+        assert!(interpret_properties(&rpc_properties(42)).unwrap().tokens.is_empty(), "Single-token network expected to have no token list.");
+        properties.insert(String::from("tokenSymbol"), JsonValue::from(vec!["KAR", "KUSD"]));
+        assert!(interpret_properties(&properties).is_err(), "Decimals and units of different length expected to fail.");
+    }
+}
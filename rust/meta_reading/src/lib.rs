@@ -5,4 +5,14 @@ pub mod interpret_chainspecs;
 
 pub mod fetch_metadata;
 
+pub mod prune_metadata;
 
+pub mod metadata_v15;
+
+pub mod diff_metadata;
+
+#[cfg(any(test, feature = "stub-server"))]
+pub mod stub_server;
+
+#[cfg(test)]
+mod test_helpers;
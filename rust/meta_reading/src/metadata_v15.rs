@@ -0,0 +1,150 @@
+use frame_metadata::v14::{ExtrinsicMetadata, PalletCallMetadata, PalletConstantMetadata, PalletErrorMetadata, PalletEventMetadata, PalletMetadata, PalletStorageMetadata, RuntimeMetadataV14, SignedExtensionMetadata};
+use parity_scale_codec_derive::{Decode, Encode};
+use scale_info::{form::PortableForm, interner::UntrackedSymbol, PortableRegistry, TypeDef};
+use std::{any::TypeId, collections::BTreeMap};
+
+/// Type id within the types registry, as it is encoded in metadata
+pub type TypeSymbol = UntrackedSymbol<TypeId>;
+
+/// Struct for RuntimeMetadataV15, following the layout in substrate;
+/// storage, calls, events, constants, errors and signed extensions
+/// are encoded same way as in RuntimeMetadataV14
+#[derive(Clone, PartialEq, Debug, Decode, Encode)]
+pub struct RuntimeMetadataV15 {
+    pub types: PortableRegistry,
+    pub pallets: Vec<PalletMetadataV15>,
+    pub extrinsic: ExtrinsicMetadataV15,
+    pub ty: TypeSymbol,
+    pub apis: Vec<RuntimeApiMetadata>,
+    pub outer_enums: OuterEnums,
+    pub custom: BTreeMap<String, CustomValueMetadata>,
+}
+
+/// Pallet entry in RuntimeMetadataV15, differs from V14 only by the docs
+#[derive(Clone, PartialEq, Debug, Decode, Encode)]
+pub struct PalletMetadataV15 {
+    pub name: String,
+    pub storage: Option<PalletStorageMetadata<PortableForm>>,
+    pub calls: Option<PalletCallMetadata<PortableForm>>,
+    pub event: Option<PalletEventMetadata<PortableForm>>,
+    pub constants: Vec<PalletConstantMetadata<PortableForm>>,
+    pub error: Option<PalletErrorMetadata<PortableForm>>,
+    pub index: u8,
+    pub docs: Vec<String>,
+}
+
+/// Extrinsic information in RuntimeMetadataV15
+#[derive(Clone, PartialEq, Debug, Decode, Encode)]
+pub struct ExtrinsicMetadataV15 {
+    pub version: u8,
+    pub address_ty: TypeSymbol,
+    pub call_ty: TypeSymbol,
+    pub signature_ty: TypeSymbol,
+    pub extra_ty: TypeSymbol,
+    pub signed_extensions: Vec<SignedExtensionMetadata<PortableForm>>,
+}
+
+/// Runtime API trait information
+#[derive(Clone, PartialEq, Debug, Decode, Encode)]
+pub struct RuntimeApiMetadata {
+    pub name: String,
+    pub methods: Vec<RuntimeApiMethodMetadata>,
+    pub docs: Vec<String>,
+}
+
+/// Runtime API method information
+#[derive(Clone, PartialEq, Debug, Decode, Encode)]
+pub struct RuntimeApiMethodMetadata {
+    pub name: String,
+    pub inputs: Vec<RuntimeApiMethodParamMetadata>,
+    pub output: TypeSymbol,
+    pub docs: Vec<String>,
+}
+
+/// Runtime API method parameter
+#[derive(Clone, PartialEq, Debug, Decode, Encode)]
+pub struct RuntimeApiMethodParamMetadata {
+    pub name: String,
+    pub ty: TypeSymbol,
+}
+
+/// Types of the outer enums of the runtime: RuntimeCall, RuntimeEvent and RuntimeError
+#[derive(Clone, PartialEq, Debug, Decode, Encode)]
+pub struct OuterEnums {
+    pub call_enum_ty: TypeSymbol,
+    pub event_enum_ty: TypeSymbol,
+    pub error_enum_ty: TypeSymbol,
+}
+
+/// Custom value in RuntimeMetadataV15, with its type and encoded value
+#[derive(Clone, PartialEq, Debug, Decode, Encode)]
+pub struct CustomValueMetadata {
+    pub ty: TypeSymbol,
+    pub value: Vec<u8>,
+}
+
+
+impl RuntimeMetadataV15 {
+
+    /// Function to find the type of pallet calls in outer call enum:
+    /// RuntimeCall has a variant with the pallet index, with a single field of pallet call type
+    pub fn call_type_from_outer_enum (&self, pallet_index: u8) -> Option<TypeSymbol> {
+        let call_enum = self.types.resolve(self.outer_enums.call_enum_ty.id())?;
+        if let TypeDef::Variant(x) = call_enum.type_def() {
+            for y in x.variants().iter() {
+                if y.index() == pallet_index {
+                    if y.fields().len() == 1 {return Some(y.fields()[0].ty().clone())}
+                    else {return None}
+                }
+            }
+        }
+        None
+    }
+
+    /// Function to transform RuntimeMetadataV15 into RuntimeMetadataV14,
+    /// so that the decoding could proceed same way as for RuntimeMetadataV14.
+    /// If the pallet entry has no calls, but the outer call enum has the variant for the pallet,
+    /// the calls type is taken from the outer call enum.
+    /// RuntimeMetadataV15 has no single extrinsic type, call type is used in its place,
+    /// extrinsic type is not used in decoding.
+    pub fn to_v14 (&self) -> RuntimeMetadataV14 {
+        let pallets = self.pallets.iter().map(|x| {
+            let calls = match &x.calls {
+                Some(a) => Some(a.clone()),
+                None => self.call_type_from_outer_enum(x.index).map(|ty| PalletCallMetadata{ty}),
+            };
+            PalletMetadata {
+                name: x.name.to_string(),
+                storage: x.storage.clone(),
+                calls,
+                event: x.event.clone(),
+                constants: x.constants.clone(),
+                error: x.error.clone(),
+                index: x.index,
+            }
+        }).collect();
+        RuntimeMetadataV14 {
+            types: self.types.clone(),
+            pallets,
+            extrinsic: ExtrinsicMetadata {
+                ty: self.extrinsic.call_ty.clone(),
+                version: self.extrinsic.version,
+                signed_extensions: self.extrinsic.signed_extensions.clone(),
+            },
+            ty: self.ty.clone(),
+        }
+    }
+
+    /// Function to show the information present only in RuntimeMetadataV15:
+    /// runtime api methods and custom values
+    pub fn show_extras (&self) -> String {
+        let apis: Vec<String> = self.apis.iter().map(|x| format!("{} ({} methods)", x.name, x.methods.len())).collect();
+        let custom: Vec<String> = self.custom.keys().map(|x| x.to_string()).collect();
+        format!("runtime apis: {}; custom values: {}", show_list(&apis), show_list(&custom))
+    }
+}
+
+fn show_list (list: &Vec<String>) -> String {
+    if list.is_empty() {String::from("none")}
+    else {list.join(", ")}
+}
@@ -0,0 +1,403 @@
+use frame_metadata::{RuntimeMetadata, decode_different::DecodeDifferent, v14::RuntimeMetadataV14};
+use parity_scale_codec::{Compact, Decode, Encode};
+use scale_info::{PortableRegistry, TypeDef};
+
+use crate::decode_metadata::{decode_runtime_metadata, get_meta_const_light};
+
+/// Struct to store pruned metadata together with the size information
+pub struct PrunedMetadata {
+    pub meta: Vec<u8>,
+    pub full_size: usize,
+    pub pruned_size: usize,
+}
+
+impl PrunedMetadata {
+    /// Function to show how the pruned metadata size compares to the full one
+    pub fn show(&self) -> String {
+        format!("Pruned metadata: {} bytes, full metadata: {} bytes ({:.1}% of full size).", self.pruned_size, self.full_size, 100.0*(self.pruned_size as f64)/(self.full_size as f64))
+    }
+}
+
+
+/// Function to produce reduced metadata, containing only the calls, the types they reach
+/// and the constants of System pallet: storage, events, errors and constants of other pallets
+/// are never used by the signer for decoding.
+/// Metadata is expected with `meta` prefix, as it comes from the rpc call.
+/// RuntimeMetadataV15 is pruned as RuntimeMetadataV14, since runtime api, outer enums
+/// and custom values are not needed for signing.
+/// Before the pruned metadata is returned, it is checked that all calls
+/// would be decoded identically with full and with pruned metadata.
+
+pub fn prune_metadata (meta: &Vec<u8>) -> Result<PrunedMetadata, String> {
+    
+    if !meta.starts_with(&vec![109, 101, 116, 97]) {return Err(String::from("No 'meta' starting sequence in metadata"))}
+    match meta.get(4) {
+        Some(a) => if *a < 12 {return Err(String::from("RuntimeMetadata version incompatible"))},
+        None => return Err(String::from("No 'meta' starting sequence in metadata")),
+    }
+    let full = decode_runtime_metadata(&meta[4..]).map_err(|e| e.to_string())?;
+    
+    let pruned = match &full {
+        RuntimeMetadata::V12(metadata_v12) => {
+            let mut pruned_v12 = metadata_v12.clone();
+            if let DecodeDifferent::Decoded(modules) = &mut pruned_v12.modules {
+                for x in modules.iter_mut() {
+                    x.storage = None;
+                    x.event = None;
+                    x.errors = DecodeDifferent::Decoded(Vec::new());
+                    if x.name != DecodeDifferent::Encode("System") {x.constants = DecodeDifferent::Decoded(Vec::new())}
+                }
+            }
+            RuntimeMetadata::V12(pruned_v12)
+        },
+        RuntimeMetadata::V13(metadata_v13) => {
+            let mut pruned_v13 = metadata_v13.clone();
+            if let DecodeDifferent::Decoded(modules) = &mut pruned_v13.modules {
+                for x in modules.iter_mut() {
+                    x.storage = None;
+                    x.event = None;
+                    x.errors = DecodeDifferent::Decoded(Vec::new());
+                    if x.name != DecodeDifferent::Encode("System") {x.constants = DecodeDifferent::Decoded(Vec::new())}
+                }
+            }
+            RuntimeMetadata::V13(pruned_v13)
+        },
+        RuntimeMetadata::V14(metadata_v14) => {
+            let mut pruned_v14 = metadata_v14.clone();
+            for x in pruned_v14.pallets.iter_mut() {
+                x.storage = None;
+                x.event = None;
+                x.error = None;
+                if x.name != "System" {x.constants = Vec::new()}
+            }
+            pruned_v14.types = prune_types(&pruned_v14)?;
+            RuntimeMetadata::V14(pruned_v14)
+        },
+        _ => return Err(String::from("RuntimeMetadata version incompatible")),
+    };
+    
+    let pruned_meta = [vec![109, 101, 116, 97], pruned.encode()].concat();
+    
+// check that the pruned metadata decodes and is compatible with the full one
+    let pruned_back = match RuntimeMetadata::decode(&mut &pruned_meta[4..]) {
+        Ok(a) => a,
+        Err(_) => return Err(String::from("Pruned metadata could not be decoded")),
+    };
+    check_pruned_metadata(&full, &pruned_back)?;
+    
+    Ok(PrunedMetadata {
+        full_size: meta.len(),
+        pruned_size: pruned_meta.len(),
+        meta: pruned_meta,
+    })
+}
+
+
+/// Function to collect ids of all types reachable from given types
+fn reachable_types (roots: Vec<u32>, types: &PortableRegistry) -> Result<Vec<u32>, String> {
+    let mut found: Vec<u32> = Vec::new();
+    let mut queue = roots;
+    while let Some(id) = queue.pop() {
+        if found.contains(&id) {continue}
+        let current_type = match types.resolve(id) {
+            Some(a) => a,
+            None => return Err(format!("Type {} could not be resolved", id)),
+        };
+        found.push(id);
+        match current_type.type_def() {
+            TypeDef::Composite(x) => {
+                for y in x.fields().iter() {queue.push(y.ty().id())}
+            },
+            TypeDef::Variant(x) => {
+                for y in x.variants().iter() {
+                    for z in y.fields().iter() {queue.push(z.ty().id())}
+                }
+            },
+            TypeDef::Sequence(x) => queue.push(x.type_param().id()),
+            TypeDef::Array(x) => queue.push(x.type_param().id()),
+            TypeDef::Tuple(x) => {
+                for y in x.fields().iter() {queue.push(y.id())}
+            },
+            TypeDef::Primitive(_) => (),
+            TypeDef::Compact(x) => queue.push(x.type_param().id()),
+            TypeDef::BitSequence(x) => {
+                queue.push(x.bit_store_type().id());
+                queue.push(x.bit_order_type().id());
+            },
+            TypeDef::Range(x) => queue.push(x.index_type().id()),
+        }
+    }
+    Ok(found)
+}
+
+
+/// Function to collect ids of the types that are used for decoding:
+/// types reachable from pallet calls, System constants and extrinsic information
+fn used_types (metadata_v14: &RuntimeMetadataV14) -> Result<Vec<u32>, String> {
+    let mut roots: Vec<u32> = vec![metadata_v14.extrinsic.ty.id(), metadata_v14.ty.id()];
+    for x in metadata_v14.extrinsic.signed_extensions.iter() {
+        roots.push(x.ty.id());
+        roots.push(x.additional_signed.id());
+    }
+    for x in metadata_v14.pallets.iter() {
+        if let Some(a) = &x.calls {roots.push(a.ty.id())}
+        for y in x.constants.iter() {roots.push(y.ty.id())}
+    }
+    reachable_types(roots, &metadata_v14.types)
+}
+
+
+/// Function to replace unused types in types registry with unit type.
+/// Type ids are positions in the registry and could not be changed,
+/// so the unused types are not removed, but replaced with the shortest possible type;
+/// if there is no unit type in registry, the registry is left as is.
+fn prune_types (metadata_v14: &RuntimeMetadataV14) -> Result<PortableRegistry, String> {
+    let used = used_types(metadata_v14)?;
+    let unit_type_encoded = {
+        let mut found = None;
+        for x in metadata_v14.types.types().iter() {
+            if let TypeDef::Tuple(a) = x.ty().type_def() {
+                if a.fields().is_empty() && x.ty().path().segments().is_empty() && x.ty().docs().is_empty() {
+                    found = Some(x.ty().encode());
+                    break;
+                }
+            }
+        }
+        match found {
+            Some(a) => a,
+            None => return Ok(metadata_v14.types.clone()),
+        }
+    };
+    let mut registry_encoded = Compact(metadata_v14.types.types().len() as u32).encode();
+    for x in metadata_v14.types.types().iter() {
+        let type_encoded = x.encode();
+        if used.contains(&x.id()) {registry_encoded.extend_from_slice(&type_encoded)}
+        else {
+        // encoded type entry starts with type id, followed by encoded type itself
+            let id_len = type_encoded.len() - x.ty().encode().len();
+            registry_encoded.extend_from_slice(&type_encoded[..id_len]);
+            registry_encoded.extend_from_slice(&unit_type_encoded);
+        }
+    }
+    match PortableRegistry::decode(&mut &registry_encoded[..]) {
+        Ok(a) => Ok(a),
+        Err(_) => return Err(String::from("Pruned types registry could not be decoded")),
+    }
+}
+
+
+/// Function to check that all calls decode identically with full and pruned metadata:
+/// pallets must be same and in same order, with same indices and same calls,
+/// for metadata V14 all types reachable from the calls must be the same,
+/// and the version constant of System pallet must be the same.
+
+pub fn check_pruned_metadata (full: &RuntimeMetadata, pruned: &RuntimeMetadata) -> Result<(), String> {
+    match (full, pruned) {
+        (RuntimeMetadata::V12(full_v12), RuntimeMetadata::V12(pruned_v12)) => {
+            if let (DecodeDifferent::Decoded(full_modules), DecodeDifferent::Decoded(pruned_modules)) = (&full_v12.modules, &pruned_v12.modules) {
+                if full_modules.len() != pruned_modules.len() {return Err(String::from("Number of pallets differs"))}
+                for (x, y) in full_modules.iter().zip(pruned_modules.iter()) {
+                    if (x.name != y.name)||(x.index != y.index)||(x.calls != y.calls) {return Err(format!("Pallet with index {} differs", x.index))}
+                }
+            }
+            else {return Err(String::from("Pallets could not be compared"))}
+        },
+        (RuntimeMetadata::V13(full_v13), RuntimeMetadata::V13(pruned_v13)) => {
+            if let (DecodeDifferent::Decoded(full_modules), DecodeDifferent::Decoded(pruned_modules)) = (&full_v13.modules, &pruned_v13.modules) {
+                if full_modules.len() != pruned_modules.len() {return Err(String::from("Number of pallets differs"))}
+                for (x, y) in full_modules.iter().zip(pruned_modules.iter()) {
+                    if (x.name != y.name)||(x.index != y.index)||(x.calls != y.calls) {return Err(format!("Pallet with index {} differs", x.index))}
+                }
+            }
+            else {return Err(String::from("Pallets could not be compared"))}
+        },
+        (RuntimeMetadata::V14(full_v14), RuntimeMetadata::V14(pruned_v14)) => {
+            if full_v14.pallets.len() != pruned_v14.pallets.len() {return Err(String::from("Number of pallets differs"))}
+            let mut call_types: Vec<u32> = Vec::new();
+            for (x, y) in full_v14.pallets.iter().zip(pruned_v14.pallets.iter()) {
+                if (x.name != y.name)||(x.index != y.index)||(x.calls != y.calls) {return Err(format!("Pallet {} differs", x.name))}
+                if let Some(a) = &x.calls {call_types.push(a.ty.id())}
+            }
+            if full_v14.extrinsic != pruned_v14.extrinsic {return Err(String::from("Extrinsic information differs"))}
+            for id in reachable_types(call_types, &full_v14.types)?.iter() {
+                if full_v14.types.resolve(*id) != pruned_v14.types.resolve(*id) {return Err(format!("Type {} reachable from calls differs", id))}
+            }
+        },
+        _ => return Err(String::from("Metadata versions differ")),
+    }
+    let full_version = get_meta_const_light(full).map_err(|e| e.to_string())?;
+    let pruned_version = get_meta_const_light(pruned).map_err(|e| e.to_string())?;
+    if full_version != pruned_version {return Err(String::from("Version constant differs"))}
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::meta_from_file;
+    
+    #[test]
+    fn prune_westend9070() {
+        let meta = meta_from_file("for_tests/westend9070");
+        let pruned = prune_metadata(&meta).unwrap();
+        assert!(pruned.pruned_size < pruned.full_size, "Pruned metadata is not smaller. {}", pruned.show());
+        assert!(get_meta_const_light(&RuntimeMetadata::decode(&mut &pruned.meta[4..]).unwrap()).unwrap() == crate::decode_metadata::get_meta_const(&meta).unwrap(), "Version constant changed after pruning.");
+    }
+    
+    #[test]
+    fn prune_polkadot30() {
+        let meta = meta_from_file("for_tests/polkadot30");
+        let pruned = prune_metadata(&meta).unwrap();
+        assert!(pruned.pruned_size < pruned.full_size, "Pruned metadata is not smaller. {}", pruned.show());
+    }
+    
+    #[test]
+    fn prune_too_short() {
+        assert!(prune_metadata(&b"meta".to_vec()).is_err(), "Metadata with only prefix should be reported.");
+    }
+    
+    #[test]
+    fn check_detects_missing_calls() {
+        let meta = meta_from_file("for_tests/westend9070");
+        let full = RuntimeMetadata::decode(&mut &meta[4..]).unwrap();
+        let mut damaged = RuntimeMetadata::decode(&mut &meta[4..]).unwrap();
+        if let RuntimeMetadata::V13(ref mut metadata_v13) = damaged {
+            if let DecodeDifferent::Decoded(modules) = &mut metadata_v13.modules {
+                for x in modules.iter_mut() {
+                    if x.name == DecodeDifferent::Encode("Balances") {x.calls = None}
+                }
+            }
+        }
+        else {panic!("Expected metadata V13 for westend9070.")}
+        assert!(check_pruned_metadata(&full, &damaged).is_err(), "Removed calls were not detected.");
+    }
+    
+    #[allow(dead_code, non_camel_case_types)]
+    mod runtime_v14 {
+        use frame_metadata::v14::{ExtrinsicMetadata, PalletCallMetadata, PalletConstantMetadata, PalletErrorMetadata, PalletEventMetadata, PalletMetadata, RuntimeMetadataV14, SignedExtensionMetadata};
+        use parity_scale_codec::Encode;
+        use scale_info::{TypeInfo, meta_type};
+        
+        #[derive(TypeInfo)]
+        pub struct AccountId32([u8; 32]);
+        
+        #[derive(TypeInfo)]
+        pub struct RuntimeVersion {
+            spec_name: String,
+            impl_name: String,
+            authoring_version: u32,
+            spec_version: u32,
+            impl_version: u32,
+            apis: Vec<([u8; 8], u32)>,
+            transaction_version: u32,
+        }
+        
+        #[derive(TypeInfo)]
+        pub struct ChargeTransactionPayment(#[codec(compact)] u128);
+        
+        #[derive(TypeInfo)]
+        pub enum BalancesCall {
+            transfer {
+                dest: AccountId32,
+                #[codec(compact)]
+                value: u128,
+            },
+        }
+        
+        /// Event and error types are reachable only from events and errors
+        #[derive(TypeInfo)]
+        pub enum BalancesEvent {
+            Transfer {
+                from: AccountId32,
+                to: AccountId32,
+                amount: u128,
+                memo: [u8; 64],
+            },
+        }
+        
+        #[derive(TypeInfo)]
+        pub enum BalancesError {
+            InsufficientBalance,
+            ExistentialDeposit,
+        }
+        
+        pub fn metadata() -> RuntimeMetadataV14 {
+            let version = (String::from("westend"), String::from("parity-westend"), 2u32, 9110u32, 0u32, Vec::<([u8; 8], u32)>::new(), 8u32).encode();
+            let pallets = vec![
+                PalletMetadata {
+                    name: "System",
+                    storage: None,
+                    calls: None,
+                    event: None,
+                    constants: vec![PalletConstantMetadata {name: "Version", ty: meta_type::<RuntimeVersion>(), value: version, docs: Vec::new()}],
+                    error: None,
+                    index: 0,
+                },
+                PalletMetadata {
+                    name: "Balances",
+                    storage: None,
+                    calls: Some(PalletCallMetadata {ty: meta_type::<BalancesCall>()}),
+                    event: Some(PalletEventMetadata {ty: meta_type::<BalancesEvent>()}),
+                    constants: vec![PalletConstantMetadata {name: "ExistentialDeposit", ty: meta_type::<u64>(), value: 10_000_000_000u64.encode(), docs: Vec::new()}],
+                    error: Some(PalletErrorMetadata {ty: meta_type::<BalancesError>()}),
+                    index: 4,
+                },
+            ];
+            let extrinsic = ExtrinsicMetadata {
+                ty: meta_type::<()>(),
+                version: 4,
+                signed_extensions: vec![SignedExtensionMetadata {identifier: "ChargeTransactionPayment", ty: meta_type::<ChargeTransactionPayment>(), additional_signed: meta_type::<()>()}],
+            };
+            RuntimeMetadataV14::new(pallets, extrinsic, meta_type::<()>())
+        }
+    }
+    
+    /// Function to find type id in registry by the last segment of type path
+    fn type_id (name: &str, metadata_v14: &RuntimeMetadataV14) -> u32 {
+        metadata_v14.types.types().iter().find(|a| a.ty().path().segments().last().map(|b| b.as_str()) == Some(name)).unwrap().id()
+    }
+    
+    #[test]
+    fn prune_v14() {
+        let full_v14 = runtime_v14::metadata();
+        let meta = [vec![109, 101, 116, 97], RuntimeMetadata::V14(full_v14.clone()).encode()].concat();
+        let pruned = prune_metadata(&meta).unwrap();
+        assert!(pruned.pruned_size < pruned.full_size, "Pruned metadata is not smaller. {}", pruned.show());
+        
+        let pruned_v14 = match RuntimeMetadata::decode(&mut &pruned.meta[4..]).unwrap() {
+            RuntimeMetadata::V14(a) => a,
+            _ => panic!("Expected pruned metadata V14."),
+        };
+        assert!(pruned_v14.types.types().len() == full_v14.types.types().len(), "Type ids should be kept.");
+        for x in pruned_v14.pallets.iter() {
+            assert!(x.storage.is_none() && x.event.is_none() && x.error.is_none(), "Storage, events and errors should be removed.");
+            if x.name != "System" {assert!(x.constants.is_empty(), "Constants of pallets other than System should be removed.")}
+        }
+        for name in ["BalancesEvent", "BalancesError"].iter() {
+            match pruned_v14.types.resolve(type_id(name, &full_v14)).unwrap().type_def() {
+                TypeDef::Tuple(a) => assert!(a.fields().is_empty(), "Type {} should be replaced with unit type.", name),
+                _ => panic!("Type {} should be replaced with unit type.", name),
+            }
+        }
+        for name in ["BalancesCall", "AccountId32", "RuntimeVersion", "ChargeTransactionPayment"].iter() {
+            let id = type_id(name, &full_v14);
+            assert!(pruned_v14.types.resolve(id) == full_v14.types.resolve(id), "Type {} is used for decoding and should be kept.", name);
+        }
+        check_pruned_metadata(&RuntimeMetadata::V14(full_v14), &RuntimeMetadata::V14(pruned_v14)).unwrap();
+    }
+    
+    #[test]
+    fn check_detects_pruned_call_types() {
+        let full_v14 = runtime_v14::metadata();
+    // types pruned as if Balances had no calls, while the calls are kept
+        let mut no_calls_v14 = full_v14.clone();
+        for x in no_calls_v14.pallets.iter_mut() {x.calls = None}
+        let mut damaged_v14 = full_v14.clone();
+        damaged_v14.types = prune_types(&no_calls_v14).unwrap();
+        match check_pruned_metadata(&RuntimeMetadata::V14(full_v14), &RuntimeMetadata::V14(damaged_v14)) {
+            Ok(()) => panic!("Pruned types reachable from calls were not detected."),
+            Err(e) => assert!(e.contains("reachable from calls differs"), "Unexpected error: {}", e),
+        }
+    }
+}
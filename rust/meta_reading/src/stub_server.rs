@@ -0,0 +1,88 @@
+use jsonrpsee_types::JsonValue;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+
+use crate::fetch_metadata::{MetadataSource, MockSource};
+
+/// Struct to store the address of running stub server
+/// and the errors that occurred while processing the connections
+pub struct StubServer {
+    pub address: String,
+    errors: Receiver<String>,
+}
+
+impl StubServer {
+    /// Function to get all connection errors received since the last call
+    pub fn errors(&self) -> Vec<String> {
+        self.errors.try_iter().collect()
+    }
+}
+
+/// Function to start local http json-rpc server, answering the requests
+/// with the results recorded in MockSource, for integration tests without live nodes.
+/// Server runs in separate thread until the process ends;
+/// errors in connections are not printed, but sent back to the caller through StubServer.
+pub fn start_stub_server (source: MockSource) -> std::io::Result<StubServer> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = format!("http://{}", listener.local_addr()?);
+    let (sender, errors) = channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let result = match stream {
+                Ok(a) => handle_connection(a, &source).map_err(|e| format!("Stub server error. {}", e)),
+                Err(e) => Err(format!("Stub server connection error. {}", e)),
+            };
+            if let Err(e) = result {
+                if sender.send(e).is_err() {break}
+            }
+        }
+    });
+    Ok(StubServer{address, errors})
+}
+
+
+/// Function to process a single http request with json-rpc call;
+/// connection is closed after the reply
+fn handle_connection (stream: TcpStream, source: &MockSource) -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {return Ok(())}
+        let line = line.trim();
+        if line.is_empty() {break}
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {content_length = value.trim().parse()?}
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let call: JsonValue = match serde_json::from_slice(&body) {
+        Ok(a) => a,
+        Err(e) => return reply(stream, serde_json::json!({"jsonrpc": "2.0", "error": {"code": -32700, "message": format!("Parse error. {}", e)}, "id": JsonValue::Null})),
+    };
+    let id = call.get("id").cloned().unwrap_or(JsonValue::Null);
+    let params = match call.get("params") {
+        Some(JsonValue::Array(a)) => a.to_vec(),
+        _ => Vec::new(),
+    };
+    let result = match call.get("method") {
+        Some(JsonValue::String(method)) => match source.request(method, &params) {
+            Ok(result) => serde_json::json!({"jsonrpc": "2.0", "result": result, "id": id}),
+            Err(e) => serde_json::json!({"jsonrpc": "2.0", "error": {"code": -32601, "message": e.to_string()}, "id": id}),
+        },
+        _ => serde_json::json!({"jsonrpc": "2.0", "error": {"code": -32600, "message": "Invalid request"}, "id": id}),
+    };
+    reply(stream, result)
+}
+
+
+/// Function to write json-rpc reply into http response
+fn reply (mut stream: TcpStream, reply: JsonValue) -> Result<(), Box<dyn std::error::Error>> {
+    let reply = reply.to_string();
+    write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", reply.len(), reply)?;
+    stream.flush()?;
+    Ok(())
+}
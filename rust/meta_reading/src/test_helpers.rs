@@ -0,0 +1,9 @@
+//! Helpers shared by the tests of metadata reading modules
+
+use std::fs::read_to_string;
+
+/// Function to read metadata with `meta` prefix from test file with hex line
+pub fn meta_from_file (filename: &str) -> Vec<u8> {
+    let meta = read_to_string(filename).unwrap();
+    hex::decode(meta.trim().trim_start_matches("0x")).unwrap()
+}
@@ -1,6 +1,6 @@
 use rscam::{Camera, Config};
 use std::io::Write;
-use image::{GenericImageView, Pixel, Luma, ImageBuffer, GrayImage};
+use image::{AnimationDecoder, DynamicImage, GenericImageView, Pixel, Luma, ImageBuffer, GrayImage, codecs::png::PngDecoder};
 use quircs;
 use hex;
 use qr_reader_phone::process_payload::{process_decoded_payload, Ready, InProgress};
@@ -51,6 +51,48 @@ pub fn run_with_camera() -> anyhow::Result<String> {
 }
 
 
+/// Function to read qr code from png or apng file, for example the one produced by `generate_message`,
+/// frames are processed in the same way as the camera captures;
+/// output is the decoded payload as hex string
+pub fn run_with_file(filename: &str) -> anyhow::Result<String> {
+
+    let file = match std::fs::File::open(filename) {
+        Ok(x) => x,
+        Err(e) => return Err(anyhow!("Error opening file {}. {}", filename, e)),
+    };
+    let decoder = match PngDecoder::new(std::io::BufReader::new(file)) {
+        Ok(x) => x,
+        Err(e) => return Err(anyhow!("Error decoding png file {}. {}", filename, e)),
+    };
+    let frames: Vec<GrayImage> = {
+        if decoder.is_apng() {
+            match decoder.apng().into_frames().collect_frames() {
+                Ok(a) => a.into_iter().map(|frame| DynamicImage::ImageRgba8(frame.into_buffer()).to_luma8()).collect(),
+                Err(e) => return Err(anyhow!("Error decoding apng frames from file {}. {}", filename, e)),
+            }
+        }
+        else {
+            match DynamicImage::from_decoder(decoder) {
+                Ok(a) => vec![a.to_luma8()],
+                Err(e) => return Err(anyhow!("Error decoding png file {}. {}", filename, e)),
+            }
+        }
+    };
+    
+    let mut out = Ready::NotYet(InProgress::None);
+    for img in frames.iter() {
+        out = match out {
+            Ready::NotYet(decoding) => process_qr_image (img, decoding)?,
+            Ready::Yes(a) => return Ok(hex::encode(&a)),
+        };
+    }
+    match out {
+        Ready::Yes(a) => Ok(hex::encode(&a)),
+        Ready::NotYet(_) => return Err(anyhow!("Not enough qr frames in file {} to decode the payload.", filename)),
+    }
+}
+
+
 fn camera_capture(camera: &Camera) -> anyhow::Result<ImageBuffer<Luma<u8>, Vec<u8>>> {
     let frame = match camera.capture() {
         Ok(x) => x,
@@ -2,6 +2,7 @@ use hex;
 use std::convert::TryInto;
 use anyhow::anyhow;
 use serde_json;
+use definitions::{qr_transfers::ContentWatchOnly, users::{escape_json, generate_address_key, print_as_base58}};
 
 pub mod process_payload;
 use process_payload::{process_decoded_payload, Ready, InProgress};
@@ -79,19 +80,21 @@ pub fn get_length (line: &str, cleaned: bool) -> anyhow::Result<u32> {
     }
 }
 
-pub fn decode_sequence (jsonline: &str, cleaned: bool) -> anyhow::Result<String> {
+/// function to collect bytes from the set of scanned qr frames
+
+fn collect_sequence (jsonline: &str, cleaned: bool) -> anyhow::Result<Vec<u8>> {
     let set: Vec<String> = match serde_json::from_str(jsonline) {
         Ok(a) => a,
         Err(_) => return Err(anyhow!("Unable to parse incoming string set")),
     };
     let mut out = Ready::NotYet(InProgress::None);
-    let mut final_result: Option<String> = None;
+    let mut final_result: Option<Vec<u8>> = None;
     for x in set.iter() {
         let payload = get_payload(x, cleaned)?;
         if let Ready::NotYet(decoding) = out {
             out = process_decoded_payload (payload, decoding)?;
             if let Ready::Yes(v) = out {
-                final_result = Some(hex::encode(&v));
+                final_result = Some(v);
                 break;
             }
         }
@@ -102,11 +105,55 @@ pub fn decode_sequence (jsonline: &str, cleaned: bool) -> anyhow::Result<String>
     }
 }
 
+pub fn decode_sequence (jsonline: &str, cleaned: bool) -> anyhow::Result<String> {
+    Ok(hex::encode(collect_sequence(jsonline, cleaned)?))
+}
+
+/// function to decode watch-only multi-account export from the set of scanned qr frames,
+/// for import into companion hot wallet apps;
+/// path is printed only if it was exported;
+/// seed names, names and paths are escaped, since these could be set to anything by user
+
+pub fn decode_watch_only (jsonline: &str, cleaned: bool) -> anyhow::Result<String> {
+    let content = collect_sequence(jsonline, cleaned)?;
+    let entries = match ContentWatchOnly::from_vec(&content).entries() {
+        Ok(a) => a,
+        Err(e) => return Err(anyhow!("Unable to decode watch-only export. {}", e)),
+    };
+    let mut out = String::from("[");
+    for (i, x) in entries.iter().enumerate() {
+        if i>0 {out.push_str(",")}
+        let address_key = match generate_address_key(&x.public_key, x.encryption) {
+            Ok(a) => a,
+            Err(e) => return Err(anyhow!("Bad public key in watch-only export. {}", e)),
+        };
+        let mut networks_print = String::new();
+        for (j, network) in x.networks.iter().enumerate() {
+            if j>0 {networks_print.push_str(",")}
+            let ss58 = match print_as_base58(&address_key, x.encryption, Some(network.base58prefix)) {
+                Ok(a) => a,
+                Err(e) => return Err(anyhow!("Unable to make ss58 address. {}", e)),
+            };
+            networks_print.push_str(&format!("{{\"genesis_hash\":\"{}\",\"ss58\":\"{}\"}}", hex::encode(network.genesis_hash), ss58));
+        }
+        let path_print = match &x.path {
+            Some(path) => format!("\"path\":\"{}\",", escape_json(path)),
+            None => String::new(),
+        };
+        out.push_str(&format!("{{\"seed_name\":\"{}\",\"name\":\"{}\",\"public_key\":\"{}\",\"encryption\":\"{}\",{}\"networks\":[{}]}}", escape_json(&x.seed_name), escape_json(&x.name), hex::encode(&x.public_key), x.encryption.show(), path_print, networks_print));
+    }
+    out.push_str("]");
+    Ok(out)
+}
+
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use definitions::{crypto::Encryption, users::{WatchOnlyEntry, WatchOnlyNetwork}};
+    use qrcode_rtx::make_data_packs;
+    use std::convert::TryInto;
     
     #[test]
     fn number_of_frames_fountain() {
@@ -114,7 +161,7 @@ mod tests {
         let length = get_length(line, false).unwrap();
         assert!(length == 15, "Expected 15, decoded {}", length);
     }
-    
+
     #[test]
     fn bad_sequence() {
         let jsonline = r#"["400021234","400021456","400021578"]"#;
@@ -128,6 +175,48 @@ mod tests {
         let result = decode_sequence(jsonline, false);
         assert!(result.is_ok(), "Expected ok, {:?}", result);
     }
-}
 
+    #[test]
+    fn watch_only_fountain() {
+        let alice_entry = |i: u32| WatchOnlyEntry {
+            seed_name: String::from("Alice"),
+            name: format!("Alice {}", i),
+            public_key: hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").unwrap(),
+            encryption: Encryption::Sr25519,
+            path: if i == 0 {Some(String::from("//Alice"))} else {None},
+            networks: vec![WatchOnlyNetwork {
+                genesis_hash: hex::decode("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap().try_into().unwrap(),
+                base58prefix: 42,
+            }],
+        };
+        let entries: Vec<WatchOnlyEntry> = (0..40).map(|i| alice_entry(i)).collect();
+        let frames: Vec<String> = make_data_packs(&ContentWatchOnly::generate(&entries).to_vec()).unwrap().iter().map(|x| hex::encode(x)).collect();
+        assert!(frames.len() > 1, "Expected multiple frames");
+        let jsonline = serde_json::to_string(&frames).unwrap();
+        let result = decode_watch_only(&jsonline, true).unwrap();
+        assert!(result.starts_with(r#"[{"seed_name":"Alice","name":"Alice 0","public_key":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519","path":"//Alice","networks":[{"genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","ss58":"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"}]},{"seed_name":"Alice","name":"Alice 1","public_key":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519","networks":"#), "Received: {}", result);
+        assert!(result.contains(r#""name":"Alice 39""#));
+    }
 
+    #[test]
+    fn watch_only_names_escaped() {
+        let entry = WatchOnlyEntry {
+            seed_name: String::from("Alice \"main\""),
+            name: String::from("say \"hi\" \\ bye"),
+            public_key: hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").unwrap(),
+            encryption: Encryption::Sr25519,
+            path: Some(String::from("//\"quoted\"")),
+            networks: vec![WatchOnlyNetwork {
+                genesis_hash: hex::decode("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap().try_into().unwrap(),
+                base58prefix: 42,
+            }],
+        };
+        let frames: Vec<String> = make_data_packs(&ContentWatchOnly::generate(&vec![entry]).to_vec()).unwrap().iter().map(|x| hex::encode(x)).collect();
+        let jsonline = serde_json::to_string(&frames).unwrap();
+        let result = decode_watch_only(&jsonline, true).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).expect("Export should be valid json");
+        assert!(parsed[0]["seed_name"] == "Alice \"main\"", "Received: {}", result);
+        assert!(parsed[0]["name"] == "say \"hi\" \\ bye", "Received: {}", result);
+        assert!(parsed[0]["path"] == "//\"quoted\"", "Received: {}", result);
+    }
+}
@@ -1,4 +1,5 @@
 use std::fs;
+use std::io::Write;
 use raptorq;
 use qrcodegen::{QrCode, QrCodeEcc};
 use apng_encoder;
@@ -53,6 +54,12 @@ pub fn make_qr_codes (data: Vec<Vec<u8>>) -> Result<Vec<QrCode>, Box<dyn std::er
 
 pub fn make_apng (data: Vec<QrCode>, output_name: &str) -> Result<(), Box<dyn std::error::Error>> {
     let mut output_file = fs::File::create(output_name)?;
+    write_apng(data, &mut output_file)
+}
+
+/// function to write apng made of Vec<QrCode> into any writer
+
+fn write_apng <W: Write> (data: Vec<QrCode>, output: &mut W) -> Result<(), Box<dyn std::error::Error>> {
     let frames_count: u32 = data.len() as u32;
     let border_size = BORDER*SCALING;
     let size: u32 = (data[0].size() as u32) * (SCALING as u32) + 2*border_size as u32; // size is always positive and small
@@ -67,7 +74,7 @@ pub fn make_apng (data: Vec<QrCode>, output_name: &str) -> Result<(), Box<dyn st
         delay: Some(apng_encoder::Delay::new(FPS_NOM, FPS_DEN)),
         ..Default::default()
     };
-    let mut apng_encoder = match apng_encoder::Encoder::create(&mut output_file, apng_meta) {
+    let mut apng_encoder = match apng_encoder::Encoder::create(output, apng_meta) {
         Ok(a) => a,
         Err(e) => {
             let err_text = format!("Apng encoder error. {}", e);
@@ -119,3 +126,13 @@ pub fn transform_into_qr_apng (input: &Vec<u8>, output_name: &str) -> Result<(),
     make_apng(make_qr_codes(data_packs)?, output_name)?;
     Ok(())
 }
+
+/// Function to transform input Vec<u8> into fountain qr-code,
+/// and get apng as Vec<u8> without writing any files
+
+pub fn transform_into_qr_apng_vec (input: &Vec<u8>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let data_packs = make_data_packs(input)?;
+    let mut out: Vec<u8> = Vec::new();
+    write_apng(make_qr_codes(data_packs)?, &mut out)?;
+    Ok(out)
+}
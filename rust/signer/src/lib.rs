@@ -35,6 +35,24 @@ export! {
 		db_handling::identities::export_identity(address, network, dbname)
 	}
 
+	@Java_io_parity_signer_models_SignerDataModel_substrateExportSeedWatchOnly
+	fn export_seed_watch_only(
+		seed_name: &str,
+		include_paths: bool,
+		dbname: &str
+	) -> anyhow::Result<String, anyhow::Error> {
+		db_handling::identities::export_seed_watch_only(seed_name, include_paths, dbname)
+	}
+
+	@Java_io_parity_signer_models_SignerDataModel_substrateExportSelectedWatchOnly
+	fn export_selected_watch_only(
+		selection: &str,
+		include_paths: bool,
+		dbname: &str
+	) -> anyhow::Result<String, anyhow::Error> {
+		db_handling::identities::export_selected_watch_only(selection, include_paths, dbname)
+	}
+
 	@Java_io_parity_signer_models_SignerDataModel_qrparserGetPacketsTotal
 	fn get_packets_total(
 		data: &str,
@@ -131,6 +149,14 @@ export! {
         db_handling::identities::print_all_identities(dbname)
     }
 
+    @Java_io_parity_signer_models_SignerDataModel_dbFindIdentitiesByAddress
+	fn find_identities_by_address(
+		address: &str,
+        dbname: &str
+	) -> anyhow::Result<String, anyhow::Error> {
+        db_handling::identities::print_identities_by_address(address, dbname)
+    }
+
     @Java_io_parity_signer_models_SignerDataModel_substrateTryCreateSeed
 	fn try_create_seed(
         seed_name: &str,
@@ -173,6 +199,20 @@ export! {
         db_handling::identities::try_create_address(id_name, seed_name, seed_phrase, crypto, path, network, has_password, dbname)
     }
 
+    @Java_io_parity_signer_models_SignerDataModel_substrateTryCreateIdentitiesBulk
+	fn try_create_identities_bulk(
+        name_template: &str,
+        seed_name: &str,
+        seed_phrase: &str,
+        crypto: &str,
+        path_template: &str,
+        networks: &str,
+        dry_run: bool,
+		dbname: &str
+	) -> anyhow::Result<String, anyhow::Error> {
+        db_handling::identities::try_create_addresses_bulk(name_template, seed_name, seed_phrase, crypto, path_template, networks, dry_run, dbname)
+    }
+
     @Java_io_parity_signer_models_SignerDataModel_substrateSuggestName
 	fn suggest_name(
         path: &str
@@ -189,6 +229,64 @@ export! {
         db_handling::identities::delete_address(pub_key, network, dbname)
     }
 
+    @Java_io_parity_signer_models_SignerDataModel_substrateRenameIdentity
+	fn rename_identity(
+        pub_key: &str,
+        crypto: &str,
+        new_name: &str,
+        dbname: &str
+	) -> anyhow::Result<(), anyhow::Error> {
+        db_handling::identities::rename_identity(pub_key, crypto, new_name, dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateAddIdentityTag
+	fn add_identity_tag(
+        pub_key: &str,
+        crypto: &str,
+        tag: &str,
+        dbname: &str
+	) -> anyhow::Result<(), anyhow::Error> {
+        db_handling::identities::add_identity_tag(pub_key, crypto, tag, dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateRemoveIdentityTag
+	fn remove_identity_tag(
+        pub_key: &str,
+        crypto: &str,
+        tag: &str,
+        dbname: &str
+	) -> anyhow::Result<(), anyhow::Error> {
+        db_handling::identities::remove_identity_tag(pub_key, crypto, tag, dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateSetIdentityNotes
+	fn set_identity_notes(
+        pub_key: &str,
+        crypto: &str,
+        notes: &str,
+        dbname: &str
+	) -> anyhow::Result<(), anyhow::Error> {
+        db_handling::identities::set_identity_notes(pub_key, crypto, notes, dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateEnableIdentityNetwork
+	fn enable_identity_network(
+        pub_key: &str,
+        network: &str,
+        dbname: &str
+	) -> anyhow::Result<(), anyhow::Error> {
+        db_handling::identities::enable_identity_network(pub_key, network, dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateDisableIdentityNetwork
+	fn disable_identity_network(
+        pub_key: &str,
+        network: &str,
+        dbname: &str
+	) -> anyhow::Result<(), anyhow::Error> {
+        db_handling::identities::disable_identity_network(pub_key, network, dbname)
+    }
+
     @Java_io_parity_signer_models_SignerDataModel_substrateGetNetworkSpecs
 	fn get_network_specs(
         network: &str,
@@ -214,6 +312,42 @@ export! {
         db_handling::remove_network::remove_metadata(network_name, network_version, dbname)
     }
 
+    @Java_io_parity_signer_models_SignerDataModel_substrateSetRetentionPolicy
+	fn set_retention_policy(
+        policy: &str,
+        dbname: &str
+	) -> anyhow::Result<(), anyhow::Error> {
+        db_handling::settings::set_retention_policy(policy, dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateGetRetentionPolicy
+	fn get_retention_policy(
+        dbname: &str
+	) -> anyhow::Result<String, anyhow::Error> {
+        db_handling::settings::print_retention_policy(dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateCompressStoredMetadata
+	fn compress_stored_metadata(
+        dbname: &str
+	) -> anyhow::Result<u32, anyhow::Error> {
+        db_handling::metadata::compress_stored_metadata(dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateMigrateNetworkSpecs
+	fn migrate_network_specs(
+        dbname: &str
+	) -> anyhow::Result<u32, anyhow::Error> {
+        db_handling::chainspecs::migrate_network_specs(dbname)
+    }
+
+    @Java_io_parity_signer_models_SignerDataModel_substrateMigrateAddressDetails
+	fn migrate_address_details(
+        dbname: &str
+	) -> anyhow::Result<u32, anyhow::Error> {
+        db_handling::identities::migrate_address_details(dbname)
+    }
+
     @Java_io_parity_signer_models_SignerDataModel_substrateRemoveSeed
 	fn remove_seed(
         seed_name: &str,
@@ -222,6 +356,15 @@ export! {
         db_handling::identities::remove_identities_for_seed(seed_name, dbname)
     }
 
+    @Java_io_parity_signer_models_SignerDataModel_substrateRenameSeed
+	fn rename_seed(
+        seed_name: &str,
+        new_seed_name: &str,
+        dbname: &str
+	) -> anyhow::Result<(), anyhow::Error> {
+        db_handling::identities::rename_seed(seed_name, new_seed_name, dbname)
+    }
+
     @Java_io_parity_signer_models_SignerDataModel_historyPrintHistory
 	fn print_history(
         dbname: &str
@@ -2,19 +2,18 @@ use hex;
 use sled::{Db, Tree};
 use constants::{ADDNETWORK, METATREE, SETTREE, SPECSTREE, TRANSACTION, VERIFIERS};
 use definitions::{history::Event, metadata::{MetaValuesDisplay, NameVersioned, NetworkDisplay, VersionDecoded}, network_specs::{ChainSpecsToSend, Verifier, generate_network_key, generate_verifier_key}, qr_transfers::ContentAddNetwork, transactions::{Transaction, AddNetwork}};
-use meta_reading::decode_metadata::{get_meta_const_light};
+use meta_reading::decode_metadata::{decode_runtime_metadata, get_meta_const_light};
 use parity_scale_codec::{Decode, Encode};
 use blake2_rfc::blake2b::blake2b;
-use frame_metadata::RuntimeMetadata;
 
 use crate::cards::{Action, Card, Warning};
 use crate::error::{Error, BadInputData, DatabaseError, CryptoError};
 use crate::check_signature::pass_crypto;
-use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_checksum, get_verifier};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_checksum, get_verifier, get_verifier_set, message_content};
 use crate::load_metadata::process_received_metadata;
 use crate::utils::{get_chainspecs, get_general_verifier};
 
-pub fn add_network (data_hex: &str, dbname: &str) -> Result<String, Error> {
+pub fn add_network (data_hex: &str, dbname: &str, compressed: bool) -> Result<String, Error> {
 
 // loading the database and its trees: chainspecs, metadata, settings, transaction;
 
@@ -29,7 +28,9 @@ pub fn add_network (data_hex: &str, dbname: &str) -> Result<String, Error> {
     
     let checked_info = pass_crypto(&data_hex)?;
     
-    let (new_meta_vec, new_chain_specs) = match ContentAddNetwork::from_vec(&checked_info.message).meta_specs() {
+    let content = message_content(checked_info.message, compressed)?;
+    
+    let (new_meta_vec, new_chain_specs) = match ContentAddNetwork::from_vec(&content).meta_specs() {
         Ok(x) => x,
         Err(_) => return Err(Error::BadInputData(BadInputData::UnableToDecodeAddNetworkMessage)),
     };
@@ -49,8 +50,24 @@ pub fn add_network (data_hex: &str, dbname: &str) -> Result<String, Error> {
         // current_verifier - current general verifier for adding networks and importing types
         // verifier - verifier of this particular message
         
-        // first check if the important specs have changed: base58prefix, decimals, name, and unit
-            if (x.base58prefix != new_chain_specs.base58prefix)|(x.decimals != new_chain_specs.decimals)|(x.encryption != new_chain_specs.encryption)|(x.name != new_chain_specs.name)|(x.unit != new_chain_specs.unit) {return Err(Error::BadInputData(BadInputData::ImportantSpecsChanged))}
+        // first check if the important specs have changed: base58prefix, decimals, name, unit, and relay chain with parachain id
+            if (x.base58prefix != new_chain_specs.base58prefix)|(x.decimals != new_chain_specs.decimals)|(x.encryption != new_chain_specs.encryption)|(x.name != new_chain_specs.name)|(x.unit != new_chain_specs.unit)|(x.relay_genesis_hash != new_chain_specs.relay_genesis_hash)|(x.parachain_id != new_chain_specs.parachain_id)|(x.tokens != new_chain_specs.tokens) {return Err(Error::BadInputData(BadInputData::ImportantSpecsChanged))}
+        
+        // network verified by verifier set accepts the metadata only if enough verifiers from the set signed it,
+        // same as in load_metadata; verifiers could not be updated through add_network message
+            if let Some(verifier_set) = get_verifier_set (x.genesis_hash, &verifiers)? {
+                let approvals = verifier_set.approvals(&checked_info.signers);
+                if approvals < verifier_set.threshold {return Err(Error::CryptoError(CryptoError::VerifierSetThresholdNotMet{approvals, verifier_set_show: verifier_set.show_error()}))}
+                let verifier_set_card = Card::VerifierSet(verifier_set.show_card()).card(0,0);
+                let warning_card_1 = Card::Warning(Warning::NetworkAlreadyHasEntries).card(1,0);
+                let history = vec![Event::Warning(Warning::NetworkAlreadyHasEntries.show())];
+                let index = 2;
+                let upd_network = None;
+                let upd_general = false;
+                let (meta_card, action_card) = process_received_metadata(new_meta_vec, Some(&new_chain_specs.name), history, index, upd_network, upd_general, verifier, &metadata, &transaction, &database)?;
+                return Ok(format!("{{\"verifier_set\":[{}],\"warning\":[{}],\"meta\":[{}],{}}}", verifier_set_card, warning_card_1, meta_card, action_card))
+            }
+            if checked_info.signers.len() > 1 {return Err(Error::CryptoError(CryptoError::NoVerifierSet))}
         
         // get network verifier
             let network_verifier = get_verifier (x.genesis_hash, &verifiers)?;
@@ -157,6 +174,8 @@ pub fn add_network (data_hex: &str, dbname: &str) -> Result<String, Error> {
         // i.e. in chainspecs tree of the database each name is encountered only once;
         // this possibilities should be looked closer into later, maybe
         
+            if checked_info.signers.len() > 1 {return Err(Error::BadInputData(BadInputData::MultipleSignaturesNotSupported))}
+        
             match verifier {
                 Verifier::None => {
                     if current_verifier == Verifier::None {
@@ -204,7 +223,7 @@ pub fn add_network (data_hex: &str, dbname: &str) -> Result<String, Error> {
 fn process_received_network_info (meta: Vec<u8>, new_chain_specs: ChainSpecsToSend, history: Vec<Event>, index: u32, verifier: Verifier, upd: bool, transaction: &Tree, database: &Db) -> Result<(String, String), Error> {
     if !meta.starts_with(&vec![109, 101, 116, 97]) {return Err(Error::BadInputData(BadInputData::NotMeta))}
     if meta[4] < 12 {return Err(Error::BadInputData(BadInputData::MetaVersionBelow12))}
-    match RuntimeMetadata::decode(&mut &meta[4..]) {
+    match decode_runtime_metadata(&meta[4..]) {
         Ok(received_metadata) => {
             match get_meta_const_light(&received_metadata) {
                 Ok(x) => {
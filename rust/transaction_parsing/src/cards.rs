@@ -10,6 +10,7 @@ pub enum Card <'a> {
     Default (&'a str),
     PathDocs {path: &'a str, docs: &'a str},
     Id (&'a str),
+    OwnedIdentity {seed_name: &'a str, path: &'a str, has_pwd: bool, name: &'a str},
     None,
     IdentityField (&'a str),
     BitVec (String), // String from printing BitVec
@@ -30,8 +31,15 @@ pub enum Card <'a> {
     AuthorPublicKey{author_public_key: Vec<u8>, encryption: Encryption},
     Verifier(String),
     Meta(String), // get String after applying show() to MetaValuesDisplay
+    MetaRemoved(String), // get String after applying show() to MetaValuesDisplay
+    MetaDiff {previous_version: u32, diff: String}, // diff is String after applying show_card() to MetadataDiff
     TypesInfo(&'a str),
     NewNetwork(String), // get String after applying show() to NetworkDisplay
+    ParachainName(&'a str),
+    VerifierSet(String), // get String after applying show_card() to VerifierSet
+    NewVerifier {verifier_line: String, effective_from: Option<u32>}, // verifier_line is String after applying show_card() to Verifier
+    Derivation {path: &'a str, name: &'a str, network_title: &'a str, public_key: &'a Vec<u8>, encryption: Encryption},
+    Assets {network_title: &'a str, assets_line: String}, // assets_line is String after applying show_assets() to asset registry
     Warning (Warning),
     Error (Error),
 }
@@ -51,6 +59,10 @@ pub enum Warning {
     MetaAlreadyThereUpdGeneralVerifier,
     NetworkAlreadyHasEntries,
     AddNetworkNotVerified,
+    VerifierRotationCompleted,
+    DerivationNetworkNotFound {path: String, genesis_hash: [u8; 32], encryption: Encryption},
+    DerivationMismatch {path: String, name: String},
+    DerivationNotImported {path: String, error: String},
 }
 
 impl Warning {
@@ -70,6 +82,10 @@ impl Warning {
             Warning::MetaAlreadyThereUpdGeneralVerifier => String::from("Received metadata is already in database, only general verifier could be added."),
             Warning::NetworkAlreadyHasEntries => String::from("Add network message is received for network that already has some entries in the database."),
             Warning::AddNetworkNotVerified => String::from("Received new network information is not verified."),
+            Warning::VerifierRotationCompleted => String::from("Received network metadata is signed by the new verifier from pending verifier rotation. If accepted, only metadata from the new verifier could be received for this network."),
+            Warning::DerivationNetworkNotFound {path, genesis_hash, encryption} => format!("Network with genesis hash {} and encryption {} is not in the database. Derivation {} is skipped.", hex::encode(genesis_hash), encryption.show(), path),
+            Warning::DerivationMismatch {path, name} => format!("Public key derived for path {} ({}) does not match the expected one. If the path is password-protected, enter the password and try again.", path, name),
+            Warning::DerivationNotImported {path, error} => format!("Derivation {} is not imported. {}", path, error),
         }
     }
 }
@@ -87,6 +103,7 @@ impl <'a> Card <'a> {
             Card::Default (decoded_string) => fancy(index, indent, "default", &format!("\"{}\"", decoded_string)),
             Card::PathDocs {path, docs} => fancy(index, indent, "path_and_docs", &format!("{{\"path\":{},\"docs\":\"{}\"}}", path, hex::encode(docs.as_bytes()))),
             Card::Id (base58_id) => fancy(index, indent, "Id", &format!("\"{}\"", base58_id)),
+            Card::OwnedIdentity {seed_name, path, has_pwd, name} => fancy(index, indent, "owned_identity", &format!("{{\"seed\":\"{}\",\"derivation_path\":\"{}\",\"has_password\":{},\"name\":\"{}\"}}", seed_name, path, has_pwd, name)),
             Card::None => fancy(index, indent, "none", "\"\""),
             Card::IdentityField (variant) => fancy(index, indent, "identity_field", &format!("\"{}\"", variant)),
             Card::BitVec (bv) => fancy(index, indent, "bitvec", &format!("\"{}\"", bv)),
@@ -107,8 +124,21 @@ impl <'a> Card <'a> {
             Card::AuthorPublicKey{author_public_key, encryption} => fancy(index, indent, "author_public_key", &format!("{{\"hex\":\"{}\",\"crypto\":\"{}\"}}", hex::encode(author_public_key), encryption.show())),
             Card::Verifier(x) => fancy(index, indent, "verifier", x),
             Card::Meta(x) => fancy(index, indent, "meta", &format!("{{{}}}", x)),
+            Card::MetaRemoved(x) => fancy(index, indent, "meta_removed", &format!("{{{}}}", x)),
+            Card::MetaDiff {previous_version, diff} => fancy(index, indent, "meta_diff", &format!("{{\"previous_version\":\"{}\",{}}}", previous_version, diff)),
             Card::TypesInfo(x) => fancy(index, indent, "types_hash", &format!("\"{}\"", x)),
             Card::NewNetwork(x) => fancy(index, indent, "new_network", &format!("{{{}}}", x)),
+            Card::ParachainName(x) => fancy(index, indent, "parachain_name", &format!("\"{}\"", x)),
+            Card::VerifierSet(x) => fancy(index, indent, "verifier_set", x),
+            Card::NewVerifier {verifier_line, effective_from} => {
+                let effective_from = match effective_from {
+                    Some(a) => a.to_string(),
+                    None => String::new(),
+                };
+                fancy(index, indent, "new_verifier", &format!("{{\"verifier\":{},\"effective_from\":\"{}\"}}", verifier_line, effective_from))
+            },
+            Card::Assets {network_title, assets_line} => fancy(index, indent, "assets", &format!("{{\"network\":\"{}\",\"assets\":{}}}", network_title, assets_line)),
+            Card::Derivation {path, name, network_title, public_key, encryption} => fancy(index, indent, "derivation", &format!("{{\"derivation_path\":\"{}\",\"name\":\"{}\",\"network\":\"{}\",\"public_key\":\"{}\",\"encryption\":\"{}\"}}", path, name, network_title, hex::encode(public_key), encryption.show())),
             Card::Warning (warn) => fancy(index, indent, "warning", &format!("\"{}\"", warn.show())),
             Card::Error (err) => fancy(index, indent, "error", &format!("\"{}\"", err.show())),
         }
@@ -126,6 +156,10 @@ pub enum Action {
     LoadMetadataAndAddGeneralVerifier (u32),
     AddNetwork (u32),
     AddNetworkAndAddGeneralVerifier (u32),
+    SetVerifiers (u32),
+    RotateVerifier (u32),
+    ImportDerivations (u32),
+    LoadAssets (u32),
 }
 
 fn print_action (action: &str, checksum: &u32) -> String {
@@ -144,6 +178,10 @@ impl Action {
             Action::LoadMetadataAndAddGeneralVerifier(x) => print_action("load_metadata_and_add_general_verifier", x),
             Action::AddNetwork(x) => print_action("add_network", x),
             Action::AddNetworkAndAddGeneralVerifier (x) => print_action("add_network_and_add_general_verifier", x),
+            Action::SetVerifiers (x) => print_action("set_verifiers", x),
+            Action::RotateVerifier (x) => print_action("rotate_verifier", x),
+            Action::ImportDerivations (x) => print_action("import_derivations", x),
+            Action::LoadAssets (x) => print_action("load_assets", x),
         }
     }
 }
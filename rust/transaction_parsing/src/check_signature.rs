@@ -1,13 +1,18 @@
 use hex;
 use sp_core::{Pair, ed25519, sr25519, ecdsa};
 use std::convert::TryInto;
-use definitions::network_specs::Verifier;
+use definitions::{crypto::SufficientCrypto, network_specs::Verifier};
+use parity_scale_codec::Decode;
 
 use crate::error::{Error, BadInputData, CryptoError};
 use crate::helpers::unhex;
 
+/// Struct to store the checked payload message and who signed it;
+/// for payloads with several signatures verifier is the first signer,
+/// and signers contains all distinct signers
 pub struct InfoPassedCrypto {
     pub verifier: Verifier,
+    pub signers: Vec<Verifier>,
     pub message: Vec<u8>,
 }
 
@@ -28,7 +33,8 @@ pub fn pass_crypto(data_hex: &str) -> Result<InfoPassedCrypto, Error> {
             if ed25519::Pair::verify(&signature, &message, &pubkey) {
                 let verifier = Verifier::Ed25519(hex::encode(&into_pubkey));
                 Ok(InfoPassedCrypto {
-                    verifier,
+                    verifier: verifier.clone(),
+                    signers: vec![verifier],
                     message,
                 })
             }
@@ -46,7 +52,8 @@ pub fn pass_crypto(data_hex: &str) -> Result<InfoPassedCrypto, Error> {
             if sr25519::Pair::verify(&signature, &message, &pubkey) {
                 let verifier = Verifier::Sr25519(hex::encode(&into_pubkey));
                 Ok(InfoPassedCrypto {
-                    verifier,
+                    verifier: verifier.clone(),
+                    signers: vec![verifier],
                     message,
                 })
             }
@@ -64,7 +71,8 @@ pub fn pass_crypto(data_hex: &str) -> Result<InfoPassedCrypto, Error> {
             if ecdsa::Pair::verify(&signature, &message, &pubkey) {
                 let verifier = Verifier::Ecdsa(hex::encode(&into_pubkey));
                 Ok(InfoPassedCrypto {
-                    verifier,
+                    verifier: verifier.clone(),
+                    signers: vec![verifier],
                     message,
                 })
             }
@@ -78,9 +86,56 @@ pub fn pass_crypto(data_hex: &str) -> Result<InfoPassedCrypto, Error> {
             let verifier = Verifier::None;
             Ok(InfoPassedCrypto {
                 verifier,
+                signers: Vec::new(),
+                message,
+            })
+        },
+        "03" => {
+        // Several verifiers signed the message, signatures are SCALE-encoded Vec<SufficientCrypto> right after the prelude
+            if data.len() < 3 {return Err(Error::BadInputData(BadInputData::TooShort))}
+            let mut remaining = &data[3..];
+            let signatures = match <Vec<SufficientCrypto>>::decode(&mut remaining) {
+                Ok(a) => a,
+                Err(_) => return Err(Error::BadInputData(BadInputData::UnableToDecodeSignatures)),
+            };
+            if signatures.len() == 0 {return Err(Error::BadInputData(BadInputData::UnableToDecodeSignatures))}
+            let message = remaining.to_vec();
+            let mut signers: Vec<Verifier> = Vec::new();
+            for x in signatures.iter() {
+                let verifier = verify_sufficient_crypto(x, &message)?;
+                if !signers.contains(&verifier) {signers.push(verifier)}
+            }
+            Ok(InfoPassedCrypto {
+                verifier: signers[0].clone(),
+                signers,
                 message,
             })
         },
         _ => return Err(Error::BadInputData(BadInputData::CryptoNotSupported))
     }
 }
+
+/// Function to check single signature from the multi-signed payload,
+/// outputs the verifier if the signature is good
+fn verify_sufficient_crypto(sufficient_crypto: &SufficientCrypto, message: &Vec<u8>) -> Result<Verifier, Error> {
+    match sufficient_crypto {
+        SufficientCrypto::Ed25519 {public_key, signature} => {
+            let pubkey = ed25519::Public::from_raw(public_key.to_owned());
+            let signature = ed25519::Signature::from_raw(signature.to_owned());
+            if ed25519::Pair::verify(&signature, message, &pubkey) {Ok(Verifier::Ed25519(hex::encode(public_key)))}
+            else {return Err(Error::CryptoError(CryptoError::BadSignature))}
+        },
+        SufficientCrypto::Sr25519 {public_key, signature} => {
+            let pubkey = sr25519::Public::from_raw(public_key.to_owned());
+            let signature = sr25519::Signature::from_raw(signature.to_owned());
+            if sr25519::Pair::verify(&signature, message, &pubkey) {Ok(Verifier::Sr25519(hex::encode(public_key)))}
+            else {return Err(Error::CryptoError(CryptoError::BadSignature))}
+        },
+        SufficientCrypto::Ecdsa {public_key, signature} => {
+            let pubkey = ecdsa::Public::from_raw(public_key.to_owned());
+            let signature = ecdsa::Signature::from_raw(signature.to_owned());
+            if ecdsa::Pair::verify(&signature, message, &pubkey) {Ok(Verifier::Ecdsa(hex::encode(public_key)))}
+            else {return Err(Error::CryptoError(CryptoError::BadSignature))}
+        },
+    }
+}
@@ -4,9 +4,11 @@ use std::mem::size_of;
 use definitions::network_specs::ChainSpecs;
 use printing_balance::convert_balance_pretty;
 use sp_core::crypto::{Ss58Codec, Ss58AddressFormat, AccountId32};
+use sled::Tree;
+use db_handling::identities::find_identities_by_public_key;
 
 use crate::cards::Card;
-use crate::error::{Error, SystemError, UnableToDecode};
+use crate::error::{Error, DatabaseError, SystemError, UnableToDecode};
 
 /// Struct to store the decoded data, used for data storage between decoding iterations.
 /// decoded_string is short json-like format,
@@ -233,13 +235,15 @@ pub fn decode_primitive_with_flags <T> (data: &Vec<u8>, compact_flag: bool, bala
 /// The function takes as arguments
 /// - data (remaining Vec<u8> of data),
 /// - index and indent that are used for creating properly formatted js cards.
-/// - chain_specs (taking base58 prefix from there).
+/// - chain_specs (taking base58 prefix from there),
+/// - addresses (tree with user identities, to check if the AccountId belongs to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 ///
 /// Resulting AccountId in base58 form is added to fancy_out on js card "Id".
+/// If the AccountId belongs to the user, js card "owned_identity" with seed name and derivation path follows.
 
-pub fn special_case_account_id (data: Vec<u8>, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+pub fn special_case_account_id (data: Vec<u8>, mut index: u32, indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
     match data.get(0..32) {
         Some(a) => {
             match <[u8; 32]>::decode(&mut &a[..]) {
@@ -247,8 +251,16 @@ pub fn special_case_account_id (data: Vec<u8>, mut index: u32, indent: u32, chai
                     let remaining_vector = data[32..].to_vec();
                     let account_id = AccountId32::new(x);
                     let base58print = account_id.to_ss58check_with_version(Ss58AddressFormat::Custom(chain_specs.base58prefix));
-                    let fancy_out = format!(",{}", (Card::Id(&base58print)).card(index, indent));
+                    let mut fancy_out = format!(",{}", (Card::Id(&base58print)).card(index, indent));
                     index = index + 1;
+                    let owned_identities = match find_identities_by_public_key(&x.to_vec(), addresses) {
+                        Ok(a) => a,
+                        Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedAddressDetails)),
+                    };
+                    for (_, address_details) in owned_identities.iter() {
+                        fancy_out.push_str(&format!(",{}", (Card::OwnedIdentity{seed_name: &address_details.seed_name, path: &address_details.path, has_pwd: address_details.has_pwd, name: &address_details.name}).card(index, indent)));
+                        index = index + 1;
+                    }
                     Ok(DecodedOut {
                         remaining_vector,
                         index,
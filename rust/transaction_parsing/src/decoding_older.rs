@@ -2,6 +2,7 @@ use regex::Regex;
 use lazy_static::lazy_static;
 use sp_arithmetic::{Percent, Perbill, PerU16};
 use bitvec::prelude::{BitVec, Lsb0};
+use sled::Tree;
 use definitions::{network_specs::ChainSpecs, types::{TypeEntry, Description, EnumVariant, EnumVariantType, StructField}};
 
 use crate::cards::Card;
@@ -65,18 +66,19 @@ fn decode_primitive (found_ty: &str, data: &Vec<u8>, index: u32, indent: u32, ch
 /// currently is retrieved and decoded from the database on device used),
 /// - index and indent that are used for creating properly formatted js cards,
 /// - chain_specs (network parameters, such as base58 prefix, currency units and decimals,
-/// all those are used in some cases for proper output formatting).
+/// all those are used in some cases for proper output formatting),
+/// - addresses (tree with user identities, used to mark AccountId entries belonging to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 ///
 /// Calls and vectors of calls are treated separately here.
 /// All simpler types are processed through decode_simple function.
 
-fn decode_complex (found_ty: &str, mut data: Vec<u8>, meta: &OlderMeta, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn decode_complex (found_ty: &str, mut data: Vec<u8>, meta: &OlderMeta, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
 
     match found_ty {
         "Box<<T as Config<I>>::Proposal>" | "Box<<T as Config>::Call>" | "Box<<T as Config>::Proposal>" => {
-            process_as_call(data, meta, type_database, index, indent, chain_specs)
+            process_as_call(data, meta, type_database, index, indent, chain_specs, addresses)
         },
         "Vec<<T as Config>::Call>" => {
             let pre_vector = get_compact::<u32>(&data)?;
@@ -87,7 +89,7 @@ fn decode_complex (found_ty: &str, mut data: Vec<u8>, meta: &OlderMeta, type_dat
                     if data.len() < start + 2*(number_of_calls as usize) {return Err(Error::UnableToDecode(UnableToDecode::DataTooShort))}
                     data = data[start..].to_vec();
                     for _i in 0..number_of_calls {
-                        let after_run = process_as_call(data, meta, type_database, index, indent, chain_specs)?;
+                        let after_run = process_as_call(data, meta, type_database, index, indent, chain_specs, addresses)?;
                         index = after_run.index;
                         fancy_output_prep.push_str(&after_run.fancy_out);
                         data = after_run.remaining_vector;
@@ -104,7 +106,7 @@ fn decode_complex (found_ty: &str, mut data: Vec<u8>, meta: &OlderMeta, type_dat
                 fancy_out: fancy_output_prep,
             })
         },
-        _ => decode_simple (found_ty, data, type_database, index, indent, chain_specs),
+        _ => decode_simple (found_ty, data, type_database, index, indent, chain_specs, addresses),
     }
 }
 
@@ -124,7 +126,8 @@ fn decode_complex (found_ty: &str, mut data: Vec<u8>, meta: &OlderMeta, type_dat
 /// currently is retrieved and decoded from the database on device used),
 /// - index and indent that are used for creating properly formatted js cards,
 /// - chain_specs (network parameters, such as base58 prefix, currency units and decimals,
-/// all those are used in some cases for proper output formatting).
+/// all those are used in some cases for proper output formatting),
+/// - addresses (tree with user identities, used to mark AccountId entries belonging to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 ///
@@ -136,7 +139,7 @@ fn decode_complex (found_ty: &str, mut data: Vec<u8>, meta: &OlderMeta, type_dat
 /// For each argument the card "varname" with argument name is added to fancy_out,
 /// followed by card(s) of actual decoded argument values.
 
-pub fn process_as_call (mut data: Vec<u8>, meta: &OlderMeta, type_database: &Vec<TypeEntry>, mut index: u32, mut indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+pub fn process_as_call (mut data: Vec<u8>, meta: &OlderMeta, type_database: &Vec<TypeEntry>, mut index: u32, mut indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
     let call_in_processing = what_next_old (data, meta)?;
     data = call_in_processing.data;
     
@@ -149,7 +152,7 @@ pub fn process_as_call (mut data: Vec<u8>, meta: &OlderMeta, type_database: &Vec
         fancy_out.push_str(&add_to_fancy_out);
         index = index + 1;
         
-        let decoded_out = decode_complex(&x.ty, data, meta, type_database, index, indent+1, chain_specs)?;
+        let decoded_out = decode_complex(&x.ty, data, meta, type_database, index, indent+1, chain_specs, addresses)?;
         index = decoded_out.index;
         data = decoded_out.remaining_vector;
         fancy_out.push_str(&decoded_out.fancy_out);
@@ -200,14 +203,15 @@ lazy_static! {
 /// currently is retrieved and decoded from the database on device used),
 /// - index and indent that are used for creating properly formatted js cards,
 /// - chain_specs (network parameters, such as base58 prefix, currency units and decimals,
-/// all those are used in some cases for proper output formatting).
+/// all those are used in some cases for proper output formatting),
+/// - addresses (tree with user identities, used to mark AccountId entries belonging to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 ///
 /// Js cards are of type "none" if the Option<_> is None.
 /// At this moment no special js card for Some(x) is presented, only the card of x itself.
 
-fn deal_with_option (inner_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn deal_with_option (inner_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
     if inner_ty == "bool" {
     
         let fancy_out = match &data[0] {
@@ -247,7 +251,7 @@ fn deal_with_option (inner_ty: &str, mut data: Vec<u8>, type_database: &Vec<Type
             1 => {
                 if data.len()==1 {return Err(Error::UnableToDecode(UnableToDecode::DataTooShort))}
                 data = data[1..].to_vec();
-                decode_simple(inner_ty, data, type_database, index, indent, chain_specs)
+                decode_simple(inner_ty, data, type_database, index, indent, chain_specs, addresses)
             },
             _ => {return Err(Error::UnableToDecode(UnableToDecode::UnexpectedOptionVariant))},
         }
@@ -273,11 +277,12 @@ fn deal_with_option (inner_ty: &str, mut data: Vec<u8>, type_database: &Vec<Type
 /// currently is retrieved and decoded from the database on device used),
 /// - index and indent that are used for creating properly formatted js cards,
 /// - chain_specs (network parameters, such as base58 prefix, currency units and decimals,
-/// all those are used in some cases for proper output formatting).
+/// all those are used in some cases for proper output formatting),
+/// - addresses (tree with user identities, used to mark AccountId entries belonging to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 
-fn deal_with_vector (inner_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn deal_with_vector (inner_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
     let pre_vector = get_compact::<u32>(&data)?;
     let mut fancy_output_prep = String::new();
     let elements_of_vector = pre_vector.compact_found;
@@ -285,7 +290,7 @@ fn deal_with_vector (inner_ty: &str, mut data: Vec<u8>, type_database: &Vec<Type
         Some(start) => {
             data = data[start..].to_vec();
             for _i in 0..elements_of_vector {
-                let after_run = decode_simple(inner_ty, data, type_database, index, indent, chain_specs)?;
+                let after_run = decode_simple(inner_ty, data, type_database, index, indent, chain_specs, addresses)?;
                 index = after_run.index;
                 fancy_output_prep.push_str(&after_run.fancy_out);
                 data = after_run.remaining_vector;
@@ -328,14 +333,15 @@ fn deal_with_vector (inner_ty: &str, mut data: Vec<u8>, type_database: &Vec<Type
 /// currently is retrieved and decoded from the database on device used),
 /// - index and indent that are used for creating properly formatted js cards,
 /// - chain_specs (network parameters, such as base58 prefix, currency units and decimals,
-/// all those are used in some cases for proper output formatting).
+/// all those are used in some cases for proper output formatting),
+/// - addresses (tree with user identities, used to mark AccountId entries belonging to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 
-fn deal_with_array (inner_ty: &str, number_of_elements: u32, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn deal_with_array (inner_ty: &str, number_of_elements: u32, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
     let mut fancy_output_prep = String::new();
     for _i in 0..number_of_elements {
-        let after_run = decode_simple(inner_ty, data, type_database, index, indent, chain_specs)?;
+        let after_run = decode_simple(inner_ty, data, type_database, index, indent, chain_specs, addresses)?;
         index = after_run.index;
         fancy_output_prep.push_str(&after_run.fancy_out);
         data = after_run.remaining_vector;
@@ -517,11 +523,12 @@ fn special_case_balance (found_ty: &str, data: Vec<u8>, index: u32, indent: u32,
 /// currently is retrieved and decoded from the database on device used),
 /// - index and indent that are used for creating properly formatted js cards,
 /// - chain_specs (network parameters, such as base58 prefix, currency units and decimals,
-/// all those are used in some cases for proper output formatting).
+/// all those are used in some cases for proper output formatting),
+/// - addresses (tree with user identities, used to mark AccountId entries belonging to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 
-fn deal_with_struct (v1: &Vec<StructField>, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn deal_with_struct (v1: &Vec<StructField>, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
     let mut fancy_out = String::new();
     for (i, y) in v1.iter().enumerate() {
         let fancy_output_prep = match &y.field_name {
@@ -530,7 +537,7 @@ fn deal_with_struct (v1: &Vec<StructField>, mut data: Vec<u8>, type_database: &V
         };
         fancy_out.push_str(&fancy_output_prep);
         index = index + 1;
-        let after_run = decode_simple(&y.field_type, data, type_database, index, indent+1, chain_specs)?;
+        let after_run = decode_simple(&y.field_type, data, type_database, index, indent+1, chain_specs, addresses)?;
         data = after_run.remaining_vector;
         index = after_run.index;
         fancy_out.push_str(&after_run.fancy_out);
@@ -559,11 +566,12 @@ fn deal_with_struct (v1: &Vec<StructField>, mut data: Vec<u8>, type_database: &V
 /// currently is retrieved and decoded from the database on device used),
 /// - index and indent that are used for creating properly formatted js cards,
 /// - chain_specs (network parameters, such as base58 prefix, currency units and decimals,
-/// all those are used in some cases for proper output formatting).
+/// all those are used in some cases for proper output formatting),
+/// - addresses (tree with user identities, used to mark AccountId entries belonging to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 
-fn deal_with_enum (v1: &Vec<EnumVariant>, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn deal_with_enum (v1: &Vec<EnumVariant>, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
     let enum_index = data[0] as usize;
     if enum_index >= v1.len() {return Err(Error::UnableToDecode(UnableToDecode::UnexpectedEnumVariant))}
     let found_variant = &v1[enum_index];
@@ -587,7 +595,7 @@ fn deal_with_enum (v1: &Vec<EnumVariant>, mut data: Vec<u8>, type_database: &Vec
             data=data[1..].to_vec();
             let mut fancy_output_prep = format!(",{}", (Card::EnumVariantName{name: &found_variant.variant_name, docs: ""}).card(index, indent));
             index = index + 1;
-            let after_run = decode_simple(&inner_ty, data, type_database, index, indent+1, chain_specs)?;
+            let after_run = decode_simple(&inner_ty, data, type_database, index, indent+1, chain_specs, addresses)?;
             index = after_run.index;
             fancy_output_prep.push_str(&after_run.fancy_out);
             data = after_run.remaining_vector;
@@ -609,7 +617,7 @@ fn deal_with_enum (v1: &Vec<EnumVariant>, mut data: Vec<u8>, type_database: &Vec
                 };
                 fancy_out.push_str(&fancy_output_prep);
                 index = index + 1;
-                let after_run = decode_simple(&y.field_type, data, type_database, index, indent+1, chain_specs)?;
+                let after_run = decode_simple(&y.field_type, data, type_database, index, indent+1, chain_specs, addresses)?;
                 data = after_run.remaining_vector;
                 index = after_run.index;
                 fancy_out.push_str(&after_run.fancy_out);
@@ -639,11 +647,12 @@ fn deal_with_enum (v1: &Vec<EnumVariant>, mut data: Vec<u8>, type_database: &Vec
 /// currently is retrieved and decoded from the database on device used),
 /// - index and indent that are used for creating properly formatted js cards,
 /// - chain_specs (network parameters, such as base58 prefix, currency units and decimals,
-/// all those are used in some cases for proper output formatting).
+/// all those are used in some cases for proper output formatting),
+/// - addresses (tree with user identities, used to mark AccountId entries belonging to the user).
 ///
 /// The function outputs the DecodedOut value in case of success.
 
-fn decode_simple (found_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn decode_simple (found_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEntry>, mut index: u32, indent: u32, chain_specs: &ChainSpecs, addresses: &Tree) -> Result<DecodedOut, Error> {
 
     if data.len()==0 {return Err(Error::UnableToDecode(UnableToDecode::DataTooShort))}
     match decode_primitive(&found_ty, &data, index, indent, chain_specs) {
@@ -656,7 +665,7 @@ fn decode_simple (found_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEnt
                         Some(c) => c.as_str(),
                         None => return Err(Error::SystemError(SystemError::RegexError)),
                     };
-                    deal_with_option(inner_ty, data, type_database, index, indent, chain_specs)
+                    deal_with_option(inner_ty, data, type_database, index, indent, chain_specs, addresses)
                 },
                 None => {
                     // check for vector
@@ -666,7 +675,7 @@ fn decode_simple (found_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEnt
                                 Some(c) => c.as_str(),
                                 None => return Err(Error::SystemError(SystemError::RegexError)),
                             };
-                            deal_with_vector(inner_ty, data, type_database, index, indent, chain_specs)
+                            deal_with_vector(inner_ty, data, type_database, index, indent, chain_specs, addresses)
                         },
                         None => {
                             // check for tuples
@@ -682,7 +691,7 @@ fn decode_simple (found_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEnt
                                                 fancy_out.push_str(&fancy_output_prep);
                                                 index = index + 1;
                                                 let inner_ty = x.as_str();
-                                                let after_run = decode_simple(inner_ty, data, type_database, index, indent+1, chain_specs)?;
+                                                let after_run = decode_simple(inner_ty, data, type_database, index, indent+1, chain_specs, addresses)?;
                                                 index = after_run.index;
                                                 fancy_out.push_str(&after_run.fancy_out);
                                                 data = after_run.remaining_vector;
@@ -704,7 +713,7 @@ fn decode_simple (found_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEnt
                                         Some(caps) => {
                                             let inner_ty = &caps["arg"];
                                             let number_of_elements: u32 = caps["num"].parse().expect("Should have captured a number.");
-                                            deal_with_array(inner_ty, number_of_elements, data, type_database, index, indent, chain_specs)
+                                            deal_with_array(inner_ty, number_of_elements, data, type_database, index, indent, chain_specs, addresses)
                                         },
                                         None => {
                                             // check for special case of Balance as is or in Compact form
@@ -729,7 +738,7 @@ fn decode_simple (found_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEnt
                                                         match new_inner_ty {
                                                             Some(a) => {
                                                                 let new_ty = found_ty.replace(inner_ty, a);
-                                                                decode_simple(&new_ty, data, type_database, index, indent, chain_specs)
+                                                                decode_simple(&new_ty, data, type_database, index, indent, chain_specs, addresses)
                                                             },
                                                             None => return Err(Error::UnableToDecode(UnableToDecode::CompactNotPrimitive)),
                                                         }
@@ -744,16 +753,16 @@ fn decode_simple (found_ty: &str, mut data: Vec<u8>, type_database: &Vec<TypeEnt
                                                             if found_ty == "BitVec" {special_case_bitvec(data, index, indent)}
                                                             else {
                                                                 // special case of AccountId type
-                                                                if (found_ty == "AccountId")||(found_ty == "T::AccountId") {special_case_account_id(data, index, indent, chain_specs)}
+                                                                if (found_ty == "AccountId")||(found_ty == "T::AccountId") {special_case_account_id(data, index, indent, chain_specs, addresses)}
                                                                 else {
                                                                     // do a search through the type database
                                                                     let mut found_solution = None;
                                                                     for x in type_database.iter() {
                                                                         if x.name == found_ty {
                                                                             let wrap_me = match &x.description {
-                                                                                Description::Type(inner_ty) => {decode_simple(&inner_ty, data, type_database, index, indent, chain_specs)?},
-                                                                                Description::Enum(v1) => {deal_with_enum(v1, data, type_database, index, indent, chain_specs)?},
-                                                                                Description::Struct(v1) => {deal_with_struct(v1, data, type_database, index, indent, chain_specs)?},
+                                                                                Description::Type(inner_ty) => {decode_simple(&inner_ty, data, type_database, index, indent, chain_specs, addresses)?},
+                                                                                Description::Enum(v1) => {deal_with_enum(v1, data, type_database, index, indent, chain_specs, addresses)?},
+                                                                                Description::Struct(v1) => {deal_with_struct(v1, data, type_database, index, indent, chain_specs, addresses)?},
                                                                             };
                                                                             found_solution = Some(wrap_me);
                                                                             break;
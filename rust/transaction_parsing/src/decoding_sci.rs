@@ -1,14 +1,16 @@
 use parity_scale_codec::Decode;
 use scale_info::{Field, Type, TypeDef, TypeDefPrimitive, TypeDefVariant, TypeDefComposite, TypeDefBitSequence, /*TypeDefRange,*/ form::PortableForm};
 use num_bigint::{BigInt, BigUint};
-use definitions::{network_specs::ChainSpecs};
+use definitions::{network_specs::{AssetInfo, ChainSpecs}};
 use frame_metadata::v14::RuntimeMetadataV14;
+use sled::Tree;
 use bitvec::{prelude::{BitVec, Lsb0, Msb0}, store::BitStore, order::BitOrder};
 //use std::mem::size_of;
 
 use crate::cards::Card;
 use crate::decoding_commons::{DecodedOut, get_compact, decode_known_length, decode_primitive_with_flags, special_case_account_id};
 use crate::error::{Error, UnableToDecode};
+use crate::utils::ParachainName;
 /*
 enum SpecialDecodingCases {
     Option,
@@ -249,7 +251,7 @@ fn field_type_name_is_balance (type_name: &str) -> bool {
 }
 
 
-pub fn decoding_sci_complete (type_id: u32, compact_flag: bool, balance_flag: bool, data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, mut indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+pub fn decoding_sci_complete (type_id: u32, compact_flag: bool, balance_flag: bool, data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, mut indent: u32, chain_specs: &ChainSpecs, parachain_names: &Vec<ParachainName>, assets: &Vec<AssetInfo>, addresses: &Tree) -> Result<DecodedOut, Error> {
     let current_type = match meta_v14.types.resolve(type_id) {
         Some(a) => a,
         None => return Err(Error::UnableToDecode(UnableToDecode::V14TypeNotResolved)),
@@ -286,35 +288,43 @@ pub fn decoding_sci_complete (type_id: u32, compact_flag: bool, balance_flag: bo
         }
     };
     let after_run = {
-        if type_is_account_id(current_type) {special_case_account_id(data, index, indent, chain_specs)?}
+        if type_is_account_id(current_type) {special_case_account_id(data, index, indent, chain_specs, addresses)?}
         else {    
             match current_type.type_def() {
                 TypeDef::Composite(x) =>  {
                     if balance_flag {return Err(Error::UnableToDecode(UnableToDecode::BalanceNotDescribed))}
-                    decode_type_def_composite (x, compact_flag, data, &meta_v14, index, indent, chain_specs)?
+                    decode_type_def_composite (x, compact_flag, data, &meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?
                 },
                 TypeDef::Variant(x) => {
                     reject_flags(compact_flag, balance_flag)?;
-                    decode_type_def_variant (x, data, &meta_v14, index, indent, chain_specs)?
+                    let parachain_destination = find_parachain_destination(current_type, x, &data);
+                    let mut decoded_variant = decode_type_def_variant (x, data, &meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?;
+                    if let Some(parachain_id) = parachain_destination {
+                        if let Some(a) = parachain_names.iter().find(|a| a.parachain_id == parachain_id) {
+                            decoded_variant.fancy_out.push_str(&format!(",{}", (Card::ParachainName(&a.title)).card(decoded_variant.index, indent+1)));
+                            decoded_variant.index = decoded_variant.index + 1;
+                        }
+                    }
+                    decoded_variant
                 },
                 TypeDef::Sequence(x) => {
                     if compact_flag {return Err(Error::UnableToDecode(UnableToDecode::UnexpectedCompactInsides))}
-                    decode_type_def_sequence (x.type_param().id(), balance_flag, data, &meta_v14, index, indent, chain_specs)?
+                    decode_type_def_sequence (x.type_param().id(), balance_flag, data, &meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?
                 },
                 TypeDef::Array(x) => {
                     if compact_flag {return Err(Error::UnableToDecode(UnableToDecode::UnexpectedCompactInsides))}
-                    decode_type_def_array(x.type_param().id(), x.len(), balance_flag, data, &meta_v14, index, indent, chain_specs)?
+                    decode_type_def_array(x.type_param().id(), x.len(), balance_flag, data, &meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?
                 },
                 TypeDef::Tuple(x) => {
                     reject_flags(compact_flag, balance_flag)?;
                     let id_set = x.fields().iter().map(|a| a.id()).collect();
-                    decode_type_def_tuple(id_set, data, &meta_v14, index, indent, chain_specs)?
+                    decode_type_def_tuple(id_set, data, &meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?
                 },
                 TypeDef::Primitive(x) => decode_type_def_primitive (x, compact_flag, balance_flag, &data, index, indent, chain_specs)?,
                 TypeDef::Compact(x) => {
                     let inner_type_id = x.type_param().id();
                     let compact_flag = true;
-                    decoding_sci_complete(inner_type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs)?
+                    decoding_sci_complete(inner_type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?
                 },
                 TypeDef::BitSequence(x) => {
                     reject_flags(compact_flag, balance_flag)?;
@@ -340,7 +350,7 @@ pub fn decoding_sci_complete (type_id: u32, compact_flag: bool, balance_flag: bo
 
 
 
-pub fn decoding_sci_entry_point (mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, mut indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+pub fn decoding_sci_entry_point (mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, mut indent: u32, chain_specs: &ChainSpecs, parachain_names: &Vec<ParachainName>, assets: &Vec<AssetInfo>, addresses: &Tree) -> Result<DecodedOut, Error> {
     let pallet_index: u8 = match data.get(0) {
         Some(x) => *x,
         None => return Err(Error::UnableToDecode(UnableToDecode::NeedPallet)),
@@ -370,7 +380,7 @@ pub fn decoding_sci_entry_point (mut data: Vec<u8>, meta_v14: &RuntimeMetadataV1
     
     let compact_flag = false;
     let balance_flag = false;
-    let decoded_out = decoding_sci_complete(type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs)?;
+    let decoded_out = decoding_sci_complete(type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?;
     fancy_out.push_str(&decoded_out.fancy_out);
     
     Ok(DecodedOut{
@@ -382,7 +392,7 @@ pub fn decoding_sci_entry_point (mut data: Vec<u8>, meta_v14: &RuntimeMetadataV1
 }
 
 
-fn decode_type_def_sequence (type_id: u32, balance_flag: bool, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn decode_type_def_sequence (type_id: u32, balance_flag: bool, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs, parachain_names: &Vec<ParachainName>, assets: &Vec<AssetInfo>, addresses: &Tree) -> Result<DecodedOut, Error> {
     let pre_vector = get_compact::<u32>(&data)?;
     let mut fancy_output_prep = String::new();
     let elements_of_vector = pre_vector.compact_found;
@@ -391,7 +401,7 @@ fn decode_type_def_sequence (type_id: u32, balance_flag: bool, mut data: Vec<u8>
             data = data[start..].to_vec();
             for _i in 0..elements_of_vector {
                 let compact_flag = false;
-                let after_run = decoding_sci_complete(type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs)?;
+                let after_run = decoding_sci_complete(type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?;
                 index = after_run.index;
                 fancy_output_prep.push_str(&after_run.fancy_out);
                 data = after_run.remaining_vector;
@@ -418,11 +428,11 @@ fn decode_type_def_sequence (type_id: u32, balance_flag: bool, mut data: Vec<u8>
 }
 
 
-fn decode_type_def_array (type_id: u32, len: u32, balance_flag: bool, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn decode_type_def_array (type_id: u32, len: u32, balance_flag: bool, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs, parachain_names: &Vec<ParachainName>, assets: &Vec<AssetInfo>, addresses: &Tree) -> Result<DecodedOut, Error> {
     let mut fancy_output_prep = String::new();
     for _i in 0..len {
         let compact_flag = false;
-        let after_run = decoding_sci_complete(type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs)?;
+        let after_run = decoding_sci_complete(type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?;
         index = after_run.index;
         fancy_output_prep.push_str(&after_run.fancy_out);
         data = after_run.remaining_vector;
@@ -436,14 +446,14 @@ fn decode_type_def_array (type_id: u32, len: u32, balance_flag: bool, mut data:
 }
 
 
-fn decode_type_def_tuple (id_set: Vec<u32>, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn decode_type_def_tuple (id_set: Vec<u32>, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs, parachain_names: &Vec<ParachainName>, assets: &Vec<AssetInfo>, addresses: &Tree) -> Result<DecodedOut, Error> {
     let mut fancy_out = String::new();
     for (i, type_id) in id_set.iter().enumerate() {
         let fancy_output_prep = format!(",{}", (Card::FieldNumber{number: i+1, docs: ""}).card(index, indent));
         fancy_out.push_str(&fancy_output_prep);
         let compact_flag = false;
         let balance_flag = false;
-        let after_run = decoding_sci_complete(*type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs)?;
+        let after_run = decoding_sci_complete(*type_id, compact_flag, balance_flag, data, meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)?;
         index = after_run.index;
         fancy_out.push_str(&after_run.fancy_out);
         data = after_run.remaining_vector;
@@ -509,7 +519,23 @@ fn is_option (found_ty: &TypeDefVariant<PortableForm>) -> bool {
 }
 */
 
-fn decode_type_def_variant (found_ty: &TypeDefVariant<PortableForm>, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+/// Function to check if the type is xcm Junction with Parachain variant,
+/// and to get the parachain id from the data if so.
+/// Parachain id is used to display the name of destination network, if it is known.
+fn find_parachain_destination (current_type: &Type<PortableForm>, found_ty: &TypeDefVariant<PortableForm>, data: &Vec<u8>) -> Option<u32> {
+    match current_type.path().ident() {
+        Some(a) => if a != "Junction" {return None},
+        None => return None,
+    }
+    let found_variant = found_ty.variants().get(*data.get(0)? as usize)?;
+    if found_variant.name() != "Parachain" {return None}
+    match get_compact::<u32>(&data[1..].to_vec()) {
+        Ok(a) => Some(a.compact_found),
+        Err(_) => None,
+    }
+}
+
+fn decode_type_def_variant (found_ty: &TypeDefVariant<PortableForm>, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs, parachain_names: &Vec<ParachainName>, assets: &Vec<AssetInfo>, addresses: &Tree) -> Result<DecodedOut, Error> {
     
     let enum_index = match data.get(0) {
         Some(x) => *x,
@@ -552,7 +578,7 @@ fn decode_type_def_variant (found_ty: &TypeDefVariant<PortableForm>, mut data: V
                     data = data[1..].to_vec();
                     let found_variant = &found_ty.variants()[1];
                     let compact_flag = false;
-                    process_fields(found_variant.fields(), compact_flag, data, meta_v14, index, indent, chain_specs)
+                    process_fields(found_variant.fields(), compact_flag, data, meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)
                 },
                 _ => {return Err(Error::UnableToDecode(UnableToDecode::UnexpectedOptionVariant))},
             }
@@ -573,7 +599,7 @@ fn decode_type_def_variant (found_ty: &TypeDefVariant<PortableForm>, mut data: V
         data = data[1..].to_vec();
         
         let compact_flag = false;
-        let fields_processed = process_fields(found_variant.fields(), compact_flag, data, meta_v14, index, indent+1, chain_specs)?;
+        let fields_processed = process_fields(found_variant.fields(), compact_flag, data, meta_v14, index, indent+1, chain_specs, parachain_names, assets, addresses)?;
         fancy_out.push_str(&fields_processed.fancy_out);
         index = fields_processed.index;
         data = fields_processed.remaining_vector;
@@ -588,9 +614,106 @@ fn decode_type_def_variant (found_ty: &TypeDefVariant<PortableForm>, mut data: V
 }
 
 
-fn process_fields (fields: &[Field<PortableForm>], compact_flag: bool, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+/// Token symbol found in the currency id of multi-token network
+enum CurrencySymbol {
+    Token(String),
+    Unresolved,
+}
+
+/// Function to check if the type is currency id of multi-token network,
+/// and to get the token symbol from the data if so.
+/// Currency id is an enum, token symbol is the name of innermost variant without fields,
+/// for example, `CurrencyId::Token(TokenSymbol::KUSD)`.
+/// Currency ids that could not be brought to token symbol,
+/// for example, `CurrencyId::ForeignAsset(u32)`, `CurrencyId::DexShare(..)` or `CurrencyId::Erc20(..)`,
+/// are unresolved, and the balances in them are displayed as plain numbers.
+fn find_currency_symbol (type_id: u32, data: &Vec<u8>, meta_v14: &RuntimeMetadataV14) -> Option<CurrencySymbol> {
+    let current_type = meta_v14.types.resolve(type_id)?;
+    match current_type.path().ident() {
+        Some(a) => if a != "CurrencyId" {return None},
+        None => return None,
+    }
+    Some(variant_symbol(current_type, data, meta_v14))
+}
+
+fn variant_symbol (current_type: &Type<PortableForm>, data: &[u8], meta_v14: &RuntimeMetadataV14) -> CurrencySymbol {
+    if let TypeDef::Variant(x) = current_type.type_def() {
+        let found_variant = match data.get(0) {
+            Some(enum_index) => match x.variants().iter().find(|a| a.index() == *enum_index) {
+                Some(a) => a,
+                None => return CurrencySymbol::Unresolved,
+            },
+            None => return CurrencySymbol::Unresolved,
+        };
+        match found_variant.fields().len() {
+            0 => CurrencySymbol::Token(found_variant.name().to_string()),
+            1 => match meta_v14.types.resolve(found_variant.fields()[0].ty().id()) {
+                Some(a) => variant_symbol(a, &data[1..], meta_v14),
+                None => CurrencySymbol::Unresolved,
+            },
+            _ => CurrencySymbol::Unresolved,
+        }
+    }
+    else {CurrencySymbol::Unresolved}
+}
+
+/// Function to check if the field is asset id of pallet_assets,
+/// and to get the asset id from the data if so.
+/// Asset id is recognized by the field type name, and is expected to be u32, possibly compact.
+fn find_asset_id (field: &Field<PortableForm>, data: &Vec<u8>, meta_v14: &RuntimeMetadataV14) -> Option<u32> {
+    if !field.type_name()?.contains("AssetId") {return None}
+    match meta_v14.types.resolve(field.ty().id())?.type_def() {
+        TypeDef::Primitive(TypeDefPrimitive::U32) => <u32>::decode(&mut &data[..]).ok(),
+        TypeDef::Compact(_) => get_compact::<u32>(data).ok().map(|a| a.compact_found),
+        _ => None,
+    }
+}
+
+fn process_fields (fields: &[Field<PortableForm>], compact_flag: bool, mut data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32, chain_specs: &ChainSpecs, parachain_names: &Vec<ParachainName>, assets: &Vec<AssetInfo>, addresses: &Tree) -> Result<DecodedOut, Error> {
     let mut fancy_out = String::new();
+// balances following the currency id in multi-token networks or the asset id of pallet_assets
+// are displayed in units of this currency or asset,
+// balances in unknown currency or asset are displayed as plain numbers
+    let mut currency_specs: Option<ChainSpecs> = None;
+    let mut currency_unknown = false;
     for (i, x) in fields.iter().enumerate() {
+        if let Some(asset_id) = find_asset_id(x, &data, meta_v14) {
+            match assets.iter().find(|a| a.asset_id == asset_id) {
+                Some(a) => {
+                    currency_specs = Some(ChainSpecs{decimals: a.decimals, unit: a.symbol.to_string(), ..chain_specs.clone()});
+                    currency_unknown = false;
+                },
+                None => {
+                    currency_specs = None;
+                    currency_unknown = true;
+                },
+            }
+        }
+        match find_currency_symbol(x.ty().id(), &data, meta_v14) {
+            Some(CurrencySymbol::Token(symbol)) => {
+                if symbol.to_lowercase() == chain_specs.unit.to_lowercase() {
+                    currency_specs = None;
+                    currency_unknown = false;
+                }
+                else {
+                    match chain_specs.find_token(&symbol) {
+                        Some(a) => {
+                            currency_specs = Some(ChainSpecs{decimals: a.decimals, unit: a.unit.to_string(), ..chain_specs.clone()});
+                            currency_unknown = false;
+                        },
+                        None => {
+                            currency_specs = None;
+                            currency_unknown = true;
+                        },
+                    }
+                }
+            },
+            Some(CurrencySymbol::Unresolved) => {
+                currency_specs = None;
+                currency_unknown = true;
+            },
+            None => (),
+        }
         let mut balance_flag = false;
         let mut field_docs = String::new();
         for (j, y) in x.docs().iter().enumerate() {
@@ -603,7 +726,7 @@ fn process_fields (fields: &[Field<PortableForm>], compact_flag: bool, mut data:
                 index = index + 1;
                 fancy_out.push_str(&fancy_out_prep);
                 balance_flag = match x.type_name() {
-                    Some(a) => field_type_name_is_balance(&a),
+                    Some(a) => field_type_name_is_balance(&a) && !currency_unknown,
                     None => false,
                 };
             },
@@ -615,7 +738,11 @@ fn process_fields (fields: &[Field<PortableForm>], compact_flag: bool, mut data:
                 }
             },
         }
-        let after_run = decoding_sci_complete(x.ty().id(), compact_flag, balance_flag, data, meta_v14, index, indent+1, chain_specs)?;
+        let current_specs = match &currency_specs {
+            Some(a) => a,
+            None => chain_specs,
+        };
+        let after_run = decoding_sci_complete(x.ty().id(), compact_flag, balance_flag, data, meta_v14, index, indent+1, current_specs, parachain_names, assets, addresses)?;
         index = after_run.index;
         fancy_out.push_str(&after_run.fancy_out);
         data = after_run.remaining_vector;
@@ -628,9 +755,9 @@ fn process_fields (fields: &[Field<PortableForm>], compact_flag: bool, mut data:
     })
 }
 
-fn decode_type_def_composite (composite_ty: &TypeDefComposite<PortableForm>, compact_flag: bool, data: Vec<u8>, meta_v14: &RuntimeMetadataV14, index: u32, indent: u32, chain_specs: &ChainSpecs) -> Result<DecodedOut, Error> {
+fn decode_type_def_composite (composite_ty: &TypeDefComposite<PortableForm>, compact_flag: bool, data: Vec<u8>, meta_v14: &RuntimeMetadataV14, index: u32, indent: u32, chain_specs: &ChainSpecs, parachain_names: &Vec<ParachainName>, assets: &Vec<AssetInfo>, addresses: &Tree) -> Result<DecodedOut, Error> {
     if compact_flag && (composite_ty.fields().len()>1) {return Err(Error::UnableToDecode(UnableToDecode::UnexpectedCompactInsides))}
-    process_fields (composite_ty.fields(), compact_flag, data, meta_v14, index, indent, chain_specs)
+    process_fields (composite_ty.fields(), compact_flag, data, meta_v14, index, indent, chain_specs, parachain_names, assets, addresses)
 }
 
 fn decode_type_def_bit_sequence (bit_ty: &TypeDefBitSequence<PortableForm>, data: Vec<u8>, meta_v14: &RuntimeMetadataV14, mut index: u32, indent: u32) -> Result<DecodedOut, Error> {
@@ -1,4 +1,5 @@
 use sled;
+use constants::MAX_DECOMPRESSED_CONTENT;
 
 #[derive(PartialEq)]
 pub enum Error {
@@ -35,6 +36,21 @@ pub enum BadInputData {
     UnableToDecodeLoadMetadataMessage,
     ImportantSpecsChanged,
     EncryptionMismatch,
+    UnableToDecodeSignatures,
+    MultipleSignaturesNotSupported,
+    UnableToDecodeSetVerifiersMessage,
+    BadVerifierSet,
+    VerifierSetAlreadyThere,
+    UnableToDecodeRotateVerifierMessage,
+    BadVerifierRotation,
+    UnableToDecodeDerivations,
+    NoDerivationsToImport,
+    UnableToDecompressContent,
+    DecompressedContentTooLarge,
+    UnableToDecodeLoadAssetsMessage,
+    BadAssets,
+    AssetsAlreadyThere,
+    UnexpectedAssetTip,
 }
 
 #[derive(PartialEq)]
@@ -83,6 +99,11 @@ pub enum DatabaseError {
     NoGeneralVerifier,
     DamagedNetworkVerifier,
     NoNetworkVerifier ([u8; 32]),
+    DamagedVerifierSet,
+    DamagedVerifierRotation,
+    DamagedRetentionPolicy,
+    DamagedCompressedMeta,
+    DamagedAssets,
 }
 
 #[derive(PartialEq)]
@@ -105,7 +126,13 @@ pub enum CryptoError {
     GeneralVerifierChanged {old_show: String, new_show: String},
     GeneralVerifierDisappeared,
     NetworkExistsVerifierDisappeared,
-    
+    VerifierSetThresholdNotMet {approvals: u32, verifier_set_show: String},
+    NoVerifierSet,
+    VerifierSetNotAuthorized,
+    NoVerifierToRotate,
+    RotationNotByVerifier {old_show: String, new_show: String},
+    VerifierRotationNotEffective {effective_from: u32, version: u32},
+    AssetsNotAuthorized,
 }
 
 impl Error {
@@ -137,6 +164,21 @@ impl Error {
                     BadInputData::UnableToDecodeLoadMetadataMessage => String::from("Unable to decode received load metadata message."),
                     BadInputData::ImportantSpecsChanged => String::from("Network already has entries. Important chainspecs in received add network message are different."),
                     BadInputData::EncryptionMismatch => String::from("Encryption used in message is not supported by the network."),
+                    BadInputData::UnableToDecodeSignatures => String::from("Unable to decode signatures of multi-signed message."),
+                    BadInputData::MultipleSignaturesNotSupported => String::from("Messages with several signatures are supported only for network metadata, verifier sets and asset registries."),
+                    BadInputData::UnableToDecodeSetVerifiersMessage => String::from("Unable to decode received set verifiers message."),
+                    BadInputData::BadVerifierSet => String::from("Received verifier set is invalid. Threshold should be between 1 and the number of verifiers, verifiers should be distinct public keys."),
+                    BadInputData::VerifierSetAlreadyThere => String::from("Received verifier set is already in database."),
+                    BadInputData::UnableToDecodeRotateVerifierMessage => String::from("Unable to decode received rotate verifier message."),
+                    BadInputData::BadVerifierRotation => String::from("New verifier in received rotate verifier message should be a public key different from the current network verifier."),
+                    BadInputData::UnableToDecodeDerivations => String::from("Unable to decode received derivations list."),
+                    BadInputData::NoDerivationsToImport => String::from("Received derivations list has no derivations for the networks in the database."),
+                    BadInputData::UnableToDecompressContent => String::from("Unable to decompress received message content."),
+                    BadInputData::DecompressedContentTooLarge => format!("Decompressed message content exceeds the size limit of {} bytes.", MAX_DECOMPRESSED_CONTENT),
+                    BadInputData::UnableToDecodeLoadAssetsMessage => String::from("Unable to decode received load assets message."),
+                    BadInputData::BadAssets => String::from("Received asset registry is invalid. Asset ids should be distinct."),
+                    BadInputData::AssetsAlreadyThere => String::from("Received asset registry is already in database."),
+                    BadInputData::UnexpectedAssetTip => String::from("Transaction tip is followed by asset id, but the network metadata has no ChargeAssetTxPayment signed extension."),
                 }
             },
             Error::UnableToDecode(x) => {
@@ -185,6 +227,11 @@ impl Error {
                     DatabaseError::NoGeneralVerifier => String::from("No general verifier information in the database."),
                     DatabaseError::DamagedNetworkVerifier => String::from("Network verifier is damaged and could not be decoded."),
                     DatabaseError::NoNetworkVerifier(x) => format!("No network verifier information in the database for genesis hash {}.", hex::encode(x)),
+                    DatabaseError::DamagedVerifierSet => String::from("Network verifier set is damaged and could not be decoded."),
+                    DatabaseError::DamagedVerifierRotation => String::from("Pending network verifier rotation is damaged and could not be decoded."),
+                    DatabaseError::DamagedRetentionPolicy => String::from("Metadata retention policy is damaged and could not be decoded."),
+                    DatabaseError::DamagedCompressedMeta => String::from("Compressed metadata from the database could not be decompressed."),
+                    DatabaseError::DamagedAssets => String::from("Network asset registry is damaged and could not be decoded."),
                 }
             },
             Error::SystemError(x) => {
@@ -207,6 +254,13 @@ impl Error {
                     CryptoError::GeneralVerifierChanged {old_show, new_show} => format!("Different general verifier was used previously. Previously used {}. Current attempt {}.", old_show, new_show),
                     CryptoError::GeneralVerifierDisappeared => String::from("General verifier information exists in the database. Received information could be accepted only from the same general verifier."),
                     CryptoError::NetworkExistsVerifierDisappeared => String::from("Network already has specs recorded in database. Received add network message is not signed, previously this network information was signed."),
+                    CryptoError::VerifierSetThresholdNotMet {approvals, verifier_set_show} => format!("Network is verified by {}. Received message is signed by only {} of them.", verifier_set_show, approvals),
+                    CryptoError::NoVerifierSet => String::from("Received message has several signatures, but network has no verifier set."),
+                    CryptoError::VerifierSetNotAuthorized => String::from("Verifier set could be changed only by current network verifier, or by general verifier for networks without one."),
+                    CryptoError::NoVerifierToRotate => String::from("Network has no verifier. Received rotate verifier message is not applicable."),
+                    CryptoError::RotationNotByVerifier {old_show, new_show} => format!("Rotate verifier message should be signed by current network verifier. Current verifier {}. Message signed by {}.", old_show, new_show),
+                    CryptoError::VerifierRotationNotEffective {effective_from, version} => format!("Network verifier rotation to this verifier takes effect from metadata version {}. Received metadata version is {}.", effective_from, version),
+                    CryptoError::AssetsNotAuthorized => String::from("Asset registry could be loaded only if signed by current network verifier, or by general verifier for networks without one."),
                 }
             },
         }
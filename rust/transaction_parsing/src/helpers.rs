@@ -1,6 +1,7 @@
 use sled::{Db, Tree, open, IVec};
 use hex;
-use definitions::network_specs::{Verifier, generate_verifier_key};
+use constants::MAX_DECOMPRESSED_CONTENT;
+use definitions::{network_specs::{AssetInfo, Verifier, VerifierRotation, VerifierSet, generate_assets_key, generate_verifier_key, generate_verifier_rotation_key, generate_verifier_set_key}, qr_transfers::{ContentDecompression, decompress_content}};
 use parity_scale_codec::Decode;
 
 use crate::error::{Error, BadInputData, DatabaseError};
@@ -84,3 +85,53 @@ pub fn get_verifier (genesis_hash: [u8; 32], verifiers: &Tree) -> Result<Verifie
         Err(e) => return Err(Error::DatabaseError(DatabaseError::Internal(e))),
     }
 }
+
+/// Function to get verifier set for network with given genesis hash, if there is one, with crate error (card)
+pub fn get_verifier_set (genesis_hash: [u8; 32], verifiers: &Tree) -> Result<Option<VerifierSet>, Error> {
+    match verifiers.get(&generate_verifier_set_key(&genesis_hash.to_vec())) {
+        Ok(Some(verifier_set_encoded)) => match <VerifierSet>::decode(&mut &verifier_set_encoded[..]) {
+            Ok(a) => Ok(Some(a)),
+            Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedVerifierSet)),
+        },
+        Ok(None) => Ok(None),
+        Err(e) => return Err(Error::DatabaseError(DatabaseError::Internal(e))),
+    }
+}
+
+/// Function to get pending verifier rotation for network with given genesis hash, if there is one, with crate error (card)
+pub fn get_verifier_rotation (genesis_hash: [u8; 32], verifiers: &Tree) -> Result<Option<VerifierRotation>, Error> {
+    match verifiers.get(&generate_verifier_rotation_key(&genesis_hash.to_vec())) {
+        Ok(Some(verifier_rotation_encoded)) => match <VerifierRotation>::decode(&mut &verifier_rotation_encoded[..]) {
+            Ok(a) => Ok(Some(a)),
+            Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedVerifierRotation)),
+        },
+        Ok(None) => Ok(None),
+        Err(e) => return Err(Error::DatabaseError(DatabaseError::Internal(e))),
+    }
+}
+
+/// Function to get asset registry for network with given genesis hash, with crate error (card);
+/// networks without asset registry get empty one
+pub fn get_assets (genesis_hash: [u8; 32], assets: &Tree) -> Result<Vec<AssetInfo>, Error> {
+    match assets.get(&generate_assets_key(&genesis_hash.to_vec())) {
+        Ok(Some(assets_encoded)) => match <Vec<AssetInfo>>::decode(&mut &assets_encoded[..]) {
+            Ok(a) => Ok(a),
+            Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedAssets)),
+        },
+        Ok(None) => Ok(Vec::new()),
+        Err(e) => return Err(Error::DatabaseError(DatabaseError::Internal(e))),
+    }
+}
+
+/// Function to get message content from checked message, decompressing it if the message was compressed;
+/// decompressed content size is limited, with crate error (card)
+pub fn message_content (message: Vec<u8>, compressed: bool) -> Result<Vec<u8>, Error> {
+    if compressed {
+        match decompress_content(&message, MAX_DECOMPRESSED_CONTENT) {
+            Ok(a) => Ok(a),
+            Err(ContentDecompression::Damaged) => return Err(Error::BadInputData(BadInputData::UnableToDecompressContent)),
+            Err(ContentDecompression::TooLarge) => return Err(Error::BadInputData(BadInputData::DecompressedContentTooLarge)),
+        }
+    }
+    else {Ok(message)}
+}
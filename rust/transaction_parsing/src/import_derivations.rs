@@ -0,0 +1,67 @@
+use constants::{IMPORTDERIVATIONS, SPECSTREE, TRANSACTION};
+use definitions::{network_specs::generate_network_key, qr_transfers::ContentDerivations, transactions::{Transaction, ImportDerivations}};
+use parity_scale_codec::Encode;
+
+use crate::cards::{Action, Card, Warning};
+use crate::check_signature::pass_crypto;
+use crate::error::{Error, BadInputData, DatabaseError};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_checksum};
+use crate::utils::get_chainspecs;
+
+
+/// Function to process import_derivations message, i.e. the list of derivations
+/// (path, network genesis hash, expected public key, name) exported from the hot side.
+/// Derivations for networks not in the database are skipped with a warning;
+/// public keys are checked against the seed phrase only when the action is accepted.
+
+pub fn import_derivations (data_hex: &str, dbname: &str) -> Result<String, Error> {
+
+    let database = open_db(dbname)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+    let transaction = open_tree(&database, TRANSACTION)?;
+
+    let checked_info = pass_crypto(&data_hex)?;
+    if checked_info.signers.len() > 1 {return Err(Error::BadInputData(BadInputData::MultipleSignaturesNotSupported))}
+
+    let received_derivations = match ContentDerivations::from_vec(&checked_info.message).derivations() {
+        Ok(x) => x,
+        Err(_) => return Err(Error::BadInputData(BadInputData::UnableToDecodeDerivations)),
+    };
+
+    let mut derivations = Vec::new();
+    let mut derivation_cards = String::new();
+    let mut warning_cards = String::new();
+    let mut index = 0;
+
+    for x in received_derivations.into_iter() {
+        let network_key = generate_network_key(&x.genesis_hash.to_vec(), x.encryption);
+        match get_chainspecs(&network_key, &chainspecs) {
+            Ok(chain_specs) => {
+                if derivation_cards.len() > 0 {derivation_cards.push_str(",")}
+                derivation_cards.push_str(&Card::Derivation{path: &x.path, name: &x.name, network_title: &chain_specs.title, public_key: &x.public_key, encryption: x.encryption}.card(index,0));
+                derivations.push(x);
+            },
+            Err(Error::DatabaseError(DatabaseError::NoNetwork)) => {
+                if warning_cards.len() > 0 {warning_cards.push_str(",")}
+                warning_cards.push_str(&Card::Warning(Warning::DerivationNetworkNotFound{path: x.path, genesis_hash: x.genesis_hash, encryption: x.encryption}).card(index,0));
+            },
+            Err(e) => return Err(e),
+        }
+        index = index + 1;
+    }
+
+    if derivations.len() == 0 {return Err(Error::BadInputData(BadInputData::NoDerivationsToImport))}
+
+// making action entry into database
+    let import_derivations = Transaction::ImportDerivations(ImportDerivations{
+        derivations,
+        history: Vec::new(),
+    });
+    insert_into_tree(IMPORTDERIVATIONS.to_vec(), import_derivations.encode(), &transaction)?;
+    flush_db(&database)?;
+    let checksum = get_checksum(&database)?;
+
+    let action_card = Action::ImportDerivations(checksum).card();
+    if warning_cards.len() == 0 {Ok(format!("{{\"derivations\":[{}],{}}}", derivation_cards, action_card))}
+    else {Ok(format!("{{\"warning\":[{}],\"derivations\":[{}],{}}}", warning_cards, derivation_cards, action_card))}
+}
@@ -11,6 +11,10 @@ mod decoding_sci;
 mod error;
     use error::{Error, BadInputData};
 mod helpers;
+mod import_derivations;
+    use import_derivations::import_derivations;
+mod load_assets;
+    use load_assets::load_assets;
 mod load_metadata;
     use load_metadata::load_metadata;
 mod load_types;
@@ -18,6 +22,10 @@ mod load_types;
 mod method;
 mod parse_transaction;
     use parse_transaction::parse_transaction;
+mod rotate_verifier;
+    use rotate_verifier::rotate_verifier;
+mod set_verifiers;
+    use set_verifiers::set_verifiers;
 pub mod test_all_cards;
     use test_all_cards::make_all_cards;
 mod tests;
@@ -47,9 +55,15 @@ fn handle_scanner_input (payload: &str, dbname: &str) -> Result<String, Error> {
     
     match &data_hex[4..6] {
         "00"|"02" => parse_transaction(data_hex, dbname),
-        "80" => load_metadata(data_hex, dbname),
+        "80" => load_metadata(data_hex, dbname, false),
         "81" => load_types(data_hex, dbname),
-        "c0" => add_network(data_hex, dbname),
+        "82" => set_verifiers(data_hex, dbname),
+        "83" => rotate_verifier(data_hex, dbname),
+        "84" => load_metadata(data_hex, dbname, true),
+        "85" => load_assets(data_hex, dbname),
+        "c0" => add_network(data_hex, dbname, false),
+        "c2" => add_network(data_hex, dbname, true),
+        "de" => import_derivations(data_hex, dbname),
 //        "c1" => add_specs(data_hex, dbname),
         "f0" => Ok(make_all_cards()),
         _ => return Err(Error::BadInputData(BadInputData::WrongPayloadType)),
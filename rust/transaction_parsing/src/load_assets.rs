@@ -0,0 +1,93 @@
+use constants::{ASSETS, LOADASSETS, SETTREE, SPECSTREE, TRANSACTION, VERIFIERS};
+use definitions::{network_specs::{ChainSpecs, Verifier, show_assets}, qr_transfers::ContentLoadAssets, transactions::{Transaction, LoadAssets}};
+use parity_scale_codec::{Decode, Encode};
+
+use crate::cards::{Action, Card};
+use crate::check_signature::pass_crypto;
+use crate::error::{Error, BadInputData, CryptoError, DatabaseError};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_assets, get_checksum, get_verifier, get_verifier_set};
+use crate::utils::get_general_verifier;
+
+
+/// Function to process load_assets message, i.e. the asset registry of the network
+/// (asset id, symbol and decimals for assets of pallet_assets), used to display asset balances.
+/// If the network has verifier set, the message must be signed by enough verifiers from it.
+/// If not, the message must be signed by the current network verifier, or, if the network is not verified,
+/// by the general verifier.
+/// Received asset registry replaces the one in the database.
+
+pub fn load_assets (data_hex: &str, dbname: &str) -> Result<String, Error> {
+
+    let database = open_db(dbname)?;
+    let chainspecs = open_tree(&database, SPECSTREE)?;
+    let settings = open_tree(&database, SETTREE)?;
+    let transaction = open_tree(&database, TRANSACTION)?;
+    let verifiers = open_tree(&database, VERIFIERS)?;
+    let assets = open_tree(&database, ASSETS)?;
+
+    let checked_info = pass_crypto(&data_hex)?;
+
+    let (gen_hash, new_assets) = match ContentLoadAssets::from_vec(&checked_info.message).genhash_assets() {
+        Ok(x) => x,
+        Err(_) => return Err(Error::BadInputData(BadInputData::UnableToDecodeLoadAssetsMessage)),
+    };
+    for (i, x) in new_assets.iter().enumerate() {
+        if new_assets[..i].iter().any(|a| a.asset_id == x.asset_id) {return Err(Error::BadInputData(BadInputData::BadAssets))}
+    }
+
+// same network could be in database with different encryptions, title of the first found one is used
+    let mut network_title = None;
+    for x in chainspecs.iter() {
+        if let Ok((_, chain_specs_encoded)) = x {
+            match <ChainSpecs>::decode(&mut &chain_specs_encoded[..]) {
+                Ok(a) => if a.genesis_hash == gen_hash {
+                    network_title = Some(a.title);
+                    break;
+                },
+                Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedChainSpecs)),
+            }
+        }
+    }
+    let network_title = match network_title {
+        Some(a) => a,
+        None => return Err(Error::DatabaseError(DatabaseError::NoNetwork)),
+    };
+
+    let current_verifier = get_verifier (gen_hash, &verifiers)?;
+    match get_verifier_set (gen_hash, &verifiers)? {
+        Some(current_verifier_set) => {
+            let approvals = current_verifier_set.approvals(&checked_info.signers);
+            if approvals < current_verifier_set.threshold {return Err(Error::CryptoError(CryptoError::VerifierSetThresholdNotMet{approvals, verifier_set_show: current_verifier_set.show_error()}))}
+        },
+        None => {
+            let authority = {
+                if current_verifier == Verifier::None {get_general_verifier(&settings)?}
+                else {current_verifier}
+            };
+            if (authority == Verifier::None)||(!checked_info.signers.contains(&authority)) {return Err(Error::CryptoError(CryptoError::AssetsNotAuthorized))}
+        },
+    }
+
+    if get_assets(gen_hash, &assets)? == new_assets {return Err(Error::BadInputData(BadInputData::AssetsAlreadyThere))}
+
+    let mut verifier_cards = String::new();
+    for (i, x) in checked_info.signers.iter().enumerate() {
+        if i>0 {verifier_cards.push_str(",")}
+        verifier_cards.push_str(&Card::Verifier(x.show_card()).card(i as u32, 0));
+    }
+    let index = checked_info.signers.len() as u32;
+    let assets_card = Card::Assets{network_title: &network_title, assets_line: show_assets(&new_assets)}.card(index, 0);
+
+// making action entry into database
+    let load_assets = Transaction::LoadAssets(LoadAssets{
+        genesis_hash: gen_hash,
+        assets: new_assets,
+        history: Vec::new(),
+    });
+    insert_into_tree(LOADASSETS.to_vec(), load_assets.encode(), &transaction)?;
+    flush_db(&database)?;
+    let checksum = get_checksum(&database)?;
+
+    let action_card = Action::LoadAssets(checksum).card();
+    Ok(format!("{{\"verifier\":[{}],\"assets\":[{}],{}}}", verifier_cards, assets_card, action_card))
+}
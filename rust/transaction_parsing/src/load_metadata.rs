@@ -1,19 +1,19 @@
 use hex;
 use sled::{Db, Tree};
-use constants::{ADDGENERALVERIFIER, ADDMETAVERIFIER, LOADMETA, METATREE, TRANSACTION, VERIFIERS};
-use definitions::{network_specs::{Verifier, generate_verifier_key, VerifierKey}, transactions::{Transaction, LoadMeta, UpdMetaVerifier, UpdGeneralVerifier}, metadata::{MetaValuesDisplay, NameVersioned, VersionDecoded}, history::Event, qr_transfers::ContentLoadMeta};
-use meta_reading::decode_metadata::get_meta_const_light;
+use constants::{ADDGENERALVERIFIER, ADDMETAVERIFIER, LOADMETA, METATREE, SETTREE, TRANSACTION, VERIFIERS};
+use db_handling::helpers::{get_retention_policy, metadata_to_remove};
+use definitions::{network_specs::{Verifier, generate_verifier_key, VerifierKey}, transactions::{Transaction, LoadMeta, UpdMetaVerifier, UpdGeneralVerifier}, metadata::{MetaValuesDisplay, NameVersioned, VersionDecoded, decompress_meta}, history::Event, qr_transfers::ContentLoadMeta};
+use meta_reading::{decode_metadata::{decode_runtime_metadata, get_meta_const_light}, diff_metadata::diff_metadata};
 use parity_scale_codec::{Decode, Encode};
 use blake2_rfc::blake2b::blake2b;
-use frame_metadata::RuntimeMetadata;
 
 use crate::cards::{Action, Card, Warning};
 use crate::check_signature::pass_crypto;
-use crate::error::{Error, BadInputData, CryptoError};
-use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_checksum, get_from_tree, get_verifier};
+use crate::error::{Error, BadInputData, CryptoError, DatabaseError};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_checksum, get_from_tree, get_verifier, get_verifier_rotation, get_verifier_set, message_content};
 
 
-pub fn load_metadata (data_hex: &str, dbname: &str) -> Result<String, Error> {
+pub fn load_metadata (data_hex: &str, dbname: &str, compressed: bool) -> Result<String, Error> {
 
 // loading the database and removing the previous (if any) load_metadata saves
     let database = open_db(dbname)?;
@@ -23,7 +23,9 @@ pub fn load_metadata (data_hex: &str, dbname: &str) -> Result<String, Error> {
     
     let checked_info = pass_crypto(&data_hex)?;
     
-    let (meta, gen_hash) = match ContentLoadMeta::from_vec(&checked_info.message).meta_genhash() {
+    let content = message_content(checked_info.message, compressed)?;
+    
+    let (meta, gen_hash) = match ContentLoadMeta::from_vec(&content).meta_genhash() {
         Ok(x) => x,
         Err(_) => return Err(Error::BadInputData(BadInputData::UnableToDecodeLoadMetadataMessage)),
     };
@@ -31,6 +33,21 @@ pub fn load_metadata (data_hex: &str, dbname: &str) -> Result<String, Error> {
     let verifier = checked_info.verifier;
     let current_verifier = get_verifier (gen_hash, &verifiers)?;
     
+    if let Some(verifier_set) = get_verifier_set (gen_hash, &verifiers)? {
+    // network is verified by verifier set, metadata is accepted only if enough verifiers from the set signed it;
+    // verifiers could not be updated through load_metadata message
+        let approvals = verifier_set.approvals(&checked_info.signers);
+        if approvals < verifier_set.threshold {return Err(Error::CryptoError(CryptoError::VerifierSetThresholdNotMet{approvals, verifier_set_show: verifier_set.show_error()}))}
+        let verifier_set_card = Card::VerifierSet(verifier_set.show_card()).card(0,0);
+        let index = 1;
+        let upd_network = None;
+        let upd_general = false;
+        let history: Vec<Event> = Vec::new();
+        let (meta_card, action_card) = process_received_metadata(meta, None, history, index, upd_network, upd_general, verifier, &metadata, &transaction, &database)?;
+        return Ok(format!("{{\"verifier_set\":[{}],\"meta\":[{}],{}}}", verifier_set_card, meta_card, action_card))
+    }
+    if checked_info.signers.len() > 1 {return Err(Error::CryptoError(CryptoError::NoVerifierSet))}
+    
     match verifier {
         Verifier::None => {
             if current_verifier == Verifier::None {
@@ -70,7 +87,26 @@ pub fn load_metadata (data_hex: &str, dbname: &str) -> Result<String, Error> {
                     if meta_card == possible_warning {Ok(format!("{{\"verifier\":[{}],\"warning\":[{},{}],{}}}", verifier_card, warning_card, meta_card, action_card))}
                     else {Ok(format!("{{\"verifier\":[{}],\"warning\":[{}],\"meta\":[{}],{}}}", verifier_card, warning_card, meta_card, action_card))}
                 }
-                else {return Err(Error::CryptoError(CryptoError::VerifierChanged{old_show: current_verifier.show_error(), new_show: verifier.show_error()}))}
+                else {
+                    match get_verifier_rotation (gen_hash, &verifiers)? {
+                        Some(verifier_rotation) if verifier_rotation.new_verifier == verifier => {
+                        // pending rotation to this verifier is completed if metadata version is high enough;
+                        // action could be either uploading of metadata or only updating of the network verifier
+                            let version = get_meta_version(&meta)?;
+                            if version < verifier_rotation.effective_from {return Err(Error::CryptoError(CryptoError::VerifierRotationNotEffective{effective_from: verifier_rotation.effective_from, version}))}
+                            let warning_card = Card::Warning(Warning::VerifierRotationCompleted).card(1,0);
+                            let possible_warning = Card::Warning(Warning::MetaAlreadyThereUpdMetaVerifier).card(2, 0);
+                            let index = 2;
+                            let upd_network = Some(generate_verifier_key(&gen_hash.to_vec()));
+                            let upd_general = false;
+                            let history: Vec<Event> = vec![Event::Warning(Warning::VerifierRotationCompleted.show())];
+                            let (meta_card, action_card) = process_received_metadata(meta, None, history, index, upd_network, upd_general, verifier, &metadata, &transaction, &database)?;
+                            if meta_card == possible_warning {Ok(format!("{{\"verifier\":[{}],\"warning\":[{},{}],{}}}", verifier_card, warning_card, meta_card, action_card))}
+                            else {Ok(format!("{{\"verifier\":[{}],\"warning\":[{}],\"meta\":[{}],{}}}", verifier_card, warning_card, meta_card, action_card))}
+                        },
+                        _ => return Err(Error::CryptoError(CryptoError::VerifierChanged{old_show: current_verifier.show_error(), new_show: verifier.show_error()})),
+                    }
+                }
             }
         },
         
@@ -78,11 +114,36 @@ pub fn load_metadata (data_hex: &str, dbname: &str) -> Result<String, Error> {
 }
 
 
+/// Function to get runtime version from received metadata, prior to its processing
+fn get_meta_version (meta: &Vec<u8>) -> Result<u32, Error> {
+    if !meta.starts_with(&vec![109, 101, 116, 97]) {return Err(Error::BadInputData(BadInputData::NotMeta))}
+    match meta.get(4) {
+        Some(a) => if *a < 12 {return Err(Error::BadInputData(BadInputData::MetaVersionBelow12))},
+        None => return Err(Error::BadInputData(BadInputData::NotMeta)),
+    }
+    let received_metadata = match decode_runtime_metadata(&meta[4..]) {
+        Ok(a) => a,
+        Err(_) => return Err(Error::BadInputData(BadInputData::UnableToDecodeMeta)),
+    };
+    let version_vector = match get_meta_const_light(&received_metadata) {
+        Ok(a) => a,
+        Err(_) => return Err(Error::BadInputData(BadInputData::NoMetaVersion)),
+    };
+    match VersionDecoded::decode(&mut &version_vector[..]) {
+        Ok(a) => Ok(a.spec_version),
+        Err(_) => return Err(Error::BadInputData(BadInputData::VersionNotDecodeable)),
+    }
+}
+
+
 /// Function to check incoming metadata, and prepare info card and database entry
 pub fn process_received_metadata (meta: Vec<u8>, name_to_check: Option<&str>, history: Vec<Event>, index: u32, upd_network: Option<VerifierKey>, upd_general: bool, verifier: Verifier, metadata: &Tree, transaction: &Tree, database: &Db) -> Result<(String, String), Error> {
     if !meta.starts_with(&vec![109, 101, 116, 97]) {return Err(Error::BadInputData(BadInputData::NotMeta))}
-    if meta[4] < 12 {return Err(Error::BadInputData(BadInputData::MetaVersionBelow12))}
-    match RuntimeMetadata::decode(&mut &meta[4..]) {
+    match meta.get(4) {
+        Some(a) => if *a < 12 {return Err(Error::BadInputData(BadInputData::MetaVersionBelow12))},
+        None => return Err(Error::BadInputData(BadInputData::NotMeta)),
+    }
+    match decode_runtime_metadata(&meta[4..]) {
         Ok(received_metadata) => {
             match get_meta_const_light(&received_metadata) {
                 Ok(x) => {
@@ -97,8 +158,12 @@ pub fn process_received_metadata (meta: Vec<u8>, name_to_check: Option<&str>, hi
                             };
                         // search through the database to check if the metadata is already there
                             match get_from_tree(&received_versioned_name.encode(), metadata)? {
-                                Some(a) => {
+                                Some(meta_stored) => {
                                 // same versioned name found
+                                    let a = match decompress_meta(&meta_stored) {
+                                        Ok(a) => a,
+                                        Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedCompressedMeta)),
+                                    };
                                     if a[..] == meta[..] {
                                     // same versioned name found, and metadata equal
                                         match upd_network {
@@ -167,7 +232,33 @@ pub fn process_received_metadata (meta: Vec<u8>, name_to_check: Option<&str>, hi
                                         version: y.spec_version,
                                         meta_hash: &hex::encode(blake2b(32, &[], &meta).as_bytes()),
                                     }.show();
-                                    let meta_card = Card::Meta(new_meta).card(index, 0);
+                                    let mut meta_card = Card::Meta(new_meta).card(index, 0);
+                                    let mut next_index = index + 1;
+                                // changes compared to the latest older version of same network, if there is one
+                                    if let Some((previous_version, previous_meta)) = previous_metadata(&y.specname, y.spec_version, metadata)? {
+                                        if let Ok(diff) = diff_metadata(&previous_meta, &meta) {
+                                            meta_card.push_str(&format!(",{}", Card::MetaDiff{previous_version, diff: diff.show_card()}.card(next_index, 0)));
+                                            next_index = next_index + 1;
+                                        }
+                                    }
+                                // older metadata versions to be removed under retention policy, if the metadata is accepted
+                                    let settings = open_tree(database, SETTREE)?;
+                                    let retention_policy = match get_retention_policy(&settings) {
+                                        Ok(a) => a,
+                                        Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedRetentionPolicy)),
+                                    };
+                                    let removed = match metadata_to_remove(retention_policy, &y.specname, y.spec_version, metadata) {
+                                        Ok(a) => a,
+                                        Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedVersName)),
+                                    };
+                                    for (i, (versioned_name, meta_stored)) in removed.iter().enumerate() {
+                                        let removed_meta = MetaValuesDisplay {
+                                            name: &versioned_name.name,
+                                            version: versioned_name.version,
+                                            meta_hash: &hex::encode(blake2b(32, &[], &meta_stored).as_bytes()),
+                                        }.show();
+                                        meta_card.push_str(&format!(",{}", Card::MetaRemoved(removed_meta).card(next_index+i as u32, 0)));
+                                    }
                                 // making action entry into database
                                     let load_meta = Transaction::LoadMeta(LoadMeta{
                                         versioned_name: received_versioned_name,
@@ -197,3 +288,31 @@ pub fn process_received_metadata (meta: Vec<u8>, name_to_check: Option<&str>, hi
     }
 }
 
+
+/// Function to find in the database the latest metadata version of the network
+/// that is older than the received one, to show what has changed
+fn previous_metadata (name: &str, new_version: u32, metadata: &Tree) -> Result<Option<(u32, Vec<u8>)>, Error> {
+    let mut found: Option<(u32, Vec<u8>)> = None;
+    for x in metadata.scan_prefix(name.encode()) {
+        if let Ok((versioned_name_encoded, meta_stored)) = x {
+            let versioned_name = match NameVersioned::decode(&mut &versioned_name_encoded[..]) {
+                Ok(a) => a,
+                Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedVersName)),
+            };
+            if (versioned_name.name == name) && (versioned_name.version < new_version) {
+                let is_latest = match &found {
+                    Some((version, _)) => versioned_name.version > *version,
+                    None => true,
+                };
+                if is_latest {
+                    let meta = match decompress_meta(&meta_stored) {
+                        Ok(a) => a,
+                        Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedCompressedMeta)),
+                    };
+                    found = Some((versioned_name.version, meta));
+                }
+            }
+        }
+    }
+    Ok(found)
+}
@@ -24,6 +24,7 @@ pub fn load_types (data_hex: &str, dbname: &str) -> Result<String, Error> {
     let current_verifier = get_general_verifier(&settings)?;
     
     let checked_info = pass_crypto(&data_hex)?;
+    if checked_info.signers.len() > 1 {return Err(Error::BadInputData(BadInputData::MultipleSignaturesNotSupported))}
     
     let new_types = match ContentLoadTypes::from_vec(&checked_info.message).types() {
         Ok(x) => x,
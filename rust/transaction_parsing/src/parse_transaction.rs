@@ -3,16 +3,16 @@ use frame_metadata::RuntimeMetadata;
 use parity_scale_codec::{Decode, Encode};
 use parity_scale_codec_derive;
 use printing_balance::{PrettyOutput, convert_balance_pretty};
-use constants::{SPECSTREE, METATREE, ADDRTREE, SETTREE, SIGNTRANS, TRANSACTION};
+use constants::{ASSETS, SPECSTREE, METATREE, ADDRTREE, SETTREE, SIGNTRANS, TRANSACTION};
 use definitions::{crypto::Encryption, network_specs::{ChainSpecs, generate_network_key}, transactions::{Transaction, Sign}, users::{AddressDetails, generate_address_key, print_as_base58}, history::Event};
 use sp_runtime::generic::Era;
 
-use crate::utils::{find_meta, get_types};
+use crate::utils::{find_meta, get_types, get_parachain_names};
 use crate::cards::{Action, Card, Warning};
 use crate::decoding_older::process_as_call;
 use crate::decoding_sci::decoding_sci_entry_point;
 use crate::error::{Error, BadInputData, UnableToDecode, DatabaseError, SystemError};
-use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_checksum, unhex, get_from_tree};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_assets, get_checksum, unhex, get_from_tree};
 use crate::method::OlderMeta;
 
 /// Transaction payload in hex format as it arrives into parsing program contains following elements:
@@ -46,11 +46,112 @@ struct ExtrinsicValues {
 }
 
 
-/// function to print full extrinsics cards
-fn print_full_extrinsics (index: u32, indent: u32, tip_output: &PrettyOutput, short: &ExtrinsicValues, chain_name: &str) -> String {
+/// Struct to decode method, extrinsics, and genesis hash from transaction Vec<u8>,
+/// for networks with ChargeAssetTxPayment signed extension
+#[derive(Debug, parity_scale_codec_derive::Decode)]
+struct TransactionPartsAssetTip {
+    method: Vec<u8>,
+    extrinsics: ExtrinsicValuesAssetTip,
+    genesis_hash: [u8; 32],
+}
+
+
+/// Struct to decode extrinsics with ChargeAssetTxPayment signed extension,
+/// the tip is followed by the id of the asset in which the fee is paid, if not in native token
+#[derive(Debug, parity_scale_codec_derive::Decode, parity_scale_codec_derive::Encode)]
+struct ExtrinsicValuesAssetTip {
+    era: Era,
+#[codec(compact)]
+    nonce: u64,
+#[codec(compact)]
+    tip: u128,
+    asset_id: Option<u32>,
+    metadata_version: u32,
+    tx_version: u32,
+    genesis_hash: [u8; 32],
+    block_hash: [u8; 32],
+}
+
+
+/// Struct to store decoded transaction parts for both extrinsics formats;
+/// asset_tip is Some only for extrinsics with ChargeAssetTxPayment signed extension,
+/// extrinsics_encoded is the extrinsics part as it goes into signing
+struct DecodedTransaction {
+    method: Vec<u8>,
+    extrinsics: ExtrinsicValues,
+    asset_tip: Option<Option<u32>>,
+    extrinsics_encoded: Vec<u8>,
+    genesis_hash: [u8; 32],
+}
+
+
+/// Function to separate method, extrinsics, and genesis hash;
+/// genesis hash is repeated in extrinsics, and is used to check which extrinsics format is used
+fn decode_transaction (data: &[u8]) -> Result<DecodedTransaction, Error> {
+    let transaction_parts = <TransactionParts>::decode(&mut &data[..]);
+    if let Ok(a) = &transaction_parts {
+        if a.genesis_hash == a.extrinsics.genesis_hash {
+            return Ok(DecodedTransaction {
+                method: a.method.to_vec(),
+                extrinsics_encoded: a.extrinsics.encode(),
+                extrinsics: ExtrinsicValues {
+                    era: a.extrinsics.era,
+                    nonce: a.extrinsics.nonce,
+                    tip: a.extrinsics.tip,
+                    metadata_version: a.extrinsics.metadata_version,
+                    tx_version: a.extrinsics.tx_version,
+                    genesis_hash: a.extrinsics.genesis_hash,
+                    block_hash: a.extrinsics.block_hash,
+                },
+                asset_tip: None,
+                genesis_hash: a.genesis_hash,
+            })
+        }
+    }
+    match <TransactionPartsAssetTip>::decode(&mut &data[..]) {
+        Ok(a) if a.genesis_hash == a.extrinsics.genesis_hash => Ok(DecodedTransaction {
+            method: a.method,
+            extrinsics_encoded: a.extrinsics.encode(),
+            extrinsics: ExtrinsicValues {
+                era: a.extrinsics.era,
+                nonce: a.extrinsics.nonce,
+                tip: a.extrinsics.tip,
+                metadata_version: a.extrinsics.metadata_version,
+                tx_version: a.extrinsics.tx_version,
+                genesis_hash: a.extrinsics.genesis_hash,
+                block_hash: a.extrinsics.block_hash,
+            },
+            asset_tip: Some(a.extrinsics.asset_id),
+            genesis_hash: a.genesis_hash,
+        }),
+        _ => match transaction_parts {
+            Ok(_) => return Err(Error::BadInputData(BadInputData::GenesisHashMismatch)),
+            Err(_) => return Err(Error::UnableToDecode(UnableToDecode::MethodAndExtrinsicsFailure)),
+        },
+    }
+}
+
+
+/// Function to check if the network uses ChargeAssetTxPayment signed extension,
+/// i.e. if the tip in extrinsics is followed by the asset id
+fn charges_asset_tip (meta: &RuntimeMetadata) -> bool {
+    match meta {
+        RuntimeMetadata::V14(meta_v14) => meta_v14.extrinsic.signed_extensions.iter().any(|a| a.identifier == "ChargeAssetTxPayment"),
+        _ => false,
+    }
+}
+
+
+/// function to print full extrinsics cards;
+/// tip is printed as plain number if paid in asset not in the asset registry of the network
+fn print_full_extrinsics (index: u32, indent: u32, tip_output: &Option<PrettyOutput>, short: &ExtrinsicValues, chain_name: &str) -> String {
+    let tip_card = match tip_output {
+        Some(a) => Card::Tip{number: &a.number, units: &a.units},
+        None => Card::TipPlain(short.tip),
+    };
     match short.era {
-        Era::Immortal => format!("{},{},{}", (Card::EraImmortalNonce(short.nonce)).card(index, indent), (Card::Tip{number: &tip_output.number, units: &tip_output.units}).card(index+1, indent), (Card::TxSpec{network: chain_name, version: short.metadata_version, tx_version: short.tx_version}).card(index+2, indent)),
-        Era::Mortal(period, phase) => format!("{},{},{},{}", (Card::EraMortalNonce{phase, period, nonce: short.nonce}).card(index, indent), (Card::Tip{number: &tip_output.number, units: &tip_output.units}).card(index+1, indent), (Card::BlockHash(&hex::encode(short.block_hash))).card(index+2, indent), (Card::TxSpec{network: chain_name, version: short.metadata_version, tx_version: short.tx_version}).card(index+3, indent)),
+        Era::Immortal => format!("{},{},{}", (Card::EraImmortalNonce(short.nonce)).card(index, indent), tip_card.card(index+1, indent), (Card::TxSpec{network: chain_name, version: short.metadata_version, tx_version: short.tx_version}).card(index+2, indent)),
+        Era::Mortal(period, phase) => format!("{},{},{},{}", (Card::EraMortalNonce{phase, period, nonce: short.nonce}).card(index, indent), tip_card.card(index+1, indent), (Card::BlockHash(&hex::encode(short.block_hash))).card(index+2, indent), (Card::TxSpec{network: chain_name, version: short.metadata_version, tx_version: short.tx_version}).card(index+3, indent)),
     }
 }
 
@@ -73,6 +174,7 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
     let addresses = open_tree(&database, ADDRTREE)?;
     let settings = open_tree(&database, SETTREE)?;
     let transaction = open_tree(&database, TRANSACTION)?;
+    let assets_tree = open_tree(&database, ASSETS)?;
     
 // input hex data of correct size should have at least 6 + 64 + 64 symbols (prelude + author public key minimal size + genesis hash)
     if data_hex.len() < 134 {return Err(Error::BadInputData(BadInputData::TooShort))}
@@ -86,10 +188,7 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
         _ => return Err(Error::BadInputData(BadInputData::CryptoNotSupported))
     };
     
-    let transaction_decoded = match <TransactionParts>::decode(&mut &data[..]) {
-        Ok(a) => a,
-        Err(_) => return Err(Error::UnableToDecode(UnableToDecode::MethodAndExtrinsicsFailure)),
-    };
+    let transaction_decoded = decode_transaction(data)?;
     
     let short = &transaction_decoded.extrinsics;
     
@@ -98,7 +197,6 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
     let indent: u32 = 0;
 
 // try to get chain specs from genesis hash
-    if transaction_decoded.genesis_hash != short.genesis_hash {return Err(Error::BadInputData(BadInputData::GenesisHashMismatch))}
 
 // this should be here by the standard; should stay commented for now, since the test transactions apparently do not comply to standard.
 //    if &data_hex[4..6] == "00" {if let Era::Immortal = short.era {return Err(Error::BadInputData(BadInputData::UnexpectedImmortality))}}
@@ -118,10 +216,23 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
             let chain_name = &chain_specs_found.name;
             let chain_prefix = chain_specs_found.base58prefix;
             
-        // update tip output since we know chain specs already
-            let tip_output = match convert_balance_pretty (&short.tip.to_string(), chain_specs_found.decimals, &chain_specs_found.unit) {
-                Ok(x) => x,
-                Err(_) => return Err(Error::SystemError(SystemError::BalanceFail)),
+        // get known parachains of the same relay chain, to show their names in cross-chain destinations
+            let parachain_names = get_parachain_names(&chain_specs_found, &chainspecs)?;
+            
+        // get asset registry of the network, to show balances in assets with proper units
+            let assets = get_assets(transaction_decoded.genesis_hash, &assets_tree)?;
+            
+        // update tip output since we know chain specs already; tip could be paid in asset
+            let tip_units = match transaction_decoded.asset_tip {
+                Some(Some(asset_id)) => assets.iter().find(|a| a.asset_id == asset_id).map(|a| (a.decimals, a.symbol.to_string())),
+                _ => Some((chain_specs_found.decimals, chain_specs_found.unit.to_string())),
+            };
+            let tip_output = match tip_units {
+                Some((decimals, unit)) => match convert_balance_pretty (&short.tip.to_string(), decimals, &unit) {
+                    Ok(x) => Some(x),
+                    Err(_) => return Err(Error::SystemError(SystemError::BalanceFail)),
+                },
+                None => None,
             };
 
         // check that the network is compatible with provided encryption
@@ -154,6 +265,8 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
                 // fetch chain metadata in RuntimeMetadataV12 format
                     match find_meta(&chain_name, short.metadata_version, &metadata) {
                         Ok((meta, ver)) => {
+                        // tip with asset id is expected only for networks with ChargeAssetTxPayment signed extension
+                            if transaction_decoded.asset_tip.is_some() && !charges_asset_tip(&meta) {return Err(Error::BadInputData(BadInputData::UnexpectedAssetTip))}
                             let mut warning_card = None;
                             let mut history: Vec<Event> = Vec::new();
                             if let Some(x) = ver {
@@ -167,7 +280,7 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
                             let type_database = get_types(&settings)?;
                     
                         // action card preparations: vector that should be signed
-                            let for_signing = [transaction_decoded.method.to_vec(), transaction_decoded.extrinsics.encode().to_vec()].concat();
+                            let for_signing = [transaction_decoded.method.to_vec(), transaction_decoded.extrinsics_encoded.to_vec()].concat();
                     
                         // transaction parsing
                             match meta {
@@ -177,7 +290,7 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
                                         RuntimeMetadata::V13(meta_v13) => {OlderMeta::V13(meta_v13)},
                                         _ => unreachable!(),
                                     };
-                                    match process_as_call (transaction_decoded.method, &older_meta, &type_database, index, indent, &chain_specs_found) {
+                                    match process_as_call (transaction_decoded.method, &older_meta, &type_database, index, indent, &chain_specs_found, &addresses) {
                                         Ok(transaction_parsed) => {
                                             let method_cards = &transaction_parsed.fancy_out[1..];
                                             let index = transaction_parsed.index;
@@ -238,7 +351,7 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
                                     }
                                 },
                                 RuntimeMetadata::V14(meta_v14) => {
-                                    match decoding_sci_entry_point (transaction_decoded.method, &meta_v14, index, indent, &chain_specs_found) {
+                                    match decoding_sci_entry_point (transaction_decoded.method, &meta_v14, index, indent, &chain_specs_found, &parachain_names, &assets, &addresses) {
                                         Ok(transaction_parsed) => {
                                             let method_cards = &transaction_parsed.fancy_out;
                                             let index = transaction_parsed.index;
@@ -328,6 +441,8 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
                     // fetch chain metadata in RuntimeMetadataV12 format
                     match find_meta(&chain_name, short.metadata_version, &metadata) {
                         Ok((meta, ver)) => {
+                        // tip with asset id is expected only for networks with ChargeAssetTxPayment signed extension
+                            if transaction_decoded.asset_tip.is_some() && !charges_asset_tip(&meta) {return Err(Error::BadInputData(BadInputData::UnexpectedAssetTip))}
                             if let Some(x) = ver {
                                 let add_this = (Card::Warning(Warning::NewerVersion{used_version: short.metadata_version, latest_version: x})).card(index, indent);
                                 warning_card.push_str(&format!(",{}", add_this));
@@ -346,7 +461,7 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
                                         RuntimeMetadata::V13(meta_v13) => {OlderMeta::V13(meta_v13)},
                                         _ => unreachable!(),
                                     };
-                                    match process_as_call (transaction_decoded.method, &older_meta, &type_database, index, indent, &chain_specs_found) {
+                                    match process_as_call (transaction_decoded.method, &older_meta, &type_database, index, indent, &chain_specs_found, &addresses) {
                                         Ok(transaction_parsed) => {
                                             let method_cards = &transaction_parsed.fancy_out[1..];
                                             let index = transaction_parsed.index;
@@ -371,7 +486,7 @@ pub fn parse_transaction (data_hex: &str, dbname: &str) -> Result<String, Error>
                                     }
                                 },
                                 RuntimeMetadata::V14(meta_v14) => {
-                                    match decoding_sci_entry_point (transaction_decoded.method, &meta_v14, index, indent, &chain_specs_found) {
+                                    match decoding_sci_entry_point (transaction_decoded.method, &meta_v14, index, indent, &chain_specs_found, &parachain_names, &assets, &addresses) {
                                         Ok(transaction_parsed) => {
                                             let method_cards = &transaction_parsed.fancy_out;
                                             let index = transaction_parsed.index;
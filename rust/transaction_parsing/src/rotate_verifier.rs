@@ -0,0 +1,53 @@
+use constants::{ROTATEVERIFIER, TRANSACTION, VERIFIERS};
+use definitions::{network_specs::Verifier, qr_transfers::ContentRotateVerifier, transactions::{Transaction, RotateVerifier}};
+use parity_scale_codec::Encode;
+
+use crate::cards::{Action, Card};
+use crate::check_signature::pass_crypto;
+use crate::error::{Error, BadInputData, CryptoError};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_checksum, get_verifier};
+
+
+/// Function to process rotate_verifier message, i.e. the authorization from the current network verifier
+/// to hand the network over to the new verifier.
+/// If effective_from metadata version is set, the rotation is completed when the metadata
+/// with this or higher version signed by the new verifier is loaded; until then current verifier is kept.
+
+pub fn rotate_verifier (data_hex: &str, dbname: &str) -> Result<String, Error> {
+
+    let database = open_db(dbname)?;
+    let transaction = open_tree(&database, TRANSACTION)?;
+    let verifiers = open_tree(&database, VERIFIERS)?;
+
+    let checked_info = pass_crypto(&data_hex)?;
+    if checked_info.signers.len() > 1 {return Err(Error::BadInputData(BadInputData::MultipleSignaturesNotSupported))}
+
+    let (gen_hash, new_verifier, effective_from) = match ContentRotateVerifier::from_vec(&checked_info.message).genhash_verifier_version() {
+        Ok(x) => x,
+        Err(_) => return Err(Error::BadInputData(BadInputData::UnableToDecodeRotateVerifierMessage)),
+    };
+
+    let current_verifier = get_verifier (gen_hash, &verifiers)?;
+    if current_verifier == Verifier::None {return Err(Error::CryptoError(CryptoError::NoVerifierToRotate))}
+    if checked_info.verifier != current_verifier {return Err(Error::CryptoError(CryptoError::RotationNotByVerifier{old_show: current_verifier.show_error(), new_show: checked_info.verifier.show_error()}))}
+    if (new_verifier == Verifier::None)||(new_verifier == current_verifier) {return Err(Error::BadInputData(BadInputData::BadVerifierRotation))}
+
+    let verifier_card = Card::Verifier(current_verifier.show_card()).card(0,0);
+    let new_verifier_card = Card::NewVerifier{verifier_line: new_verifier.show_card(), effective_from}.card(1,0);
+
+// making action entry into database
+    let rotate_verifier = Transaction::RotateVerifier(RotateVerifier{
+        genesis_hash: gen_hash,
+        old_verifier: current_verifier,
+        new_verifier,
+        effective_from,
+        history: Vec::new(),
+    });
+    insert_into_tree(ROTATEVERIFIER.to_vec(), rotate_verifier.encode(), &transaction)?;
+    flush_db(&database)?;
+    let checksum = get_checksum(&database)?;
+
+    let action_card = Action::RotateVerifier(checksum).card();
+    Ok(format!("{{\"verifier\":[{}],\"new_verifier\":[{}],{}}}", verifier_card, new_verifier_card, action_card))
+}
+
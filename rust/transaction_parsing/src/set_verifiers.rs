@@ -0,0 +1,70 @@
+use constants::{SETTREE, SETVERIFIERS, TRANSACTION, VERIFIERS};
+use definitions::{network_specs::{Verifier, generate_verifier_set_key}, qr_transfers::ContentSetVerifiers, transactions::{Transaction, SetVerifiers}};
+use parity_scale_codec::Encode;
+
+use crate::cards::{Action, Card};
+use crate::check_signature::pass_crypto;
+use crate::error::{Error, BadInputData, CryptoError};
+use crate::helpers::{open_db, open_tree, flush_db, insert_into_tree, get_checksum, get_verifier, get_verifier_set};
+use crate::utils::get_general_verifier;
+
+
+/// Function to process set_verifiers message, i.e. to set up or to change the verifier set for the network.
+/// If the network already has verifier set, the message must be signed by enough verifiers from it.
+/// If not, the message must be signed by the current network verifier, or, if the network is not verified,
+/// by the general verifier.
+
+pub fn set_verifiers (data_hex: &str, dbname: &str) -> Result<String, Error> {
+
+    let database = open_db(dbname)?;
+    let settings = open_tree(&database, SETTREE)?;
+    let transaction = open_tree(&database, TRANSACTION)?;
+    let verifiers = open_tree(&database, VERIFIERS)?;
+
+    let checked_info = pass_crypto(&data_hex)?;
+
+    let (gen_hash, new_verifier_set) = match ContentSetVerifiers::from_vec(&checked_info.message).genhash_set() {
+        Ok(x) => x,
+        Err(_) => return Err(Error::BadInputData(BadInputData::UnableToDecodeSetVerifiersMessage)),
+    };
+    if !new_verifier_set.is_valid() {return Err(Error::BadInputData(BadInputData::BadVerifierSet))}
+
+    let current_verifier = get_verifier (gen_hash, &verifiers)?;
+
+    match get_verifier_set (gen_hash, &verifiers)? {
+        Some(current_verifier_set) => {
+            let approvals = current_verifier_set.approvals(&checked_info.signers);
+            if approvals < current_verifier_set.threshold {return Err(Error::CryptoError(CryptoError::VerifierSetThresholdNotMet{approvals, verifier_set_show: current_verifier_set.show_error()}))}
+            if current_verifier_set == new_verifier_set {return Err(Error::BadInputData(BadInputData::VerifierSetAlreadyThere))}
+        },
+        None => {
+            let authority = {
+                if current_verifier == Verifier::None {get_general_verifier(&settings)?}
+                else {current_verifier}
+            };
+            if (authority == Verifier::None)||(!checked_info.signers.contains(&authority)) {return Err(Error::CryptoError(CryptoError::VerifierSetNotAuthorized))}
+        },
+    }
+
+    let mut verifier_cards = String::new();
+    for (i, x) in checked_info.signers.iter().enumerate() {
+        if i>0 {verifier_cards.push_str(",")}
+        verifier_cards.push_str(&Card::Verifier(x.show_card()).card(i as u32, 0));
+    }
+    let index = checked_info.signers.len() as u32;
+    let verifier_set_card = Card::VerifierSet(new_verifier_set.show_card()).card(index, 0);
+
+// making action entry into database
+    let set_verifiers = Transaction::SetVerifiers(SetVerifiers{
+        verifier_key: generate_verifier_set_key(&gen_hash.to_vec()),
+        verifier_set: new_verifier_set,
+        history: Vec::new(),
+    });
+    insert_into_tree(SETVERIFIERS.to_vec(), set_verifiers.encode(), &transaction)?;
+    flush_db(&database)?;
+    let checksum = get_checksum(&database)?;
+
+    let action_card = Action::SetVerifiers(checksum).card();
+    Ok(format!("{{\"verifier\":[{}],\"verifier_set\":[{}],{}}}", verifier_cards, verifier_set_card, action_card))
+}
+
@@ -1,7 +1,8 @@
 use bitvec::prelude::{BitVec, Lsb0};
 use sled::IVec;
-use definitions::{crypto::Encryption, metadata::{MetaValuesDisplay, NetworkDisplay}, network_specs::{Verifier, ChainSpecsToSend}};
+use definitions::{crypto::Encryption, metadata::{MetaValuesDisplay, NetworkDisplay}, network_specs::{AssetInfo, Verifier, VerifierSet, ChainSpecsToSend, show_assets}};
 use hex;
+use meta_reading::diff_metadata::{ConstantChange, MetadataDiff};
 use std::convert::TryInto;
 
 use crate::cards::{Card, Warning};
@@ -21,6 +22,7 @@ pub fn make_all_cards() -> String {
     all_cards.push(Card::Default("12345"));
     all_cards.push(Card::PathDocs {path: r#"["frame_system","pallet","Call"]"#, docs: "test docs"});
     all_cards.push(Card::Id("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"));
+    all_cards.push(Card::OwnedIdentity{seed_name: "Alice", path: "//Alice", has_pwd: false, name: "Alice_test_westend"});
     all_cards.push(Card::None);
     all_cards.push(Card::IdentityField("Twitter"));
     
@@ -52,7 +54,17 @@ pub fn make_all_cards() -> String {
         meta_hash: "69300be6f9f5d14ee98294ad15c7af8d34aa6c16f94517216dc4178faadacabb",
     }.show();
     
-    all_cards.push(Card::Meta(new_meta));
+    all_cards.push(Card::Meta(new_meta.to_string()));
+    all_cards.push(Card::MetaRemoved(new_meta));
+    let diff = MetadataDiff {
+        pallets_added: vec![String::from("Test_Pallet")],
+        pallets_removed: Vec::new(),
+        calls_added: vec![String::from("Test_Pallet.test_method")],
+        calls_removed: Vec::new(),
+        calls_changed: Vec::new(),
+        constants_changed: vec![ConstantChange {name: String::from("System.SS58Prefix"), old_value: vec![42, 0], new_value: vec![43, 0]}],
+    }.show_card();
+    all_cards.push(Card::MetaDiff{previous_version: 9010, diff});
     all_cards.push(Card::TypesInfo("345f53c073281fc382d20758aee06ceae3014fd53df734d3e94d54642a56dd51"));
     
     let chain_specs = ChainSpecsToSend {
@@ -67,6 +79,9 @@ pub fn make_all_cards() -> String {
         secondary_color: String::from("#262626"),
         title: String::from("Westend"),
         unit: String::from("WND"),
+        relay_genesis_hash: None,
+        parachain_id: None,
+        tokens: Vec::new(),
     };
     
     let new_network = (NetworkDisplay{
@@ -80,6 +95,18 @@ pub fn make_all_cards() -> String {
     }).show();
     
     all_cards.push(Card::NewNetwork(new_network));
+    all_cards.push(Card::ParachainName("Westmint"));
+    
+    let verifier_set = VerifierSet {
+        verifiers: vec![Verifier::Sr25519(String::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")), Verifier::Sr25519(String::from("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"))],
+        threshold: 2,
+    };
+    all_cards.push(Card::VerifierSet(verifier_set.show_card()));
+    all_cards.push(Card::NewVerifier{verifier_line: Verifier::Sr25519(String::from("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48")).show_card(), effective_from: Some(9070)});
+    let derivation_public_key = hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").expect("known value");
+    all_cards.push(Card::Derivation{path: "//Alice", name: "Alice_test_westend", network_title: "Westend", public_key: &derivation_public_key, encryption: Encryption::Sr25519});
+    let assets = vec![AssetInfo{asset_id: 1984, symbol: String::from("USDt"), decimals: 6}];
+    all_cards.push(Card::Assets{network_title: "Westmint", assets_line: show_assets(&assets)});
     
     all_cards.push(Card::Warning(Warning::AuthorNotFound));
     all_cards.push(Card::Warning(Warning::NewerVersion{used_version: 50, latest_version: 9010}));
@@ -95,6 +122,10 @@ pub fn make_all_cards() -> String {
     all_cards.push(Card::Warning(Warning::MetaAlreadyThereUpdGeneralVerifier));
     all_cards.push(Card::Warning(Warning::NetworkAlreadyHasEntries));
     all_cards.push(Card::Warning(Warning::AddNetworkNotVerified));
+    all_cards.push(Card::Warning(Warning::VerifierRotationCompleted));
+    all_cards.push(Card::Warning(Warning::DerivationNetworkNotFound{path: String::from("//Alice"), genesis_hash: chain_specs.genesis_hash, encryption: Encryption::Ed25519}));
+    all_cards.push(Card::Warning(Warning::DerivationMismatch{path: String::from("//Alice"), name: String::from("Alice_test_westend")}));
+    all_cards.push(Card::Warning(Warning::DerivationNotImported{path: String::from("//Alice"), error: String::from("Seed name Alice already exists.")}));
     
     all_cards.push(Card::Error(Error::BadInputData(BadInputData::TooShort)));
     all_cards.push(Card::Error(Error::BadInputData(BadInputData::NotSubstrate)));
@@ -118,6 +149,21 @@ pub fn make_all_cards() -> String {
     all_cards.push(Card::Error(Error::BadInputData(BadInputData::TypesAlreadyThere)));
     all_cards.push(Card::Error(Error::BadInputData(BadInputData::UnableToDecodeAddNetworkMessage)));
     all_cards.push(Card::Error(Error::BadInputData(BadInputData::ImportantSpecsChanged)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::UnableToDecodeSignatures)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::MultipleSignaturesNotSupported)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::UnableToDecodeSetVerifiersMessage)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::BadVerifierSet)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::VerifierSetAlreadyThere)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::UnableToDecodeRotateVerifierMessage)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::BadVerifierRotation)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::UnableToDecodeLoadAssetsMessage)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::BadAssets)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::AssetsAlreadyThere)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::UnexpectedAssetTip)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::UnableToDecodeDerivations)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::NoDerivationsToImport)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::UnableToDecompressContent)));
+    all_cards.push(Card::Error(Error::BadInputData(BadInputData::DecompressedContentTooLarge)));
     
     all_cards.push(Card::Error(Error::UnableToDecode(UnableToDecode::MethodAndExtrinsicsFailure)));
     all_cards.push(Card::Error(Error::UnableToDecode(UnableToDecode::NeedPalletAndMethod)));
@@ -162,6 +208,11 @@ pub fn make_all_cards() -> String {
     all_cards.push(Card::Error(Error::DatabaseError(DatabaseError::NoMetaAtAll)));
     all_cards.push(Card::Error(Error::DatabaseError(DatabaseError::DamagedGeneralVerifier)));
     all_cards.push(Card::Error(Error::DatabaseError(DatabaseError::NoGeneralVerifier)));
+    all_cards.push(Card::Error(Error::DatabaseError(DatabaseError::DamagedVerifierSet)));
+    all_cards.push(Card::Error(Error::DatabaseError(DatabaseError::DamagedVerifierRotation)));
+    all_cards.push(Card::Error(Error::DatabaseError(DatabaseError::DamagedRetentionPolicy)));
+    all_cards.push(Card::Error(Error::DatabaseError(DatabaseError::DamagedCompressedMeta)));
+    all_cards.push(Card::Error(Error::DatabaseError(DatabaseError::DamagedAssets)));
     
     all_cards.push(Card::Error(Error::SystemError(SystemError::BalanceFail)));
     all_cards.push(Card::Error(Error::SystemError(SystemError::NotMeta)));
@@ -178,6 +229,13 @@ pub fn make_all_cards() -> String {
     all_cards.push(Card::Error(Error::CryptoError(CryptoError::GeneralVerifierChanged {old_show: Verifier::Ed25519(String::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")).show_error(), new_show: Verifier::Sr25519(String::from("5a4a03f84a19cf8ebda40e62358c592870691a9cf456138bb4829969d10fe969")).show_error()})));
     all_cards.push(Card::Error(Error::CryptoError(CryptoError::GeneralVerifierDisappeared)));
     all_cards.push(Card::Error(Error::CryptoError(CryptoError::NetworkExistsVerifierDisappeared)));
+    all_cards.push(Card::Error(Error::CryptoError(CryptoError::VerifierSetThresholdNotMet {approvals: 1, verifier_set_show: verifier_set.show_error()})));
+    all_cards.push(Card::Error(Error::CryptoError(CryptoError::NoVerifierSet)));
+    all_cards.push(Card::Error(Error::CryptoError(CryptoError::VerifierSetNotAuthorized)));
+    all_cards.push(Card::Error(Error::CryptoError(CryptoError::NoVerifierToRotate)));
+    all_cards.push(Card::Error(Error::CryptoError(CryptoError::RotationNotByVerifier {old_show: Verifier::Sr25519(String::from("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")).show_error(), new_show: Verifier::Sr25519(String::from("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48")).show_error()})));
+    all_cards.push(Card::Error(Error::CryptoError(CryptoError::VerifierRotationNotEffective {effective_from: 9080, version: 9070})));
+    all_cards.push(Card::Error(Error::CryptoError(CryptoError::AssetsNotAuthorized)));
     
     let mut output_cards = String::from("{\"method\":[");
     
@@ -4,10 +4,40 @@
 #[cfg(test)]
 mod tests {
     use crate::produce_output;
-    use db_handling::{populate_cold, populate_cold_no_meta, populate_cold_no_networks, manage_history::print_history};
+    use crate::decoding_sci::decoding_sci_entry_point;
+    use frame_metadata::RuntimeMetadata;
+    use db_handling::{populate_cold, populate_cold_no_meta, populate_cold_no_networks, manage_history::print_history, settings::set_general_verifier};
+    use constants::{ADDRTREE, ASSETS, MAX_DECOMPRESSED_CONTENT, METATREE, VERIFIERS};
+    use definitions::{crypto::Encryption, defaults::get_default_chainspecs, metadata::NameVersioned, network_specs::{AssetInfo, ChainSpecs, TokenInfo, Verifier, generate_assets_key, VerifierRotation, VerifierSet, generate_verifier_key, generate_verifier_rotation_key, generate_verifier_set_key}, qr_transfers::compress_content, users::DerivationEntry};
+    use std::convert::TryInto;
+    use parity_scale_codec::{Compact, Encode};
+    use sp_core::{Pair, sr25519};
     use std::fs;
     
     const METADATA_FILE: &str = "for_tests/metadata_database.ts";
+    const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+    const BOB: &str = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48";
+    const WESTEND_GENESIS_HASH: &str = "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+    
+    fn set_westend_verifier_set (dbname: &str, threshold: u32) {
+        let database = sled::open(dbname).unwrap();
+        let verifiers = database.open_tree(VERIFIERS).unwrap();
+        let verifier_set = VerifierSet {
+            verifiers: vec![Verifier::Sr25519(ALICE.to_string()), Verifier::Sr25519(BOB.to_string())],
+            threshold,
+        };
+        verifiers.insert(generate_verifier_set_key(&hex::decode(WESTEND_GENESIS_HASH).unwrap()), verifier_set.encode()).unwrap();
+        database.flush().unwrap();
+    }
+    
+    fn set_westend_verifier (dbname: &str, verifier: Verifier, verifier_rotation: Option<VerifierRotation>) {
+        let database = sled::open(dbname).unwrap();
+        let verifiers = database.open_tree(VERIFIERS).unwrap();
+        let genesis_hash = hex::decode(WESTEND_GENESIS_HASH).unwrap();
+        verifiers.insert(generate_verifier_key(&genesis_hash), verifier.encode()).unwrap();
+        if let Some(a) = verifier_rotation {verifiers.insert(generate_verifier_rotation_key(&genesis_hash), a.encode()).unwrap();}
+        database.flush().unwrap();
+    }
     
     #[test]
     fn add_network_westend9090_when_no_network_info_not_signed() {
@@ -17,7 +47,7 @@ mod tests {
         assert!(current_history == "[]", "Current history: \n{}", current_history);
         let line = fs::read_to_string("for_tests/add_network_westendV9090_unverified.txt").unwrap();
         let reply = produce_output(&line.trim(), dbname);
-        let reply_known = r##"{"warning":[{"index":0,"indent":0,"type":"warning","payload":"Received new network information is not verified."}],"new_network":[{"index":1,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","verifier":{"hex":"","encryption":"none"}}}],"action":{"type":"add_network","payload":{"type":"add_network","checksum":"439183898"}}}"##;
+        let reply_known = r##"{"warning":[{"index":0,"indent":0,"type":"warning","payload":"Received new network information is not verified."}],"new_network":[{"index":1,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","relay_genesis_hash":"","parachain_id":"","tokens":[],"verifier":{"hex":"","encryption":"none"}}}],"action":{"type":"add_network","payload":{"type":"add_network","checksum":"439183898"}}}"##;
         assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
         fs::remove_dir_all(dbname).unwrap();
     }
@@ -30,11 +60,28 @@ mod tests {
         assert!(current_history == "[]", "Current history: \n{}", current_history);
         let line = fs::read_to_string("for_tests/add_network_westendV9090_Alice.txt").unwrap();
         let reply = produce_output(&line.trim(), dbname);
-        let reply_known = r##"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"warning":[{"index":1,"indent":0,"type":"warning","payload":"Previously unverified information now received signed by a verifier. If accepted, updating types and adding networks could be verified only by this verifier."}],"new_network":[{"index":2,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","verifier":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}}],"action":{"type":"add_network_and_add_general_verifier","payload":{"type":"add_network_and_add_general_verifier","checksum":"3922263540"}}}"##;
+        let reply_known = r##"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"warning":[{"index":1,"indent":0,"type":"warning","payload":"Previously unverified information now received signed by a verifier. If accepted, updating types and adding networks could be verified only by this verifier."}],"new_network":[{"index":2,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","relay_genesis_hash":"","parachain_id":"","tokens":[],"verifier":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}}],"action":{"type":"add_network_and_add_general_verifier","payload":{"type":"add_network_and_add_general_verifier","checksum":"3922263540"}}}"##;
         assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
         fs::remove_dir_all(dbname).unwrap();
     }
     
+    #[test]
+    fn add_network_westend9090_specs_in_earlier_and_current_form() {
+        let dbname = "for_tests/add_network_westend9090_specs_in_earlier_and_current_form";
+        populate_cold_no_networks(dbname).unwrap();
+    // add_network_westendV9090_Alice.txt was generated before parachain information and tokens were added to network specs,
+    // add_network_westendV9090_new_specs_Alice.txt is the same message with network specs in current form
+        let line = fs::read_to_string("for_tests/add_network_westendV9090_Alice.txt").unwrap();
+        let reply_earlier = produce_output(&line.trim(), dbname);
+        let line = fs::read_to_string("for_tests/add_network_westendV9090_new_specs_Alice.txt").unwrap();
+        let reply_current = produce_output(&line.trim(), dbname);
+        let cards_earlier = reply_earlier.split("\"action\"").next().unwrap();
+        let cards_current = reply_current.split("\"action\"").next().unwrap();
+        assert!(cards_earlier.contains(r#""relay_genesis_hash":"","parachain_id":"","tokens":[]"#), "Network with no relay chain and no tokens expected:\n{}", reply_earlier);
+        assert!(cards_earlier == cards_current, "Same network expected from specs in earlier and current form.\nEarlier: {}\nCurrent: {}", reply_earlier, reply_current);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+    
     #[test]
     fn load_types_known_not_signed() {
         let dbname = "for_tests/load_types_known_not_signed";
@@ -150,7 +197,7 @@ mod tests {
  DB Weight:
  - Read: Bonded, Ledger, [Origin Account], Current Era, History Depth, Locks
  - Write: Bonded, Payee, [Origin Account], Locks, Ledger
- # </weight>"}},{"index":4,"indent":3,"type":"varname","payload":"controller"},{"index":5,"indent":4,"type":"enum_variant_name","payload":{"name":"Id","docs":""}},{"index":6,"indent":5,"type":"Id","payload":"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"},{"index":7,"indent":3,"type":"varname","payload":"value"},{"index":8,"indent":4,"type":"balance","payload":{"amount":"300.000000000","units":"mWND"}},{"index":9,"indent":3,"type":"varname","payload":"payee"},{"index":10,"indent":4,"type":"enum_variant_name","payload":{"name":"Staked","docs":""}},{"index":11,"indent":2,"type":"call","payload":{"method":"nominate","pallet":"Staking","docs":" Declare the desire to nominate `targets` for the origin controller.
+ # </weight>"}},{"index":4,"indent":3,"type":"varname","payload":"controller"},{"index":5,"indent":4,"type":"enum_variant_name","payload":{"name":"Id","docs":""}},{"index":6,"indent":5,"type":"Id","payload":"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"},{"index":7,"indent":5,"type":"owned_identity","payload":{"seed":"Alice","derivation_path":"//Alice","has_password":false,"name":"Alice_test_westend"}},{"index":8,"indent":3,"type":"varname","payload":"value"},{"index":9,"indent":4,"type":"balance","payload":{"amount":"300.000000000","units":"mWND"}},{"index":10,"indent":3,"type":"varname","payload":"payee"},{"index":11,"indent":4,"type":"enum_variant_name","payload":{"name":"Staked","docs":""}},{"index":12,"indent":2,"type":"call","payload":{"method":"nominate","pallet":"Staking","docs":" Declare the desire to nominate `targets` for the origin controller.
 
  Effects will be felt at the beginning of the next era. This can only be called when
  [`EraElectionStatus`] is `Closed`.
@@ -168,7 +215,7 @@ mod tests {
  DB Weight:
  - Reads: Era Election Status, Ledger, Current Era
  - Writes: Validators, Nominators
- # </weight>"}},{"index":12,"indent":3,"type":"varname","payload":"targets"},{"index":13,"indent":4,"type":"enum_variant_name","payload":{"name":"Id","docs":""}},{"index":14,"indent":5,"type":"Id","payload":"5G1ojzh47Yt8KoYhuAjXpHcazvsoCXe3G8LZchKDvumozJJJ"},{"index":15,"indent":4,"type":"enum_variant_name","payload":{"name":"Id","docs":""}},{"index":16,"indent":5,"type":"Id","payload":"5FZoQhgUCmqBxnkHX7jCqThScS2xQWiwiF61msg63CFL3Y8f"},{"index":17,"indent":2,"type":"call","payload":{"method":"set_controller","pallet":"Staking","docs":" (Re-)set the controller of a stash.
+ # </weight>"}},{"index":13,"indent":3,"type":"varname","payload":"targets"},{"index":14,"indent":4,"type":"enum_variant_name","payload":{"name":"Id","docs":""}},{"index":15,"indent":5,"type":"Id","payload":"5G1ojzh47Yt8KoYhuAjXpHcazvsoCXe3G8LZchKDvumozJJJ"},{"index":16,"indent":4,"type":"enum_variant_name","payload":{"name":"Id","docs":""}},{"index":17,"indent":5,"type":"Id","payload":"5FZoQhgUCmqBxnkHX7jCqThScS2xQWiwiF61msg63CFL3Y8f"},{"index":18,"indent":2,"type":"call","payload":{"method":"set_controller","pallet":"Staking","docs":" (Re-)set the controller of a stash.
 
  Effects will be felt at the beginning of the next era.
 
@@ -183,7 +230,7 @@ mod tests {
  DB Weight:
  - Read: Bonded, Ledger New Controller, Ledger Old Controller
  - Write: Bonded, Ledger New Controller, Ledger Old Controller
- # </weight>"}},{"index":18,"indent":3,"type":"varname","payload":"controller"},{"index":19,"indent":4,"type":"enum_variant_name","payload":{"name":"Id","docs":""}},{"index":20,"indent":5,"type":"Id","payload":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"}],"extrinsics":[{"index":21,"indent":0,"type":"era_mortal_nonce","payload":{"era":"Mortal","phase":"31","period":"64","nonce":"45"}},{"index":22,"indent":0,"type":"tip","payload":{"amount":"0","units":"pWND"}},{"index":23,"indent":0,"type":"block_hash","payload":"314e9f9aef4e836a54bdd109aba380106e05e2ea83fbc490206b476840cd68e3"},{"index":24,"indent":0,"type":"tx_spec","payload":{"network":"westend","version":"9010","tx_version":"5"}}],"action":{"type":"sign_transaction","payload":{"type":"sign_transaction","checksum":""#;
+ # </weight>"}},{"index":19,"indent":3,"type":"varname","payload":"controller"},{"index":20,"indent":4,"type":"enum_variant_name","payload":{"name":"Id","docs":""}},{"index":21,"indent":5,"type":"Id","payload":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"}],"extrinsics":[{"index":22,"indent":0,"type":"era_mortal_nonce","payload":{"era":"Mortal","phase":"31","period":"64","nonce":"45"}},{"index":23,"indent":0,"type":"tip","payload":{"amount":"0","units":"pWND"}},{"index":24,"indent":0,"type":"block_hash","payload":"314e9f9aef4e836a54bdd109aba380106e05e2ea83fbc490206b476840cd68e3"},{"index":25,"indent":0,"type":"tx_spec","payload":{"network":"westend","version":"9010","tx_version":"5"}}],"action":{"type":"sign_transaction","payload":{"type":"sign_transaction","checksum":""#;
         assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
         fs::remove_dir_all(dbname).unwrap();
     }
@@ -215,7 +262,7 @@ mod tests {
         populate_cold_no_networks(dbname).unwrap();
         let line = "5300f0";
         let reply = produce_output(line, dbname);
-        let reply_known = r##"{"method":[{"index":0,"indent":0,"type":"call","payload":{"method":"test_Method","pallet":"test_Pallet","docs":"test docs description"}},{"index":1,"indent":0,"type":"pallet","payload":"test_pallet_v14"},{"index":2,"indent":0,"type":"varname","payload":"test_Varname"},{"index":3,"indent":0,"type":"default","payload":"12345"},{"index":4,"indent":0,"type":"path_and_docs","payload":{"path":["frame_system","pallet","Call"],"docs":"test docs"}},{"index":5,"indent":0,"type":"Id","payload":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"},{"index":6,"indent":0,"type":"none","payload":""},{"index":7,"indent":0,"type":"identity_field","payload":"Twitter"},{"index":8,"indent":0,"type":"bitvec","payload":"[00000100, 00100000, 11011001]"},{"index":9,"indent":0,"type":"balance","payload":{"amount":"300.000000","units":"KULU"}},{"index":10,"indent":0,"type":"field_name","payload":{"name":"test_FieldName","docs":""}},{"index":11,"indent":0,"type":"field_number","payload":{"number":"1","docs":""}},{"index":12,"indent":0,"type":"enum_variant_name","payload":{"name":"test_EnumVariantName","docs":""}},{"index":13,"indent":0,"type":"range","payload":{"start":"3","end":"14","inclusive":"false"}},{"index":14,"indent":0,"type":"era_immortal_nonce","payload":{"era":"Immortal","nonce":"4980"}},{"index":15,"indent":0,"type":"era_mortal_nonce","payload":{"era":"Mortal","phase":"55","period":"64","nonce":"89"}},{"index":16,"indent":0,"type":"tip","payload":{"amount":"0","units":"pWND"}},{"index":17,"indent":0,"type":"tip_plain","payload":"8800"},{"index":18,"indent":0,"type":"block_hash","payload":"a8dfb73a4b44e6bf84affe258954c12db1fe8e8cf00b965df2af2f49c1ec11cd"},{"index":19,"indent":0,"type":"tx_spec","payload":{"network":"westend","version":"50","tx_version":"5"}},{"index":20,"indent":0,"type":"tx_spec_plain","payload":{"network_genesis_hash":"a8dfb73a4b44e6bf84affe258954c12db1fe8e8cf00b965df2af2f49c1ec11cd","version":"50","tx_version":"5"}},{"index":21,"indent":0,"type":"author","payload":{"base58":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty","seed":"Alice","derivation_path":"//Alice","has_password":false,"name":""}},{"index":22,"indent":0,"type":"author_plain","payload":{"base58":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"}},{"index":23,"indent":0,"type":"author_public_key","payload":{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","crypto":"sr25519"}},{"index":24,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}},{"index":25,"indent":0,"type":"meta","payload":{"specname":"westend","spec_version":"9033","meta_hash":"69300be6f9f5d14ee98294ad15c7af8d34aa6c16f94517216dc4178faadacabb"}},{"index":26,"indent":0,"type":"types_hash","payload":"345f53c073281fc382d20758aee06ceae3014fd53df734d3e94d54642a56dd51"},{"index":27,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9033","meta_hash":"69300be6f9f5d14ee98294ad15c7af8d34aa6c16f94517216dc4178faadacabb","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","verifier":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}},{"index":28,"indent":0,"type":"warning","payload":"Transaction author public key not found."},{"index":29,"indent":0,"type":"warning","payload":"Transaction uses outdated runtime version 50. Latest known available version is 9010."},{"index":30,"indent":0,"type":"warning","payload":"Public key is on record, but not associated with the network used."},{"index":31,"indent":0,"type":"warning","payload":"Previously unverified network metadata now received signed by a verifier. If accepted, only metadata from same verifier could be received for this network."},{"index":32,"indent":0,"type":"warning","payload":"Received network metadata is not verified."},{"index":33,"indent":0,"type":"warning","payload":"Updating types (really rare operation)."},{"index":34,"indent":0,"type":"warning","payload":"Received types information is not verified."},{"index":35,"indent":0,"type":"warning","payload":"Previously unverified information now received signed by a verifier. If accepted, updating types and adding networks could be verified only by this verifier."},{"index":36,"indent":0,"type":"warning","payload":"Received types information is already in database, only verifier could be added."},{"index":37,"indent":0,"type":"warning","payload":"Received metadata is already in database, both general verifier and network verifier could be added."},{"index":38,"indent":0,"type":"warning","payload":"Received metadata is already in database, only network verifier could be added."},{"index":39,"indent":0,"type":"warning","payload":"Received metadata is already in database, only general verifier could be added."},{"index":40,"indent":0,"type":"warning","payload":"Add network message is received for network that already has some entries in the database."},{"index":41,"indent":0,"type":"warning","payload":"Received new network information is not verified."},{"index":42,"indent":0,"type":"error","payload":"Data is too short."},{"index":43,"indent":0,"type":"error","payload":"Only Substrate transactions are supported. Transaction is expected to start with 0x53."},{"index":44,"indent":0,"type":"error","payload":"Input data not in hex format."},{"index":45,"indent":0,"type":"error","payload":"Crypto type not supported."},{"index":46,"indent":0,"type":"error","payload":"Expected mortal transaction due to prelude format. Found immortal transaction."},{"index":47,"indent":0,"type":"error","payload":"Expected immortal transaction due to prelude format. Found mortal transaction."},{"index":48,"indent":0,"type":"error","payload":"Wrong payload type, as announced by prelude."},{"index":49,"indent":0,"type":"error","payload":"Genesis hash from extrinsics not matching with genesis hash at the transaction end."},{"index":50,"indent":0,"type":"error","payload":"Block hash for immortal transaction not matching genesis hash for the network."},{"index":51,"indent":0,"type":"error","payload":"After decoding some data remained unused."},{"index":52,"indent":0,"type":"error","payload":"First characters in metadata are expected to be 0x6d657461."},{"index":53,"indent":0,"type":"error","payload":"Received metadata could not be decoded. Runtime metadata version is below 12."},{"index":54,"indent":0,"type":"error","payload":"Received metadata specname does not match."},{"index":55,"indent":0,"type":"error","payload":"Metadata already in database."},{"index":56,"indent":0,"type":"error","payload":"Attempt to load different metadata for same name and version."},{"index":57,"indent":0,"type":"error","payload":"Received metadata version could not be decoded."},{"index":58,"indent":0,"type":"error","payload":"No version in received metadata."},{"index":59,"indent":0,"type":"error","payload":"Unable to decode received metadata."},{"index":60,"indent":0,"type":"error","payload":"Unable to decode received types information."},{"index":61,"indent":0,"type":"error","payload":"Types information already in database."},{"index":62,"indent":0,"type":"error","payload":"Unable to decode received add network message."},{"index":63,"indent":0,"type":"error","payload":"Network already has entries. Important chainspecs in received add network message are different."},{"index":64,"indent":0,"type":"error","payload":"Unable to separate transaction vector, extrinsics, and genesis hash."},{"index":65,"indent":0,"type":"error","payload":"Error on decoding. Expected method and pallet information. Found data is shorter."},{"index":66,"indent":0,"type":"error","payload":"Error on decoding. Expected pallet information. Found data is shorter."},{"index":67,"indent":0,"type":"error","payload":"Method number 2 not found in pallet test_Pallet."},{"index":68,"indent":0,"type":"error","payload":"Pallet with index 3 not found."},{"index":69,"indent":0,"type":"error","payload":"Method number 5 too high for pallet number 3. Only 4 indices available."},{"index":70,"indent":0,"type":"error","payload":"No calls found in pallet test_pallet_v14."},{"index":71,"indent":0,"type":"error","payload":"Error decoding with v14 metadata. Referenced type could not be resolved."},{"index":72,"indent":0,"type":"error","payload":"Argument type error."},{"index":73,"indent":0,"type":"error","payload":"Argument name error."},{"index":74,"indent":0,"type":"error","payload":"Error decoding call contents. Expected primitive type. Found Option<u8>."},{"index":75,"indent":0,"type":"error","payload":"Error decoding call contents. Expected compact. Not found it."},{"index":76,"indent":0,"type":"error","payload":"Error decoding call contents. Data too short for expected content."},{"index":77,"indent":0,"type":"error","payload":"Error decoding call content. Unable to decode part of data as u32."},{"index":78,"indent":0,"type":"error","payload":"Error decoding call content. Encountered unexpected Option<_> variant."},{"index":79,"indent":0,"type":"error","payload":"Error decoding call content. IdentityField description error."},{"index":80,"indent":0,"type":"error","payload":"Error decoding call content. Unable to decode part of data as an [u8; 32] array."},{"index":81,"indent":0,"type":"error","payload":"Error decoding call content. Unexpected type encountered for Balance"},{"index":82,"indent":0,"type":"error","payload":"Error decoding call content. Encountered unexpected enum variant."},{"index":83,"indent":0,"type":"error","payload":"Error decoding call content. Unexpected type inside compact."},{"index":84,"indent":0,"type":"error","payload":"Error decoding call content. Type inside compact cound not be transformed into primitive."},{"index":85,"indent":0,"type":"error","payload":"Error decoding call content. No description found for type T::SomeUnknownType."},{"index":86,"indent":0,"type":"error","payload":"Error decoding call content. Declared type is not suitable BitStore type for BitVec."},{"index":87,"indent":0,"type":"error","payload":"Error decoding call content. Declared type is not suitable BitOrder type for BitVec."},{"index":88,"indent":0,"type":"error","payload":"Error decoding call content. Could not decode BitVec."},{"index":89,"indent":0,"type":"error","payload":"Error decoding call content. Declared type is not suitable index type for Range."},{"index":90,"indent":0,"type":"error","payload":"Error decoding call content. Could not decode Range."},{"index":91,"indent":0,"type":"error","payload":"Database internal error. Collection [1] does not exist"},{"index":92,"indent":0,"type":"error","payload":"Database internal error. Unsupported: Something Unsupported."},{"index":93,"indent":0,"type":"error","payload":"Database internal error. Unexpected bug has happened: Please report me. PLEASE REPORT THIS BUG!"},{"index":94,"indent":0,"type":"error","payload":"Database internal error. IO error: oh no!"},{"index":95,"indent":0,"type":"error","payload":"Database internal error. Read corrupted data at file offset None backtrace ()"},{"index":96,"indent":0,"type":"error","payload":"ChainSpecs from database could not be decoded."},{"index":97,"indent":0,"type":"error","payload":"Network not found. Please add the network."},{"index":98,"indent":0,"type":"error","payload":"Address details from database could not be decoded."},{"index":99,"indent":0,"type":"error","payload":"Types database from database could not be decoded."},{"index":100,"indent":0,"type":"error","payload":"Types information not found in the database"},{"index":101,"indent":0,"type":"error","payload":"Network versioned name from metadata database could not be decoded."},{"index":102,"indent":0,"type":"error","payload":"No metadata on file for this version."},{"index":103,"indent":0,"type":"error","payload":"No metadata on file for this network."},{"index":104,"indent":0,"type":"error","payload":"General verifier information from database could not be decoded."},{"index":105,"indent":0,"type":"error","payload":"No general verifier information in the database."},{"index":106,"indent":0,"type":"error","payload":"System error. Balance printing failed."},{"index":107,"indent":0,"type":"error","payload":"System error. First characters in metadata are expected to be 0x6d657461."},{"index":108,"indent":0,"type":"error","payload":"System error. Metadata could not be decoded. Runtime metadata version is below 12."},{"index":109,"indent":0,"type":"error","payload":"Network metadata entry corrupted in database. Please remove the entry and download the metadata for this network."},{"index":110,"indent":0,"type":"error","payload":"System error. No version in metadata."},{"index":111,"indent":0,"type":"error","payload":"System error. Retrieved from metadata version constant could not be decoded."},{"index":112,"indent":0,"type":"error","payload":"System error. Unable to decode metadata."},{"index":113,"indent":0,"type":"error","payload":"System error. Unexpected regular expressions error."},{"index":114,"indent":0,"type":"error","payload":"Corrupted data. Bad signature."},{"index":115,"indent":0,"type":"error","payload":"Different verifier was used for this network previously. Previously used public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: ed25519. Current attempt public key: 5a4a03f84a19cf8ebda40e62358c592870691a9cf456138bb4829969d10fe969, encryption: sr25519."},{"index":116,"indent":0,"type":"error","payload":"Saved metadata for this network was signed by a verifier. This metadata is not."},{"index":117,"indent":0,"type":"error","payload":"Different general verifier was used previously. Previously used public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: ed25519. Current attempt public key: 5a4a03f84a19cf8ebda40e62358c592870691a9cf456138bb4829969d10fe969, encryption: sr25519."},{"index":118,"indent":0,"type":"error","payload":"General verifier information exists in the database. Received information could be accepted only from the same general verifier."},{"index":119,"indent":0,"type":"error","payload":"Network already has specs recorded in database. Received add network message is not signed, previously this network information was signed."}]}"##;
+        let reply_known = r##"{"method":[{"index":0,"indent":0,"type":"call","payload":{"method":"test_Method","pallet":"test_Pallet","docs":"test docs description"}},{"index":1,"indent":0,"type":"pallet","payload":"test_pallet_v14"},{"index":2,"indent":0,"type":"varname","payload":"test_Varname"},{"index":3,"indent":0,"type":"default","payload":"12345"},{"index":4,"indent":0,"type":"path_and_docs","payload":{"path":["frame_system","pallet","Call"],"docs":"test docs"}},{"index":5,"indent":0,"type":"Id","payload":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"},{"index":6,"indent":0,"type":"owned_identity","payload":{"seed":"Alice","derivation_path":"//Alice","has_password":false,"name":"Alice_test_westend"}},{"index":7,"indent":0,"type":"none","payload":""},{"index":8,"indent":0,"type":"identity_field","payload":"Twitter"},{"index":9,"indent":0,"type":"bitvec","payload":"[00000100, 00100000, 11011001]"},{"index":10,"indent":0,"type":"balance","payload":{"amount":"300.000000","units":"KULU"}},{"index":11,"indent":0,"type":"field_name","payload":{"name":"test_FieldName","docs":""}},{"index":12,"indent":0,"type":"field_number","payload":{"number":"1","docs":""}},{"index":13,"indent":0,"type":"enum_variant_name","payload":{"name":"test_EnumVariantName","docs":""}},{"index":14,"indent":0,"type":"range","payload":{"start":"3","end":"14","inclusive":"false"}},{"index":15,"indent":0,"type":"era_immortal_nonce","payload":{"era":"Immortal","nonce":"4980"}},{"index":16,"indent":0,"type":"era_mortal_nonce","payload":{"era":"Mortal","phase":"55","period":"64","nonce":"89"}},{"index":17,"indent":0,"type":"tip","payload":{"amount":"0","units":"pWND"}},{"index":18,"indent":0,"type":"tip_plain","payload":"8800"},{"index":19,"indent":0,"type":"block_hash","payload":"a8dfb73a4b44e6bf84affe258954c12db1fe8e8cf00b965df2af2f49c1ec11cd"},{"index":20,"indent":0,"type":"tx_spec","payload":{"network":"westend","version":"50","tx_version":"5"}},{"index":21,"indent":0,"type":"tx_spec_plain","payload":{"network_genesis_hash":"a8dfb73a4b44e6bf84affe258954c12db1fe8e8cf00b965df2af2f49c1ec11cd","version":"50","tx_version":"5"}},{"index":22,"indent":0,"type":"author","payload":{"base58":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty","seed":"Alice","derivation_path":"//Alice","has_password":false,"name":""}},{"index":23,"indent":0,"type":"author_plain","payload":{"base58":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"}},{"index":24,"indent":0,"type":"author_public_key","payload":{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","crypto":"sr25519"}},{"index":25,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}},{"index":26,"indent":0,"type":"meta","payload":{"specname":"westend","spec_version":"9033","meta_hash":"69300be6f9f5d14ee98294ad15c7af8d34aa6c16f94517216dc4178faadacabb"}},{"index":27,"indent":0,"type":"meta_removed","payload":{"specname":"westend","spec_version":"9033","meta_hash":"69300be6f9f5d14ee98294ad15c7af8d34aa6c16f94517216dc4178faadacabb"}},{"index":28,"indent":0,"type":"meta_diff","payload":{"previous_version":"9010","pallets_added":["Test_Pallet"],"pallets_removed":[],"calls_added":["Test_Pallet.test_method"],"calls_removed":[],"calls_changed":[],"constants_changed":[{"constant":"System.SS58Prefix","old_value":"2a00","new_value":"2b00"}]}},{"index":29,"indent":0,"type":"types_hash","payload":"345f53c073281fc382d20758aee06ceae3014fd53df734d3e94d54642a56dd51"},{"index":30,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9033","meta_hash":"69300be6f9f5d14ee98294ad15c7af8d34aa6c16f94517216dc4178faadacabb","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","relay_genesis_hash":"","parachain_id":"","tokens":[],"verifier":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}},{"index":31,"indent":0,"type":"parachain_name","payload":"Westmint"},{"index":32,"indent":0,"type":"verifier_set","payload":{"threshold":"2","verifiers":[{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"},{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"}]}},{"index":33,"indent":0,"type":"new_verifier","payload":{"verifier":{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"},"effective_from":"9070"}},{"index":34,"indent":0,"type":"derivation","payload":{"derivation_path":"//Alice","name":"Alice_test_westend","network":"Westend","public_key":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}},{"index":35,"indent":0,"type":"assets","payload":{"network":"Westmint","assets":[{"asset_id":"1984","symbol":"USDt","decimals":"6"}]}},{"index":36,"indent":0,"type":"warning","payload":"Transaction author public key not found."},{"index":37,"indent":0,"type":"warning","payload":"Transaction uses outdated runtime version 50. Latest known available version is 9010."},{"index":38,"indent":0,"type":"warning","payload":"Public key is on record, but not associated with the network used."},{"index":39,"indent":0,"type":"warning","payload":"Previously unverified network metadata now received signed by a verifier. If accepted, only metadata from same verifier could be received for this network."},{"index":40,"indent":0,"type":"warning","payload":"Received network metadata is not verified."},{"index":41,"indent":0,"type":"warning","payload":"Updating types (really rare operation)."},{"index":42,"indent":0,"type":"warning","payload":"Received types information is not verified."},{"index":43,"indent":0,"type":"warning","payload":"Previously unverified information now received signed by a verifier. If accepted, updating types and adding networks could be verified only by this verifier."},{"index":44,"indent":0,"type":"warning","payload":"Received types information is already in database, only verifier could be added."},{"index":45,"indent":0,"type":"warning","payload":"Received metadata is already in database, both general verifier and network verifier could be added."},{"index":46,"indent":0,"type":"warning","payload":"Received metadata is already in database, only network verifier could be added."},{"index":47,"indent":0,"type":"warning","payload":"Received metadata is already in database, only general verifier could be added."},{"index":48,"indent":0,"type":"warning","payload":"Add network message is received for network that already has some entries in the database."},{"index":49,"indent":0,"type":"warning","payload":"Received new network information is not verified."},{"index":50,"indent":0,"type":"warning","payload":"Received network metadata is signed by the new verifier from pending verifier rotation. If accepted, only metadata from the new verifier could be received for this network."},{"index":51,"indent":0,"type":"warning","payload":"Network with genesis hash e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e and encryption ed25519 is not in the database. Derivation //Alice is skipped."},{"index":52,"indent":0,"type":"warning","payload":"Public key derived for path //Alice (Alice_test_westend) does not match the expected one. If the path is password-protected, enter the password and try again."},{"index":53,"indent":0,"type":"warning","payload":"Derivation //Alice is not imported. Seed name Alice already exists."},{"index":54,"indent":0,"type":"error","payload":"Data is too short."},{"index":55,"indent":0,"type":"error","payload":"Only Substrate transactions are supported. Transaction is expected to start with 0x53."},{"index":56,"indent":0,"type":"error","payload":"Input data not in hex format."},{"index":57,"indent":0,"type":"error","payload":"Crypto type not supported."},{"index":58,"indent":0,"type":"error","payload":"Expected mortal transaction due to prelude format. Found immortal transaction."},{"index":59,"indent":0,"type":"error","payload":"Expected immortal transaction due to prelude format. Found mortal transaction."},{"index":60,"indent":0,"type":"error","payload":"Wrong payload type, as announced by prelude."},{"index":61,"indent":0,"type":"error","payload":"Genesis hash from extrinsics not matching with genesis hash at the transaction end."},{"index":62,"indent":0,"type":"error","payload":"Block hash for immortal transaction not matching genesis hash for the network."},{"index":63,"indent":0,"type":"error","payload":"After decoding some data remained unused."},{"index":64,"indent":0,"type":"error","payload":"First characters in metadata are expected to be 0x6d657461."},{"index":65,"indent":0,"type":"error","payload":"Received metadata could not be decoded. Runtime metadata version is below 12."},{"index":66,"indent":0,"type":"error","payload":"Received metadata specname does not match."},{"index":67,"indent":0,"type":"error","payload":"Metadata already in database."},{"index":68,"indent":0,"type":"error","payload":"Attempt to load different metadata for same name and version."},{"index":69,"indent":0,"type":"error","payload":"Received metadata version could not be decoded."},{"index":70,"indent":0,"type":"error","payload":"No version in received metadata."},{"index":71,"indent":0,"type":"error","payload":"Unable to decode received metadata."},{"index":72,"indent":0,"type":"error","payload":"Unable to decode received types information."},{"index":73,"indent":0,"type":"error","payload":"Types information already in database."},{"index":74,"indent":0,"type":"error","payload":"Unable to decode received add network message."},{"index":75,"indent":0,"type":"error","payload":"Network already has entries. Important chainspecs in received add network message are different."},{"index":76,"indent":0,"type":"error","payload":"Unable to decode signatures of multi-signed message."},{"index":77,"indent":0,"type":"error","payload":"Messages with several signatures are supported only for network metadata, verifier sets and asset registries."},{"index":78,"indent":0,"type":"error","payload":"Unable to decode received set verifiers message."},{"index":79,"indent":0,"type":"error","payload":"Received verifier set is invalid. Threshold should be between 1 and the number of verifiers, verifiers should be distinct public keys."},{"index":80,"indent":0,"type":"error","payload":"Received verifier set is already in database."},{"index":81,"indent":0,"type":"error","payload":"Unable to decode received rotate verifier message."},{"index":82,"indent":0,"type":"error","payload":"New verifier in received rotate verifier message should be a public key different from the current network verifier."},{"index":83,"indent":0,"type":"error","payload":"Unable to decode received load assets message."},{"index":84,"indent":0,"type":"error","payload":"Received asset registry is invalid. Asset ids should be distinct."},{"index":85,"indent":0,"type":"error","payload":"Received asset registry is already in database."},{"index":86,"indent":0,"type":"error","payload":"Transaction tip is followed by asset id, but the network metadata has no ChargeAssetTxPayment signed extension."},{"index":87,"indent":0,"type":"error","payload":"Unable to decode received derivations list."},{"index":88,"indent":0,"type":"error","payload":"Received derivations list has no derivations for the networks in the database."},{"index":89,"indent":0,"type":"error","payload":"Unable to decompress received message content."},{"index":90,"indent":0,"type":"error","payload":"Decompressed message content exceeds the size limit of 8388608 bytes."},{"index":91,"indent":0,"type":"error","payload":"Unable to separate transaction vector, extrinsics, and genesis hash."},{"index":92,"indent":0,"type":"error","payload":"Error on decoding. Expected method and pallet information. Found data is shorter."},{"index":93,"indent":0,"type":"error","payload":"Error on decoding. Expected pallet information. Found data is shorter."},{"index":94,"indent":0,"type":"error","payload":"Method number 2 not found in pallet test_Pallet."},{"index":95,"indent":0,"type":"error","payload":"Pallet with index 3 not found."},{"index":96,"indent":0,"type":"error","payload":"Method number 5 too high for pallet number 3. Only 4 indices available."},{"index":97,"indent":0,"type":"error","payload":"No calls found in pallet test_pallet_v14."},{"index":98,"indent":0,"type":"error","payload":"Error decoding with v14 metadata. Referenced type could not be resolved."},{"index":99,"indent":0,"type":"error","payload":"Argument type error."},{"index":100,"indent":0,"type":"error","payload":"Argument name error."},{"index":101,"indent":0,"type":"error","payload":"Error decoding call contents. Expected primitive type. Found Option<u8>."},{"index":102,"indent":0,"type":"error","payload":"Error decoding call contents. Expected compact. Not found it."},{"index":103,"indent":0,"type":"error","payload":"Error decoding call contents. Data too short for expected content."},{"index":104,"indent":0,"type":"error","payload":"Error decoding call content. Unable to decode part of data as u32."},{"index":105,"indent":0,"type":"error","payload":"Error decoding call content. Encountered unexpected Option<_> variant."},{"index":106,"indent":0,"type":"error","payload":"Error decoding call content. IdentityField description error."},{"index":107,"indent":0,"type":"error","payload":"Error decoding call content. Unable to decode part of data as an [u8; 32] array."},{"index":108,"indent":0,"type":"error","payload":"Error decoding call content. Unexpected type encountered for Balance"},{"index":109,"indent":0,"type":"error","payload":"Error decoding call content. Encountered unexpected enum variant."},{"index":110,"indent":0,"type":"error","payload":"Error decoding call content. Unexpected type inside compact."},{"index":111,"indent":0,"type":"error","payload":"Error decoding call content. Type inside compact cound not be transformed into primitive."},{"index":112,"indent":0,"type":"error","payload":"Error decoding call content. No description found for type T::SomeUnknownType."},{"index":113,"indent":0,"type":"error","payload":"Error decoding call content. Declared type is not suitable BitStore type for BitVec."},{"index":114,"indent":0,"type":"error","payload":"Error decoding call content. Declared type is not suitable BitOrder type for BitVec."},{"index":115,"indent":0,"type":"error","payload":"Error decoding call content. Could not decode BitVec."},{"index":116,"indent":0,"type":"error","payload":"Error decoding call content. Declared type is not suitable index type for Range."},{"index":117,"indent":0,"type":"error","payload":"Error decoding call content. Could not decode Range."},{"index":118,"indent":0,"type":"error","payload":"Database internal error. Collection [1] does not exist"},{"index":119,"indent":0,"type":"error","payload":"Database internal error. Unsupported: Something Unsupported."},{"index":120,"indent":0,"type":"error","payload":"Database internal error. Unexpected bug has happened: Please report me. PLEASE REPORT THIS BUG!"},{"index":121,"indent":0,"type":"error","payload":"Database internal error. IO error: oh no!"},{"index":122,"indent":0,"type":"error","payload":"Database internal error. Read corrupted data at file offset None backtrace ()"},{"index":123,"indent":0,"type":"error","payload":"ChainSpecs from database could not be decoded."},{"index":124,"indent":0,"type":"error","payload":"Network not found. Please add the network."},{"index":125,"indent":0,"type":"error","payload":"Address details from database could not be decoded."},{"index":126,"indent":0,"type":"error","payload":"Types database from database could not be decoded."},{"index":127,"indent":0,"type":"error","payload":"Types information not found in the database"},{"index":128,"indent":0,"type":"error","payload":"Network versioned name from metadata database could not be decoded."},{"index":129,"indent":0,"type":"error","payload":"No metadata on file for this version."},{"index":130,"indent":0,"type":"error","payload":"No metadata on file for this network."},{"index":131,"indent":0,"type":"error","payload":"General verifier information from database could not be decoded."},{"index":132,"indent":0,"type":"error","payload":"No general verifier information in the database."},{"index":133,"indent":0,"type":"error","payload":"Network verifier set is damaged and could not be decoded."},{"index":134,"indent":0,"type":"error","payload":"Pending network verifier rotation is damaged and could not be decoded."},{"index":135,"indent":0,"type":"error","payload":"Metadata retention policy is damaged and could not be decoded."},{"index":136,"indent":0,"type":"error","payload":"Compressed metadata from the database could not be decompressed."},{"index":137,"indent":0,"type":"error","payload":"Network asset registry is damaged and could not be decoded."},{"index":138,"indent":0,"type":"error","payload":"System error. Balance printing failed."},{"index":139,"indent":0,"type":"error","payload":"System error. First characters in metadata are expected to be 0x6d657461."},{"index":140,"indent":0,"type":"error","payload":"System error. Metadata could not be decoded. Runtime metadata version is below 12."},{"index":141,"indent":0,"type":"error","payload":"Network metadata entry corrupted in database. Please remove the entry and download the metadata for this network."},{"index":142,"indent":0,"type":"error","payload":"System error. No version in metadata."},{"index":143,"indent":0,"type":"error","payload":"System error. Retrieved from metadata version constant could not be decoded."},{"index":144,"indent":0,"type":"error","payload":"System error. Unable to decode metadata."},{"index":145,"indent":0,"type":"error","payload":"System error. Unexpected regular expressions error."},{"index":146,"indent":0,"type":"error","payload":"Corrupted data. Bad signature."},{"index":147,"indent":0,"type":"error","payload":"Different verifier was used for this network previously. Previously used public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: ed25519. Current attempt public key: 5a4a03f84a19cf8ebda40e62358c592870691a9cf456138bb4829969d10fe969, encryption: sr25519."},{"index":148,"indent":0,"type":"error","payload":"Saved metadata for this network was signed by a verifier. This metadata is not."},{"index":149,"indent":0,"type":"error","payload":"Different general verifier was used previously. Previously used public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: ed25519. Current attempt public key: 5a4a03f84a19cf8ebda40e62358c592870691a9cf456138bb4829969d10fe969, encryption: sr25519."},{"index":150,"indent":0,"type":"error","payload":"General verifier information exists in the database. Received information could be accepted only from the same general verifier."},{"index":151,"indent":0,"type":"error","payload":"Network already has specs recorded in database. Received add network message is not signed, previously this network information was signed."},{"index":152,"indent":0,"type":"error","payload":"Network is verified by threshold 2 of verifiers public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519; public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519. Received message is signed by only 1 of them."},{"index":153,"indent":0,"type":"error","payload":"Received message has several signatures, but network has no verifier set."},{"index":154,"indent":0,"type":"error","payload":"Verifier set could be changed only by current network verifier, or by general verifier for networks without one."},{"index":155,"indent":0,"type":"error","payload":"Network has no verifier. Received rotate verifier message is not applicable."},{"index":156,"indent":0,"type":"error","payload":"Rotate verifier message should be signed by current network verifier. Current verifier public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519. Message signed by public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519."},{"index":157,"indent":0,"type":"error","payload":"Network verifier rotation to this verifier takes effect from metadata version 9080. Received metadata version is 9070."},{"index":158,"indent":0,"type":"error","payload":"Asset registry could be loaded only if signed by current network verifier, or by general verifier for networks without one."}]}"##;
         assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
         fs::remove_dir_all(dbname).unwrap();
     }
@@ -231,6 +278,45 @@ mod tests {
         fs::remove_dir_all(dbname).unwrap();
     }
 
+    #[test]
+    fn load_westend9070_shows_diff() {
+        let dbname = "for_tests/load_westend9070_shows_diff";
+        populate_cold(dbname, METADATA_FILE, true).unwrap();
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#"{"index":2,"indent":0,"type":"meta_diff","payload":{"previous_version":"9010","#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_westend9070_compressed_not_signed() {
+        let dbname = "for_tests/load_westend9070_compressed_not_signed";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+        let content = hex::decode(&line.trim()[6..]).unwrap();
+        let compressed_line = format!("53ff84{}", hex::encode(compress_content(&content).unwrap()));
+        assert!(compressed_line.len() < line.trim().len(), "Compressed message is not shorter than the uncompressed one.");
+        let reply = produce_output(&compressed_line, dbname);
+        let reply_known_part = r#"{"warning":[{"index":0,"indent":0,"type":"warning","payload":"Received network metadata is not verified."}],"meta":[{"index":1,"indent":0,"type":"meta","payload":{"specname":"westend","spec_version":"9070","meta_hash":"e281fbc53168a6b87d1ea212923811f4c083e7be7d18df4b8527b9532e5f5fec"}}],"action":{"type":"load_metadata","payload":{"type":"load_metadata","checksum":""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_compressed_damaged_or_too_large() {
+        let dbname = "for_tests/load_compressed_damaged_or_too_large";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let reply = produce_output("53ff8401020304", dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Unable to decompress received message content."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        let bomb = compress_content(&vec![0; MAX_DECOMPRESSED_CONTENT + 1]).unwrap();
+        let reply = produce_output(&format!("53ffc2{}", hex::encode(bomb)), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Decompressed message content exceeds the size limit of 8388608 bytes."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
     #[test]
     fn load_westend9070_alice_signed() {
         let dbname = "for_tests/load_westend9070_alice_signed";
@@ -352,4 +438,522 @@ mod tests {
         fs::remove_dir_all(dbname).unwrap();
     }
 
+    #[test]
+    fn set_verifiers_westend_not_authorized() {
+        let dbname = "for_tests/set_verifiers_westend_not_authorized";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let line = fs::read_to_string("for_tests/set_verifiers_westend_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Verifier set could be changed only by current network verifier, or by general verifier for networks without one."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn set_verifiers_westend_general_verifier_alice() {
+        let dbname = "for_tests/set_verifiers_westend_general_verifier_alice";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_general_verifier(dbname, Verifier::Sr25519(ALICE.to_string())).unwrap();
+        let line = fs::read_to_string("for_tests/set_verifiers_westend_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"verifier_set":[{"index":1,"indent":0,"type":"verifier_set","payload":{"threshold":"2","verifiers":[{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"},{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"}]}}],"action":{"type":"set_verifiers","payload":{"type":"set_verifiers","checksum":""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn set_verifiers_westend_threshold_not_met() {
+        let dbname = "for_tests/set_verifiers_westend_threshold_not_met";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier_set(dbname, 2);
+        let line = fs::read_to_string("for_tests/set_verifiers_westend_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Network is verified by threshold 2 of verifiers public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519; public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519. Received message is signed by only 1 of them."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn set_verifiers_westend_change_existing_set() {
+        let dbname = "for_tests/set_verifiers_westend_change_existing_set";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier_set(dbname, 1);
+        let line = fs::read_to_string("for_tests/set_verifiers_westend_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"verifier_set":[{"index":1,"indent":0,"type":"verifier_set","payload":{"threshold":"2","#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_westend9070_verifier_set_alice_bob_signed() {
+        let dbname = "for_tests/load_westend9070_verifier_set_alice_bob_signed";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier_set(dbname, 2);
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Alice_Bob.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#"{"verifier_set":[{"index":0,"indent":0,"type":"verifier_set","payload":{"threshold":"2","verifiers":[{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"},{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"}]}}],"meta":[{"index":1,"indent":0,"type":"meta","payload":{"specname":"westend","spec_version":"9070","meta_hash":"e281fbc53168a6b87d1ea212923811f4c083e7be7d18df4b8527b9532e5f5fec"}}],"action":{"type":"load_metadata","payload":{"type":"load_metadata","checksum":""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_westend9070_verifier_set_alice_signed() {
+        let dbname = "for_tests/load_westend9070_verifier_set_alice_signed";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier_set(dbname, 2);
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Network is verified by threshold 2 of verifiers public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519; public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519. Received message is signed by only 1 of them."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_westend9070_alice_bob_signed_no_verifier_set() {
+        let dbname = "for_tests/load_westend9070_alice_bob_signed_no_verifier_set";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Alice_Bob.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Received message has several signatures, but network has no verifier set."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn add_network_westend9090_verifier_set_alice_signed() {
+        let dbname = "for_tests/add_network_westend9090_verifier_set_alice_signed";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier_set(dbname, 2);
+        let line = fs::read_to_string("for_tests/add_network_westendV9090_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Network is verified by threshold 2 of verifiers public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519; public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519. Received message is signed by only 1 of them."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn add_network_westend9090_verifier_set_threshold_met() {
+        let dbname = "for_tests/add_network_westend9090_verifier_set_threshold_met";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier_set(dbname, 1);
+        let line = fs::read_to_string("for_tests/add_network_westendV9090_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#"{"verifier_set":[{"index":0,"indent":0,"type":"verifier_set","payload":{"threshold":"1","verifiers":[{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"},{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"}]}}],"warning":[{"index":1,"indent":0,"type":"warning","payload":"Add network message is received for network that already has some entries in the database."}],"meta":[{"index":2,"indent":0,"type":"meta","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b"}}],"action":{"type":"load_metadata","payload":{"type":"load_metadata","checksum":""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn rotate_verifier_westend_no_verifier() {
+        let dbname = "for_tests/rotate_verifier_westend_no_verifier";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let line = fs::read_to_string("for_tests/rotate_verifier_westend_Alice_to_Bob_9070.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Network has no verifier. Received rotate verifier message is not applicable."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn rotate_verifier_westend_not_by_verifier() {
+        let dbname = "for_tests/rotate_verifier_westend_not_by_verifier";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier(dbname, Verifier::Sr25519(BOB.to_string()), None);
+        let line = fs::read_to_string("for_tests/rotate_verifier_westend_Alice_to_Bob_9070.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Rotate verifier message should be signed by current network verifier. Current verifier public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519. Message signed by public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn rotate_verifier_westend_alice_to_bob() {
+        let dbname = "for_tests/rotate_verifier_westend_alice_to_bob";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier(dbname, Verifier::Sr25519(ALICE.to_string()), None);
+        let line = fs::read_to_string("for_tests/rotate_verifier_westend_Alice_to_Bob_9070.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"new_verifier":[{"index":1,"indent":0,"type":"new_verifier","payload":{"verifier":{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"},"effective_from":"9070"}}],"action":{"type":"rotate_verifier","payload":{"type":"rotate_verifier","checksum":""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_assets_westend_not_authorized() {
+        let dbname = "for_tests/load_assets_westend_not_authorized";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let line = fs::read_to_string("for_tests/load_assets_westend_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Asset registry could be loaded only if signed by current network verifier, or by general verifier for networks without one."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_assets_westend_network_verifier_alice() {
+        let dbname = "for_tests/load_assets_westend_network_verifier_alice";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier(dbname, Verifier::Sr25519(ALICE.to_string()), None);
+        let line = fs::read_to_string("for_tests/load_assets_westend_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"assets":[{"index":1,"indent":0,"type":"assets","payload":{"network":"Westend","assets":[{"asset_id":"1984","symbol":"USDt","decimals":"6"}]}}],"action":{"type":"load_assets","payload":{"type":"load_assets","checksum":""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_westend9070_bob_signed_rotation_not_effective() {
+        let dbname = "for_tests/load_westend9070_bob_signed_rotation_not_effective";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier(dbname, Verifier::Sr25519(ALICE.to_string()), Some(VerifierRotation{new_verifier: Verifier::Sr25519(BOB.to_string()), effective_from: 9080}));
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Bob.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Network verifier rotation to this verifier takes effect from metadata version 9080. Received metadata version is 9070."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_westend9070_bob_signed_rotation_effective() {
+        let dbname = "for_tests/load_westend9070_bob_signed_rotation_effective";
+        populate_cold_no_meta(dbname, true).unwrap();
+        set_westend_verifier(dbname, Verifier::Sr25519(ALICE.to_string()), Some(VerifierRotation{new_verifier: Verifier::Sr25519(BOB.to_string()), effective_from: 9070}));
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Bob.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"}}],"warning":[{"index":1,"indent":0,"type":"warning","payload":"Received network metadata is signed by the new verifier from pending verifier rotation. If accepted, only metadata from the new verifier could be received for this network."}],"meta":[{"index":2,"indent":0,"type":"meta","payload":{"specname":"westend","spec_version":"9070","meta_hash":"e281fbc53168a6b87d1ea212923811f4c083e7be7d18df4b8527b9532e5f5fec"}}],"action":{"type":"load_metadata","payload":{"type":"load_metadata","checksum":""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn load_metadata_too_short() {
+        let dbname = "for_tests/load_metadata_too_short";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let mut genesis_hash = [0; 32];
+        genesis_hash.copy_from_slice(&hex::decode(WESTEND_GENESIS_HASH).unwrap());
+        let content = (b"meta".to_vec(), genesis_hash).encode();
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"First characters in metadata are expected to be 0x6d657461."}]}"#;
+
+        let reply = produce_output(&format!("53ff80{}", hex::encode(&content)), dbname);
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+
+    // metadata version is checked before the metadata processing, if metadata is signed by the new verifier from pending rotation
+        set_westend_verifier(dbname, Verifier::Sr25519(ALICE.to_string()), Some(VerifierRotation{new_verifier: Verifier::Sr25519(BOB.to_string()), effective_from: 9070}));
+        let bob = sr25519::Pair::from_string("//Bob", None).unwrap();
+        let reply = produce_output(&format!("530180{}{}{}", BOB, hex::encode(&content), hex::encode(bob.sign(&content))), dbname);
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    fn derivations_payload (derivations: Vec<DerivationEntry>) -> String {
+        format!("53ffde{}", hex::encode(derivations.encode()))
+    }
+
+    fn alice_derivation (encryption: Encryption) -> DerivationEntry {
+        DerivationEntry {
+            path: String::from("//Alice"),
+            genesis_hash: hex::decode(WESTEND_GENESIS_HASH).unwrap().try_into().unwrap(),
+            encryption,
+            public_key: hex::decode(ALICE).unwrap(),
+            name: String::from("Alice"),
+        }
+    }
+
+    #[test]
+    fn import_derivations_westend_unknown_network_skipped() {
+        let dbname = "for_tests/import_derivations_westend_unknown_network_skipped";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let line = derivations_payload(vec![alice_derivation(Encryption::Sr25519), alice_derivation(Encryption::Ed25519)]);
+        let reply = produce_output(&line, dbname);
+        let reply_known_part = r#"{"warning":[{"index":1,"indent":0,"type":"warning","payload":"Network with genesis hash e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e and encryption ed25519 is not in the database. Derivation //Alice is skipped."}],"derivations":[{"index":0,"indent":0,"type":"derivation","payload":{"derivation_path":"//Alice","name":"Alice","network":"Westend","public_key":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"action":{"type":"import_derivations","payload":{"type":"import_derivations","checksum":"#;
+        assert!(reply.contains(reply_known_part), "Expected: {}...\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    #[test]
+    fn import_derivations_no_known_networks() {
+        let dbname = "for_tests/import_derivations_no_known_networks";
+        populate_cold_no_meta(dbname, true).unwrap();
+        let line = derivations_payload(vec![alice_derivation(Encryption::Ed25519)]);
+        let reply = produce_output(&line, dbname);
+        let reply_known = r#"{"error":[{"index":0,"indent":0,"type":"error","payload":"Received derivations list has no derivations for the networks in the database."}]}"#;
+        assert!(reply == reply_known, "Expected: {}\nReceived: {}", reply_known, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+    /// Minimal runtime of multi-token network with v14 metadata,
+    /// to check how the balances are displayed depending on currency id
+    #[allow(dead_code, non_camel_case_types)]
+    mod multi_token_runtime {
+        use frame_metadata::v14::{ExtrinsicMetadata, PalletCallMetadata, PalletMetadata, RuntimeMetadataV14};
+        use scale_info::{TypeInfo, meta_type};
+        
+        type Balance = u128;
+        
+        #[derive(TypeInfo)]
+        pub struct AccountId32([u8; 32]);
+        
+        #[derive(TypeInfo)]
+        pub enum TokenSymbol {
+            KAR,
+            KUSD,
+            LKSM,
+        }
+        
+        #[derive(TypeInfo)]
+        pub enum CurrencyId {
+            Token(TokenSymbol),
+            DexShare(TokenSymbol, TokenSymbol),
+            ForeignAsset(u32),
+        }
+        
+        #[derive(TypeInfo)]
+        pub enum CurrenciesCall {
+            transfer {
+                dest: AccountId32,
+                currency_id: CurrencyId,
+                #[codec(compact)]
+                amount: Balance,
+            },
+        }
+        
+        pub const CURRENCIES_INDEX: u8 = 12;
+        
+        pub fn metadata() -> RuntimeMetadataV14 {
+            let pallets = vec![
+                PalletMetadata {
+                    name: "Currencies",
+                    storage: None,
+                    calls: Some(PalletCallMetadata {ty: meta_type::<CurrenciesCall>()}),
+                    event: None,
+                    constants: Vec::new(),
+                    error: None,
+                    index: CURRENCIES_INDEX,
+                },
+            ];
+            let extrinsic = ExtrinsicMetadata {
+                ty: meta_type::<()>(),
+                version: 4,
+                signed_extensions: Vec::new(),
+            };
+            RuntimeMetadataV14::new(pallets, extrinsic, meta_type::<()>())
+        }
+    }
+    
+    fn multi_token_specs () -> ChainSpecs {
+        let mut chain_specs = get_default_chainspecs().remove(3);
+        chain_specs.decimals = 12;
+        chain_specs.unit = String::from("KAR");
+        chain_specs.tokens = vec![
+            TokenInfo {decimals: 12, unit: String::from("KAR")},
+            TokenInfo {decimals: 12, unit: String::from("KUSD")},
+        ];
+        chain_specs
+    }
+    
+    fn decode_currencies_transfer (dbname: &str, currency_id: Vec<u8>) -> String {
+        let meta_v14 = multi_token_runtime::metadata();
+        let database = sled::open(dbname).unwrap();
+        let addresses = database.open_tree(ADDRTREE).unwrap();
+        let amount: u128 = 1_500_000_000_000;
+        let data = [vec![multi_token_runtime::CURRENCIES_INDEX, 0], hex::decode(BOB).unwrap(), currency_id, Compact(amount).encode()].concat();
+        let decoded = decoding_sci_entry_point(data, &meta_v14, 0, 0, &multi_token_specs(), &Vec::new(), &Vec::new(), &addresses).unwrap();
+        assert!(decoded.remaining_vector.is_empty(), "Some data remained after decoding currencies.transfer.");
+        decoded.fancy_out
+    }
+    
+    #[test]
+    fn currencies_transfer_known_token() {
+        let dbname = "for_tests/currencies_transfer_known_token";
+        let reply = decode_currencies_transfer(dbname, vec![0, 1]);
+        let reply_known_part = r#""type":"balance","payload":{"amount":"1.500000000000","units":"KUSD"}"#;
+        assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+    
+    #[test]
+    fn currencies_transfer_native_token() {
+        let dbname = "for_tests/currencies_transfer_native_token";
+        let reply = decode_currencies_transfer(dbname, vec![0, 0]);
+        let reply_known_part = r#""type":"balance","payload":{"amount":"1.500000000000","units":"KAR"}"#;
+        assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+    
+    #[test]
+    fn currencies_transfer_unknown_or_unresolved_currency() {
+        let dbname = "for_tests/currencies_transfer_unknown_or_unresolved_currency";
+    // token not in network specs, dex share and foreign asset
+        for currency_id in [vec![0, 2], vec![1, 0, 1], vec![2, 0, 0, 0, 0]].iter() {
+            let reply = decode_currencies_transfer(dbname, currency_id.to_vec());
+            let reply_known_part = r#""type":"default","payload":"1500000000000""#;
+            assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+            assert!(!reply.contains(r#""type":"balance""#), "Balance in unresolved currency should not be displayed in units. Received: {}", reply);
+        }
+        fs::remove_dir_all(dbname).unwrap();
+    }
+    
+    #[allow(dead_code, non_camel_case_types)]
+    mod asset_runtime {
+        use frame_metadata::v14::{ExtrinsicMetadata, PalletCallMetadata, PalletConstantMetadata, PalletMetadata, RuntimeMetadataV14, SignedExtensionMetadata};
+        use parity_scale_codec::Encode;
+        use scale_info::{TypeInfo, meta_type};
+        
+        type Balance = u128;
+        type AssetId = u32;
+        
+        #[derive(TypeInfo)]
+        pub struct AccountId32([u8; 32]);
+        
+        #[derive(TypeInfo)]
+        pub struct RuntimeVersion {
+            spec_name: String,
+            impl_name: String,
+            authoring_version: u32,
+            spec_version: u32,
+            impl_version: u32,
+            apis: Vec<([u8; 8], u32)>,
+            transaction_version: u32,
+        }
+        
+        #[derive(TypeInfo)]
+        pub struct ChargeAssetTxPayment {
+            #[codec(compact)]
+            tip: Balance,
+            asset_id: Option<AssetId>,
+        }
+        
+        #[derive(TypeInfo)]
+        pub enum AssetsCall {
+            transfer {
+                #[codec(compact)]
+                id: AssetId,
+                target: AccountId32,
+                #[codec(compact)]
+                amount: Balance,
+            },
+        }
+        
+        pub const ASSETS_INDEX: u8 = 50;
+        pub const SPEC_VERSION: u32 = 9200;
+        
+        pub fn metadata() -> RuntimeMetadataV14 {
+            let version = (String::from("westend"), String::from("parity-westend"), 2u32, SPEC_VERSION, 0u32, Vec::<([u8; 8], u32)>::new(), 8u32).encode();
+            let pallets = vec![
+                PalletMetadata {
+                    name: "System",
+                    storage: None,
+                    calls: None,
+                    event: None,
+                    constants: vec![PalletConstantMetadata {name: "Version", ty: meta_type::<RuntimeVersion>(), value: version, docs: Vec::new()}],
+                    error: None,
+                    index: 0,
+                },
+                PalletMetadata {
+                    name: "Assets",
+                    storage: None,
+                    calls: Some(PalletCallMetadata {ty: meta_type::<AssetsCall>()}),
+                    event: None,
+                    constants: Vec::new(),
+                    error: None,
+                    index: ASSETS_INDEX,
+                },
+            ];
+            let extrinsic = ExtrinsicMetadata {
+                ty: meta_type::<()>(),
+                version: 4,
+                signed_extensions: vec![SignedExtensionMetadata {identifier: "ChargeAssetTxPayment", ty: meta_type::<ChargeAssetTxPayment>(), additional_signed: meta_type::<()>()}],
+            };
+            RuntimeMetadataV14::new(pallets, extrinsic, meta_type::<()>())
+        }
+    }
+    
+    fn usdt () -> AssetInfo {
+        AssetInfo {asset_id: 1984, symbol: String::from("USDt"), decimals: 6}
+    }
+    
+    fn assets_transfer_method (asset_id: u32) -> Vec<u8> {
+        let amount: u128 = 10_000_000;
+        [vec![asset_runtime::ASSETS_INDEX, 0], Compact(asset_id).encode(), hex::decode(BOB).unwrap(), Compact(amount).encode()].concat()
+    }
+    
+    fn decode_assets_transfer (dbname: &str, asset_id: u32) -> String {
+        let meta_v14 = asset_runtime::metadata();
+        let database = sled::open(dbname).unwrap();
+        let addresses = database.open_tree(ADDRTREE).unwrap();
+        let decoded = decoding_sci_entry_point(assets_transfer_method(asset_id), &meta_v14, 0, 0, &get_default_chainspecs().remove(3), &Vec::new(), &vec![usdt()], &addresses).unwrap();
+        assert!(decoded.remaining_vector.is_empty(), "Some data remained after decoding assets.transfer.");
+        decoded.fancy_out
+    }
+    
+    #[test]
+    fn assets_transfer_known_asset() {
+        let dbname = "for_tests/assets_transfer_known_asset";
+        let reply = decode_assets_transfer(dbname, 1984);
+        let reply_known_part = r#""type":"balance","payload":{"amount":"10.000000","units":"USDt"}"#;
+        assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+    
+    #[test]
+    fn assets_transfer_unknown_asset() {
+        let dbname = "for_tests/assets_transfer_unknown_asset";
+        let reply = decode_assets_transfer(dbname, 1337);
+        let reply_known_part = r#""type":"default","payload":"10000000""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+        assert!(!reply.contains(r#""type":"balance""#), "Balance in asset not in registry should not be displayed in units. Received: {}", reply);
+        fs::remove_dir_all(dbname).unwrap();
+    }
+    
+    /// Function to put metadata with ChargeAssetTxPayment signed extension and USDt asset registry
+    /// for westend into the database
+    fn set_westend_asset_runtime (dbname: &str) {
+        let database = sled::open(dbname).unwrap();
+        let metadata = database.open_tree(METATREE).unwrap();
+        let assets = database.open_tree(ASSETS).unwrap();
+        let meta = [b"meta".to_vec(), RuntimeMetadata::V14(asset_runtime::metadata()).encode()].concat();
+        metadata.insert(NameVersioned{name: String::from("westend"), version: asset_runtime::SPEC_VERSION}.encode(), meta).unwrap();
+        assets.insert(generate_assets_key(&hex::decode(WESTEND_GENESIS_HASH).unwrap()), vec![usdt()].encode()).unwrap();
+        database.flush().unwrap();
+    }
+    
+    /// Function to make immortal westend transaction signed by Alice,
+    /// with assets.transfer call and the tip followed by the asset id
+    fn transaction_with_asset_tip (spec_version: u32, tip: u128, asset_id: Option<u32>) -> String {
+        let genesis_hash = hex::decode(WESTEND_GENESIS_HASH).unwrap();
+        let extrinsics = [vec![0], Compact(1u64).encode(), Compact(tip).encode(), asset_id.encode(), spec_version.encode(), 8u32.encode(), genesis_hash.to_vec(), genesis_hash.to_vec()].concat();
+        format!("530100{}{}{}{}", ALICE, hex::encode(assets_transfer_method(1984).encode()), hex::encode(extrinsics), WESTEND_GENESIS_HASH)
+    }
+    
+    #[test]
+    fn parse_transaction_with_asset_tip() {
+        let dbname = "for_tests/parse_transaction_with_asset_tip";
+        populate_cold(dbname, METADATA_FILE, true).unwrap();
+        set_westend_asset_runtime(dbname);
+        
+    // tip paid in asset from registry
+        let reply = produce_output(&transaction_with_asset_tip(asset_runtime::SPEC_VERSION, 1_500_000, Some(1984)), dbname);
+        let reply_known_part = r#""type":"tip","payload":{"amount":"1.500000","units":"USDt"}"#;
+        assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+        assert!(reply.contains(r#""type":"balance","payload":{"amount":"10.000000","units":"USDt"}"#), "Transferred amount in USDt expected. Received: {}", reply);
+        assert!(reply.contains(r#""action":{"type":"sign_transaction""#), "Transaction should be ready for signing. Received: {}", reply);
+        
+    // tip paid in asset not in registry
+        let reply = produce_output(&transaction_with_asset_tip(asset_runtime::SPEC_VERSION, 1_500_000, Some(1337)), dbname);
+        let reply_known_part = r#""type":"tip_plain","payload":"1500000""#;
+        assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+        
+    // tip paid in native token
+        let reply = produce_output(&transaction_with_asset_tip(asset_runtime::SPEC_VERSION, 10_000_000_000, None), dbname);
+        let reply_known_part = r#""type":"tip","payload":{"amount":"10.000000000","units":"mWND"}"#;
+        assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+        
+    // metadata without ChargeAssetTxPayment signed extension
+        let reply = produce_output(&transaction_with_asset_tip(9010, 1_500_000, Some(1984)), dbname);
+        let reply_known_part = "Transaction tip is followed by asset id, but the network metadata has no ChargeAssetTxPayment signed extension.";
+        assert!(reply.contains(reply_known_part), "Expected: {}\nReceived: {}", reply_known_part, reply);
+        
+        fs::remove_dir_all(dbname).unwrap();
+    }
 }
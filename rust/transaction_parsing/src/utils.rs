@@ -1,7 +1,7 @@
 use frame_metadata::RuntimeMetadata;
-use meta_reading::decode_metadata::{get_meta_const_light};
+use meta_reading::decode_metadata::{decode_runtime_metadata, get_meta_const_light};
 use constants::{GENERALVERIFIER, TRANSACTION, TYPES};
-use definitions::{network_specs::{ChainSpecs, Verifier, NetworkKey}, metadata::{NameVersioned, VersionDecoded}, types::TypeEntry};
+use definitions::{network_specs::{ChainSpecs, Verifier, NetworkKey}, metadata::{NameVersioned, VersionDecoded, decompress_meta}, types::TypeEntry};
 use parity_scale_codec::{Decode, Encode};
 use sled::Tree;
 
@@ -37,12 +37,16 @@ pub fn find_meta(chain_name: &str, version: u32, metadata: &Tree) -> Result<(Run
     }
     
     match meta {
-        Some(m) => {
+        Some(meta_stored) => {
+            let m = match decompress_meta(&meta_stored) {
+                Ok(a) => a,
+                Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedCompressedMeta)),
+            };
             if !m.starts_with(&vec![109, 101, 116, 97]) {return Err(Error::SystemError(SystemError::NotMeta))}
             if m[4] < 12 {
                 return Err(Error::SystemError(SystemError::MetaVersionBelow12));
             }
-            let data_back = RuntimeMetadata::decode(&mut &m[4..]);
+            let data_back = decode_runtime_metadata(&m[4..]);
             match data_back {
                 Ok(metadata) => {
                 // check if the name and version are same in metadata, i.e. the database is not damaged
@@ -91,6 +95,45 @@ pub fn get_chainspecs (network_key: &NetworkKey, chainspecs: &Tree) -> Result<Ch
 }
 
 
+/// Struct to store parachain id and title of a network known to the database
+pub struct ParachainName {
+    pub parachain_id: u32,
+    pub title: String,
+}
+
+/// function to collect parachains sharing the relay chain with given network;
+/// if the network itself is a relay chain, its parachains are collected
+pub fn get_parachain_names (chain_specs: &ChainSpecs, chainspecs: &Tree) -> Result<Vec<ParachainName>, Error> {
+    let relay_genesis_hash = match chain_specs.relay_genesis_hash {
+        Some(a) => a,
+        None => chain_specs.genesis_hash,
+    };
+    let mut out: Vec<ParachainName> = Vec::new();
+    for x in chainspecs.iter() {
+        let (_, chain_specs_encoded) = match x {
+            Ok(t) => t,
+            Err(e) => return Err(Error::DatabaseError(DatabaseError::Internal(e))),
+        };
+        let found_specs = match <ChainSpecs>::decode(&mut &chain_specs_encoded[..]) {
+            Ok(a) => a,
+            Err(_) => return Err(Error::DatabaseError(DatabaseError::DamagedChainSpecs)),
+        };
+        if found_specs.relay_genesis_hash == Some(relay_genesis_hash) {
+            if let Some(parachain_id) = found_specs.parachain_id {
+            // same network could be in database with different encryptions, title of the first found one is used
+                if !out.iter().any(|a| a.parachain_id == parachain_id) {
+                    out.push(ParachainName {
+                        parachain_id,
+                        title: found_specs.title,
+                    })
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+
 /// function to search database for the TypeEntry vector
 pub fn get_types (settings: &Tree) -> Result<Vec<TypeEntry>, Error> {
     match get_from_tree(&TYPES.to_vec(), settings)? {
@@ -0,0 +1,58 @@
+use constants::{ASSETS, HISTORY, LOADASSETS, TRANSACTION};
+use definitions::{history::Event, network_specs::{AssetInfo, NetworkAssets, generate_assets_key, show_assets}, transactions::Transaction};
+use parity_scale_codec::{Decode, Encode};
+use anyhow;
+use db_handling::{helpers::{open_db, open_tree, flush_db, insert_into_tree}, manage_history::{enter_events_into_tree}};
+
+use crate::error::{Error, ActionFailure};
+use crate::helpers::verify_checksum;
+
+/// function to load approved asset registry for the network;
+/// previously recorded asset registry, if any, is replaced
+
+pub fn load_assets (database_name: &str, checksum: u32) -> anyhow::Result<String> {
+
+    let database = open_db(database_name)?;
+    verify_checksum(&database, checksum)?;
+    let transaction = open_tree(&database, TRANSACTION)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let action = match transaction.remove(LOADASSETS) {
+        Ok(Some(encoded_action)) => match <Transaction>::decode(&mut &encoded_action[..]) {
+            Ok(Transaction::LoadAssets(x)) => x,
+            Ok(_) => return Err(Error::NoAction(ActionFailure::LoadAssets).show()),
+            Err(_) => return Err(Error::BadActionDecode(ActionFailure::LoadAssets).show()),
+        },
+        Ok(None) => return Err(Error::NoAction(ActionFailure::LoadAssets).show()),
+        Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+    };
+    flush_db(&database)?;
+
+    let mut events = action.history;
+    let assets = open_tree(&database, ASSETS)?;
+    let assets_key = generate_assets_key(&action.genesis_hash.to_vec());
+    let genesis_hash_string = hex::encode(&action.genesis_hash);
+
+    if let Ok(Some(old_assets_encoded)) = assets.get(&assets_key) {
+        if let Ok(old_assets) = <Vec<AssetInfo>>::decode(&mut &old_assets_encoded[..]) {
+            let network_assets_show = NetworkAssets {
+                genesis_hash: &genesis_hash_string,
+                assets_line: show_assets(&old_assets),
+            }.show();
+            events.push(Event::AssetsRemoved(network_assets_show));
+        }
+    }
+
+    let network_assets_show = NetworkAssets {
+        genesis_hash: &genesis_hash_string,
+        assets_line: show_assets(&action.assets),
+    }.show();
+    events.push(Event::AssetsLoaded(network_assets_show));
+    insert_into_tree(assets_key, action.assets.encode(), &assets)?;
+    flush_db(&database)?;
+
+    enter_events_into_tree(&history, events)?;
+    flush_db(&database)?;
+
+    Ok(String::from("Network asset registry successfully updated."))
+}
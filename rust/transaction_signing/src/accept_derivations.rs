@@ -0,0 +1,90 @@
+use constants::{IMPORTDERIVATIONS, TRANSACTION};
+use definitions::{crypto::Encryption, network_specs::generate_network_key, transactions::Transaction, users::SeedObject};
+use parity_scale_codec::Decode;
+use anyhow;
+use db_handling::{chainspecs::get_network, helpers::{open_db, open_tree, flush_db}, identities::{derive_public_key, get_seed_name_by_phrase, try_create_address}};
+use transaction_parsing::cards::{Card, Warning};
+
+use crate::error::{Error, ActionFailure};
+use crate::helpers::verify_checksum;
+
+/// function to import approved derivations list:
+/// each path is derived from the entered seed phrase, and the identity is created
+/// only if the derived public key matches the expected one;
+/// if the public key does not match and the password is entered, the path is tried again with the password;
+/// mismatched derivations are reported with warning cards;
+/// approved action is kept in the database until the import is completed
+
+pub fn import_derivations (seed_phrase: &str, pwd_entry: &str, database_name: &str, checksum: u32) -> anyhow::Result<String> {
+
+    let action = {
+        let database = open_db(database_name)?;
+        verify_checksum(&database, checksum)?;
+        let transaction = open_tree(&database, TRANSACTION)?;
+        let action = match transaction.get(IMPORTDERIVATIONS) {
+            Ok(Some(encoded_action)) => match <Transaction>::decode(&mut &encoded_action[..]) {
+                Ok(Transaction::ImportDerivations(x)) => x,
+                Ok(_) => return Err(Error::NoAction(ActionFailure::ImportDerivations).show()),
+                Err(_) => return Err(Error::BadActionDecode(ActionFailure::ImportDerivations).show()),
+            },
+            Ok(None) => return Err(Error::NoAction(ActionFailure::ImportDerivations).show()),
+            Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+        };
+        action
+    };
+
+// seed names are searched once per encryption
+    let mut seed_names: Vec<(Encryption, String)> = Vec::new();
+    let mut imported_cards = String::new();
+    let mut warning_cards = String::new();
+
+    for (index, x) in action.derivations.iter().enumerate() {
+        let seed_name = match seed_names.iter().find(|(encryption, _)| *encryption == x.encryption) {
+            Some((_, a)) => a.to_string(),
+            None => {
+                let a = get_seed_name_by_phrase(seed_phrase, x.encryption, database_name)?;
+                seed_names.push((x.encryption, a.to_string()));
+                a
+            },
+        };
+        let seed_object = SeedObject {
+            seed_name: seed_name.to_string(),
+            seed_phrase: seed_phrase.to_string(),
+            encryption: x.encryption,
+        };
+        let mut path = x.path.to_string();
+        let mut has_pwd = false;
+        let mut public_key = derive_public_key(&seed_object, &path)?;
+        if (public_key != x.public_key) && (pwd_entry != "") {
+            path = format!("{}///{}", x.path, pwd_entry);
+            has_pwd = true;
+            public_key = derive_public_key(&seed_object, &path)?;
+        }
+        if public_key == x.public_key {
+            let network_key_string = hex::encode(generate_network_key(&x.genesis_hash.to_vec(), x.encryption));
+            match try_create_address(&x.name, &seed_name, seed_phrase, &x.encryption.show(), &path, &network_key_string, has_pwd, database_name) {
+                Ok(()) => {
+                    let network_title = get_network(database_name, &network_key_string)?.title;
+                    if imported_cards.len() > 0 {imported_cards.push_str(",")}
+                    imported_cards.push_str(&Card::Derivation{path: &x.path, name: &x.name, network_title: &network_title, public_key: &x.public_key, encryption: x.encryption}.card(index as u32,0));
+                },
+                Err(e) => {
+                    if warning_cards.len() > 0 {warning_cards.push_str(",")}
+                    warning_cards.push_str(&Card::Warning(Warning::DerivationNotImported{path: x.path.to_string(), error: e.to_string()}).card(index as u32,0));
+                },
+            }
+        }
+        else {
+            if warning_cards.len() > 0 {warning_cards.push_str(",")}
+            warning_cards.push_str(&Card::Warning(Warning::DerivationMismatch{path: x.path.to_string(), name: x.name.to_string()}).card(index as u32,0));
+        }
+    }
+
+// action is removed only after the import went through, so that it could be retried in case of error
+    let database = open_db(database_name)?;
+    let transaction = open_tree(&database, TRANSACTION)?;
+    if let Err(e) = transaction.remove(IMPORTDERIVATIONS) {return Err(Error::InternalDatabaseError(e).show())}
+    flush_db(&database)?;
+
+    Ok(format!("{{\"imported\":[{}],\"warning\":[{}]}}", imported_cards, warning_cards))
+}
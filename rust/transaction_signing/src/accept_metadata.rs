@@ -1,14 +1,15 @@
 use constants::{ADDMETAVERIFIER, GENERALVERIFIER, HISTORY, LOADMETA, METATREE, SETTREE, VERIFIERS, TRANSACTION};
-use definitions::{history::Event, metadata::{MetaValuesDisplay}, network_specs::NetworkVerifier, transactions::Transaction};
+use definitions::{history::Event, metadata::{MetaValuesDisplay}, network_specs::{NetworkVerifier, generate_verifier_rotation_key}, transactions::Transaction};
 use parity_scale_codec::{Decode, Encode};
 use anyhow;
-use db_handling::{helpers::{open_db, open_tree, flush_db, insert_into_tree}, manage_history::{enter_events_into_tree}};
+use db_handling::{helpers::{open_db, open_tree, flush_db, insert_into_tree, remove_from_tree, get_retention_policy, metadata_to_remove, meta_for_storage}, manage_history::{enter_events_into_tree}};
 use blake2_rfc::blake2b::blake2b;
 
 use crate::error::{Error, ActionFailure};
 use crate::helpers::verify_checksum;
 
 /// function to add approved metadata for known network to the database;
+/// older metadata versions of the network are removed according to the retention policy from settings;
 
 pub fn accept_metadata (database_name: &str, checksum: u32, upd_general: bool) -> anyhow::Result<String> {
     
@@ -38,12 +39,23 @@ pub fn accept_metadata (database_name: &str, checksum: u32, upd_general: bool) -
     events.push(Event::MetadataAdded(meta_values_display));
     
     let metadata = open_tree(&database, METATREE)?;
-    insert_into_tree(action.versioned_name.encode(), action.meta, &metadata)?;
+    let settings = open_tree(&database, SETTREE)?;
+    let retention_policy = get_retention_policy(&settings)?;
+    let removed = metadata_to_remove(retention_policy, &action.versioned_name.name, action.versioned_name.version, &metadata)?;
+    insert_into_tree(action.versioned_name.encode(), meta_for_storage(&action.meta)?, &metadata)?;
+    for (versioned_name, meta_stored) in removed.iter() {
+        remove_from_tree(versioned_name.encode(), &metadata)?;
+        let meta_values_display = MetaValuesDisplay {
+            name: &versioned_name.name,
+            version: versioned_name.version,
+            meta_hash: &hex::encode(blake2b(32, &[], &meta_stored).as_bytes()),
+        }.show();
+        events.push(Event::MetadataRemoved(meta_values_display));
+    }
     flush_db(&database)?;
     
     if upd_general {
         events.push(Event::GeneralVerifierAdded(action.verifier.show_card()));
-        let settings = open_tree(&database, SETTREE)?;
         insert_into_tree(GENERALVERIFIER.to_vec(), action.verifier.encode(), &settings)?;
         flush_db(&database)?;
     }
@@ -56,14 +68,21 @@ pub fn accept_metadata (database_name: &str, checksum: u32, upd_general: bool) -
         events.push(Event::MetadataVerifierAdded(network_verifier_show));
         let verifiers = open_tree(&database, VERIFIERS)?;
         insert_into_tree(verifier_key.to_vec(), action.verifier.encode(), &verifiers)?;
+    // pending verifier rotation, if any, is completed or superseded
+        remove_from_tree(generate_verifier_rotation_key(&verifier_key), &verifiers)?;
         flush_db(&database)?;
     }
     
     enter_events_into_tree(&history, events)?;
     flush_db(&database)?;
     
-    if upd_general {Ok(String::from("Metadata successfully loaded. General verifier successfully updated."))}
-    else {Ok(String::from("Metadata successfully loaded."))}
+    let removed_print = match removed.len() {
+        0 => String::new(),
+        1 => String::from(" 1 older metadata version removed under retention policy."),
+        n => format!(" {} older metadata versions removed under retention policy.", n),
+    };
+    if upd_general {Ok(format!("Metadata successfully loaded. General verifier successfully updated.{}", removed_print))}
+    else {Ok(format!("Metadata successfully loaded.{}", removed_print))}
     
 }
 
@@ -97,6 +116,8 @@ pub fn add_meta_verifier (database_name: &str, checksum: u32, upd_general: bool)
     events.push(Event::MetadataVerifierAdded(network_verifier_show));
     let verifiers = open_tree(&database, VERIFIERS)?;
     insert_into_tree(action.verifier_key.to_vec(), action.verifier.encode(), &verifiers)?;
+// pending verifier rotation, if any, is completed or superseded
+    remove_from_tree(generate_verifier_rotation_key(&action.verifier_key), &verifiers)?;
     flush_db(&database)?;
     
     if upd_general {
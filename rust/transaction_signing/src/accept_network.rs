@@ -2,7 +2,7 @@ use anyhow;
 use constants::{ADDNETWORK, ADDRTREE, GENERALVERIFIER, HISTORY, METATREE, SETTREE, SPECSTREE, TRANSACTION, VERIFIERS};
 use definitions::{history::Event, metadata::{MetaValuesDisplay, NetworkDisplay}, network_specs::{ChainSpecs, generate_network_key, generate_verifier_key}, transactions::Transaction, users::IdentityHistory};
 use parity_scale_codec::{Decode, Encode};
-use db_handling::{helpers::{open_db, open_tree, flush_db, insert_into_tree, decode_address_details, reverse_address_key}, manage_history::enter_events_into_tree};
+use db_handling::{helpers::{open_db, open_tree, flush_db, insert_into_tree, decode_address_details, reverse_address_key, meta_for_storage}, manage_history::enter_events_into_tree};
 use blake2_rfc::blake2b::blake2b;
 
 use crate::error::{Error, ActionFailure};
@@ -42,7 +42,7 @@ pub fn add_network (database_name: &str, checksum: u32, upd_general: bool) -> an
     events.push(Event::NetworkAdded(network_display));
     
     let metadata = open_tree(&database, METATREE)?;
-    insert_into_tree(action.versioned_name.encode(), action.meta, &metadata)?;
+    insert_into_tree(action.versioned_name.encode(), meta_for_storage(&action.meta)?, &metadata)?;
     flush_db(&database)?;
     
 // updating general verifier if requested
@@ -71,6 +71,9 @@ pub fn add_network (database_name: &str, checksum: u32, upd_general: bool) -> an
         secondary_color: action.chainspecs.secondary_color,
         title: action.chainspecs.title,
         unit: action.chainspecs.unit,
+        relay_genesis_hash: action.chainspecs.relay_genesis_hash,
+        parachain_id: action.chainspecs.parachain_id,
+        tokens: action.chainspecs.tokens,
     };
     insert_into_tree(network_key.to_vec(), new_chainspecs.encode(), &chainspecs)?;
     flush_db(&database)?;
@@ -0,0 +1,114 @@
+use constants::{HISTORY, ROTATEVERIFIER, SETVERIFIERS, TRANSACTION, VERIFIERS};
+use definitions::{history::Event, network_specs::{NetworkVerifierRotation, NetworkVerifierSet, VerifierRotation, VerifierSet, generate_verifier_key, generate_verifier_rotation_key}, transactions::Transaction};
+use parity_scale_codec::{Decode, Encode};
+use anyhow;
+use db_handling::{helpers::{open_db, open_tree, flush_db, insert_into_tree, remove_from_tree}, manage_history::{enter_events_into_tree}};
+
+use crate::error::{Error, ActionFailure};
+use crate::helpers::verify_checksum;
+
+/// function to set approved verifier set for the network;
+/// previously recorded verifier set, if any, is replaced
+
+pub fn set_verifiers (database_name: &str, checksum: u32) -> anyhow::Result<String> {
+
+    let database = open_db(database_name)?;
+    verify_checksum(&database, checksum)?;
+    let transaction = open_tree(&database, TRANSACTION)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let action = match transaction.remove(SETVERIFIERS) {
+        Ok(Some(encoded_action)) => match <Transaction>::decode(&mut &encoded_action[..]) {
+            Ok(Transaction::SetVerifiers(x)) => x,
+            Ok(_) => return Err(Error::NoAction(ActionFailure::SetVerifiers).show()),
+            Err(_) => return Err(Error::BadActionDecode(ActionFailure::SetVerifiers).show()),
+        },
+        Ok(None) => return Err(Error::NoAction(ActionFailure::SetVerifiers).show()),
+        Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+    };
+    flush_db(&database)?;
+
+    let mut events = action.history;
+    let verifiers = open_tree(&database, VERIFIERS)?;
+
+    if let Ok(Some(old_verifier_set_encoded)) = verifiers.get(&action.verifier_key) {
+        if let Ok(old_verifier_set) = <VerifierSet>::decode(&mut &old_verifier_set_encoded[..]) {
+            let network_verifier_set_show = NetworkVerifierSet {
+                verifier_key: &hex::encode(&action.verifier_key),
+                verifier_set_line: old_verifier_set.show_card(),
+            }.show();
+            events.push(Event::MetadataVerifierSetRemoved(network_verifier_set_show));
+        }
+    }
+
+    let network_verifier_set_show = NetworkVerifierSet {
+        verifier_key: &hex::encode(&action.verifier_key),
+        verifier_set_line: action.verifier_set.show_card(),
+    }.show();
+    events.push(Event::MetadataVerifierSetAdded(network_verifier_set_show));
+    insert_into_tree(action.verifier_key.to_vec(), action.verifier_set.encode(), &verifiers)?;
+    flush_db(&database)?;
+
+    enter_events_into_tree(&history, events)?;
+    flush_db(&database)?;
+
+    Ok(String::from("Network verifier set successfully updated."))
+}
+
+
+/// function to accept approved network verifier rotation;
+/// if the rotation has effective_from metadata version, it is recorded as pending,
+/// and the network verifier is updated when the corresponding metadata is loaded
+
+pub fn rotate_verifier (database_name: &str, checksum: u32) -> anyhow::Result<String> {
+
+    let database = open_db(database_name)?;
+    verify_checksum(&database, checksum)?;
+    let transaction = open_tree(&database, TRANSACTION)?;
+    let history = open_tree(&database, HISTORY)?;
+
+    let action = match transaction.remove(ROTATEVERIFIER) {
+        Ok(Some(encoded_action)) => match <Transaction>::decode(&mut &encoded_action[..]) {
+            Ok(Transaction::RotateVerifier(x)) => x,
+            Ok(_) => return Err(Error::NoAction(ActionFailure::RotateVerifier).show()),
+            Err(_) => return Err(Error::BadActionDecode(ActionFailure::RotateVerifier).show()),
+        },
+        Ok(None) => return Err(Error::NoAction(ActionFailure::RotateVerifier).show()),
+        Err(e) => return Err(Error::InternalDatabaseError(e).show()),
+    };
+    flush_db(&database)?;
+
+    let mut events = action.history;
+    let verifier_key = generate_verifier_key(&action.genesis_hash.to_vec());
+    let network_verifier_rotation_show = NetworkVerifierRotation {
+        verifier_key: &hex::encode(&verifier_key),
+        old_verifier_line: action.old_verifier.show_card(),
+        new_verifier_line: action.new_verifier.show_card(),
+        effective_from: action.effective_from,
+    }.show();
+    events.push(Event::MetadataVerifierRotated(network_verifier_rotation_show));
+
+    let verifiers = open_tree(&database, VERIFIERS)?;
+    let rotation_key = generate_verifier_rotation_key(&action.genesis_hash.to_vec());
+    let reply = match action.effective_from {
+        Some(effective_from) => {
+            let verifier_rotation = VerifierRotation {
+                new_verifier: action.new_verifier,
+                effective_from,
+            };
+            insert_into_tree(rotation_key, verifier_rotation.encode(), &verifiers)?;
+            format!("Network verifier rotation successfully recorded. New verifier is used from metadata version {}.", effective_from)
+        },
+        None => {
+            insert_into_tree(verifier_key, action.new_verifier.encode(), &verifiers)?;
+            remove_from_tree(rotation_key, &verifiers)?;
+            String::from("Network verifier successfully rotated.")
+        },
+    };
+    flush_db(&database)?;
+
+    enter_events_into_tree(&history, events)?;
+    flush_db(&database)?;
+
+    Ok(reply)
+}
@@ -23,6 +23,10 @@ pub enum ActionFailure {
     LoadTypes,
     AddGeneralVerifier,
     SignTransaction,
+    SetVerifiers,
+    RotateVerifier,
+    ImportDerivations,
+    LoadAssets,
 }
 
 #[derive(PartialEq)]
@@ -54,6 +58,10 @@ impl Error {
                 ActionFailure::LoadTypes => anyhow!("No approved load_types message found."),
                 ActionFailure::AddGeneralVerifier => anyhow!("No approved add_general_verifier message found."),
                 ActionFailure::SignTransaction => anyhow!("No approved sign_transaction message found."),
+                ActionFailure::SetVerifiers => anyhow!("No approved set_verifiers message found."),
+                ActionFailure::RotateVerifier => anyhow!("No approved rotate_verifier message found."),
+                ActionFailure::ImportDerivations => anyhow!("No approved import_derivations message found."),
+                ActionFailure::LoadAssets => anyhow!("No approved load_assets message found."),
             },
             Error::BadActionDecode(e) => match e {
                 ActionFailure::LoadMeta => anyhow!("Found load_metadata message could not be decoded."),
@@ -62,6 +70,10 @@ impl Error {
                 ActionFailure::LoadTypes => anyhow!("Found load_types message could not be decoded."),
                 ActionFailure::AddGeneralVerifier => anyhow!("Found add_general_verifier message could not be decoded."),
                 ActionFailure::SignTransaction => anyhow!("Found sign_transaction message could not be decoded."),
+                ActionFailure::SetVerifiers => anyhow!("Found set_verifiers message could not be decoded."),
+                ActionFailure::RotateVerifier => anyhow!("Found rotate_verifier message could not be decoded."),
+                ActionFailure::ImportDerivations => anyhow!("Found import_derivations message could not be decoded."),
+                ActionFailure::LoadAssets => anyhow!("Found load_assets message could not be decoded."),
             },
             Error::AddressDetailsNotFound => anyhow!("Identity not found."),
             Error::Interpretation(e) => match e {
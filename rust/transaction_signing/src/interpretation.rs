@@ -38,6 +38,10 @@ pub fn interpret_action (action_line: &str) -> anyhow::Result<Action> {
                         "load_metadata_and_add_general_verifier" => Ok(Action::LoadMetadataAndAddGeneralVerifier(checksum)),
                         "add_network" => Ok(Action::AddNetwork(checksum)),
                         "add_network_and_add_general_verifier" => Ok(Action::AddNetworkAndAddGeneralVerifier(checksum)),
+                        "set_verifiers" => Ok(Action::SetVerifiers(checksum)),
+                        "rotate_verifier" => Ok(Action::RotateVerifier(checksum)),
+                        "import_derivations" => Ok(Action::ImportDerivations(checksum)),
+                        "load_assets" => Ok(Action::LoadAssets(checksum)),
                         _ => return Err(Error::Interpretation(Interpretation::UnsupportedAction).show()),
                     }
                 },
@@ -1,12 +1,18 @@
 use anyhow;
 use transaction_parsing::cards::Action;
 
+mod accept_assets;
+    use accept_assets::load_assets;
+mod accept_derivations;
+    use accept_derivations::import_derivations;
 mod accept_metadata;
     use accept_metadata::{accept_metadata, add_meta_verifier};
 mod accept_network;
     use accept_network::add_network;
 mod accept_types;
     use accept_types::{accept_types, add_general_verifier};
+mod accept_verifiers;
+    use accept_verifiers::{set_verifiers, rotate_verifier};
 mod error;
 mod helpers;
 mod interpretation;
@@ -32,5 +38,9 @@ pub fn handle_action (action_line: &str, seed_phrase: &str, pwd_entry: &str, use
         Action::LoadMetadataAndAddGeneralVerifier(checksum) => accept_metadata (dbname, checksum, true),
         Action::AddNetwork(checksum) => add_network (dbname, checksum, false),
         Action::AddNetworkAndAddGeneralVerifier(checksum) => add_network (dbname, checksum, true),
+        Action::SetVerifiers(checksum) => set_verifiers (dbname, checksum),
+        Action::RotateVerifier(checksum) => rotate_verifier (dbname, checksum),
+        Action::ImportDerivations(checksum) => import_derivations (seed_phrase, pwd_entry, dbname, checksum),
+        Action::LoadAssets(checksum) => load_assets (dbname, checksum),
     }
 }
@@ -5,7 +5,10 @@
 mod tests {
     use transaction_parsing::{produce_output, cards::Action};
     use crate::{handle_action, error::{Error, ActionFailure}, interpretation::interpret_action, sign_transaction::create_signature};
-    use db_handling::{populate_cold, populate_cold_no_networks, populate_cold_no_meta};
+    use db_handling::{populate_cold, populate_cold_no_networks, populate_cold_no_meta, identities::print_relevant_identities, manage_history::print_history, settings::set_retention_policy};
+    use definitions::{crypto::Encryption, metadata::NameVersioned, network_specs::generate_network_key, users::DerivationEntry};
+    use parity_scale_codec::Encode;
+    use std::convert::TryInto;
     use constants::{METATREE, SPECSTREE};
     use std::fs;
     use sled::{Db, open, Tree};
@@ -90,7 +93,7 @@ mod tests {
         
         let line = fs::read_to_string("for_tests/add_network_westendV9090_unverified.txt").unwrap();
         let reply = produce_output(&line.trim(), dbname);
-        let reply_known_part = r##"{"warning":[{"index":0,"indent":0,"type":"warning","payload":"Received new network information is not verified."}],"new_network":[{"index":1,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","verifier":{"hex":"","encryption":"none"}}}],"action":{"type":"add_network","payload":{"type":"add_network","checksum":""##;
+        let reply_known_part = r##"{"warning":[{"index":0,"indent":0,"type":"warning","payload":"Received new network information is not verified."}],"new_network":[{"index":1,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","relay_genesis_hash":"","parachain_id":"","tokens":[],"verifier":{"hex":"","encryption":"none"}}}],"action":{"type":"add_network","payload":{"type":"add_network","checksum":""##;
         assert!(reply.contains(reply_known_part), "Error in action.\nReceived: {}", reply);
         let mock_action_line = get_action_line(&reply);
         let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
@@ -130,7 +133,7 @@ mod tests {
         
         let line = fs::read_to_string("for_tests/add_network_westendV9090_Alice.txt").unwrap();
         let reply = produce_output(&line.trim(), dbname);
-        let reply_known_part = r##"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"warning":[{"index":1,"indent":0,"type":"warning","payload":"Previously unverified information now received signed by a verifier. If accepted, updating types and adding networks could be verified only by this verifier."}],"new_network":[{"index":2,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","verifier":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}}],"action":{"type":"add_network_and_add_general_verifier","payload":{"type":"add_network_and_add_general_verifier","checksum":""##;
+        let reply_known_part = r##"{"verifier":[{"index":0,"indent":0,"type":"verifier","payload":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}],"warning":[{"index":1,"indent":0,"type":"warning","payload":"Previously unverified information now received signed by a verifier. If accepted, updating types and adding networks could be verified only by this verifier."}],"new_network":[{"index":2,"indent":0,"type":"new_network","payload":{"specname":"westend","spec_version":"9090","meta_hash":"62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b","base58prefix":"42","color":"#660D35","decimals":"12","encryption":"sr25519","genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","logo":"westend","name":"westend","path_id":"//westend","secondary_color":"#262626","title":"Westend","unit":"WND","relay_genesis_hash":"","parachain_id":"","tokens":[],"verifier":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"}}}],"action":{"type":"add_network_and_add_general_verifier","payload":{"type":"add_network_and_add_general_verifier","checksum":""##;
         assert!(reply.contains(reply_known_part), "Error in action.\nReceived: {}", reply);
         let mock_action_line = get_action_line(&reply);
         let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
@@ -469,4 +472,206 @@ mod tests {
         fs::remove_dir_all(dbname).unwrap();
     }
 
+// load_types with verifier, general verifier appears and sets verifier set for westend,
+// after that westend metadata is accepted only with enough signatures from the set
+    #[test]
+    fn set_verifiers_then_load_metadata_signed_by_verifier_set() {
+        
+        let dbname = "for_tests/set_verifiers_then_load_metadata_signed_by_verifier_set";
+        populate_cold_no_meta(dbname, true).unwrap();
+
+        let line = fs::read_to_string("for_tests/updating_types_info_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        if let Err(e) = result {panic!("Was unable to load types with adding general verifier. {}", e)}
+        
+    // setting verifier set, signed by general verifier
+        let line = fs::read_to_string("for_tests/set_verifiers_westend_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#""verifier_set":[{"index":1,"indent":0,"type":"verifier_set","payload":{"threshold":"2","verifiers":[{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"},{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"}]}}],"action":{"type":"set_verifiers""#;
+        assert!(reply.contains(reply_known_part), "Error in action.\nReceived: {}", reply);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        if let Err(e) = result {panic!("Was unable to set verifier set. {}", e)}
+        let history_printed = print_history(dbname).unwrap();
+        assert!(history_printed.contains(r#"{"event":"metadata_verifier_set_added","payload":{"specname":"76657269666965725f736574e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","verifier_set":{"threshold":"2","#), "Expected verifier set event in history.\nReceived: {}", history_printed);
+        
+    // loading metadata signed only by Alice - should not work
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        assert!(reply.contains("Received message is signed by only 1 of them."), "Error in parsing outcome.\nReceived: {}", reply);
+        
+    // loading metadata signed by Alice and Bob - should work
+        let meta1 = meta_count_test(dbname);
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Alice_Bob.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        if let Err(e) = result {panic!("Was unable to load metadata signed by verifier set. {}", e)}
+        let meta2 = meta_count_test(dbname);
+        assert!(meta2 == meta1+1, "Did not add metadata to database.");
+        
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+// load_metadata for westend9070 with verifier Alice, then Alice authorizes rotation to Bob effective from version 9070,
+// Bob re-signs already known metadata and becomes network verifier, after that Alice could not verify westend metadata
+    #[test]
+    fn rotate_verifier_then_new_verifier_signs_metadata() {
+        
+        let dbname = "for_tests/rotate_verifier_then_new_verifier_signs_metadata";
+        populate_cold_no_meta(dbname, true).unwrap();
+
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        if let Err(e) = result {panic!("Was unable to load metadata with verifier. {}", e)}
+        
+    // rotation signed by Alice
+        let line = fs::read_to_string("for_tests/rotate_verifier_westend_Alice_to_Bob_9070.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        match result {
+            Ok(a) => assert!(a == "Network verifier rotation successfully recorded. New verifier is used from metadata version 9070.", "Unexpected reply: {}", a),
+            Err(e) => panic!("Was unable to rotate verifier. {}", e),
+        }
+        let history_printed = print_history(dbname).unwrap();
+        assert!(history_printed.contains(r#"{"event":"metadata_verifier_rotated","payload":{"specname":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","old_verifier":{"hex":"d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d","encryption":"sr25519"},"new_verifier":{"hex":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"},"effective_from":"9070"}}"#), "Expected verifier rotation event in history.\nReceived: {}", history_printed);
+        
+    // same metadata signed by Bob - only network verifier is updated
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Bob.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r#""warning":[{"index":1,"indent":0,"type":"warning","payload":"Received network metadata is signed by the new verifier from pending verifier rotation. If accepted, only metadata from the new verifier could be received for this network."},{"index":2,"indent":0,"type":"warning","payload":"Received metadata is already in database, only network verifier could be added."}],"action":{"type":"add_metadata_verifier""#;
+        assert!(reply.contains(reply_known_part), "Error in action.\nReceived: {}", reply);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        if let Err(e) = result {panic!("Was unable to complete verifier rotation. {}", e)}
+        
+    // metadata signed by Alice is no longer accepted
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        assert!(reply.contains("Different verifier was used for this network previously."), "Error in parsing outcome.\nReceived: {}", reply);
+        
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+// load_metadata for westend9070 with verifier Alice, then Alice loads asset registry for westend,
+// repeated loading of the same asset registry is rejected
+    #[test]
+    fn load_assets_signed_by_network_verifier() {
+        
+        let dbname = "for_tests/load_assets_signed_by_network_verifier";
+        populate_cold_no_meta(dbname, true).unwrap();
+
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        if let Err(e) = result {panic!("Was unable to load metadata with verifier. {}", e)}
+        
+        let line = fs::read_to_string("for_tests/load_assets_westend_Alice.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        match result {
+            Ok(a) => assert!(a == "Network asset registry successfully updated.", "Unexpected reply: {}", a),
+            Err(e) => panic!("Was unable to load asset registry. {}", e),
+        }
+        let history_printed = print_history(dbname).unwrap();
+        assert!(history_printed.contains(r#"{"event":"assets_loaded","payload":{"genesis_hash":"e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","assets":[{"asset_id":"1984","symbol":"USDt","decimals":"6"}]}}"#), "Expected asset registry event in history.\nReceived: {}", history_printed);
+        
+        let reply = produce_output(&line.trim(), dbname);
+        assert!(reply.contains("Received asset registry is already in database."), "Error in parsing outcome.\nReceived: {}", reply);
+        
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+// derivations list from hot side: //Bob matches, //secret matches only with password entered,
+// //Charlie has public key of //Bob and is reported as mismatch
+    #[test]
+    fn import_derivations_with_password_and_mismatch() {
+        
+        let dbname = "for_tests/import_derivations_with_password_and_mismatch";
+        populate_cold_no_meta(dbname, true).unwrap();
+        
+        let westend_genesis_hash = hex::decode("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap();
+        let derivation = |path: &str, public_key: &str, name: &str| DerivationEntry {
+            path: path.to_string(),
+            genesis_hash: westend_genesis_hash.to_vec().try_into().unwrap(),
+            encryption: Encryption::Sr25519,
+            public_key: hex::decode(public_key).unwrap(),
+            name: name.to_string(),
+        };
+        let derivations = vec![
+            derivation("//Bob", "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48", "Bob"),
+            derivation("//secret", "aa2ab6553ed70dc5a2d8ab140f7f4d0726341d1b1337953160911322888f9a39", "secret"),
+            derivation("//Charlie", "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48", "Charlie"),
+        ];
+        let line = format!("53ffde{}", hex::encode(derivations.encode()));
+        let reply = produce_output(&line, dbname);
+        let mock_action_line = get_action_line(&reply);
+        
+        let unknown_seed_phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert!(handle_action(&mock_action_line, unknown_seed_phrase, PWD, USER_COMMENT, dbname).is_err(), "Seed phrase is not known to the database.");
+        
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        let reply_known = r#"{"imported":[{"index":0,"indent":0,"type":"derivation","payload":{"derivation_path":"//Bob","name":"Bob","network":"Westend","public_key":"8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48","encryption":"sr25519"}},{"index":1,"indent":0,"type":"derivation","payload":{"derivation_path":"//secret","name":"secret","network":"Westend","public_key":"aa2ab6553ed70dc5a2d8ab140f7f4d0726341d1b1337953160911322888f9a39","encryption":"sr25519"}}],"warning":[{"index":2,"indent":0,"type":"warning","payload":"Public key derived for path //Charlie (Charlie) does not match the expected one. If the path is password-protected, enter the password and try again."}]}"#;
+        match result {
+            Ok(a) => assert!(a == reply_known, "Expected: {}\nReceived: {}", reply_known, a),
+            Err(e) => panic!("Was unable to import derivations. {}", e),
+        }
+        
+        let network_key_string = hex::encode(generate_network_key(&westend_genesis_hash, Encryption::Sr25519));
+        let identities_printed = print_relevant_identities("Alice", &network_key_string, dbname).unwrap();
+        assert!(identities_printed.contains(r#""path":"//Bob","has_password":"false","name":"Bob""#), "Expected imported identity //Bob.\nReceived: {}", identities_printed);
+        assert!(identities_printed.contains(r#""path":"//secret","has_password":"true","name":"secret""#), "Expected imported identity //secret with password.\nReceived: {}", identities_printed);
+        assert!(!identities_printed.contains("Charlie"), "Mismatched identity should not be imported.\nReceived: {}", identities_printed);
+        assert!(handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname).is_err(), "Derivations import should be removed from the database after completion.");
+        
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
+// older westend metadata versions are in the database, retention policy keeps only 2 latest versions;
+// loading westend9070 removes two oldest versions, removals are shown on the load card and logged
+    #[test]
+    fn load_metadata_with_retention_policy() {
+        
+        let dbname = "for_tests/load_metadata_with_retention_policy";
+        populate_cold_no_meta(dbname, true).unwrap();
+        {
+            let database: Db = open(dbname).unwrap();
+            let metadata: Tree = database.open_tree(METATREE).unwrap();
+            for version in [9000, 9010, 9050].iter() {
+                let versioned_name = NameVersioned {name: String::from("westend"), version: *version};
+                metadata.insert(versioned_name.encode(), format!("dummy metadata {}", version).as_bytes().to_vec()).unwrap();
+            }
+            database.flush().unwrap();
+        }
+        assert!(set_retention_policy("keep_latest:0", dbname).is_err(), "At least one version should be kept");
+        set_retention_policy("keep_latest:2", dbname).unwrap();
+        let meta1 = meta_count_test(dbname);
+        
+        let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+        let reply = produce_output(&line.trim(), dbname);
+        let reply_known_part = r##""meta":[{"index":1,"indent":0,"type":"meta","payload":{"specname":"westend","spec_version":"9070","meta_hash":"e281fbc53168a6b87d1ea212923811f4c083e7be7d18df4b8527b9532e5f5fec"}},{"index":2,"indent":0,"type":"meta_removed","payload":{"specname":"westend","spec_version":"9000","meta_hash":"dcd26a5d16b82938b5a898fe34cdc1b61c6b8f25fdd24d90c99a5e459fffd1e2"}},{"index":3,"indent":0,"type":"meta_removed","payload":{"specname":"westend","spec_version":"9010","meta_hash":"5f7381065b92d41c9da1c01bd6bd60f304792b01702f0c1d8249abb1b7534e94"}}],"action":{"type":"load_metadata""##;
+        assert!(reply.contains(reply_known_part), "Error in action.\nReceived: {}", reply);
+        let mock_action_line = get_action_line(&reply);
+        let result = handle_action(&mock_action_line, SEED_PHRASE, PWD, USER_COMMENT, dbname);
+        match result {
+            Ok(a) => assert!(a == "Metadata successfully loaded. 2 older metadata versions removed under retention policy.", "Unexpected reply: {}", a),
+            Err(e) => panic!("Was unable to load metadata for westend 9070 network. {}", e),
+        }
+        
+        let meta2 = meta_count_test(dbname);
+        assert!(meta2 == meta1 - 1, "Expected one version added and two removed.");
+        let history_printed = print_history(dbname).unwrap();
+        assert!(history_printed.contains(r#"{"event":"metadata_removed","payload":{"specname":"westend","spec_version":"9000","meta_hash":"dcd26a5d16b82938b5a898fe34cdc1b61c6b8f25fdd24d90c99a5e459fffd1e2"}}"#), "Expected metadata removal in history.\nReceived: {}", history_printed);
+        assert!(!history_printed.contains(r#""spec_version":"9050""#), "Version 9050 should be kept.\nReceived: {}", history_printed);
+        
+        fs::remove_dir_all(dbname).unwrap();
+    }
+
 }